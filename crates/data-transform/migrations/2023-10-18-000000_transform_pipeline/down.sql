@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS transformed_objects;
+DROP TABLE IF EXISTS transformed_events;
+DROP TABLE IF EXISTS transform_watermarks;
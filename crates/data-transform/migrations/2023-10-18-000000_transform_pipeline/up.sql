@@ -0,0 +1,42 @@
+-- Progress of each task of a pipeline. `task` is `live` for the task following the chain and
+-- `backfill-<start>-<end>` for backfills of a checkpoint range.
+CREATE TABLE transform_watermarks
+(
+    pipeline                    TEXT    NOT NULL,
+    task                        TEXT    NOT NULL,
+    checkpoint_sequence_number  BIGINT  NOT NULL,
+    PRIMARY KEY (pipeline, task)
+);
+
+-- Events of all projections without a table of their own.
+CREATE TABLE transformed_events
+(
+    projection                  TEXT    NOT NULL,
+    checkpoint_sequence_number  BIGINT  NOT NULL,
+    transaction_digest          TEXT    NOT NULL,
+    event_sequence              BIGINT  NOT NULL,
+    timestamp_ms                BIGINT  NOT NULL,
+    sender                      TEXT    NOT NULL,
+    package                     TEXT    NOT NULL,
+    module                      TEXT    NOT NULL,
+    event_type                  TEXT    NOT NULL,
+    event_json                  JSONB   NOT NULL,
+    PRIMARY KEY (projection, transaction_digest, event_sequence)
+);
+CREATE INDEX transformed_events_type ON transformed_events (projection, event_type, checkpoint_sequence_number);
+
+-- Objects of all projections without a table of their own, one row per object version.
+CREATE TABLE transformed_objects
+(
+    projection                  TEXT    NOT NULL,
+    checkpoint_sequence_number  BIGINT  NOT NULL,
+    transaction_digest          TEXT    NOT NULL,
+    timestamp_ms                BIGINT  NOT NULL,
+    object_id                   TEXT    NOT NULL,
+    object_version              BIGINT  NOT NULL,
+    object_type                 TEXT    NOT NULL,
+    owner                       TEXT    NOT NULL,
+    object_json                 JSONB   NOT NULL,
+    PRIMARY KEY (projection, object_id, object_version)
+);
+CREATE INDEX transformed_objects_type ON transformed_objects (projection, object_type, checkpoint_sequence_number);
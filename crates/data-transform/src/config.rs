@@ -0,0 +1,231 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::StructTag;
+use serde::Deserialize;
+use sui_types::parse_sui_struct_tag;
+
+/// A pipeline decodes the events and objects of the configured types into JSON and writes them
+/// to Postgres, e.g.
+///
+/// ```yaml
+/// name: deepbook
+/// projections:
+///   - name: orders
+///     kind: event
+///     types:
+///       - "0xdee9::clob_v2::OrderPlaced"
+///       - "0xdee9::clob_v2::OrderCanceled"
+///   - name: pools
+///     kind: object
+///     types:
+///       - "0xdee9::clob_v2::Pool"
+///     table: deepbook_pools
+/// ```
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PipelineConfig {
+    /// Identifies the pipeline in the watermarks table, must not change between runs.
+    pub name: String,
+    pub projections: Vec<Projection>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Projection {
+    pub name: String,
+    pub kind: ProjectionKind,
+    /// Events or objects matching any of these types are projected.
+    pub types: Vec<TypeFilter>,
+    /// Table to write to, with one JSONB column per top-level field of the decoded values.
+    /// If unset, the decoded values are written to a single JSON column of the shared
+    /// `transformed_events` or `transformed_objects` table.
+    #[serde(default)]
+    pub table: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectionKind {
+    Event,
+    Object,
+}
+
+/// Selects Move types by package (`0x2`), module (`0x2::coin`), struct (`0x2::coin::Coin`,
+/// matching all instantiations) or fully instantiated struct (`0x2::coin::Coin<0x2::sui::SUI>`).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(try_from = "String")]
+pub enum TypeFilter {
+    Package(AccountAddress),
+    Module(AccountAddress, String),
+    Struct(AccountAddress, String, String),
+    Exact(StructTag),
+}
+
+impl PipelineConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let config: Self = serde_yaml::from_str(&fs::read_to_string(path)?)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        let mut tables = HashSet::new();
+        for projection in &self.projections {
+            if !names.insert(&projection.name) {
+                bail!("Duplicate projection {}", projection.name);
+            }
+            if projection.types.is_empty() {
+                bail!("Projection {} does not select any types", projection.name);
+            }
+            if let Some(table) = &projection.table {
+                if !is_valid_table_name(table) {
+                    bail!(
+                        "Invalid table name {table} for projection {}",
+                        projection.name
+                    );
+                }
+                if !tables.insert(table) {
+                    bail!("Table {table} is used by more than one projection");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Projection {
+    pub fn matches(&self, type_: &StructTag) -> bool {
+        self.types.iter().any(|filter| filter.matches(type_))
+    }
+}
+
+impl TypeFilter {
+    pub fn matches(&self, type_: &StructTag) -> bool {
+        match self {
+            TypeFilter::Package(address) => &type_.address == address,
+            TypeFilter::Module(address, module) => {
+                &type_.address == address && type_.module.as_str() == module
+            }
+            TypeFilter::Struct(address, module, name) => {
+                &type_.address == address
+                    && type_.module.as_str() == module
+                    && type_.name.as_str() == name
+            }
+            TypeFilter::Exact(tag) => tag == type_,
+        }
+    }
+}
+
+impl FromStr for TypeFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.contains('<') {
+            return Ok(TypeFilter::Exact(parse_sui_struct_tag(s)?));
+        }
+        let parts: Vec<_> = s.split("::").collect();
+        let address = AccountAddress::from_hex_literal(parts[0])
+            .map_err(|e| anyhow!("Invalid address in type filter {s}: {e}"))?;
+        match &parts[1..] {
+            [] => Ok(TypeFilter::Package(address)),
+            [module] => Ok(TypeFilter::Module(address, module.to_string())),
+            [module, name] => Ok(TypeFilter::Struct(
+                address,
+                module.to_string(),
+                name.to_string(),
+            )),
+            _ => Err(anyhow!("Invalid type filter {s}")),
+        }
+    }
+}
+
+impl TryFrom<String> for TypeFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+/// Table names are interpolated into SQL statements, so only plain lower case identifiers are
+/// accepted.
+fn is_valid_table_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some('a'..='z' | '_'))
+        && chars.all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_'))
+        && name.len() < 64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_filters() {
+        let coin = parse_sui_struct_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+        let matching = [
+            "0x2",
+            "0x2::coin",
+            "0x2::coin::Coin",
+            "0x2::coin::Coin<0x2::sui::SUI>",
+        ];
+        for filter in matching {
+            let filter: TypeFilter = filter.parse().unwrap();
+            assert!(filter.matches(&coin), "{filter:?}");
+        }
+        let not_matching = [
+            "0x3",
+            "0x2::balance",
+            "0x2::coin::TreasuryCap",
+            "0x2::coin::Coin<0x3::usdc::USDC>",
+        ];
+        for filter in not_matching {
+            let filter: TypeFilter = filter.parse().unwrap();
+            assert!(!filter.matches(&coin), "{filter:?}");
+        }
+        assert!("coin::Coin".parse::<TypeFilter>().is_err());
+        assert!("0x2::coin::Coin::Extra".parse::<TypeFilter>().is_err());
+    }
+
+    #[test]
+    fn parse_and_validate_config() {
+        let config: PipelineConfig = serde_yaml::from_str(
+            r#"
+            name: deepbook
+            projections:
+              - name: orders
+                kind: event
+                types: ["0xdee9::clob_v2::OrderPlaced"]
+              - name: pools
+                kind: object
+                types: ["0xdee9::clob_v2::Pool"]
+                table: deepbook_pools
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.projections[0].kind, ProjectionKind::Event);
+        assert_eq!(config.projections[0].table, None);
+        assert_eq!(
+            config.projections[1].table.as_deref(),
+            Some("deepbook_pools")
+        );
+
+        let mut invalid = config.clone();
+        invalid.projections[1].table = Some("pools; DROP TABLE x".to_string());
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = config;
+        invalid.projections[1].name = "orders".to_string();
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use anyhow::{anyhow, Result};
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::{GetModule, SyncModuleCache};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::value::MoveStruct;
+use sui_indexer::store::module_resolver::IndexerModuleResolver;
+use sui_json_rpc_types::SuiMoveStruct;
+use sui_types::move_package::MovePackage;
+use sui_types::object::{MoveObject, ObjectFormatOptions};
+
+/// Decodes BCS encoded Move values into JSON.
+///
+/// Type layouts are resolved from the packages published in the checkpoints processed so far
+/// and, for packages published earlier, from the `packages` table of a `sui-indexer` database.
+pub struct Decoder {
+    modules: RwLock<HashMap<ModuleId, Arc<CompiledModule>>>,
+    indexer_modules: Option<SyncModuleCache<IndexerModuleResolver>>,
+}
+
+impl Decoder {
+    pub fn new(indexer_modules: Option<IndexerModuleResolver>) -> Self {
+        Self {
+            modules: RwLock::new(HashMap::new()),
+            indexer_modules: indexer_modules.map(SyncModuleCache::new),
+        }
+    }
+
+    /// Makes the modules of `package` available for resolving type layouts.
+    pub fn add_package(&self, package: &MovePackage) -> Result<()> {
+        let mut modules = self.modules.write().unwrap();
+        for (name, bytes) in package.serialized_module_map() {
+            let module = CompiledModule::deserialize_with_defaults(bytes)
+                .map_err(|e| anyhow!("Failed to deserialize module {name}: {e}"))?;
+            let id = ModuleId::new(package.id().into(), Identifier::new(name.as_str())?);
+            modules.insert(id, Arc::new(module));
+        }
+        Ok(())
+    }
+
+    pub fn decode(&self, type_: &StructTag, contents: &[u8]) -> Result<serde_json::Value> {
+        let layout = MoveObject::get_layout_from_struct_tag(
+            type_.clone(),
+            ObjectFormatOptions::default(),
+            self,
+        )?;
+        let move_struct = MoveStruct::simple_deserialize(contents, &layout)?;
+        Ok(SuiMoveStruct::from(move_struct).to_json_value())
+    }
+}
+
+impl GetModule for Decoder {
+    type Error = anyhow::Error;
+    type Item = Arc<CompiledModule>;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> Result<Option<Arc<CompiledModule>>> {
+        if let Some(module) = self.modules.read().unwrap().get(id) {
+            return Ok(Some(module.clone()));
+        }
+        match &self.indexer_modules {
+            Some(indexer_modules) => indexer_modules.get_module_by_id(id),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,429 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use move_core_types::language_storage::StructTag;
+use serde_json::{json, Map, Value};
+use sui_indexer::framework::Handler;
+use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use tokio::sync::oneshot;
+use tracing::info;
+
+use crate::config::{PipelineConfig, Projection, ProjectionKind};
+use crate::decoder::Decoder;
+use crate::store::{Rows, Store, EVENTS_TABLE, OBJECTS_TABLE};
+
+/// A pipeline follows the chain in its `live` task. Checkpoint ranges, e.g. ones preceding
+/// the first checkpoint of the live task or ones to re-process after adding a projection, are
+/// covered by backfill tasks. Every task keeps a watermark of its own, so tasks can run
+/// concurrently and each of them resumes where it stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Task {
+    Live,
+    Backfill {
+        start: CheckpointSequenceNumber,
+        end: CheckpointSequenceNumber,
+    },
+}
+
+impl Task {
+    fn includes(&self, checkpoint: CheckpointSequenceNumber) -> bool {
+        match self {
+            Task::Live => true,
+            Task::Backfill { start, end } => (*start..=*end).contains(&checkpoint),
+        }
+    }
+
+    /// Whether the task has nothing left to do once `watermark` has been processed.
+    pub fn is_complete(&self, watermark: CheckpointSequenceNumber) -> bool {
+        matches!(self, Task::Backfill { end, .. } if watermark >= *end)
+    }
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Task::Live => write!(f, "live"),
+            Task::Backfill { start, end } => write!(f, "backfill-{start}-{end}"),
+        }
+    }
+}
+
+pub struct Pipeline {
+    config: PipelineConfig,
+    decoder: Decoder,
+    store: Store,
+}
+
+impl Pipeline {
+    pub fn new(config: PipelineConfig, decoder: Decoder, store: Store) -> Result<Self> {
+        for projection in &config.projections {
+            if let Some(table) = &projection.table {
+                store.create_table(table, projection.kind)?;
+            }
+        }
+        Ok(Self {
+            config,
+            decoder,
+            store,
+        })
+    }
+
+    pub fn watermark(&self, task: &Task) -> Result<Option<CheckpointSequenceNumber>> {
+        self.store.watermark(&self.config.name, &task.to_string())
+    }
+
+    /// Projects the events and objects of `checkpoints` and advances the watermark of `task`
+    /// to the last of them.
+    fn process(&self, task: &Task, checkpoints: &[CheckpointData]) -> Result<()> {
+        let Some(last) = checkpoints.last() else {
+            return Ok(());
+        };
+
+        // Packages have to be known before the types they define can be decoded, including
+        // types of events emitted in the transaction publishing the package.
+        for checkpoint in checkpoints {
+            for transaction in &checkpoint.transactions {
+                for object in &transaction.output_objects {
+                    if let Some(package) = object.data.try_as_package() {
+                        self.decoder.add_package(package)?;
+                    }
+                }
+            }
+        }
+
+        // A value that can't be decoded fails the whole batch, so that the watermark doesn't
+        // move past rows that were never written.
+        let mut tables = BTreeMap::new();
+        for checkpoint in checkpoints {
+            for transaction in &checkpoint.transactions {
+                self.project_events(checkpoint, transaction, &mut tables)?;
+                self.project_objects(checkpoint, transaction, &mut tables)?;
+            }
+        }
+
+        self.store.commit(
+            &self.config.name,
+            &task.to_string(),
+            &tables,
+            last.checkpoint_summary.sequence_number,
+        )
+    }
+
+    fn project_events(
+        &self,
+        checkpoint: &CheckpointData,
+        transaction: &CheckpointTransaction,
+        tables: &mut BTreeMap<String, Rows>,
+    ) -> Result<()> {
+        let Some(events) = &transaction.events else {
+            return Ok(());
+        };
+        for (sequence, event) in events.data.iter().enumerate() {
+            let metadata = || {
+                let mut row = Map::new();
+                row.insert(
+                    "checkpoint_sequence_number".into(),
+                    json!(checkpoint.checkpoint_summary.sequence_number),
+                );
+                row.insert(
+                    "transaction_digest".into(),
+                    json!(transaction.transaction.digest().to_string()),
+                );
+                row.insert("event_sequence".into(), json!(sequence));
+                row.insert(
+                    "timestamp_ms".into(),
+                    json!(checkpoint.checkpoint_summary.timestamp_ms),
+                );
+                row.insert("sender".into(), json!(event.sender.to_string()));
+                row.insert("package".into(), json!(event.package_id.to_string()));
+                row.insert("module".into(), json!(event.transaction_module.to_string()));
+                row.insert("event_type".into(), json!(event.type_.to_string()));
+                row
+            };
+            project(
+                &self.config.projections,
+                &self.decoder,
+                ProjectionKind::Event,
+                &event.type_,
+                &event.contents,
+                metadata,
+                tables,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn project_objects(
+        &self,
+        checkpoint: &CheckpointData,
+        transaction: &CheckpointTransaction,
+        tables: &mut BTreeMap<String, Rows>,
+    ) -> Result<()> {
+        for object in &transaction.output_objects {
+            let Some(move_object) = object.data.try_as_move() else {
+                continue;
+            };
+            let type_: StructTag = move_object.type_().clone().into();
+            let metadata = || object_metadata(checkpoint, transaction, object, &type_);
+            project(
+                &self.config.projections,
+                &self.decoder,
+                ProjectionKind::Object,
+                &type_,
+                move_object.contents(),
+                metadata,
+                tables,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Adds a row to the table of every projection of `kind` selecting `type_`. The value is only
+/// decoded if at least one projection selects it.
+fn project(
+    projections: &[Projection],
+    decoder: &Decoder,
+    kind: ProjectionKind,
+    type_: &StructTag,
+    contents: &[u8],
+    metadata: impl Fn() -> Map<String, Value>,
+    tables: &mut BTreeMap<String, Rows>,
+) -> Result<()> {
+    let mut decoded = None;
+    for projection in projections {
+        if projection.kind != kind || !projection.matches(type_) {
+            continue;
+        }
+        let value = match &decoded {
+            Some(value) => value.clone(),
+            None => {
+                let value = decoder.decode(type_, contents).with_context(|| {
+                    format!(
+                        "Failed to decode value of type {type_} for projection {}",
+                        projection.name
+                    )
+                })?;
+                decoded = Some(value.clone());
+                value
+            }
+        };
+        add_row(projection, metadata(), value, tables);
+    }
+    Ok(())
+}
+
+fn object_metadata(
+    checkpoint: &CheckpointData,
+    transaction: &CheckpointTransaction,
+    object: &Object,
+    type_: &StructTag,
+) -> Map<String, Value> {
+    let mut row = Map::new();
+    row.insert(
+        "checkpoint_sequence_number".into(),
+        json!(checkpoint.checkpoint_summary.sequence_number),
+    );
+    row.insert(
+        "transaction_digest".into(),
+        json!(transaction.transaction.digest().to_string()),
+    );
+    row.insert(
+        "timestamp_ms".into(),
+        json!(checkpoint.checkpoint_summary.timestamp_ms),
+    );
+    row.insert("object_id".into(), json!(object.id().to_string()));
+    row.insert("object_version".into(), json!(object.version().value()));
+    row.insert("object_type".into(), json!(type_.to_string()));
+    row.insert("owner".into(), json!(object.owner.to_string()));
+    row
+}
+
+/// Adds `value` to the row with the given metadata columns. Projections with a table of their
+/// own get a column per top-level field, others store the whole value in a single JSON column
+/// of the shared table.
+fn add_row(
+    projection: &Projection,
+    mut row: Map<String, Value>,
+    value: Value,
+    tables: &mut BTreeMap<String, Rows>,
+) {
+    let table = match (&projection.table, projection.kind) {
+        (Some(table), _) => table.as_str(),
+        (None, ProjectionKind::Event) => EVENTS_TABLE,
+        (None, ProjectionKind::Object) => OBJECTS_TABLE,
+    };
+    let rows = tables.entry(table.to_string()).or_default();
+
+    match (&projection.table, projection.kind) {
+        (Some(_), _) => {
+            let fields = match value {
+                Value::Object(fields) => fields,
+                value => Map::from_iter([("value".to_string(), value)]),
+            };
+            for (field, value) in fields {
+                // Metadata columns take precedence over fields of the same name.
+                if !row.contains_key(&field) {
+                    rows.field_columns.insert(field.clone());
+                    row.insert(field, value);
+                }
+            }
+        }
+        (None, kind) => {
+            let column = match kind {
+                ProjectionKind::Event => "event_json",
+                ProjectionKind::Object => "object_json",
+            };
+            row.insert("projection".into(), json!(projection.name));
+            row.insert(column.into(), value);
+        }
+    }
+    rows.rows.push(row);
+}
+
+/// Runs a [`Pipeline`] task on the checkpoints delivered by the indexer framework.
+pub struct TransformHandler {
+    pipeline: Arc<Pipeline>,
+    task: Task,
+    /// Notified once a backfill task has processed the last checkpoint of its range.
+    complete: Option<oneshot::Sender<()>>,
+}
+
+impl TransformHandler {
+    pub fn new(pipeline: Arc<Pipeline>, task: Task, complete: oneshot::Sender<()>) -> Self {
+        Self {
+            pipeline,
+            task,
+            complete: Some(complete),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for TransformHandler {
+    fn name(&self) -> &str {
+        "data-transform"
+    }
+
+    async fn process_checkpoints(&mut self, checkpoints: &[CheckpointData]) -> Result<()> {
+        let Some(last) = checkpoints.last() else {
+            return Ok(());
+        };
+        let last = last.checkpoint_summary.sequence_number;
+        let in_range: Vec<_> = checkpoints
+            .iter()
+            .filter(|checkpoint| {
+                self.task
+                    .includes(checkpoint.checkpoint_summary.sequence_number)
+            })
+            .cloned()
+            .collect();
+
+        if !in_range.is_empty() {
+            let pipeline = self.pipeline.clone();
+            let task = self.task;
+            // Decoding may fall back to reading packages from the database, so the whole batch
+            // is processed off the async runtime.
+            tokio::task::spawn_blocking(move || pipeline.process(&task, &in_range)).await??;
+        }
+
+        if self.task.is_complete(last) {
+            if let Some(complete) = self.complete.take() {
+                info!(task = %self.task, "Task complete");
+                let _ = complete.send(());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projection(table: Option<&str>) -> Projection {
+        Projection {
+            name: "orders".to_string(),
+            kind: ProjectionKind::Event,
+            types: vec!["0xdee9::clob_v2::OrderPlaced".parse().unwrap()],
+            table: table.map(str::to_string),
+        }
+    }
+
+    fn metadata() -> Map<String, Value> {
+        Map::from_iter([
+            ("event_sequence".to_string(), json!(0)),
+            ("sender".to_string(), json!("0x1")),
+        ])
+    }
+
+    #[test]
+    fn undecodable_values_fail_the_batch() {
+        // Without the package defining the type, the value can't be decoded. Skipping it would
+        // lose the row for good once the watermark moves on.
+        let decoder = Decoder::new(None);
+        let type_ = sui_types::parse_sui_struct_tag("0xdee9::clob_v2::OrderPlaced").unwrap();
+        let mut tables = BTreeMap::new();
+        let result = project(
+            &[projection(None)],
+            &decoder,
+            ProjectionKind::Event,
+            &type_,
+            &[0; 32],
+            metadata,
+            &mut tables,
+        );
+        assert!(result.is_err());
+        assert!(tables.is_empty());
+
+        // Values no projection selects are not decoded at all.
+        project(
+            &[projection(None)],
+            &decoder,
+            ProjectionKind::Object,
+            &type_,
+            &[0; 32],
+            metadata,
+            &mut tables,
+        )
+        .unwrap();
+        assert!(tables.is_empty());
+    }
+
+    #[test]
+    fn task_names_and_ranges() {
+        let backfill = Task::Backfill { start: 10, end: 20 };
+        assert_eq!(Task::Live.to_string(), "live");
+        assert_eq!(backfill.to_string(), "backfill-10-20");
+        assert!(backfill.includes(10) && backfill.includes(20));
+        assert!(!backfill.includes(9) && !backfill.includes(21));
+        assert!(!backfill.is_complete(19));
+        assert!(backfill.is_complete(20));
+        assert!(!Task::Live.is_complete(u64::MAX));
+    }
+
+    #[test]
+    fn rows_of_shared_and_own_tables() {
+        let value = json!({ "price": "100", "sender": "0x2" });
+        let mut tables = BTreeMap::new();
+
+        add_row(&projection(None), metadata(), value.clone(), &mut tables);
+        let shared = &tables[EVENTS_TABLE];
+        assert!(shared.field_columns.is_empty());
+        assert_eq!(shared.rows[0]["projection"], json!("orders"));
+        assert_eq!(shared.rows[0]["event_json"], value);
+
+        add_row(&projection(Some("orders")), metadata(), value, &mut tables);
+        let own = &tables["orders"];
+        assert_eq!(own.field_columns.iter().collect::<Vec<_>>(), vec!["price"]);
+        assert_eq!(own.rows[0]["price"], json!("100"));
+        assert_eq!(own.rows[0]["sender"], json!("0x1"));
+    }
+}
@@ -1,22 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-pub mod models;
-pub mod schema;
-
-use diesel::pg::PgConnection;
-use diesel::prelude::*;
-use diesel::r2d2::ConnectionManager;
-use dotenvy::dotenv;
-use std::env;
+//! Projects Move events and objects of configured types into query-friendly Postgres tables.
+//! See [`config::PipelineConfig`] for how pipelines are configured.
 
-pub type PgConnectionPool = diesel::r2d2::Pool<ConnectionManager<PgConnection>>;
-pub type PgPoolConnection = diesel::r2d2::PooledConnection<ConnectionManager<PgConnection>>;
-
-pub fn establish_connection() -> PgConnection {
-    dotenv().ok();
+pub mod config;
+pub mod decoder;
+pub mod handler;
+pub mod schema;
+pub mod store;
 
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    PgConnection::establish(&database_url)
-        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url))
-}
+pub use config::PipelineConfig;
+pub use handler::{Pipeline, Task, TransformHandler};
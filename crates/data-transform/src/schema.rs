@@ -2,22 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 diesel::table! {
-    events (id) {
-        id -> Int8,
-        transaction_digest -> Varchar,
-        event_sequence -> Int8,
-        sender -> Varchar,
-        package -> Varchar,
-        module -> Text,
-        event_type -> Text,
-        event_time_ms -> Nullable<Int8>,
-        event_bcs -> Bytea,
-    }
-}
-
-diesel::table! {
-    events_json (id) {
-        id -> Int8,
-        event_json -> Varchar,
+    transform_watermarks (pipeline, task) {
+        pipeline -> Text,
+        task -> Text,
+        checkpoint_sequence_number -> Int8,
     }
 }
@@ -0,0 +1,179 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel::upsert::excluded;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use serde_json::{Map, Value};
+use sui_indexer::{get_pg_pool_connection, PgConnectionPool};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::config::ProjectionKind;
+use crate::schema::transform_watermarks;
+
+const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+pub const EVENTS_TABLE: &str = "transformed_events";
+pub const OBJECTS_TABLE: &str = "transformed_objects";
+
+/// Columns of projection specific tables, before the columns of the decoded fields.
+const EVENT_COLUMNS: &str = "
+    checkpoint_sequence_number  BIGINT  NOT NULL,
+    transaction_digest          TEXT    NOT NULL,
+    event_sequence              BIGINT  NOT NULL,
+    timestamp_ms                BIGINT  NOT NULL,
+    sender                      TEXT    NOT NULL,
+    package                     TEXT    NOT NULL,
+    module                      TEXT    NOT NULL,
+    event_type                  TEXT    NOT NULL,
+    PRIMARY KEY (transaction_digest, event_sequence)";
+
+const OBJECT_COLUMNS: &str = "
+    checkpoint_sequence_number  BIGINT  NOT NULL,
+    transaction_digest          TEXT    NOT NULL,
+    timestamp_ms                BIGINT  NOT NULL,
+    object_id                   TEXT    NOT NULL,
+    object_version              BIGINT  NOT NULL,
+    object_type                 TEXT    NOT NULL,
+    owner                       TEXT    NOT NULL,
+    PRIMARY KEY (object_id, object_version)";
+
+/// Rows to insert into a single table, as JSON objects keyed by column name.
+#[derive(Debug, Default)]
+pub struct Rows {
+    pub rows: Vec<Map<String, Value>>,
+    /// Columns holding decoded fields, which are added to the table on first use.
+    pub field_columns: BTreeSet<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = transform_watermarks)]
+struct StoredWatermark<'a> {
+    pipeline: &'a str,
+    task: &'a str,
+    checkpoint_sequence_number: i64,
+}
+
+pub struct Store {
+    pool: PgConnectionPool,
+    /// Field columns known to exist, per table.
+    field_columns: Mutex<HashMap<String, HashSet<String>>>,
+}
+
+impl Store {
+    pub fn new(pool: PgConnectionPool) -> Self {
+        Self {
+            pool,
+            field_columns: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn run_migrations(&self) -> Result<()> {
+        get_pg_pool_connection(&self.pool)?
+            .run_pending_migrations(MIGRATIONS)
+            .map_err(|e| anyhow!("Failed to run migrations {e}"))?;
+        Ok(())
+    }
+
+    pub fn create_table(&self, table: &str, kind: ProjectionKind) -> Result<()> {
+        let columns = match kind {
+            ProjectionKind::Event => EVENT_COLUMNS,
+            ProjectionKind::Object => OBJECT_COLUMNS,
+        };
+        diesel::sql_query(format!(
+            "CREATE TABLE IF NOT EXISTS \"{table}\" ({columns})"
+        ))
+        .execute(&mut get_pg_pool_connection(&self.pool)?)?;
+        Ok(())
+    }
+
+    pub fn watermark(
+        &self,
+        pipeline: &str,
+        task: &str,
+    ) -> Result<Option<CheckpointSequenceNumber>> {
+        let watermark = transform_watermarks::table
+            .filter(transform_watermarks::pipeline.eq(pipeline))
+            .filter(transform_watermarks::task.eq(task))
+            .select(transform_watermarks::checkpoint_sequence_number)
+            .first::<i64>(&mut get_pg_pool_connection(&self.pool)?)
+            .optional()?;
+        Ok(watermark.map(|watermark| watermark as CheckpointSequenceNumber))
+    }
+
+    /// Writes `tables` and advances the watermark of `task` to `watermark` in one transaction,
+    /// so that a restarted task continues exactly where it left off. Rows that already exist,
+    /// e.g. because overlapping ranges were backfilled, are left untouched.
+    pub fn commit(
+        &self,
+        pipeline: &str,
+        task: &str,
+        tables: &BTreeMap<String, Rows>,
+        watermark: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        let mut conn = get_pg_pool_connection(&self.pool)?;
+        let mut new_columns: Vec<(&String, &String)> = vec![];
+        {
+            let known = self.field_columns.lock().unwrap();
+            for (table, rows) in tables {
+                for column in &rows.field_columns {
+                    if !known.get(table).is_some_and(|known| known.contains(column)) {
+                        new_columns.push((table, column));
+                    }
+                }
+            }
+        }
+
+        conn.build_transaction()
+            .read_write()
+            .run::<_, diesel::result::Error, _>(|conn| {
+                for (table, column) in &new_columns {
+                    diesel::sql_query(format!(
+                        "ALTER TABLE \"{table}\" ADD COLUMN IF NOT EXISTS \"{column}\" JSONB"
+                    ))
+                    .execute(conn)?;
+                }
+                for (table, rows) in tables {
+                    if rows.rows.is_empty() {
+                        continue;
+                    }
+                    let rows = Value::Array(rows.rows.iter().cloned().map(Value::Object));
+                    diesel::sql_query(format!(
+                        "INSERT INTO \"{table}\" \
+                         SELECT * FROM jsonb_populate_recordset(NULL::\"{table}\", $1::jsonb) \
+                         ON CONFLICT DO NOTHING"
+                    ))
+                    .bind::<Text, _>(rows.to_string())
+                    .execute(conn)?;
+                }
+                diesel::insert_into(transform_watermarks::table)
+                    .values(&StoredWatermark {
+                        pipeline,
+                        task,
+                        checkpoint_sequence_number: watermark as i64,
+                    })
+                    .on_conflict((transform_watermarks::pipeline, transform_watermarks::task))
+                    .do_update()
+                    .set(
+                        transform_watermarks::checkpoint_sequence_number
+                            .eq(excluded(transform_watermarks::checkpoint_sequence_number)),
+                    )
+                    .execute(conn)?;
+                Ok(())
+            })?;
+
+        let mut known = self.field_columns.lock().unwrap();
+        for (table, column) in new_columns {
+            known
+                .entry(table.clone())
+                .or_default()
+                .insert(column.clone());
+        }
+        Ok(())
+    }
+}
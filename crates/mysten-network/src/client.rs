@@ -1,12 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::fault_injection::{self, NodeId};
 use crate::{
     config::Config,
     multiaddr::{parse_dns, parse_ip4, parse_ip6, Multiaddr, Protocol},
 };
 use eyre::{eyre, Context, Result};
+use tokio::net::TcpStream;
+use tonic::codegen::BoxFuture;
 use tonic::transport::{Channel, Endpoint, Uri};
+use tower::Service;
 
 pub async fn connect(address: &Multiaddr) -> Result<Channel> {
     let channel = endpoint_from_multiaddr(address)?.connect().await?;
@@ -114,6 +118,12 @@ impl MyEndpoint {
                 }));
         }
 
+        if let Some(node) = fault_injection_node() {
+            return self
+                .endpoint
+                .connect_with_connector_lazy(fault_injection_connector(node));
+        }
+
         self.endpoint.connect_lazy()
     }
 
@@ -132,10 +142,51 @@ impl MyEndpoint {
                 .map_err(Into::into);
         }
 
+        if let Some(node) = fault_injection_node() {
+            return self
+                .endpoint
+                .connect_with_connector(fault_injection_connector(node))
+                .await
+                .map_err(Into::into);
+        }
+
         self.endpoint.connect().await.map_err(Into::into)
     }
 }
 
+/// The node opening the connection, if its connections should be subject to fault injection.
+fn fault_injection_node() -> Option<NodeId> {
+    fault_injection::injector()
+        .is_enabled()
+        .then(fault_injection::current_node)
+        .flatten()
+}
+
+/// Connects over TCP and records the local address of every connection, so that servers can
+/// attribute incoming requests to `node`.
+fn fault_injection_connector(
+    node: NodeId,
+) -> impl Service<
+    Uri,
+    Response = TcpStream,
+    Error = std::io::Error,
+    Future = BoxFuture<TcpStream, std::io::Error>,
+> + Clone
+       + Send
+       + 'static {
+    tower::service_fn(move |uri: Uri| -> BoxFuture<TcpStream, std::io::Error> {
+        let node = node.clone();
+        Box::pin(async move {
+            let host = uri.host().unwrap_or_default().trim_matches(['[', ']']);
+            let port = uri.port_u16().unwrap_or(80);
+            let stream = TcpStream::connect((host, port)).await?;
+            stream.set_nodelay(true)?;
+            fault_injection::register_connection(stream.local_addr()?, node);
+            Ok(stream)
+        })
+    })
+}
+
 fn apply_config_to_endpoint(config: &Config, mut endpoint: Endpoint) -> Endpoint {
     if let Some(limit) = config.concurrency_limit_per_connection {
         endpoint = endpoint.concurrency_limit(limit);
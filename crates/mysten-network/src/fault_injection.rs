@@ -0,0 +1,347 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Process-wide network fault injection for in-process test clusters.
+//!
+//! Every node of an in-process cluster runs on its own threads, so the node that is currently
+//! executing is tracked in a thread local (see [`set_current_node`]). Inbound anemo and tonic
+//! requests are attributed to the sending node through the peer id of the anemo connection or
+//! the local address of the tonic client connection, both of which are registered while the
+//! networks are set up. With both ends of a request known, the [`FaultInjectionLayer`] consults
+//! the global [`FaultInjector`] and either delivers the request, possibly after a delay, or
+//! drops it.
+//!
+//! A dropped request is never handed to the inner service and never answered, exactly as if
+//! it had been lost on the wire; the caller observes its own request timeout.
+//!
+//! Fault injection is disabled by default, in which case the layers only do a single atomic
+//! load per request.
+
+use once_cell::sync::Lazy;
+use rand::Rng;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tonic::codegen::BoxFuture;
+use tower::{Layer, Service};
+
+/// Identifies a node of a test cluster. Callers are free to pick any stable naming scheme as long
+/// as the same name is used for [`set_current_node`] and for configuring faults.
+pub type NodeId = String;
+
+static INJECTOR: Lazy<FaultInjector> = Lazy::new(FaultInjector::default);
+
+thread_local! {
+    static CURRENT_NODE: RefCell<Option<NodeId>> = RefCell::new(None);
+}
+
+/// Returns the process-wide fault injector.
+pub fn injector() -> &'static FaultInjector {
+    &INJECTOR
+}
+
+/// Marks the calling thread as running on behalf of `node`.
+pub fn set_current_node(node: Option<NodeId>) {
+    CURRENT_NODE.with(|current| *current.borrow_mut() = node);
+}
+
+/// The node the calling thread runs on behalf of, if any.
+pub fn current_node() -> Option<NodeId> {
+    CURRENT_NODE.with(|current| current.borrow().clone())
+}
+
+/// Associates an anemo peer id with the current node. Does nothing if fault injection is
+/// disabled or the thread does not belong to a node.
+pub fn register_peer(peer_id: anemo::PeerId) {
+    if let Some(node) = current_node().filter(|_| injector().is_enabled()) {
+        injector()
+            .state
+            .write()
+            .unwrap()
+            .peers
+            .insert(peer_id, node);
+    }
+}
+
+/// Associates the local address of an outgoing tonic connection with `node`.
+pub(crate) fn register_connection(local_addr: SocketAddr, node: NodeId) {
+    injector()
+        .state
+        .write()
+        .unwrap()
+        .connections
+        .insert(local_addr, node);
+}
+
+/// Faults applied to every request travelling over a directed link between two nodes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkFaults {
+    /// Fixed delay added to every request.
+    pub latency: Duration,
+    /// Upper bound of a uniformly distributed delay added on top of `latency`.
+    pub jitter: Duration,
+    /// Probability in `[0, 1]` that a request is dropped.
+    pub drop_rate: f64,
+}
+
+/// What should happen to a single request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Deliver(Duration),
+    Drop,
+}
+
+#[derive(Default)]
+pub struct FaultInjector {
+    enabled: AtomicBool,
+    state: RwLock<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Each partition splits its nodes into groups that cannot reach each other. Nodes not
+    /// mentioned by a partition are unaffected by it.
+    partitions: Vec<Vec<BTreeSet<NodeId>>>,
+    links: HashMap<(NodeId, NodeId), LinkFaults>,
+    peers: HashMap<anemo::PeerId, NodeId>,
+    connections: HashMap<SocketAddr, NodeId>,
+}
+
+impl FaultInjector {
+    /// Turns fault injection on. Must be called before the nodes are started so that their
+    /// connections get registered.
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Cuts every group of nodes off from the nodes in the other groups, in both directions.
+    pub fn partition(&self, groups: Vec<BTreeSet<NodeId>>) {
+        self.state.write().unwrap().partitions.push(groups);
+    }
+
+    /// Removes all partitions that involve any of `nodes`.
+    pub fn heal(&self, nodes: &BTreeSet<NodeId>) {
+        self.state
+            .write()
+            .unwrap()
+            .partitions
+            .retain(|groups| !groups.iter().any(|group| !group.is_disjoint(nodes)));
+    }
+
+    /// Sets the faults for requests sent from `from` to `to`, replacing any previous ones.
+    pub fn set_link_faults(&self, from: NodeId, to: NodeId, faults: LinkFaults) {
+        assert!(
+            (0.0..=1.0).contains(&faults.drop_rate),
+            "drop rate must be within [0, 1]"
+        );
+        self.state.write().unwrap().links.insert((from, to), faults);
+    }
+
+    /// Removes all partitions and link faults that involve any of `nodes`.
+    pub fn clear(&self, nodes: &BTreeSet<NodeId>) {
+        self.heal(nodes);
+        self.state
+            .write()
+            .unwrap()
+            .links
+            .retain(|(from, to), _| !nodes.contains(from) && !nodes.contains(to));
+    }
+
+    /// Decides the fate of a request sent from `from` to `to`.
+    pub fn verdict(&self, from: &NodeId, to: &NodeId) -> Verdict {
+        let state = self.state.read().unwrap();
+
+        let partitioned = state.partitions.iter().any(|groups| {
+            let from_group = groups.iter().position(|group| group.contains(from));
+            let to_group = groups.iter().position(|group| group.contains(to));
+            matches!((from_group, to_group), (Some(a), Some(b)) if a != b)
+        });
+        if partitioned {
+            return Verdict::Drop;
+        }
+
+        let Some(faults) = state.links.get(&(from.clone(), to.clone())) else {
+            return Verdict::Deliver(Duration::ZERO);
+        };
+        let mut rng = rand::thread_rng();
+        if faults.drop_rate > 0.0 && rng.gen_bool(faults.drop_rate) {
+            return Verdict::Drop;
+        }
+        let jitter = faults.jitter.mul_f64(rng.gen::<f64>());
+        Verdict::Deliver(faults.latency + jitter)
+    }
+
+    fn peer_node(&self, peer_id: &anemo::PeerId) -> Option<NodeId> {
+        self.state.read().unwrap().peers.get(peer_id).cloned()
+    }
+
+    fn connection_node(&self, remote_addr: &SocketAddr) -> Option<NodeId> {
+        self.state
+            .read()
+            .unwrap()
+            .connections
+            .get(remote_addr)
+            .cloned()
+    }
+}
+
+/// Server side layer applying the configured faults to inbound requests.
+pub struct FaultInjectionLayer<Request> {
+    remote_node: fn(&Request) -> Option<NodeId>,
+}
+
+impl<Request> Clone for FaultInjectionLayer<Request> {
+    fn clone(&self) -> Self {
+        Self {
+            remote_node: self.remote_node,
+        }
+    }
+}
+
+impl FaultInjectionLayer<anemo::Request<bytes::Bytes>> {
+    pub fn anemo() -> Self {
+        Self {
+            remote_node: |request| {
+                request
+                    .peer_id()
+                    .and_then(|peer_id| injector().peer_node(peer_id))
+            },
+        }
+    }
+}
+
+impl FaultInjectionLayer<http::Request<tonic::transport::Body>> {
+    pub fn tonic() -> Self {
+        Self {
+            remote_node: |request| {
+                request
+                    .extensions()
+                    .get::<tonic::transport::server::TcpConnectInfo>()
+                    .and_then(|info| info.remote_addr())
+                    .and_then(|addr| injector().connection_node(&addr))
+            },
+        }
+    }
+}
+
+impl<S, Request> Layer<S> for FaultInjectionLayer<Request> {
+    type Service = FaultInjection<S, Request>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        FaultInjection {
+            inner,
+            remote_node: self.remote_node,
+            _request: PhantomData,
+        }
+    }
+}
+
+pub struct FaultInjection<S, Request> {
+    inner: S,
+    remote_node: fn(&Request) -> Option<NodeId>,
+    _request: PhantomData<fn(Request)>,
+}
+
+impl<S: Clone, Request> Clone for FaultInjection<S, Request> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            remote_node: self.remote_node,
+            _request: PhantomData,
+        }
+    }
+}
+
+impl<S, Request> Service<Request> for FaultInjection<S, Request>
+where
+    S: Service<Request>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<S::Response, S::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if !injector().is_enabled() {
+            return Box::pin(self.inner.call(request));
+        }
+
+        let verdict = match (current_node(), (self.remote_node)(&request)) {
+            (Some(local), Some(remote)) => injector().verdict(&remote, &local),
+            _ => Verdict::Deliver(Duration::ZERO),
+        };
+        match verdict {
+            Verdict::Drop => Box::pin(futures::future::pending()),
+            Verdict::Deliver(delay) if delay.is_zero() => Box::pin(self.inner.call(request)),
+            Verdict::Deliver(delay) => {
+                let response = self.inner.call(request);
+                Box::pin(async move {
+                    tokio::time::sleep(delay).await;
+                    response.await
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn nodes(names: &[&str]) -> BTreeSet<NodeId> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn partitions_and_link_faults() {
+        let injector = FaultInjector::default();
+        let (a, b, c) = ("a".to_string(), "b".to_string(), "c".to_string());
+
+        injector.partition(vec![nodes(&["a"]), nodes(&["b"])]);
+        assert_eq!(injector.verdict(&a, &b), Verdict::Drop);
+        assert_eq!(injector.verdict(&b, &a), Verdict::Drop);
+        assert_eq!(injector.verdict(&a, &c), Verdict::Deliver(Duration::ZERO));
+
+        injector.heal(&nodes(&["b"]));
+        assert_eq!(injector.verdict(&a, &b), Verdict::Deliver(Duration::ZERO));
+
+        let latency = Duration::from_millis(100);
+        injector.set_link_faults(
+            a.clone(),
+            b.clone(),
+            LinkFaults {
+                latency,
+                ..Default::default()
+            },
+        );
+        assert_eq!(injector.verdict(&a, &b), Verdict::Deliver(latency));
+        assert_eq!(injector.verdict(&b, &a), Verdict::Deliver(Duration::ZERO));
+
+        injector.set_link_faults(
+            b.clone(),
+            c.clone(),
+            LinkFaults {
+                drop_rate: 1.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(injector.verdict(&b, &c), Verdict::Drop);
+
+        injector.clear(&nodes(&["a", "b", "c"]));
+        assert_eq!(injector.verdict(&a, &b), Verdict::Deliver(Duration::ZERO));
+        assert_eq!(injector.verdict(&b, &c), Verdict::Deliver(Duration::ZERO));
+    }
+}
@@ -3,6 +3,7 @@
 pub mod client;
 pub mod codec;
 pub mod config;
+pub mod fault_injection;
 pub mod metrics;
 pub mod multiaddr;
 pub mod server;
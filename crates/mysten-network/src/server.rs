@@ -6,6 +6,7 @@ use crate::metrics::{
 };
 use crate::{
     config::Config,
+    fault_injection::FaultInjectionLayer,
     multiaddr::{parse_dns, parse_ip4, parse_ip6, Multiaddr, Protocol},
 };
 use eyre::{eyre, Result};
@@ -61,7 +62,10 @@ type WrapperService<M> = Stack<
                     RequestLifetimeLayer<M>,
                     Stack<
                         Either<LoadShedLayer, Identity>,
-                        Stack<Either<GlobalConcurrencyLimitLayer, Identity>, Identity>,
+                        Stack<
+                            Either<GlobalConcurrencyLimitLayer, Identity>,
+                            Stack<FaultInjectionLayer<Request<Body>>, Identity>,
+                        >,
                     >,
                 >,
             >,
@@ -108,6 +112,7 @@ impl<M: MetricsCallbackProvider> ServerBuilder<M> {
         }
 
         let layer = ServiceBuilder::new()
+            .layer(FaultInjectionLayer::tonic())
             .option_layer(global_concurrency_limit)
             .option_layer(load_shed)
             .layer(RequestLifetimeLayer { metrics_provider })
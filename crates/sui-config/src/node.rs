@@ -77,6 +77,10 @@ pub struct NodeConfig {
     #[serde(default = "default_enable_index_processing")]
     pub enable_index_processing: bool,
 
+    /// If set, events older than this many seconds are dropped from the event indexes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_index_retention_secs: Option<u64>,
+
     #[serde(default)]
     pub grpc_load_shed: Option<bool>,
 
@@ -162,6 +166,9 @@ pub struct NodeConfig {
 
     #[serde(default = "default_overload_threshold_config")]
     pub overload_threshold_config: OverloadThresholdConfig,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_cache_config: Option<ExecutionCacheConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
@@ -697,6 +704,30 @@ fn default_overload_threshold_config() -> OverloadThresholdConfig {
     OverloadThresholdConfig::default()
 }
 
+/// Configuration of the in-memory cache for the outputs of executed transactions. When set,
+/// executed objects, locks and markers are served from memory and written to the database in
+/// batches at checkpoint boundaries instead of after every transaction.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExecutionCacheConfig {
+    /// Number of executed transactions after which the cache is flushed even if no checkpoint
+    /// boundary was reached.
+    #[serde(default = "default_execution_cache_max_pending_transactions")]
+    pub max_pending_transactions: usize,
+}
+
+impl Default for ExecutionCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_transactions: default_execution_cache_max_pending_transactions(),
+        }
+    }
+}
+
+fn default_execution_cache_max_pending_transactions() -> usize {
+    10_000
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq)]
 pub struct Genesis {
     #[serde(flatten)]
@@ -179,6 +179,20 @@ pub struct StateSyncConfig {
     /// If unspecified, this will default to no limit.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub get_checkpoint_contents_per_checkpoint_limit: Option<usize>,
+
+    /// Number of consecutive failed requests (timeouts or errors) after which a peer is
+    /// temporarily banned from being used for syncing. Peers that serve data that fails
+    /// verification are banned right away.
+    ///
+    /// If unspecified, this will default to `5`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_ban_failure_threshold: Option<u32>,
+
+    /// How long a misbehaving peer stays banned.
+    ///
+    /// If unspecified, this will default to `300,000` milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_ban_duration_ms: Option<u64>,
 }
 
 impl StateSyncConfig {
@@ -237,6 +251,19 @@ impl StateSyncConfig {
             .map(Duration::from_millis)
             .unwrap_or(DEFAULT_TIMEOUT)
     }
+
+    pub fn peer_ban_failure_threshold(&self) -> u32 {
+        const PEER_BAN_FAILURE_THRESHOLD: u32 = 5;
+
+        self.peer_ban_failure_threshold
+            .unwrap_or(PEER_BAN_FAILURE_THRESHOLD)
+    }
+
+    pub fn peer_ban_duration(&self) -> Duration {
+        const PEER_BAN_DURATION_MS: u64 = 300_000; // 5 minutes
+
+        Duration::from_millis(self.peer_ban_duration_ms.unwrap_or(PEER_BAN_DURATION_MS))
+    }
 }
 
 /// Access Type of a node.
@@ -174,6 +174,8 @@ pub mod test_authority_builder;
 
 pub(crate) mod authority_notify_read;
 pub(crate) mod authority_store;
+pub(crate) mod execution_cache;
+pub(crate) mod shared_object_congestion_tracker;
 
 pub static CHAIN_IDENTIFIER: OnceCell<ChainIdentifier> = OnceCell::new();
 
@@ -2237,6 +2239,7 @@ impl AuthorityState {
         self.committee_store.insert_new_committee(&new_committee)?;
         let db = self.db();
         let mut execution_lock = db.execution_lock_for_reconfiguration().await;
+        db.flush_execution_cache()?;
         self.revert_uncommitted_epoch_transactions(cur_epoch_store)
             .await?;
         self.check_system_consistency(
@@ -3571,11 +3574,20 @@ impl AuthorityState {
 
         // The insertion to epoch_store is not atomic with the insertion to the perpetual store. This is OK because
         // we insert to the epoch store first. And during lookups we always look up in the perpetual store first.
-        epoch_store.insert_tx_cert_and_effects_signature(
-            tx_digest,
-            certificate.certificate_sig(),
-            effects_sig.as_ref(),
-        )?;
+        // When the execution cache is enabled, the signatures are persisted when the cache is flushed instead.
+        if self.database.is_execution_cache_enabled() {
+            epoch_store.insert_unflushed_tx_cert_and_effects_signature(
+                tx_digest,
+                certificate.certificate_sig(),
+                effects_sig.as_ref(),
+            );
+        } else {
+            epoch_store.insert_tx_cert_and_effects_signature(
+                tx_digest,
+                certificate.certificate_sig(),
+                effects_sig.as_ref(),
+            )?;
+        }
 
         // Allow testing what happens if we crash here.
         fail_point_async!("crash");
@@ -3585,7 +3597,7 @@ impl AuthorityState {
                 inner_temporary_store,
                 &certificate.clone().into_unsigned(),
                 effects,
-                epoch_store.epoch(),
+                epoch_store,
             )
             .await
             .tap_ok(|_| {
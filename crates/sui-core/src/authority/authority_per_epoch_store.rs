@@ -39,6 +39,7 @@ use typed_store::traits::{TableSummary, TypedStoreDebug};
 
 use super::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::{EpochFlag, EpochStartConfiguration};
+use crate::authority::shared_object_congestion_tracker::SharedObjectCongestionTracker;
 use crate::authority::{AuthorityStore, ResolverWrapper};
 use crate::checkpoints::{
     BuilderCheckpointSummary, CheckpointCommitHeight, CheckpointServiceNotify, EpochStats,
@@ -63,7 +64,7 @@ use prometheus::IntCounter;
 use std::str::FromStr;
 use sui_execution::{self, Executor};
 use sui_macros::fail_point;
-use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_protocol_config::{Chain, ConsensusTransactionOrdering, ProtocolConfig, ProtocolVersion};
 use sui_storage::mutex_table::{MutexGuard, MutexTable};
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
 use sui_types::executable_transaction::{
@@ -247,6 +248,11 @@ pub struct AuthorityPerEpochStore {
     /// In particular, this lock is always acquired after taking read or write lock on reconfig state
     pending_consensus_certificates: Mutex<HashSet<TransactionDigest>>,
 
+    /// Certificate and effects signatures of executed transactions whose outputs are held by the
+    /// execution cache. They are persisted when the cache is flushed, and served from here until
+    /// the transactions are removed from `pending_execution`.
+    unflushed_signatures: RwLock<HashMap<TransactionDigest, UnflushedSignatures>>,
+
     /// MutexTable for transaction locks (prevent concurrent execution of same transaction)
     mutex_table: MutexTable<TransactionDigest>,
 
@@ -272,6 +278,12 @@ pub struct AuthorityPerEpochStore {
     jwk_aggregator: Mutex<JwkAggregator>,
 }
 
+#[derive(Clone)]
+struct UnflushedSignatures {
+    cert_sig: Option<AuthorityStrongQuorumSignInfo>,
+    effects_signature: Option<AuthoritySignInfo>,
+}
+
 /// AuthorityEpochTables contains tables that contain data that is only valid within an epoch.
 #[derive(DBMapUtils)]
 pub struct AuthorityEpochTables {
@@ -437,6 +449,7 @@ pub enum DeferralKey {
 }
 
 impl DeferralKey {
+    #[allow(dead_code)]
     fn new_for_randomness_round(future_round: u64, deferred_from_round: u64) -> Self {
         Self::RandomnessRound {
             future_round,
@@ -464,10 +477,12 @@ impl DeferralKey {
         )
     }
 
-    fn range_for_consensus_round(future_round: u64) -> (Self, Self) {
+    // Consensus commits are not made in consecutive rounds, so this returns the range of all
+    // transactions deferred to `future_round` or to any round before it.
+    fn range_for_up_to_consensus_round(future_round: u64) -> (Self, Self) {
         (
             Self::ConsensusRound {
-                future_round,
+                future_round: 0,
                 deferred_from_round: 0,
             },
             Self::ConsensusRound {
@@ -748,6 +763,7 @@ impl AuthorityPerEpochStore {
             checkpoint_state_notify_read: NotifyRead::new(),
             end_of_publish: Mutex::new(end_of_publish),
             pending_consensus_certificates: Mutex::new(pending_consensus_certificates),
+            unflushed_signatures: RwLock::new(HashMap::new()),
             mutex_table: MutexTable::new(MUTEX_TABLE_SIZE),
             epoch_open_time: current_time,
             epoch_close_time: Default::default(),
@@ -943,17 +959,85 @@ impl AuthorityPerEpochStore {
         Ok(())
     }
 
+    /// Records the signatures of a transaction whose outputs are held by the execution cache,
+    /// without persisting them. See `persist_unflushed_signatures`.
+    pub fn insert_unflushed_tx_cert_and_effects_signature(
+        &self,
+        tx_digest: &TransactionDigest,
+        cert_sig: Option<&AuthorityStrongQuorumSignInfo>,
+        effects_signature: Option<&AuthoritySignInfo>,
+    ) {
+        self.unflushed_signatures.write().insert(
+            *tx_digest,
+            UnflushedSignatures {
+                cert_sig: cert_sig.cloned(),
+                effects_signature: effects_signature.cloned(),
+            },
+        );
+    }
+
+    /// Persists the signatures of transactions from the execution cache. Called before their
+    /// outputs are flushed, so that signatures are never missing for a persisted transaction.
+    pub fn persist_unflushed_signatures(&self, digests: &[TransactionDigest]) -> SuiResult {
+        let unflushed = self.unflushed_signatures.read();
+        let mut batch = self.tables.effects_signatures.batch();
+        for (digest, signatures) in digests
+            .iter()
+            .filter_map(|digest| Some((digest, unflushed.get(digest)?)))
+        {
+            batch.insert_batch(
+                &self.tables.transaction_cert_signatures,
+                signatures.cert_sig.iter().map(|sig| (digest, sig)),
+            )?;
+            batch.insert_batch(
+                &self.tables.effects_signatures,
+                signatures.effects_signature.iter().map(|sig| (digest, sig)),
+            )?;
+        }
+        batch.write()?;
+        Ok(())
+    }
+
+    /// Removes transactions from `pending_execution` once their outputs have been flushed from
+    /// the execution cache, so that they are re-executed if the node crashes before the flush.
+    pub fn remove_flushed_pending_executions(&self, digests: &[TransactionDigest]) -> SuiResult {
+        let mut batch = self.tables.pending_execution.batch();
+        batch.delete_batch(&self.tables.pending_execution, digests)?;
+        batch.write()?;
+        let mut unflushed = self.unflushed_signatures.write();
+        for digest in digests {
+            unflushed.remove(digest);
+        }
+        Ok(())
+    }
+
     pub fn effects_signatures_exists<'a>(
         &self,
         digests: impl IntoIterator<Item = &'a TransactionDigest>,
     ) -> Result<Vec<bool>, TypedStoreError> {
-        self.tables.effects_signatures.multi_contains_keys(digests)
+        let digests: Vec<_> = digests.into_iter().collect();
+        let mut exists = self
+            .tables
+            .effects_signatures
+            .multi_contains_keys(digests.iter().copied())?;
+        let unflushed = self.unflushed_signatures.read();
+        for (exists, digest) in exists.iter_mut().zip(digests) {
+            *exists |= unflushed
+                .get(digest)
+                .is_some_and(|signatures| signatures.effects_signature.is_some());
+        }
+        Ok(exists)
     }
 
     pub fn get_effects_signature(
         &self,
         tx_digest: &TransactionDigest,
     ) -> SuiResult<Option<AuthoritySignInfo>> {
+        if let Some(signatures) = self.unflushed_signatures.read().get(tx_digest) {
+            if signatures.effects_signature.is_some() {
+                return Ok(signatures.effects_signature.clone());
+            }
+        }
         Ok(self.tables.effects_signatures.get(tx_digest)?)
     }
 
@@ -961,6 +1045,11 @@ impl AuthorityPerEpochStore {
         &self,
         tx_digest: &TransactionDigest,
     ) -> Result<Option<AuthorityStrongQuorumSignInfo>, TypedStoreError> {
+        if let Some(signatures) = self.unflushed_signatures.read().get(tx_digest) {
+            if signatures.cert_sig.is_some() {
+                return Ok(signatures.cert_sig.clone());
+            }
+        }
         self.tables.transaction_cert_signatures.get(tx_digest)
     }
 
@@ -1269,12 +1358,12 @@ impl AuthorityPerEpochStore {
         self.load_deferred_transactions(batch, min, max)
     }
 
-    fn load_deferred_transactions_for_consensus_round(
+    fn load_deferred_transactions_for_up_to_consensus_round(
         &self,
         batch: &mut DBBatch,
         consensus_round: u64,
     ) -> SuiResult<Vec<VerifiedSequencedConsensusTransaction>> {
-        let (min, max) = DeferralKey::range_for_consensus_round(consensus_round);
+        let (min, max) = DeferralKey::range_for_up_to_consensus_round(consensus_round);
         self.load_deferred_transactions(batch, min, max)
     }
 
@@ -1314,12 +1403,31 @@ impl AuthorityPerEpochStore {
         Ok(txns)
     }
 
-    // Placeholder implementation
-    fn should_defer(&self, _cert: &VerifiedExecutableTransaction) -> Option<DeferralKey> {
-        // placeholder constructions to silence lints
-        let _ = DeferralKey::new_for_randomness_round(0, 0);
-        let _ = DeferralKey::new_for_consensus_round(0, 0);
-        None
+    /// Decides whether a certificate sequenced in the commit at `commit_round` should be
+    /// deferred. Certificates mutating a shared object that has used up its transaction budget
+    /// for the commit are deferred to the next commit.
+    fn should_defer(
+        &self,
+        cert: &VerifiedExecutableTransaction,
+        commit_round: Round,
+        congestion_tracker: &mut SharedObjectCongestionTracker,
+    ) -> Option<DeferralKey> {
+        let mutable_shared_objects: Vec<_> = cert
+            .data()
+            .transaction_data()
+            .shared_input_objects()
+            .into_iter()
+            .filter(|object| object.mutable)
+            .map(|object| object.id)
+            .collect();
+        if congestion_tracker.try_schedule(&mutable_shared_objects) {
+            None
+        } else {
+            Some(DeferralKey::new_for_consensus_round(
+                commit_round + 1,
+                commit_round,
+            ))
+        }
     }
 
     /// Lock a sequence number for the shared objects of the input transaction based on the effects
@@ -2006,21 +2114,20 @@ impl AuthorityPerEpochStore {
                 self.verify_consensus_transaction(transaction, skipped_consensus_txns)
             })
             .collect();
-        let roots: BTreeSet<_> = verified_transactions
-            .iter()
-            .filter_map(|transaction| transaction.0.transaction.executable_transaction_digest())
-            .collect();
-        let (end_of_publish_transactions, mut sequenced_transactions): (Vec<_>, Vec<_>) =
+        let (end_of_publish_transactions, sequenced_transactions): (Vec<_>, Vec<_>) =
             verified_transactions
                 .into_iter()
                 .partition(|transaction| transaction.0.is_end_of_publish());
 
         let mut batch = self.db_batch();
 
-        sequenced_transactions.extend(
-            self.load_deferred_transactions_for_consensus_round(&mut batch, commit_round)?
-                .into_iter(),
-        );
+        // Transactions deferred from earlier commits go first, so that they are scheduled before
+        // new transactions with the same gas price.
+        let mut sequenced_transactions: Vec<_> = self
+            .load_deferred_transactions_for_up_to_consensus_round(&mut batch, commit_round)?
+            .into_iter()
+            .chain(sequenced_transactions)
+            .collect();
 
         // TODO: This is a no-op until we start using random round transactions
         let placeholder_random_round = u64::MAX - 1;
@@ -2032,20 +2139,35 @@ impl AuthorityPerEpochStore {
             .into_iter(),
         );
 
-        PostConsensusTxReorder::reorder(
-            &mut sequenced_transactions,
-            self.protocol_config.consensus_transaction_ordering(),
+        let congestion_tracker = SharedObjectCongestionTracker::new(
+            self.protocol_config
+                .max_txns_per_shared_object_per_commit_as_option(),
         );
+        // Congestion control schedules the transactions with the highest gas price first.
+        let ordering = if congestion_tracker.is_enabled() {
+            ConsensusTransactionOrdering::ByGasPrice
+        } else {
+            self.protocol_config.consensus_transaction_ordering()
+        };
+        PostConsensusTxReorder::reorder(&mut sequenced_transactions, ordering);
 
-        let (transactions_to_schedule, notifications, lock_and_final_round) = self
-            .process_consensus_transactions(
+        let (transactions_to_schedule, notifications, deferred_transactions, lock_and_final_round) =
+            self.process_consensus_transactions(
                 &mut batch,
                 &sequenced_transactions,
                 &end_of_publish_transactions,
                 checkpoint_service,
                 object_store,
+                commit_round,
+                congestion_tracker,
             )
             .await?;
+        // Deferred transactions are roots of the checkpoint of the commit they are scheduled in.
+        let roots: BTreeSet<_> = sequenced_transactions
+            .iter()
+            .filter_map(|transaction| transaction.0.transaction.executable_transaction_digest())
+            .filter(|digest| !deferred_transactions.contains(digest))
+            .collect();
         self.record_consensus_commit_stats(&mut batch, consensus_stats)?;
 
         // The last block in this function notifies about new checkpoint if needed
@@ -2157,9 +2279,12 @@ impl AuthorityPerEpochStore {
         end_of_publish_transactions: &[VerifiedSequencedConsensusTransaction],
         checkpoint_service: &Arc<C>,
         object_store: impl ObjectStore,
+        commit_round: Round,
+        mut congestion_tracker: SharedObjectCongestionTracker,
     ) -> SuiResult<(
         Vec<VerifiedExecutableTransaction>,
         Vec<SequencedConsensusTransactionKey>, // keys to notify as complete
+        HashSet<TransactionDigest>,            // transactions deferred to a later commit
         Option<(parking_lot::RwLockWriteGuard<ReconfigState>, u64)>,
     )> {
         let mut verified_certificates = Vec::with_capacity(transactions.len());
@@ -2191,8 +2316,17 @@ impl AuthorityPerEpochStore {
             .await?
         };
 
+        // Deferred certificates live in per-epoch tables and have already been recorded as
+        // processed, so nothing may be deferred past the last commit of the epoch. The commit
+        // completing the EndOfPublish quorum schedules every certificate instead, including the
+        // ones deferred from earlier commits.
+        if self.completes_end_of_publish_quorum(end_of_publish_transactions) {
+            congestion_tracker = SharedObjectCongestionTracker::new(None);
+        }
+
         let mut deferred_txns: BTreeMap<DeferralKey, Vec<VerifiedSequencedConsensusTransaction>> =
             BTreeMap::new();
+        let mut deferred_digests = HashSet::new();
 
         for tx in transactions {
             let key = tx.0.transaction.key();
@@ -2203,6 +2337,8 @@ impl AuthorityPerEpochStore {
                     &mut shared_input_next_versions,
                     tx,
                     checkpoint_service,
+                    commit_round,
+                    &mut congestion_tracker,
                 )
                 .await?
             {
@@ -2213,6 +2349,9 @@ impl AuthorityPerEpochStore {
                 ConsensusCertificateResult::Defered(deferral_key) => {
                     // Note: record_consensus_message_processed() must have been called for this
                     // cert even though we are not processing it now!
+                    if let Some(digest) = tx.0.transaction.executable_transaction_digest() {
+                        deferred_digests.insert(digest);
+                    }
                     deferred_txns
                         .entry(deferral_key)
                         .or_default()
@@ -2226,6 +2365,12 @@ impl AuthorityPerEpochStore {
         for (key, txns) in deferred_txns.into_iter() {
             self.defer_transactions(batch, key, txns)?;
         }
+        self.metrics
+            .consensus_handler_deferred_transactions
+            .inc_by(deferred_digests.len() as u64);
+        self.metrics
+            .consensus_handler_congested_objects
+            .inc_by(congestion_tracker.num_congested_objects() as u64);
 
         batch.insert_batch(
             &self.tables.next_shared_object_versions,
@@ -2234,8 +2379,50 @@ impl AuthorityPerEpochStore {
 
         let lock_and_final_round =
             self.process_end_of_publish_transactions(batch, end_of_publish_transactions)?;
+        assert!(
+            lock_and_final_round.is_none() || deferred_digests.is_empty(),
+            "Transactions must not be deferred past the end of the epoch"
+        );
 
-        Ok((verified_certificates, notifications, lock_and_final_round))
+        Ok((
+            verified_certificates,
+            notifications,
+            deferred_digests,
+            lock_and_final_round,
+        ))
+    }
+
+    /// Whether `transactions` complete the quorum of EndOfPublish messages, which makes the
+    /// current commit the last one to schedule certificates in this epoch. This only looks at
+    /// the collected messages, which are updated in `process_end_of_publish_transactions`.
+    fn completes_end_of_publish_quorum(
+        &self,
+        transactions: &[VerifiedSequencedConsensusTransaction],
+    ) -> bool {
+        if !self
+            .get_reconfig_state_read_lock_guard()
+            .should_accept_consensus_certs()
+        {
+            return false;
+        }
+        let end_of_publish = self.end_of_publish.try_lock().expect(
+            "No contention on Authority::end_of_publish as it is only accessed from consensus handler",
+        );
+        let committee = end_of_publish.committee();
+        let mut votes = end_of_publish.total_votes();
+        let mut authorities = HashSet::new();
+        for transaction in transactions {
+            if let SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                kind: ConsensusTransactionKind::EndOfPublish(authority),
+                ..
+            }) = &transaction.0.transaction
+            {
+                if !end_of_publish.contains_key(authority) && authorities.insert(*authority) {
+                    votes += committee.weight(authority);
+                }
+            }
+        }
+        votes >= committee.threshold::<true>()
     }
 
     fn process_end_of_publish_transactions(
@@ -2322,6 +2509,8 @@ impl AuthorityPerEpochStore {
         shared_input_next_versions: &mut HashMap<ObjectID, SequenceNumber>,
         transaction: &VerifiedSequencedConsensusTransaction,
         checkpoint_service: &Arc<C>,
+        commit_round: Round,
+        congestion_tracker: &mut SharedObjectCongestionTracker,
     ) -> SuiResult<ConsensusCertificateResult> {
         let _scope = monitored_scope("HandleConsensusTransaction");
         let VerifiedSequencedConsensusTransaction(SequencedConsensusTransaction {
@@ -2374,7 +2563,9 @@ impl AuthorityPerEpochStore {
                     return Ok(ConsensusCertificateResult::Ignored);
                 }
 
-                if let Some(deferral_key) = self.should_defer(&certificate) {
+                if let Some(deferral_key) =
+                    self.should_defer(&certificate, commit_round, congestion_tracker)
+                {
                     debug!(
                         "Deferring consensus certificate for transaction {:?} until {:?}",
                         certificate.digest(),
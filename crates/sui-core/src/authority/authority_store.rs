@@ -27,7 +27,7 @@ use sui_types::sui_system_state::get_sui_system_state;
 use sui_types::{base_types::SequenceNumber, fp_bail, fp_ensure, storage::ParentSync};
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tokio::time::Instant;
-use tracing::{debug, info, trace};
+use tracing::{debug, info, trace, warn};
 use typed_store::rocks::{DBBatch, DBMap, TypedStoreError};
 use typed_store::traits::Map;
 
@@ -36,13 +36,18 @@ use crate::authority::authority_store_types::{
     get_store_object_pair, ObjectContentDigest, StoreObject, StoreObjectPair, StoreObjectWrapper,
 };
 use crate::authority::epoch_start_configuration::{EpochFlag, EpochStartConfiguration};
+use crate::authority::execution_cache::{
+    multi_get_cached, CachedLock, CachedObject, ExecutionCache,
+};
 
 use super::authority_store_tables::LiveObject;
 use super::{authority_store_tables::AuthorityPerpetualTables, *};
 use mysten_common::sync::notify_read::NotifyRead;
+use sui_config::node::ExecutionCacheConfig;
 use sui_storage::package_object_cache::PackageObjectCache;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::gas_coin::TOTAL_SUPPLY_MIST;
+use sui_types::inner_temporary_store::WrittenObjects;
 use typed_store::rocks::util::is_ref_count_value;
 
 const NUM_SHARDS: usize = 4096;
@@ -135,11 +140,27 @@ pub struct AuthorityStore {
     metrics: AuthorityStoreMetrics,
 
     package_cache: Arc<PackageObjectCache>,
+
+    /// Holds the outputs of executed transactions in memory until the next checkpoint boundary,
+    /// if enabled.
+    execution_cache: Option<ExecutionCache>,
 }
 
 pub type ExecutionLockReadGuard<'a> = RwLockReadGuard<'a, EpochId>;
 pub type ExecutionLockWriteGuard<'a> = RwLockWriteGuard<'a, EpochId>;
 
+/// The writes resulting from the execution of a transaction, besides the transaction and its
+/// effects.
+pub(crate) struct TransactionOutputs {
+    pub markers: Vec<((EpochId, ObjectKey), MarkerValue)>,
+    pub written: WrittenObjects,
+    /// Deleted and wrapped objects.
+    pub tombstones: Vec<(ObjectKey, StoreObject)>,
+    pub events: TransactionEvents,
+    pub locks_to_initialize: Vec<ObjectRef>,
+    pub locks_to_delete: Vec<ObjectRef>,
+}
+
 impl AuthorityStore {
     /// Open an authority store by directory path.
     /// If the store is empty, initialize it using genesis.
@@ -149,6 +170,7 @@ impl AuthorityStore {
         committee_store: &Arc<CommitteeStore>,
         indirect_objects_threshold: usize,
         enable_epoch_sui_conservation_check: bool,
+        execution_cache_config: Option<ExecutionCacheConfig>,
         registry: &Registry,
     ) -> SuiResult<Arc<Self>> {
         let epoch_start_configuration = if perpetual_tables.database_is_empty()? {
@@ -182,6 +204,7 @@ impl AuthorityStore {
             &committee,
             indirect_objects_threshold,
             enable_epoch_sui_conservation_check,
+            execution_cache_config,
             registry,
         )
         .await?;
@@ -209,6 +232,7 @@ impl AuthorityStore {
         committee: &Committee,
         genesis: &Genesis,
         indirect_objects_threshold: usize,
+        execution_cache_config: Option<ExecutionCacheConfig>,
     ) -> SuiResult<Arc<Self>> {
         // TODO: Since we always start at genesis, the committee should be technically the same
         // as the genesis committee.
@@ -219,6 +243,7 @@ impl AuthorityStore {
             committee,
             indirect_objects_threshold,
             true,
+            execution_cache_config,
             &Registry::new(),
         )
         .await
@@ -230,9 +255,21 @@ impl AuthorityStore {
         committee: &Committee,
         indirect_objects_threshold: usize,
         enable_epoch_sui_conservation_check: bool,
+        execution_cache_config: Option<ExecutionCacheConfig>,
         registry: &Registry,
     ) -> SuiResult<Arc<Self>> {
         let epoch = committee.epoch;
+        let execution_cache = execution_cache_config.and_then(|config| {
+            // Reference counts of indirect objects are updated based on the contents of the
+            // database at write time, which the cache would delay.
+            if indirect_objects_threshold > 0 && indirect_objects_threshold < usize::MAX {
+                warn!("Execution cache is not supported with indirect objects, disabling it");
+                None
+            } else {
+                info!("Execution cache enabled: {:?}", config);
+                Some(ExecutionCache::new(config, registry))
+            }
+        });
 
         let store = Arc::new(Self {
             mutex_table: MutexTable::new(NUM_SHARDS),
@@ -247,6 +284,7 @@ impl AuthorityStore {
             enable_epoch_sui_conservation_check,
             metrics: AuthorityStoreMetrics::new(registry),
             package_cache: PackageObjectCache::new(),
+            execution_cache,
         });
         // Only initialize an empty database.
         if store
@@ -315,12 +353,24 @@ impl AuthorityStore {
     pub fn get_effects(
         &self,
         effects_digest: &TransactionEffectsDigest,
-    ) -> SuiResult<Option<TransactionEffects>> {
-        Ok(self.perpetual_tables.effects.get(effects_digest)?)
+    ) -> Result<Option<TransactionEffects>, TypedStoreError> {
+        if let Some(effects) = self
+            .cache()
+            .and_then(|cache| cache.get_effects(effects_digest))
+        {
+            return Ok(Some(effects));
+        }
+        self.perpetual_tables.effects.get(effects_digest)
     }
 
     /// Returns true if we have an effects structure for this transaction digest
     pub fn effects_exists(&self, effects_digest: &TransactionEffectsDigest) -> SuiResult<bool> {
+        if self
+            .cache()
+            .is_some_and(|cache| cache.get_effects(effects_digest).is_some())
+        {
+            return Ok(true);
+        }
         self.perpetual_tables
             .effects
             .contains_key(effects_digest)
@@ -331,6 +381,12 @@ impl AuthorityStore {
         &self,
         event_digest: &TransactionEventsDigest,
     ) -> Result<Option<TransactionEvents>, TypedStoreError> {
+        if let Some(events) = self
+            .cache()
+            .and_then(|cache| cache.get_events(event_digest))
+        {
+            return Ok(Some(events));
+        }
         let data = self
             .perpetual_tables
             .events
@@ -354,16 +410,27 @@ impl AuthorityStore {
         &self,
         effects_digests: impl Iterator<Item = &'a TransactionEffectsDigest>,
     ) -> SuiResult<Vec<Option<TransactionEffects>>> {
-        Ok(self.perpetual_tables.effects.multi_get(effects_digests)?)
+        let effects_digests: Vec<_> = effects_digests.copied().collect();
+        multi_get_cached(
+            &effects_digests,
+            |digest| self.cache()?.get_effects(digest).map(Some),
+            |digests| Ok(self.perpetual_tables.effects.multi_get(digests)?),
+        )
     }
 
     pub fn get_executed_effects(
         &self,
         tx_digest: &TransactionDigest,
     ) -> SuiResult<Option<TransactionEffects>> {
-        let effects_digest = self.perpetual_tables.executed_effects.get(tx_digest)?;
+        let effects_digest = match self
+            .cache()
+            .and_then(|cache| cache.get_executed_effects_digest(tx_digest))
+        {
+            Some(digest) => Some(digest),
+            None => self.perpetual_tables.executed_effects.get(tx_digest)?,
+        };
         match effects_digest {
-            Some(digest) => Ok(self.perpetual_tables.effects.get(&digest)?),
+            Some(digest) => Ok(self.get_effects(&digest)?),
             None => Ok(None),
         }
     }
@@ -374,7 +441,11 @@ impl AuthorityStore {
         &self,
         digests: &[TransactionDigest],
     ) -> SuiResult<Vec<Option<TransactionEffectsDigest>>> {
-        Ok(self.perpetual_tables.executed_effects.multi_get(digests)?)
+        multi_get_cached(
+            digests,
+            |digest| self.cache()?.get_executed_effects_digest(digest).map(Some),
+            |digests| Ok(self.perpetual_tables.executed_effects.multi_get(digests)?),
+        )
     }
 
     /// Given a list of transaction digests, returns a list of the corresponding effects only if they have been
@@ -383,7 +454,7 @@ impl AuthorityStore {
         &self,
         digests: &[TransactionDigest],
     ) -> SuiResult<Vec<Option<TransactionEffects>>> {
-        let executed_effects_digests = self.perpetual_tables.executed_effects.multi_get(digests)?;
+        let executed_effects_digests = self.multi_get_executed_effects_digests(digests)?;
         let effects = self.multi_get_effects(executed_effects_digests.iter().flatten())?;
         let mut tx_to_effects_map = effects
             .into_iter()
@@ -397,6 +468,12 @@ impl AuthorityStore {
     }
 
     pub fn is_tx_already_executed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
+        if self
+            .cache()
+            .is_some_and(|cache| cache.get_executed_effects_digest(digest).is_some())
+        {
+            return Ok(true);
+        }
         Ok(self
             .perpetual_tables
             .executed_effects
@@ -411,11 +488,17 @@ impl AuthorityStore {
     ) -> Result<Option<TransactionDigest>, TypedStoreError> {
         let object_key = (epoch_id, ObjectKey(*object_id, *version));
 
-        match self
-            .perpetual_tables
-            .object_per_epoch_marker_table
-            .get(&object_key)?
+        let marker = match self
+            .cache()
+            .and_then(|cache| cache.get_marker(epoch_id, &object_key.1))
         {
+            Some(marker) => Some(marker),
+            None => self
+                .perpetual_tables
+                .object_per_epoch_marker_table
+                .get(&object_key)?,
+        };
+        match marker {
             Some(MarkerValue::SharedDeleted(digest)) => Ok(Some(digest)),
             _ => Ok(None),
         }
@@ -426,6 +509,12 @@ impl AuthorityStore {
         object_id: &ObjectID,
         epoch_id: EpochId,
     ) -> SuiResult<bool> {
+        if let Some((_, marker)) = self
+            .cache()
+            .and_then(|cache| cache.get_latest_marker(epoch_id, object_id))
+        {
+            return Ok(matches!(marker, MarkerValue::SharedDeleted(_)));
+        }
         let object_key = ObjectKey::max_for_id(object_id);
         let marker_key = (epoch_id, object_key);
 
@@ -544,6 +633,12 @@ impl AuthorityStore {
         let Some(prior_version) = version.one_before() else {
             return Ok(None);
         };
+        if let Some((object_key, object)) = self
+            .cache()
+            .and_then(|cache| cache.find_object_lt_or_eq_version(object_id, prior_version))
+        {
+            return Ok(Some(object.object_reference(&object_key)));
+        }
         let mut iterator = self
             .perpetual_tables
             .objects
@@ -564,20 +659,30 @@ impl AuthorityStore {
         &self,
         object_keys: &[ObjectKey],
     ) -> Result<Vec<Option<Object>>, SuiError> {
-        let wrappers = self
-            .perpetual_tables
-            .objects
-            .multi_get(object_keys.to_vec())?;
-        let mut ret = vec![];
-
-        for (idx, w) in wrappers.into_iter().enumerate() {
-            ret.push(
-                w.map(|object| self.perpetual_tables.object(&object_keys[idx], object))
-                    .transpose()?
-                    .flatten(),
-            );
-        }
-        Ok(ret)
+        multi_get_cached(
+            object_keys,
+            |object_key| {
+                self.cache()?
+                    .get_object_by_key(object_key)
+                    .map(CachedObject::into_object)
+            },
+            |object_keys| {
+                let wrappers = self
+                    .perpetual_tables
+                    .objects
+                    .multi_get(object_keys.to_vec())?;
+                let mut ret = vec![];
+
+                for (idx, w) in wrappers.into_iter().enumerate() {
+                    ret.push(
+                        w.map(|object| self.perpetual_tables.object(&object_keys[idx], object))
+                            .transpose()?
+                            .flatten(),
+                    );
+                }
+                Ok(ret)
+            },
+        )
     }
 
     /// Get many objects
@@ -596,11 +701,17 @@ impl AuthorityStore {
         epoch_id: EpochId,
     ) -> Result<bool, SuiError> {
         let marker_key = (epoch_id, ObjectKey(*object_id, version));
-        Ok(self
-            .perpetual_tables
-            .object_per_epoch_marker_table
-            .get(&marker_key)?
-            .is_some_and(|marker_value| marker_value == MarkerValue::Received))
+        let marker = match self
+            .cache()
+            .and_then(|cache| cache.get_marker(epoch_id, &marker_key.1))
+        {
+            Some(marker) => Some(marker),
+            None => self
+                .perpetual_tables
+                .object_per_epoch_marker_table
+                .get(&marker_key)?,
+        };
+        Ok(marker.is_some_and(|marker_value| marker_value == MarkerValue::Received))
     }
 
     pub fn have_deleted_owned_object_at_version_or_after(
@@ -609,6 +720,12 @@ impl AuthorityStore {
         version: VersionNumber,
         epoch_id: EpochId,
     ) -> Result<bool, SuiError> {
+        if let Some((marker_version, marker)) = self
+            .cache()
+            .and_then(|cache| cache.get_latest_marker(epoch_id, object_id))
+        {
+            return Ok(marker_version >= version && marker == MarkerValue::OwnedDeleted);
+        }
         let object_key = ObjectKey::max_for_id(object_id);
         let marker_key = (epoch_id, object_key);
 
@@ -697,16 +814,23 @@ impl AuthorityStore {
             .enumerate()
             .partition(|(_, key)| key.version().is_some());
 
+        let versioned_object_keys: Vec<_> = keys_with_version
+            .iter()
+            .map(|(_, k)| ObjectKey(k.id(), k.version().unwrap()))
+            .collect();
         let mut versioned_results = vec![];
         for ((idx, input_key), has_key) in keys_with_version.iter().zip(
-            self.perpetual_tables
-                .objects
-                .multi_contains_keys(
-                    keys_with_version
-                        .iter()
-                        .map(|(_, k)| ObjectKey(k.id(), k.version().unwrap())),
-                )?
-                .into_iter(),
+            multi_get_cached(
+                &versioned_object_keys,
+                |object_key| self.cache()?.get_object_by_key(object_key).map(|_| true),
+                |object_keys| {
+                    Ok(self
+                        .perpetual_tables
+                        .objects
+                        .multi_contains_keys(object_keys)?)
+                },
+            )?
+            .into_iter(),
         ) {
             // If the key exists at the specified version, then the object is available.
             if has_key {
@@ -959,54 +1083,55 @@ impl AuthorityStore {
     ///
     /// Internally it checks that all locks for active inputs are at the correct
     /// version, and then writes objects, certificates, parents and clean up locks atomically.
+    /// When the execution cache is enabled, the writes are held in memory until the cache is
+    /// flushed.
     #[instrument(level = "debug", skip_all)]
     pub async fn update_state(
         &self,
         inner_temporary_store: InnerTemporaryStore,
         transaction: &VerifiedTransaction,
         effects: &TransactionEffects,
-        epoch_id: EpochId,
+        epoch_store: &Arc<AuthorityPerEpochStore>,
     ) -> SuiResult {
         let _locks = self
             .acquire_read_locks_for_indirect_objects(&inner_temporary_store)
             .await;
-        // Extract the new state from the execution
-        let mut write_batch = self.perpetual_tables.transactions.batch();
-
-        // Store the certificate indexed by transaction digest
         let transaction_digest = transaction.digest();
-        write_batch.insert_batch(
-            &self.perpetual_tables.transactions,
-            iter::once((transaction_digest, transaction.serializable_ref())),
-        )?;
-
-        // Add batched writes for objects and locks.
         let effects_digest = effects.digest();
-        self.update_objects_and_locks(
-            &mut write_batch,
+
+        // Extract the new state from the execution
+        let outputs = self.build_transaction_outputs(
             inner_temporary_store,
             effects,
             transaction,
-            epoch_id,
-        )
-        .await?;
-
-        // Store the signed effects of the transaction
-        // We can't write this until after sequencing succeeds (which happens in
-        // batch_update_objects), as effects_exists is used as a check in many places
-        // for "did the tx finish".
-        write_batch
-            .insert_batch(&self.perpetual_tables.effects, [(effects_digest, effects)])?
-            .insert_batch(
-                &self.perpetual_tables.executed_effects,
-                [(transaction_digest, effects_digest)],
-            )?;
+            epoch_store.epoch(),
+        )?;
 
         // test crashing before writing the batch
         fail_point_async!("crash");
 
-        // Commit.
-        write_batch.write()?;
+        if let Some(cache) = &self.execution_cache {
+            if cache.insert(
+                transaction.clone(),
+                effects.clone(),
+                outputs,
+                epoch_store.clone(),
+            ) {
+                self.flush_execution_cache()?;
+            }
+        } else {
+            let mut write_batch = self.perpetual_tables.transactions.batch();
+            self.write_transaction_outputs(
+                &mut write_batch,
+                transaction,
+                effects,
+                &outputs,
+                false,
+            )?;
+
+            // Commit.
+            write_batch.write()?;
+        }
 
         if transaction.transaction_data().is_end_of_epoch_tx() {
             // At the end of epoch, since system packages may have been upgraded, force
@@ -1025,6 +1150,73 @@ impl AuthorityStore {
         Ok(())
     }
 
+    /// Persists all transaction outputs held by the execution cache, in a single batch.
+    /// Does nothing if the cache is disabled.
+    ///
+    /// The signatures of the flushed transactions are persisted before the batch, and the
+    /// transactions are removed from `pending_execution` after it.
+    pub fn flush_execution_cache(&self) -> SuiResult {
+        let Some(cache) = &self.execution_cache else {
+            return Ok(());
+        };
+        cache.flush(|transactions| {
+            // Transactions are grouped by epoch, since a flush may span a reconfiguration.
+            let mut epochs: Vec<(&Arc<AuthorityPerEpochStore>, Vec<TransactionDigest>)> =
+                Vec::new();
+            for tx in transactions {
+                match epochs.last_mut() {
+                    Some((epoch_store, digests)) if Arc::ptr_eq(epoch_store, &tx.epoch_store) => {
+                        digests.push(*tx.transaction.digest())
+                    }
+                    _ => epochs.push((&tx.epoch_store, vec![*tx.transaction.digest()])),
+                }
+            }
+            for (epoch_store, digests) in &epochs {
+                epoch_store.persist_unflushed_signatures(digests)?;
+            }
+
+            let mut write_batch = self.perpetual_tables.transactions.batch();
+            for tx in transactions {
+                self.write_transaction_outputs(
+                    &mut write_batch,
+                    &tx.transaction,
+                    &tx.effects,
+                    &tx.outputs,
+                    true,
+                )?;
+            }
+            write_batch.write()?;
+
+            // The outputs are durable at this point, so failing to clean up is not an error:
+            // transactions left in `pending_execution` are skipped as already executed after
+            // a restart.
+            for (epoch_store, digests) in &epochs {
+                if let Err(err) = epoch_store.remove_flushed_pending_executions(digests) {
+                    warn!(
+                        ?err,
+                        "Failed to remove flushed transactions from pending execution"
+                    );
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns true if the transaction was executed, but its outputs are still held by the
+    /// execution cache.
+    pub fn is_tx_pending_flush(&self, digest: &TransactionDigest) -> bool {
+        self.cache()
+            .is_some_and(|cache| cache.contains_transaction(digest))
+    }
+
+    pub fn is_execution_cache_enabled(&self) -> bool {
+        self.execution_cache.is_some()
+    }
+
+    fn cache(&self) -> Option<&ExecutionCache> {
+        self.execution_cache.as_ref()
+    }
+
     fn force_reload_system_packages_into_cache(&self) {
         info!("Reload all system packages in the cache");
         self.package_cache
@@ -1056,15 +1248,15 @@ impl AuthorityStore {
         self.objects_lock_table.acquire_read_locks(digests).await
     }
 
-    /// Helper function for updating the objects and locks in the state
-    async fn update_objects_and_locks(
+    /// Computes the objects, markers, events and lock changes resulting from the execution of a
+    /// transaction, and checks that the locks of its owned inputs still exist.
+    fn build_transaction_outputs(
         &self,
-        write_batch: &mut DBBatch,
         inner_temporary_store: InnerTemporaryStore,
         effects: &TransactionEffects,
         transaction: &VerifiedTransaction,
         epoch_id: EpochId,
-    ) -> SuiResult {
+    ) -> SuiResult<TransactionOutputs> {
         let InnerTemporaryStore {
             input_objects,
             mutable_inputs,
@@ -1076,8 +1268,6 @@ impl AuthorityStore {
             runtime_packages_loaded_from_db: _,
             lamport_version,
         } = inner_temporary_store;
-        trace!(written =? written.iter().map(|(obj_id, obj)| (obj_id, obj.version())).collect::<Vec<_>>(),
-               "batch_update_objects: temp store written");
 
         let deleted: HashMap<_, _> = effects
             .deleted()
@@ -1106,7 +1296,7 @@ impl AuthorityStore {
         // We record any received or deleted objects since they could be pruned, and smear shared
         // object deletions in the marker table. For deleted entries in the marker table we need to
         // make sure we don't accidentally overwrite entries.
-        let markers_to_place = {
+        let markers = {
             let received = received_objects.iter().map(|(object_id, version, _)| {
                 (
                     (epoch_id, ObjectKey(*object_id, *version)),
@@ -1142,14 +1332,9 @@ impl AuthorityStore {
                 )
             });
 
-            received.chain(deleted).chain(shared_smears)
+            received.chain(deleted).chain(shared_smears).collect()
         };
 
-        write_batch.insert_batch(
-            &self.perpetual_tables.object_per_epoch_marker_table,
-            markers_to_place,
-        )?;
-
         let owned_inputs: Vec<_> = mutable_inputs
             .into_iter()
             .filter_map(|(id, ((version, digest), owner))| {
@@ -1161,20 +1346,92 @@ impl AuthorityStore {
             .deleted()
             .into_iter()
             .chain(effects.unwrapped_then_deleted())
-            .map(|oref| (oref, StoreObject::Deleted))
+            .map(|oref| (ObjectKey::from(oref), StoreObject::Deleted))
             .chain(
                 effects
                     .wrapped()
                     .into_iter()
-                    .map(|oref| (oref, StoreObject::Wrapped)),
+                    .map(|oref| (ObjectKey::from(oref), StoreObject::Wrapped)),
             )
-            .map(|(oref, store_object)| {
-                (
-                    ObjectKey::from(oref),
-                    StoreObjectWrapper::from(store_object),
-                )
-            });
-        write_batch.insert_batch(&self.perpetual_tables.objects, tombstones)?;
+            .collect();
+
+        let locks_to_initialize = written
+            .values()
+            .filter_map(|new_object| {
+                if new_object.is_address_owned() {
+                    Some(new_object.compute_object_reference())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // NOTE: We just check here that locks exist, not that they are locked to a specific TX. Why?
+        // 1. Lock existence prevents re-execution of old certs when objects have been upgraded
+        // 2. Not all validators lock, just 2f+1, so transaction should proceed regardless
+        //    (But the lock should exist which means previous transactions finished)
+        // 3. Equivocation possible (different TX) but as long as 2f+1 approves current TX its
+        //    fine
+        // 4. Locks may have existed when we started processing this tx, but could have since
+        //    been deleted by a concurrent tx that finished first. In that case, check if the
+        //    tx effects exist.
+        self.check_owned_object_locks_exist(&owned_inputs)?;
+
+        // Make sure to delete the locks for any received objects.
+        // Any objects that occur as a `Receiving` argument but have not been received will not
+        // have their locks touched.
+        let locks_to_delete = owned_inputs.into_iter().chain(received_objects).collect();
+
+        Ok(TransactionOutputs {
+            markers,
+            written,
+            tombstones,
+            events,
+            locks_to_initialize,
+            locks_to_delete,
+        })
+    }
+
+    /// Adds the transaction, its effects and its outputs to a batch. When flushing the execution
+    /// cache, `keep_acquired_locks` preserves the locks that were acquired on the outputs while
+    /// they were cached.
+    fn write_transaction_outputs(
+        &self,
+        write_batch: &mut DBBatch,
+        transaction: &VerifiedTransaction,
+        effects: &TransactionEffects,
+        outputs: &TransactionOutputs,
+        keep_acquired_locks: bool,
+    ) -> SuiResult {
+        let TransactionOutputs {
+            markers,
+            written,
+            tombstones,
+            events,
+            locks_to_initialize,
+            locks_to_delete,
+        } = outputs;
+        trace!(written =? written.iter().map(|(obj_id, obj)| (obj_id, obj.version())).collect::<Vec<_>>(),
+               "batch_update_objects: temp store written");
+
+        // Store the certificate indexed by transaction digest
+        let transaction_digest = transaction.digest();
+        write_batch.insert_batch(
+            &self.perpetual_tables.transactions,
+            iter::once((transaction_digest, transaction.serializable_ref())),
+        )?;
+
+        write_batch.insert_batch(
+            &self.perpetual_tables.object_per_epoch_marker_table,
+            markers.iter().cloned(),
+        )?;
+
+        write_batch.insert_batch(
+            &self.perpetual_tables.objects,
+            tombstones.iter().map(|(object_key, store_object)| {
+                (*object_key, StoreObjectWrapper::from(store_object.clone()))
+            }),
+        )?;
 
         // Insert each output object into the stores
         let (new_objects, new_indirect_move_objects): (Vec<_>, Vec<_>) = written
@@ -1222,41 +1479,41 @@ impl AuthorityStore {
         let event_digest = events.digest();
         let events = events
             .data
-            .into_iter()
+            .iter()
             .enumerate()
             .map(|(i, e)| ((event_digest, i), e));
 
         write_batch.insert_batch(&self.perpetual_tables.events, events)?;
 
-        let new_locks_to_init: Vec<_> = written
-            .values()
-            .filter_map(|new_object| {
-                if new_object.is_address_owned() {
-                    Some(new_object.compute_object_reference())
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        // NOTE: We just check here that locks exist, not that they are locked to a specific TX. Why?
-        // 1. Lock existence prevents re-execution of old certs when objects have been upgraded
-        // 2. Not all validators lock, just 2f+1, so transaction should proceed regardless
-        //    (But the lock should exist which means previous transactions finished)
-        // 3. Equivocation possible (different TX) but as long as 2f+1 approves current TX its
-        //    fine
-        // 4. Locks may have existed when we started processing this tx, but could have since
-        //    been deleted by a concurrent tx that finished first. In that case, check if the
-        //    tx effects exist.
-        self.check_owned_object_locks_exist(&owned_inputs)?;
-
-        self.initialize_locks_impl(write_batch, &new_locks_to_init, false)?;
-        self.delete_locks(write_batch, &owned_inputs)?;
+        if keep_acquired_locks {
+            let existing_locks = self
+                .perpetual_tables
+                .owned_object_transaction_locks
+                .multi_get(locks_to_initialize)?;
+            let missing_locks: Vec<_> = locks_to_initialize
+                .iter()
+                .zip(existing_locks)
+                .filter(|(_, lock)| lock.is_none())
+                .map(|(obj_ref, _)| *obj_ref)
+                .collect();
+            self.initialize_locks_impl(write_batch, &missing_locks, false)?;
+        } else {
+            self.initialize_locks_impl(write_batch, locks_to_initialize, false)?;
+        }
+        self.delete_locks(write_batch, locks_to_delete)?;
 
-        // Make sure to delete the locks for any received objects.
-        // Any objects that occur as a `Receiving` argument but have not been received will not
-        // have their locks touched.
-        self.delete_locks(write_batch, &received_objects)
+        // Store the signed effects of the transaction
+        // We can't write this until after sequencing succeeds (which happens in
+        // batch_update_objects), as effects_exists is used as a check in many places
+        // for "did the tx finish".
+        let effects_digest = effects.digest();
+        write_batch
+            .insert_batch(&self.perpetual_tables.effects, [(effects_digest, effects)])?
+            .insert_batch(
+                &self.perpetual_tables.executed_effects,
+                [(transaction_digest, effects_digest)],
+            )?;
+        Ok(())
     }
 
     /// Acquires a lock for a transaction on the given objects if they have all been initialized previously
@@ -1274,10 +1531,7 @@ impl AuthorityStore {
         trace!(?owned_input_objects, "acquire_locks");
         let mut locks_to_write = Vec::new();
 
-        let locks = self
-            .perpetual_tables
-            .owned_object_transaction_locks
-            .multi_get(owned_input_objects)?;
+        let locks = self.multi_get_locks(owned_input_objects)?;
 
         for ((i, lock), obj_ref) in locks.into_iter().enumerate().zip(owned_input_objects) {
             // The object / version must exist, and therefore lock initialized.
@@ -1332,6 +1586,13 @@ impl AuthorityStore {
         }
 
         if !locks_to_write.is_empty() {
+            // Locks are written directly to the database, even if the transactions initializing
+            // them are still cached. Flushing keeps acquired locks, but must not run while they
+            // are being written, since it reads the lock rows before writing its batch.
+            let _flush_guard = self
+                .cache()
+                .filter(|cache| cache.contains_any_lock(owned_input_objects))
+                .map(|cache| cache.lock_flushes());
             trace!(?locks_to_write, "Writing locks");
             let mut batch = self.perpetual_tables.owned_object_transaction_locks.batch();
             batch.insert_batch(
@@ -1347,45 +1608,74 @@ impl AuthorityStore {
     /// Gets ObjectLockInfo that represents state of lock on an object.
     /// Returns UserInputError::ObjectNotFound if cannot find lock record for this object
     pub(crate) fn get_lock(&self, obj_ref: ObjectRef, epoch_id: EpochId) -> SuiLockResult {
-        Ok(
-            if let Some(lock_info) = self
-                .perpetual_tables
-                .owned_object_transaction_locks
-                .get(&obj_ref)
-                .map_err(SuiError::StorageError)?
-            {
-                match lock_info {
-                    Some(lock_info) => {
-                        let lock_info = lock_info.migrate().into_inner();
-                        match Ord::cmp(&lock_info.epoch, &epoch_id) {
-                            // If the object was locked in a previous epoch, we can say that it's
-                            // no longer locked and is considered as just Initialized.
-                            Ordering::Less => ObjectLockStatus::Initialized,
-                            Ordering::Equal => ObjectLockStatus::LockedToTx {
-                                locked_by_tx: lock_info,
-                            },
-                            Ordering::Greater => {
-                                return Err(SuiError::ObjectLockedAtFutureEpoch {
-                                    obj_refs: vec![obj_ref],
-                                    locked_epoch: lock_info.epoch,
-                                    new_epoch: epoch_id,
-                                    locked_by_tx: lock_info.tx_digest,
-                                });
-                            }
+        Ok(if let Some(lock_info) = self.get_lock_entry(&obj_ref)? {
+            match lock_info {
+                Some(lock_info) => {
+                    let lock_info = lock_info.migrate().into_inner();
+                    match Ord::cmp(&lock_info.epoch, &epoch_id) {
+                        // If the object was locked in a previous epoch, we can say that it's
+                        // no longer locked and is considered as just Initialized.
+                        Ordering::Less => ObjectLockStatus::Initialized,
+                        Ordering::Equal => ObjectLockStatus::LockedToTx {
+                            locked_by_tx: lock_info,
+                        },
+                        Ordering::Greater => {
+                            return Err(SuiError::ObjectLockedAtFutureEpoch {
+                                obj_refs: vec![obj_ref],
+                                locked_epoch: lock_info.epoch,
+                                new_epoch: epoch_id,
+                                locked_by_tx: lock_info.tx_digest,
+                            });
                         }
                     }
-                    None => ObjectLockStatus::Initialized,
-                }
-            } else {
-                ObjectLockStatus::LockedAtDifferentVersion {
-                    locked_ref: self.get_latest_lock_for_object_id(obj_ref.0)?,
                 }
-            },
-        )
+                None => ObjectLockStatus::Initialized,
+            }
+        } else {
+            ObjectLockStatus::LockedAtDifferentVersion {
+                locked_ref: self.get_latest_lock_for_object_id(obj_ref.0)?,
+            }
+        })
+    }
+
+    /// Returns the lock of the object, if it exists. The inner value is the transaction the
+    /// object is locked to, if any.
+    fn get_lock_entry(&self, obj_ref: &ObjectRef) -> SuiResult<Option<Option<LockDetailsWrapper>>> {
+        Ok(self.multi_get_locks(&[*obj_ref])?.pop().flatten())
+    }
+
+    /// Reads locks from the database, overridden by the locks initialized or deleted by cached
+    /// transactions. A lock initialized in the cache may already be acquired in the database.
+    fn multi_get_locks(
+        &self,
+        objects: &[ObjectRef],
+    ) -> SuiResult<Vec<Option<Option<LockDetailsWrapper>>>> {
+        let locks = self
+            .perpetual_tables
+            .owned_object_transaction_locks
+            .multi_get(objects)?;
+        let Some(cache) = self.cache() else {
+            return Ok(locks);
+        };
+        Ok(objects
+            .iter()
+            .zip(locks)
+            .map(|(obj_ref, lock)| match cache.get_lock(obj_ref) {
+                Some(CachedLock::Initialized) => Some(lock.flatten()),
+                Some(CachedLock::Deleted) => None,
+                None => lock,
+            })
+            .collect())
     }
 
     /// Returns UserInputError::ObjectNotFound if no lock records found for this object.
     fn get_latest_lock_for_object_id(&self, object_id: ObjectID) -> SuiResult<ObjectRef> {
+        if let Some(obj_ref) = self
+            .cache()
+            .and_then(|cache| cache.get_latest_initialized_lock(&object_id))
+        {
+            return Ok(obj_ref);
+        }
         let mut iterator = self
             .perpetual_tables
             .owned_object_transaction_locks
@@ -1395,7 +1685,12 @@ impl AuthorityStore {
         Ok(iterator
             .next()
             .and_then(|value| {
-                if value.0 .0 == object_id {
+                // A lock deleted by a pending transaction no longer exists.
+                let is_deleted = self
+                    .cache()
+                    .and_then(|cache| cache.get_lock(&value.0))
+                    .is_some_and(|lock| lock == CachedLock::Deleted);
+                if value.0 .0 == object_id && !is_deleted {
                     Some(value)
                 } else {
                     None
@@ -1415,10 +1710,7 @@ impl AuthorityStore {
     /// Returns UserInputError::ObjectVersionUnavailableForConsumption if at least one object lock is not initialized
     ///     at the given version.
     pub fn check_owned_object_locks_exist(&self, objects: &[ObjectRef]) -> SuiResult {
-        let locks = self
-            .perpetual_tables
-            .owned_object_transaction_locks
-            .multi_get(objects)?;
+        let locks = self.multi_get_locks(objects)?;
         for (lock, obj_ref) in locks.into_iter().zip(objects) {
             if lock.is_none() {
                 let latest_lock = self.get_latest_lock_for_object_id(obj_ref.0)?;
@@ -1533,6 +1825,8 @@ impl AuthorityStore {
     /// sync, we are able to execute the checkpoint.
     /// TODO: implement GC for transactions that are no longer needed.
     pub async fn revert_state_update(&self, tx_digest: &TransactionDigest) -> SuiResult {
+        // Reverts are written directly to the database.
+        self.flush_execution_cache()?;
         let Some(effects) = self.get_executed_effects(tx_digest)? else {
             debug!("Not reverting {:?} as it was not executed", tx_digest);
             return Ok(());
@@ -1629,6 +1923,12 @@ impl AuthorityStore {
         object_id: ObjectID,
         version: SequenceNumber,
     ) -> Option<Object> {
+        if let Some((_, object)) = self
+            .cache()
+            .and_then(|cache| cache.find_object_lt_or_eq_version(&object_id, version))
+        {
+            return object.into_object();
+        }
         self.perpetual_tables
             .find_object_lt_or_eq_version(object_id, version)
     }
@@ -1646,6 +1946,12 @@ impl AuthorityStore {
         &self,
         object_id: ObjectID,
     ) -> Result<Option<ObjectRef>, SuiError> {
+        if let Some((object_key, object)) = self
+            .cache()
+            .and_then(|cache| cache.get_latest_object(&object_id))
+        {
+            return Ok(Some(object.object_reference(&object_key)));
+        }
         self.perpetual_tables
             .get_latest_object_ref_or_tombstone(object_id)
     }
@@ -1657,6 +1963,19 @@ impl AuthorityStore {
         &self,
         object_id: ObjectID,
     ) -> Result<Option<(ObjectKey, StoreObjectWrapper)>, SuiError> {
+        if let Some((object_key, object)) = self
+            .cache()
+            .and_then(|cache| cache.get_latest_object(&object_id))
+        {
+            let store_object = match object {
+                CachedObject::Object(object) => {
+                    get_store_object_pair(object, self.indirect_objects_threshold).0
+                }
+                CachedObject::Deleted => StoreObject::Deleted.into(),
+                CachedObject::Wrapped => StoreObject::Wrapped.into(),
+            };
+            return Ok(Some((object_key, store_object)));
+        }
         self.perpetual_tables
             .get_latest_object_or_tombstone(object_id)
     }
@@ -1706,17 +2025,29 @@ impl AuthorityStore {
         &self,
         tx_digests: &[TransactionDigest],
     ) -> SuiResult<Vec<Option<VerifiedTransaction>>> {
-        Ok(self
-            .perpetual_tables
-            .transactions
-            .multi_get(tx_digests)
-            .map(|v| v.into_iter().map(|v| v.map(|v| v.into())).collect())?)
+        multi_get_cached(
+            tx_digests,
+            |digest| self.cache()?.get_transaction(digest).map(Some),
+            |digests| {
+                Ok(self
+                    .perpetual_tables
+                    .transactions
+                    .multi_get(digests)
+                    .map(|v| v.into_iter().map(|v| v.map(|v| v.into())).collect())?)
+            },
+        )
     }
 
     pub fn get_transaction_block(
         &self,
         tx_digest: &TransactionDigest,
     ) -> Result<Option<VerifiedTransaction>, TypedStoreError> {
+        if let Some(transaction) = self
+            .cache()
+            .and_then(|cache| cache.get_transaction(tx_digest))
+        {
+            return Ok(Some(transaction));
+        }
         self.perpetual_tables
             .transactions
             .get(tx_digest)
@@ -1727,27 +2058,38 @@ impl AuthorityStore {
         &self,
         digests: impl IntoIterator<Item = &'a TransactionDigest>,
     ) -> Result<Vec<Option<(VerifiedTransaction, usize)>>, TypedStoreError> {
-        self.perpetual_tables
-            .transactions
-            .multi_get_raw_bytes(digests)?
-            .into_iter()
-            .map(|raw_bytes_option| {
-                raw_bytes_option
-                    .map(|tx_bytes| {
-                        let tx: VerifiedTransaction =
-                            bcs::from_bytes::<TrustedTransaction>(&tx_bytes)?.into();
-                        Ok((tx, tx_bytes.len()))
+        let digests: Vec<_> = digests.into_iter().copied().collect();
+        multi_get_cached(
+            &digests,
+            |digest| {
+                let tx = self.cache()?.get_transaction(digest)?;
+                let size = bcs::serialized_size(tx.serializable_ref()).ok()?;
+                Some(Some((tx, size)))
+            },
+            |digests| {
+                self.perpetual_tables
+                    .transactions
+                    .multi_get_raw_bytes(digests)?
+                    .into_iter()
+                    .map(|raw_bytes_option| {
+                        raw_bytes_option
+                            .map(|tx_bytes| {
+                                let tx: VerifiedTransaction =
+                                    bcs::from_bytes::<TrustedTransaction>(&tx_bytes)?.into();
+                                Ok((tx, tx_bytes.len()))
+                            })
+                            .transpose()
                     })
-                    .transpose()
-            })
-            .collect()
+                    .collect()
+            },
+        )
     }
 
     // TODO: Transaction Orchestrator also calls this, which is not ideal.
     // Instead of this function use AuthorityEpochStore::epoch_start_configuration() to access this object everywhere
     // besides when we are reading fields for the current epoch
     pub fn get_sui_system_state_object(&self) -> SuiResult<SuiSystemState> {
-        get_sui_system_state(self.perpetual_tables.as_ref())
+        get_sui_system_state(self)
     }
 
     pub fn iter_live_object_set(
@@ -2014,6 +2356,12 @@ impl BackingPackageStore for AuthorityStore {
 impl ObjectStore for AuthorityStore {
     /// Read an object and return it, or Ok(None) if the object was not found.
     fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
+        if let Some((_, object)) = self
+            .cache()
+            .and_then(|cache| cache.get_latest_object(object_id))
+        {
+            return Ok(object.into_object());
+        }
         self.perpetual_tables.as_ref().get_object(object_id)
     }
 
@@ -2022,6 +2370,12 @@ impl ObjectStore for AuthorityStore {
         object_id: &ObjectID,
         version: VersionNumber,
     ) -> Result<Option<Object>, SuiError> {
+        if let Some(object) = self
+            .cache()
+            .and_then(|cache| cache.get_object_by_key(&ObjectKey(*object_id, version)))
+        {
+            return Ok(object.into_object());
+        }
         self.perpetual_tables.get_object_by_key(object_id, version)
     }
 }
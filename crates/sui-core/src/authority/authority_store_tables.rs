@@ -281,6 +281,10 @@ impl AuthorityPerpetualTables {
             .epoch())
     }
 
+    pub fn get_epoch_start_configuration(&self) -> SuiResult<Option<EpochStartConfiguration>> {
+        Ok(self.epoch_start_configuration.get(&())?)
+    }
+
     pub async fn set_epoch_start_configuration(
         &self,
         epoch_start_configuration: &EpochStartConfiguration,
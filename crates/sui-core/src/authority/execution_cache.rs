@@ -0,0 +1,529 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-memory write-back cache for the outputs of executed transactions.
+//!
+//! Executing a transaction normally writes its objects, locks, markers, events and effects to
+//! the perpetual tables in one batch. When the cache is enabled, these writes are instead kept
+//! in memory and served from there, and are persisted together by `flush`, which is called at
+//! checkpoint boundaries, at reconfiguration, and when too many transactions are pending.
+//!
+//! All pending transactions are flushed in a single atomic batch, in the order in which they
+//! were executed, so a crash loses a suffix of the executed transactions. The writes to the
+//! epoch store that mark a transaction as executed are deferred accordingly: its certificate and
+//! effects signatures are persisted right before the batch, and it is removed from
+//! `pending_execution` only after the batch is written. Transactions lost in a crash are
+//! therefore still pending when the node restarts, and are re-executed.
+//!
+//! Owned object locks are the exception: a lock on an object created by a pending transaction
+//! is acquired directly in the database, and flushing only initializes locks that do not exist
+//! yet, so that it never resets an acquired lock.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+
+use parking_lot::{Mutex, MutexGuard, RwLock};
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_with_registry, Histogram, IntCounter,
+    IntCounterVec, IntGauge, Registry,
+};
+use sui_config::node::ExecutionCacheConfig;
+use sui_types::base_types::{
+    EpochId, ObjectDigest, ObjectID, ObjectRef, SequenceNumber, TransactionDigest,
+};
+use sui_types::digests::{TransactionEffectsDigest, TransactionEventsDigest};
+use sui_types::effects::{TransactionEffects, TransactionEvents};
+use sui_types::error::SuiResult;
+use sui_types::message_envelope::Message;
+use sui_types::object::Object;
+use sui_types::storage::{MarkerValue, ObjectKey};
+use sui_types::transaction::VerifiedTransaction;
+use tracing::debug;
+
+use super::authority_per_epoch_store::AuthorityPerEpochStore;
+use super::authority_store::TransactionOutputs;
+use super::authority_store_types::StoreObject;
+use super::LATENCY_SEC_BUCKETS;
+
+struct ExecutionCacheMetrics {
+    pending_transactions: IntGauge,
+    reads: IntCounterVec,
+    flushes: IntCounter,
+    flushed_transactions: IntCounter,
+    flush_latency: Histogram,
+}
+
+impl ExecutionCacheMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            pending_transactions: register_int_gauge_with_registry!(
+                "execution_cache_pending_transactions",
+                "Number of executed transactions whose outputs are not flushed yet",
+                registry,
+            )
+            .unwrap(),
+            reads: register_int_counter_vec_with_registry!(
+                "execution_cache_reads",
+                "Number of reads served by the execution cache",
+                &["table", "result"],
+                registry,
+            )
+            .unwrap(),
+            flushes: register_int_counter_with_registry!(
+                "execution_cache_flushes",
+                "Number of times the execution cache was flushed",
+                registry,
+            )
+            .unwrap(),
+            flushed_transactions: register_int_counter_with_registry!(
+                "execution_cache_flushed_transactions",
+                "Number of transactions persisted by flushing the execution cache",
+                registry,
+            )
+            .unwrap(),
+            flush_latency: register_histogram_with_registry!(
+                "execution_cache_flush_latency",
+                "Latency of flushing the execution cache",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// An object version written by a pending transaction.
+pub(crate) enum CachedObject {
+    Object(Object),
+    Deleted,
+    Wrapped,
+}
+
+impl CachedObject {
+    pub fn into_object(self) -> Option<Object> {
+        match self {
+            CachedObject::Object(object) => Some(object),
+            CachedObject::Deleted | CachedObject::Wrapped => None,
+        }
+    }
+
+    pub fn object_reference(&self, object_key: &ObjectKey) -> ObjectRef {
+        match self {
+            CachedObject::Object(object) => object.compute_object_reference(),
+            CachedObject::Deleted => (
+                object_key.0,
+                object_key.1,
+                ObjectDigest::OBJECT_DIGEST_DELETED,
+            ),
+            CachedObject::Wrapped => (
+                object_key.0,
+                object_key.1,
+                ObjectDigest::OBJECT_DIGEST_WRAPPED,
+            ),
+        }
+    }
+}
+
+/// The state of an owned object lock after a pending transaction. Executing a transaction only
+/// ever initializes or deletes locks: locks are acquired directly in the database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CachedLock {
+    Initialized,
+    Deleted,
+}
+
+/// A transaction executed since the last flush, together with its outputs.
+pub(crate) struct CachedTransaction {
+    /// Position of the transaction in execution order.
+    seq: u64,
+    pub transaction: VerifiedTransaction,
+    pub effects: TransactionEffects,
+    pub outputs: TransactionOutputs,
+    /// The epoch store the transaction was executed in, whose signatures and pending
+    /// executions are updated when the transaction is flushed.
+    pub epoch_store: Arc<AuthorityPerEpochStore>,
+}
+
+impl CachedTransaction {
+    fn get_object(&self, object_key: &ObjectKey) -> Option<CachedObject> {
+        if let Some(object) = self
+            .outputs
+            .written
+            .get(&object_key.0)
+            .filter(|object| object.version() == object_key.1)
+        {
+            return Some(CachedObject::Object(object.clone()));
+        }
+        self.outputs
+            .tombstones
+            .iter()
+            .rfind(|(key, _)| key == object_key)
+            .map(|(_, tombstone)| match tombstone {
+                StoreObject::Wrapped => CachedObject::Wrapped,
+                _ => CachedObject::Deleted,
+            })
+    }
+
+    fn get_marker(&self, epoch: EpochId, object_key: &ObjectKey) -> Option<MarkerValue> {
+        self.outputs
+            .markers
+            .iter()
+            .rfind(|((marker_epoch, key), _)| *marker_epoch == epoch && key == object_key)
+            .map(|(_, marker)| *marker)
+    }
+}
+
+struct CachedLockEntry {
+    seq: u64,
+    lock: CachedLock,
+}
+
+/// Index of the writes of pending transactions. Every entry points to the latest pending
+/// transaction that wrote it.
+#[derive(Default)]
+struct CachedWrites {
+    next_seq: u64,
+    transactions: HashMap<TransactionDigest, Arc<CachedTransaction>>,
+    effects: HashMap<TransactionEffectsDigest, Arc<CachedTransaction>>,
+    events: HashMap<TransactionEventsDigest, Arc<CachedTransaction>>,
+    objects: HashMap<ObjectID, BTreeMap<SequenceNumber, Arc<CachedTransaction>>>,
+    markers: HashMap<(EpochId, ObjectID), BTreeMap<SequenceNumber, Arc<CachedTransaction>>>,
+    locks: HashMap<ObjectID, BTreeMap<(SequenceNumber, ObjectDigest), CachedLockEntry>>,
+}
+
+impl CachedWrites {
+    fn insert(&mut self, transaction: Arc<CachedTransaction>) {
+        let outputs = &transaction.outputs;
+        self.transactions
+            .insert(*transaction.transaction.digest(), transaction.clone());
+        self.effects
+            .insert(transaction.effects.digest(), transaction.clone());
+        if !outputs.events.data.is_empty() {
+            self.events
+                .insert(outputs.events.digest(), transaction.clone());
+        }
+
+        let object_keys = outputs
+            .written
+            .iter()
+            .map(|(id, object)| ObjectKey(*id, object.version()))
+            .chain(outputs.tombstones.iter().map(|(key, _)| *key));
+        for ObjectKey(id, version) in object_keys {
+            self.objects
+                .entry(id)
+                .or_default()
+                .insert(version, transaction.clone());
+        }
+        for ((epoch, ObjectKey(id, version)), _) in &outputs.markers {
+            self.markers
+                .entry((*epoch, *id))
+                .or_default()
+                .insert(*version, transaction.clone());
+        }
+
+        let locks = outputs
+            .locks_to_initialize
+            .iter()
+            .map(|obj_ref| (obj_ref, CachedLock::Initialized))
+            .chain(
+                outputs
+                    .locks_to_delete
+                    .iter()
+                    .map(|obj_ref| (obj_ref, CachedLock::Deleted)),
+            );
+        for ((id, version, digest), lock) in locks {
+            self.locks.entry(*id).or_default().insert(
+                (*version, *digest),
+                CachedLockEntry {
+                    seq: transaction.seq,
+                    lock,
+                },
+            );
+        }
+    }
+
+    /// Removes the entries of all transactions up to and including `seq`. Entries that were
+    /// overwritten by a later transaction are kept.
+    fn evict(&mut self, seq: u64) {
+        self.transactions.retain(|_, tx| tx.seq > seq);
+        self.effects.retain(|_, tx| tx.seq > seq);
+        self.events.retain(|_, tx| tx.seq > seq);
+        self.objects.retain(|_, versions| {
+            versions.retain(|_, tx| tx.seq > seq);
+            !versions.is_empty()
+        });
+        self.markers.retain(|_, versions| {
+            versions.retain(|_, tx| tx.seq > seq);
+            !versions.is_empty()
+        });
+        self.locks.retain(|_, locks| {
+            locks.retain(|_, entry| entry.seq > seq);
+            !locks.is_empty()
+        });
+    }
+}
+
+pub(crate) struct ExecutionCache {
+    max_pending_transactions: usize,
+    writes: RwLock<CachedWrites>,
+    /// Pending transactions in execution order. Only drained while `flush_lock` is held.
+    pending: Mutex<VecDeque<Arc<CachedTransaction>>>,
+    flush_lock: Mutex<()>,
+    metrics: ExecutionCacheMetrics,
+}
+
+impl ExecutionCache {
+    pub fn new(config: ExecutionCacheConfig, registry: &Registry) -> Self {
+        Self {
+            max_pending_transactions: config.max_pending_transactions,
+            writes: RwLock::new(CachedWrites::default()),
+            pending: Mutex::new(VecDeque::new()),
+            flush_lock: Mutex::new(()),
+            metrics: ExecutionCacheMetrics::new(registry),
+        }
+    }
+
+    /// Adds the outputs of an executed transaction to the cache. Returns true if the cache
+    /// should be flushed.
+    pub fn insert(
+        &self,
+        transaction: VerifiedTransaction,
+        effects: TransactionEffects,
+        outputs: TransactionOutputs,
+        epoch_store: Arc<AuthorityPerEpochStore>,
+    ) -> bool {
+        let mut writes = self.writes.write();
+        let transaction = Arc::new(CachedTransaction {
+            seq: writes.next_seq,
+            transaction,
+            effects,
+            outputs,
+            epoch_store,
+        });
+        writes.next_seq += 1;
+        writes.insert(transaction.clone());
+
+        // The queue is appended to while holding the index lock, so that it stays in
+        // execution order.
+        let mut pending = self.pending.lock();
+        pending.push_back(transaction);
+        self.metrics.pending_transactions.set(pending.len() as i64);
+        pending.len() >= self.max_pending_transactions
+    }
+
+    /// Persists all pending transactions with `write`, in execution order. Entries stay
+    /// readable from the cache until `write` has returned.
+    pub fn flush(&self, write: impl FnOnce(&[Arc<CachedTransaction>]) -> SuiResult) -> SuiResult {
+        let _flush_guard = self.flush_lock.lock();
+        let transactions: Vec<_> = self.pending.lock().drain(..).collect();
+        let Some(last_seq) = transactions.last().map(|tx| tx.seq) else {
+            return Ok(());
+        };
+
+        let _timer = self.metrics.flush_latency.start_timer();
+        if let Err(err) = write(&transactions) {
+            let mut pending = self.pending.lock();
+            for transaction in transactions.into_iter().rev() {
+                pending.push_front(transaction);
+            }
+            return Err(err);
+        }
+        self.writes.write().evict(last_seq);
+
+        debug!(
+            num_transactions = transactions.len(),
+            "Flushed execution cache"
+        );
+        self.metrics.flushes.inc();
+        self.metrics
+            .flushed_transactions
+            .inc_by(transactions.len() as u64);
+        self.metrics
+            .pending_transactions
+            .set(self.pending.lock().len() as i64);
+        Ok(())
+    }
+
+    /// Blocks flushes for as long as the guard is held.
+    pub fn lock_flushes(&self) -> MutexGuard<'_, ()> {
+        self.flush_lock.lock()
+    }
+
+    fn record_read<T>(&self, table: &str, result: Option<T>) -> Option<T> {
+        let label = if result.is_some() { "hit" } else { "miss" };
+        self.metrics.reads.with_label_values(&[table, label]).inc();
+        result
+    }
+
+    pub fn get_object_by_key(&self, object_key: &ObjectKey) -> Option<CachedObject> {
+        let result = self
+            .writes
+            .read()
+            .objects
+            .get(&object_key.0)
+            .and_then(|versions| versions.get(&object_key.1))
+            .and_then(|tx| tx.get_object(object_key));
+        self.record_read("objects", result)
+    }
+
+    /// Returns the latest pending version of the object. When present, it is more recent than
+    /// any version in the database.
+    pub fn get_latest_object(&self, object_id: &ObjectID) -> Option<(ObjectKey, CachedObject)> {
+        self.find_object_lt_or_eq_version(object_id, SequenceNumber::MAX)
+    }
+
+    pub fn find_object_lt_or_eq_version(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Option<(ObjectKey, CachedObject)> {
+        let result = self
+            .writes
+            .read()
+            .objects
+            .get(object_id)
+            .and_then(|versions| versions.range(..=version).next_back())
+            .and_then(|(version, tx)| {
+                let object_key = ObjectKey(*object_id, *version);
+                tx.get_object(&object_key)
+                    .map(|object| (object_key, object))
+            });
+        self.record_read("objects", result)
+    }
+
+    pub fn get_marker(&self, epoch: EpochId, object_key: &ObjectKey) -> Option<MarkerValue> {
+        let result = self
+            .writes
+            .read()
+            .markers
+            .get(&(epoch, object_key.0))
+            .and_then(|versions| versions.get(&object_key.1))
+            .and_then(|tx| tx.get_marker(epoch, object_key));
+        self.record_read("markers", result)
+    }
+
+    /// Returns the marker of the latest version of the object in the given epoch.
+    pub fn get_latest_marker(
+        &self,
+        epoch: EpochId,
+        object_id: &ObjectID,
+    ) -> Option<(SequenceNumber, MarkerValue)> {
+        let result = self
+            .writes
+            .read()
+            .markers
+            .get(&(epoch, *object_id))
+            .and_then(|versions| versions.iter().next_back())
+            .and_then(|(version, tx)| {
+                tx.get_marker(epoch, &ObjectKey(*object_id, *version))
+                    .map(|marker| (*version, marker))
+            });
+        self.record_read("markers", result)
+    }
+
+    pub fn get_lock(&self, obj_ref: &ObjectRef) -> Option<CachedLock> {
+        let result = self
+            .writes
+            .read()
+            .locks
+            .get(&obj_ref.0)
+            .and_then(|locks| locks.get(&(obj_ref.1, obj_ref.2)))
+            .map(|entry| entry.lock);
+        self.record_read("locks", result)
+    }
+
+    /// Returns the latest lock of the object initialized by a pending transaction.
+    pub fn get_latest_initialized_lock(&self, object_id: &ObjectID) -> Option<ObjectRef> {
+        self.writes.read().locks.get(object_id).and_then(|locks| {
+            locks
+                .iter()
+                .rev()
+                .find(|(_, entry)| entry.lock == CachedLock::Initialized)
+                .map(|((version, digest), _)| (*object_id, *version, *digest))
+        })
+    }
+
+    pub fn contains_any_lock(&self, objects: &[ObjectRef]) -> bool {
+        let writes = self.writes.read();
+        objects.iter().any(|(id, version, digest)| {
+            writes
+                .locks
+                .get(id)
+                .is_some_and(|locks| locks.contains_key(&(*version, *digest)))
+        })
+    }
+
+    /// Returns true if the transaction was executed but is not flushed yet.
+    pub fn contains_transaction(&self, digest: &TransactionDigest) -> bool {
+        self.writes.read().transactions.contains_key(digest)
+    }
+
+    pub fn get_transaction(&self, digest: &TransactionDigest) -> Option<VerifiedTransaction> {
+        let result = self
+            .writes
+            .read()
+            .transactions
+            .get(digest)
+            .map(|tx| tx.transaction.clone());
+        self.record_read("transactions", result)
+    }
+
+    pub fn get_executed_effects_digest(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Option<TransactionEffectsDigest> {
+        let result = self
+            .writes
+            .read()
+            .transactions
+            .get(digest)
+            .map(|tx| tx.effects.digest());
+        self.record_read("executed_effects", result)
+    }
+
+    pub fn get_effects(&self, digest: &TransactionEffectsDigest) -> Option<TransactionEffects> {
+        let result = self
+            .writes
+            .read()
+            .effects
+            .get(digest)
+            .map(|tx| tx.effects.clone());
+        self.record_read("effects", result)
+    }
+
+    pub fn get_events(&self, digest: &TransactionEventsDigest) -> Option<TransactionEvents> {
+        let result = self
+            .writes
+            .read()
+            .events
+            .get(digest)
+            .map(|tx| tx.outputs.events.clone());
+        self.record_read("events", result)
+    }
+}
+
+/// Reads `keys` from the cache with `get_cached`, and the keys that are not cached with `get_db`.
+pub(crate) fn multi_get_cached<K: Clone, V, E>(
+    keys: &[K],
+    get_cached: impl Fn(&K) -> Option<V>,
+    get_db: impl FnOnce(&[K]) -> Result<Vec<V>, E>,
+) -> Result<Vec<V>, E> {
+    let mut results: Vec<_> = keys.iter().map(get_cached).collect();
+    let missing: Vec<_> = keys
+        .iter()
+        .zip(&results)
+        .filter(|(_, result)| result.is_none())
+        .map(|(key, _)| key.clone())
+        .collect();
+    if !missing.is_empty() {
+        let mut db_results = get_db(&missing)?.into_iter();
+        for result in results.iter_mut().filter(|result| result.is_none()) {
+            *result = db_results.next();
+        }
+    }
+    Ok(results
+        .into_iter()
+        .map(|result| result.expect("Every key is read from the cache or the database"))
+        .collect())
+}
@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use sui_types::base_types::ObjectID;
+
+/// Tracks the number of transactions mutating each shared object that are scheduled in a single
+/// consensus commit. Transactions that would take a shared object over its per-commit budget are
+/// not scheduled, and are deferred to a later commit by the caller.
+///
+/// All the state is derived from the sequenced transactions of a commit, so every validator makes
+/// the same scheduling decisions.
+pub(crate) struct SharedObjectCongestionTracker {
+    /// Maximum number of transactions mutating the same shared object in a commit. Congestion
+    /// control is disabled when this is None.
+    max_txns_per_object: Option<u64>,
+    scheduled_txns: HashMap<ObjectID, u64>,
+    congested_objects: HashSet<ObjectID>,
+}
+
+impl SharedObjectCongestionTracker {
+    pub fn new(max_txns_per_object: Option<u64>) -> Self {
+        Self {
+            max_txns_per_object,
+            scheduled_txns: HashMap::new(),
+            congested_objects: HashSet::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_txns_per_object.is_some()
+    }
+
+    /// Records a transaction mutating `mutable_shared_objects` as scheduled in the current commit
+    /// and returns true if all of the objects are within their budget. Otherwise, nothing is
+    /// recorded for the objects, the ones over budget are marked as congested and false is
+    /// returned.
+    pub fn try_schedule(&mut self, mutable_shared_objects: &[ObjectID]) -> bool {
+        let Some(max_txns_per_object) = self.max_txns_per_object else {
+            return true;
+        };
+
+        let mut fits = true;
+        for id in mutable_shared_objects {
+            if self.scheduled_txns.get(id).copied().unwrap_or_default() >= max_txns_per_object {
+                self.congested_objects.insert(*id);
+                fits = false;
+            }
+        }
+        if !fits {
+            return false;
+        }
+
+        for id in mutable_shared_objects {
+            *self.scheduled_txns.entry(*id).or_default() += 1;
+        }
+        true
+    }
+
+    /// Number of shared objects that went over budget in the current commit.
+    pub fn num_congested_objects(&self) -> usize {
+        self.congested_objects.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_schedules_everything() {
+        let mut tracker = SharedObjectCongestionTracker::new(None);
+        let obj = ObjectID::random();
+        assert!(!tracker.is_enabled());
+        for _ in 0..100 {
+            assert!(tracker.try_schedule(&[obj]));
+        }
+        assert_eq!(tracker.num_congested_objects(), 0);
+    }
+
+    #[test]
+    fn test_per_object_budget() {
+        let mut tracker = SharedObjectCongestionTracker::new(Some(2));
+        let obj1 = ObjectID::random();
+        let obj2 = ObjectID::random();
+
+        assert!(tracker.try_schedule(&[obj1]));
+        assert!(tracker.try_schedule(&[obj1, obj2]));
+        // obj1 is at its budget, so transactions touching it are deferred, and do not use up
+        // the budget of the other objects they mutate.
+        assert!(!tracker.try_schedule(&[obj1]));
+        assert!(!tracker.try_schedule(&[obj2, obj1]));
+        assert!(tracker.try_schedule(&[obj2]));
+        assert!(!tracker.try_schedule(&[obj2]));
+        // Transactions without mutable shared objects are never deferred.
+        assert!(tracker.try_schedule(&[]));
+
+        assert_eq!(tracker.num_congested_objects(), 2);
+    }
+}
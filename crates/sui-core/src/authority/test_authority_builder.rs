@@ -20,7 +20,7 @@ use sui_config::genesis::Genesis;
 use sui_config::node::{
     AuthorityStorePruningConfig, DBCheckpointConfig, ExpensiveSafetyCheckConfig,
 };
-use sui_config::node::{OverloadThresholdConfig, StateDebugDumpConfig};
+use sui_config::node::{ExecutionCacheConfig, OverloadThresholdConfig, StateDebugDumpConfig};
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_macros::nondeterministic;
 use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
@@ -53,6 +53,7 @@ pub struct TestAuthorityBuilder<'a> {
     /// By default, we don't insert the genesis checkpoint, which isn't needed by most tests.
     insert_genesis_checkpoint: bool,
     overload_threshold_config: Option<OverloadThresholdConfig>,
+    execution_cache_config: Option<ExecutionCacheConfig>,
 }
 
 impl<'a> TestAuthorityBuilder<'a> {
@@ -149,6 +150,11 @@ impl<'a> TestAuthorityBuilder<'a> {
         self
     }
 
+    pub fn with_execution_cache_config(mut self, config: ExecutionCacheConfig) -> Self {
+        assert!(self.execution_cache_config.replace(config).is_none());
+        self
+    }
+
     pub async fn build(self) -> Arc<AuthorityState> {
         let mut local_network_config_builder =
             sui_swarm_config::network_config_builder::ConfigBuilder::new_with_temp_dir()
@@ -179,6 +185,7 @@ impl<'a> TestAuthorityBuilder<'a> {
                     &genesis_committee,
                     genesis,
                     0,
+                    self.execution_cache_config,
                 )
                 .await
                 .unwrap()
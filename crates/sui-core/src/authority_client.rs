@@ -2,6 +2,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::transaction_trace;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use mysten_network::config::Config;
@@ -105,7 +106,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
         transaction: Transaction,
     ) -> Result<HandleTransactionResponse, SuiError> {
         self.client()
-            .transaction(transaction)
+            .transaction(traced_request(transaction))
             .await
             .map(tonic::Response::into_inner)
             .map_err(Into::into)
@@ -117,7 +118,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
         certificate: CertifiedTransaction,
     ) -> Result<HandleCertificateResponse, SuiError> {
         self.client()
-            .handle_certificate(certificate)
+            .handle_certificate(traced_request(certificate))
             .await
             .map(tonic::Response::into_inner)
             .map_err(Into::into)
@@ -130,7 +131,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
     ) -> Result<HandleCertificateResponseV2, SuiError> {
         let response = self
             .client()
-            .handle_certificate_v2(certificate.clone())
+            .handle_certificate_v2(traced_request(certificate.clone()))
             .await
             .map(tonic::Response::into_inner);
 
@@ -141,7 +142,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
         if response.as_ref().err().unwrap().code() == tonic::Code::Unimplemented {
             let response = self
                 .client()
-                .handle_certificate(certificate)
+                .handle_certificate(traced_request(certificate))
                 .await
                 .map(tonic::Response::into_inner)
                 .map_err(SuiError::from)?;
@@ -201,6 +202,13 @@ impl AuthorityAPI for NetworkAuthorityClient {
     }
 }
 
+/// Wraps `message` into a request carrying the trace context of the current span.
+fn traced_request<T>(message: T) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    transaction_trace::inject_current(request.metadata_mut());
+    request
+}
+
 pub fn make_network_authority_clients_with_network_config(
     committee: &CommitteeWithNetworkMetadata,
     network_config: &Config,
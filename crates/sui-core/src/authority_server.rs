@@ -38,6 +38,7 @@ use crate::consensus_adapter::{ConnectionMonitorStatusForTests, LazyNarwhalClien
 use crate::{
     authority::AuthorityState,
     consensus_adapter::{ConsensusAdapter, ConsensusAdapterMetrics},
+    transaction_trace,
 };
 
 #[cfg(test)]
@@ -288,6 +289,7 @@ impl ValidatorService {
             metrics,
         } = self;
 
+        let trace_context = transaction_trace::extract(request.metadata());
         let transaction = request.into_inner();
         let epoch_store = state.load_epoch_store_one_call_per_task();
 
@@ -347,6 +349,8 @@ impl ValidatorService {
 
         // Enable Trace Propagation across spans/processes using tx_digest
         let span = error_span!("validator_state_process_tx", ?tx_digest);
+        transaction_trace::record(*tx_digest, trace_context);
+        transaction_trace::attach(tx_digest, &span);
 
         let info = state
             .handle_transaction(&epoch_store, transaction)
@@ -521,7 +525,10 @@ impl Validator for ValidatorService {
         // Spawns a task which handles the certificate. The task will unconditionally continue
         // processing in the event that the client connection is dropped.
         spawn_monitored_task!(async move {
-            let span = error_span!("submit_certificate", tx_digest = ?request.get_ref().digest());
+            let tx_digest = *request.get_ref().digest();
+            let span = error_span!("submit_certificate", ?tx_digest);
+            transaction_trace::record(tx_digest, transaction_trace::extract(request.metadata()));
+            transaction_trace::attach(&tx_digest, &span);
             Self::handle_certificate(validator_service, request, false)
                 .instrument(span)
                 .await
@@ -543,7 +550,10 @@ impl Validator for ValidatorService {
         // Spawns a task which handles the certificate. The task will unconditionally continue
         // processing in the event that the client connection is dropped.
         spawn_monitored_task!(async move {
-            let span = error_span!("handle_certificate", tx_digest = ?request.get_ref().digest());
+            let tx_digest = *request.get_ref().digest();
+            let span = error_span!("handle_certificate", ?tx_digest);
+            transaction_trace::record(tx_digest, transaction_trace::extract(request.metadata()));
+            transaction_trace::attach(&tx_digest, &span);
             Self::handle_certificate(validator_service, request, true)
                 .instrument(span)
                 .await
@@ -46,13 +46,14 @@ use tokio::{
     time::timeout,
 };
 use tokio_stream::StreamExt;
-use tracing::{debug, error, info, instrument, trace, warn};
+use tracing::{debug, debug_span, error, info, instrument, trace, warn};
 use typed_store::Map;
 
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::authority::AuthorityStore;
 use crate::state_accumulator::StateAccumulator;
 use crate::transaction_manager::TransactionManager;
+use crate::transaction_trace;
 use crate::{authority::EffectsNotifyRead, checkpoints::CheckpointStore};
 
 use self::metrics::CheckpointExecutorMetrics;
@@ -270,6 +271,12 @@ impl CheckpointExecutor {
                 .expect("Failed to delete contents digest -> sequence number mapping");
         }
 
+        // Transactions executed in this checkpoint may still be in the execution cache. They must
+        // be persisted before the watermark moves past them, since a restart would not execute
+        // them again.
+        self.authority_store
+            .flush_execution_cache()
+            .expect("Failed to flush execution cache");
         self.checkpoint_store
             .update_highest_executed_checkpoint(checkpoint)
             .unwrap();
@@ -427,9 +434,7 @@ impl CheckpointExecutor {
 
         let digest_to_effects: HashMap<TransactionDigest, TransactionEffects> = self
             .authority_store
-            .perpetual_tables
-            .effects
-            .multi_get(shared_effects_digests.clone())?
+            .multi_get_effects(shared_effects_digests.iter().copied())?
             .into_iter()
             .zip(shared_effects_digests)
             .map(|(fx, fx_digest)| {
@@ -513,9 +518,7 @@ impl CheckpointExecutor {
     ) {
         let change_epoch_fx = self
             .authority_store
-            .perpetual_tables
-            .effects
-            .get(&execution_digests.effects)
+            .get_effects(&execution_digests.effects)
             .expect("Fetching effects for change_epoch tx cannot fail")
             .expect("Change_epoch tx effects must exist");
 
@@ -740,6 +743,14 @@ async fn handle_execution_effects(
                         &actual_effects.digest(),
                         authority_store.clone(),
                     );
+
+                    // Ends the trace of a sampled transaction with the execution of its checkpoint.
+                    let span = debug_span!(
+                        "checkpoint_executed",
+                        ?tx_digest,
+                        seq = ?checkpoint.sequence_number()
+                    );
+                    transaction_trace::attach(tx_digest, &span);
                 }
 
                 // return Ok(effects);
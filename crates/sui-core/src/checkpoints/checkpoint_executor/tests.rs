@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use sui_config::node::ExpensiveSafetyCheckConfig;
+use sui_config::node::{ExecutionCacheConfig, ExpensiveSafetyCheckConfig};
+use sui_types::base_types::{dbg_addr, ExecutionData};
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
 use sui_types::gas::GasCostSummary;
 use tempfile::tempdir;
 
@@ -12,9 +14,18 @@ use crate::authority::epoch_start_configuration::EpochStartConfiguration;
 use broadcast::{Receiver, Sender};
 use sui_protocol_config::SupportedProtocolVersions;
 use sui_types::committee::ProtocolVersion;
-use sui_types::messages_checkpoint::{ECMHLiveObjectSetDigest, EndOfEpochData, VerifiedCheckpoint};
+use sui_types::messages_checkpoint::{
+    ECMHLiveObjectSetDigest, EndOfEpochData, FullCheckpointContents, VerifiedCheckpoint,
+    VerifiedCheckpointContents,
+};
+use sui_types::object::{Object, Owner};
+use sui_types::transaction::{
+    TransactionData, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+};
+use sui_types::utils::to_sender_signed_transaction;
 use tokio::{sync::broadcast, time::timeout};
 
+use crate::authority::authority_test_utils::send_and_confirm_transaction;
 use crate::authority::test_authority_builder::TestAuthorityBuilder;
 use crate::{
     authority::AuthorityState, checkpoints::CheckpointStore, state_accumulator::StateAccumulator,
@@ -98,6 +109,134 @@ pub async fn test_checkpoint_executor_crash_recovery() {
     executor_handle.abort();
 }
 
+/// Test that transactions whose outputs were still in the execution cache when the node crashed
+/// are executed again from their checkpoint after a restart, to the same state.
+#[tokio::test]
+pub async fn test_execution_cache_crash_recovery() {
+    let network_config =
+        sui_swarm_config::network_config_builder::ConfigBuilder::new_with_temp_dir().build();
+    let committee = CommitteeFixture::from_network_config(&network_config);
+    let store_dir = tempdir().unwrap();
+    let build_state = || {
+        TestAuthorityBuilder::new()
+            .with_network_config(&network_config)
+            .with_store_base_path(store_dir.path().to_path_buf())
+            .with_execution_cache_config(ExecutionCacheConfig::default())
+            .build()
+    };
+
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object = Object::with_owner_for_testing(sender);
+    let gas_object = Object::with_owner_for_testing(sender);
+    let object_id = object.id();
+
+    let state = build_state().await;
+    state
+        .insert_genesis_objects(&[object.clone(), gas_object.clone()])
+        .await;
+    let rgp = state.reference_gas_price_for_testing().unwrap();
+    let transaction = to_sender_signed_transaction(
+        TransactionData::new_transfer(
+            dbg_addr(2),
+            object.compute_object_reference(),
+            sender,
+            gas_object.compute_object_reference(),
+            rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            rgp,
+        ),
+        &sender_key,
+    );
+    let (_, effects) = send_and_confirm_transaction(&state, transaction.clone())
+        .await
+        .unwrap();
+    let effects = effects.into_data();
+    let tx_digest = *effects.transaction_digest();
+    let expected_object = state.get_object(&object_id).await.unwrap().unwrap();
+    assert_eq!(expected_object.owner, Owner::AddressOwner(dbg_addr(2)));
+
+    // Crash before the execution cache is flushed
+    assert!(!state
+        .database
+        .perpetual_tables
+        .executed_effects
+        .contains_key(&tx_digest)
+        .unwrap());
+    drop(state);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let state = build_state().await;
+    assert!(!state.database.is_tx_already_executed(&tx_digest).unwrap());
+    assert_eq!(state.get_object(&object_id).await.unwrap().unwrap(), object);
+
+    // State sync stores the checkpoint of the transaction, with the transaction and its effects
+    let checkpoint_dir = tempdir().unwrap();
+    let checkpoint_store = CheckpointStore::new(checkpoint_dir.path());
+    let (checkpoint_sender, _): (Sender<VerifiedCheckpoint>, Receiver<VerifiedCheckpoint>) =
+        broadcast::channel(10);
+    let (checkpoints, _, _, _) = committee.make_empty_checkpoints(1, None);
+    sync_checkpoint(&checkpoints[0], &checkpoint_store, &checkpoint_sender);
+    let contents = VerifiedCheckpointContents::new_unchecked(
+        FullCheckpointContents::new_with_causally_ordered_transactions([ExecutionData::new(
+            transaction.clone(),
+            effects.clone(),
+        )]),
+    );
+    let checkpoint =
+        committee.make_checkpoint_with_contents(checkpoints[0].clone(), contents.clone());
+    state
+        .database
+        .insert_transaction_and_effects(&VerifiedTransaction::new_unchecked(transaction), &effects)
+        .unwrap();
+    checkpoint_store
+        .insert_verified_checkpoint(&checkpoint)
+        .unwrap();
+    checkpoint_store
+        .insert_checkpoint_contents(contents.into_inner().into_checkpoint_contents())
+        .unwrap();
+    checkpoint_store
+        .update_highest_synced_checkpoint(&checkpoint)
+        .unwrap();
+    checkpoint_sender.send(checkpoint).unwrap();
+
+    let mut executor = CheckpointExecutor::new_for_tests(
+        checkpoint_sender.subscribe(),
+        checkpoint_store.clone(),
+        state.database.clone(),
+        state.transaction_manager().clone(),
+        Arc::new(StateAccumulator::new(state.database.clone())),
+    );
+    let epoch_store = state.epoch_store_for_testing().clone();
+    let executor_handle =
+        spawn_monitored_task!(async move { executor.run_epoch(epoch_store).await });
+    timeout(Duration::from_secs(10), async {
+        while checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()
+            .unwrap()
+            != Some(1)
+        {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("Checkpoint of the lost transaction was not executed");
+    executor_handle.abort();
+
+    // The transaction was executed again to the same effects, which are now persisted
+    assert_eq!(
+        state
+            .database
+            .perpetual_tables
+            .executed_effects
+            .get(&tx_digest)
+            .unwrap(),
+        Some(effects.digest())
+    );
+    assert_eq!(
+        state.get_object(&object_id).await.unwrap().unwrap(),
+        expected_object
+    );
+}
+
 /// Test that checkpoint execution correctly signals end of epoch after
 /// receiving last checkpoint of epoch, then resumes executing cehckpoints
 /// from the next epoch if called after reconfig
@@ -46,6 +46,7 @@ use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::consensus_handler::{classify, SequencedConsensusTransactionKey};
 use crate::consensus_throughput_calculator::{ConsensusThroughputProfiler, Level};
 use crate::epoch::reconfiguration::{ReconfigState, ReconfigurationInitiator};
+use crate::transaction_trace;
 use mysten_metrics::{spawn_monitored_task, GaugeGuard, GaugeGuardFutureExt};
 use sui_protocol_config::ProtocolConfig;
 use sui_simulator::anemo::PeerId;
@@ -55,7 +56,7 @@ use sui_types::fp_ensure;
 use sui_types::messages_consensus::ConsensusTransaction;
 use sui_types::messages_consensus::ConsensusTransactionKind;
 use tokio::time::Duration;
-use tracing::{debug, info, warn};
+use tracing::{debug, debug_span, info, warn, Instrument, Span};
 
 #[cfg(test)]
 #[path = "unit_tests/consensus_tests.rs"]
@@ -647,10 +648,20 @@ impl ConsensusAdapter {
         transaction: ConsensusTransaction,
         epoch_store: &Arc<AuthorityPerEpochStore>,
     ) -> JoinHandle<()> {
+        let span = match &transaction.kind {
+            ConsensusTransactionKind::UserTransaction(certificate) => {
+                let tx_digest = *certificate.digest();
+                let span = debug_span!("consensus_adapter_submit", ?tx_digest);
+                transaction_trace::attach(&tx_digest, &span);
+                span
+            }
+            _ => Span::none(),
+        };
         // Reconfiguration lock is dropped when pending_consensus_transactions is persisted, before it is handled by consensus
         let async_stage = self
             .clone()
-            .submit_and_wait(transaction, epoch_store.clone());
+            .submit_and_wait(transaction, epoch_store.clone())
+            .instrument(span);
         // Number of these tasks is weakly limited based on `num_inflight_transactions`.
         // (Limit is not applied atomically, and only to user transactions.)
         let join_handle = spawn_monitored_task!(async_stage);
@@ -1,7 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
 use std::sync::Arc;
 
 pub struct EpochMetrics {
@@ -76,6 +79,14 @@ pub struct EpochMetrics {
 
     /// Buffer stake current in effect for this epoch
     pub effective_buffer_stake: IntGauge,
+
+    /// Number of transactions deferred to a later consensus commit because a shared object they
+    /// mutate exceeded its per-commit budget.
+    pub consensus_handler_deferred_transactions: IntCounter,
+
+    /// Number of times a shared object exceeded its per-commit budget, counted once per object
+    /// and commit.
+    pub consensus_handler_congested_objects: IntCounter,
 }
 
 impl EpochMetrics {
@@ -158,6 +169,16 @@ impl EpochMetrics {
                 "Buffer stake current in effect for this epoch",
                 registry,
             ).unwrap(),
+            consensus_handler_deferred_transactions: register_int_counter_with_registry!(
+                "consensus_handler_deferred_transactions",
+                "Number of transactions deferred to a later commit by shared object congestion control",
+                registry,
+            ).unwrap(),
+            consensus_handler_congested_objects: register_int_counter_with_registry!(
+                "consensus_handler_congested_objects",
+                "Number of times a shared object exceeded its per-commit transaction budget",
+                registry,
+            ).unwrap(),
         };
         Arc::new(this)
     }
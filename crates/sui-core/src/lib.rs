@@ -34,6 +34,7 @@ pub mod subscription_handler;
 pub mod test_utils;
 mod transaction_manager;
 pub mod transaction_orchestrator;
+pub mod transaction_trace;
 pub mod verify_indexes;
 
 #[cfg(test)]
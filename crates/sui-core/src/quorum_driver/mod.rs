@@ -30,6 +30,7 @@ use crate::authority_aggregator::{
     ProcessTransactionResult,
 };
 use crate::authority_client::AuthorityAPI;
+use crate::transaction_trace;
 use mysten_common::sync::notify_read::{NotifyRead, Registration};
 use mysten_metrics::{spawn_monitored_task, GaugeGuard};
 use std::fmt::Write;
@@ -246,9 +247,11 @@ where
         let auth_agg = self.validators.load();
         let _tx_guard = GaugeGuard::acquire(&auth_agg.metrics.inflight_transactions);
         let tx_digest = *transaction.digest();
+        let span = tracing::debug_span!("aggregator_process_tx", ?tx_digest);
+        transaction_trace::attach(&tx_digest, &span);
         let result = auth_agg
             .process_transaction(transaction)
-            .instrument(tracing::debug_span!("aggregator_process_tx", ?tx_digest))
+            .instrument(span)
             .await;
 
         self.process_transaction_result(result, tx_digest).await
@@ -411,9 +414,11 @@ where
         let auth_agg = self.validators.load();
         let _cert_guard = GaugeGuard::acquire(&auth_agg.metrics.inflight_certificates);
         let tx_digest = *certificate.digest();
+        let span = tracing::debug_span!("aggregator_process_cert", ?tx_digest);
+        transaction_trace::attach(&tx_digest, &span);
         let (effects, events) = auth_agg
             .process_certificate(certificate.clone())
-            .instrument(tracing::debug_span!("aggregator_process_cert", ?tx_digest))
+            .instrument(span)
             .await
             .map_err(|agg_err| match agg_err {
                 AggregatorProcessCertificateError::FatalExecuteCertificate {
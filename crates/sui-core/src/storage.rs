@@ -155,7 +155,7 @@ impl ReadStore for RocksDbStore {
         &self,
         digest: &TransactionEffectsDigest,
     ) -> Result<Option<TransactionEffects>, Self::Error> {
-        self.authority_store.perpetual_tables.effects.get(digest)
+        self.authority_store.get_effects(digest)
     }
 
     fn get_transaction_events(
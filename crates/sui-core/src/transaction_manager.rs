@@ -465,7 +465,7 @@ impl TransactionManager {
                     .expect("Failed to check if tx is already executed")
                 {
                     // also ensure the transaction will not be retried after restart.
-                    let _ = epoch_store.remove_pending_execution(&digest);
+                    self.remove_pending_execution(epoch_store, &digest);
                     self.metrics
                         .transaction_manager_num_enqueued_certificates
                         .with_label_values(&["already_executed"])
@@ -631,7 +631,7 @@ impl TransactionManager {
             // skip already executed txes
             if self.authority_store.is_tx_already_executed(&digest)? {
                 // also ensure the transaction will not be retried after restart.
-                let _ = epoch_store.remove_pending_execution(&digest);
+                self.remove_pending_execution(epoch_store, &digest);
                 self.metrics
                     .transaction_manager_num_enqueued_certificates
                     .with_label_values(&["already_executed"])
@@ -821,7 +821,20 @@ impl TransactionManager {
             inner.maybe_shrink_capacity();
         }
 
-        let _ = epoch_store.remove_pending_execution(digest);
+        self.remove_pending_execution(epoch_store, digest);
+    }
+
+    /// Removes an executed transaction from pending execution, unless its outputs are still held
+    /// by the execution cache: it must then be re-executed if the node crashes before the cache
+    /// is flushed, and flushing removes it.
+    fn remove_pending_execution(
+        &self,
+        epoch_store: &AuthorityPerEpochStore,
+        digest: &TransactionDigest,
+    ) {
+        if !self.authority_store.is_tx_pending_flush(digest) {
+            let _ = epoch_store.remove_pending_execution(digest);
+        }
     }
 
     /// Sends the ready certificate for execution.
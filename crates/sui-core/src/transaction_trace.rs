@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Propagation of the trace context of a transaction, so that the spans created for a sampled
+//! transaction by the transaction orchestrator, the quorum driver, the validators' gRPC handlers,
+//! consensus submission and checkpoint execution form a single trace.
+//!
+//! Between processes the context travels in the gRPC metadata of validator requests. Inside a
+//! process it is looked up by transaction digest, since the components hand transactions to each
+//! other through queues and spawned tasks rather than through a common call stack.
+
+use once_cell::sync::Lazy;
+use sui_network::tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use sui_types::digests::TransactionDigest;
+use telemetry_subscribers::trace_context::{TraceContext, TraceContextRegistry};
+use tracing::Span;
+
+const MAX_TRACED_TRANSACTIONS: usize = 100_000;
+
+static TRANSACTION_TRACE_CONTEXTS: Lazy<TraceContextRegistry<TransactionDigest>> =
+    Lazy::new(|| TraceContextRegistry::new(MAX_TRACED_TRANSACTIONS));
+
+/// Remembers the current span as the origin of the trace of `digest`.
+pub fn record_current(digest: TransactionDigest) {
+    record(digest, TraceContext::current());
+}
+
+pub fn record(digest: TransactionDigest, context: TraceContext) {
+    TRANSACTION_TRACE_CONTEXTS.insert(digest, context);
+}
+
+/// Adds `span` to the trace of `digest`, if the transaction is traced.
+pub fn attach(digest: &TransactionDigest, span: &Span) {
+    TRANSACTION_TRACE_CONTEXTS.set_as_parent_of(digest, span);
+}
+
+/// Adds the trace context of the current span to the metadata of an outgoing request.
+pub fn inject_current(metadata: &mut MetadataMap) {
+    for (field, value) in TraceContext::current().iter() {
+        let (Ok(key), Ok(value)) = (
+            MetadataKey::from_bytes(field.as_bytes()),
+            MetadataValue::try_from(value),
+        ) else {
+            continue;
+        };
+        metadata.insert(key, value);
+    }
+}
+
+/// Reads the trace context propagated by the sender of an incoming request.
+pub fn extract(metadata: &MetadataMap) -> TraceContext {
+    TraceContext::FIELDS
+        .iter()
+        .filter_map(|field| {
+            let value = metadata.get(*field)?.to_str().ok()?;
+            Some((field.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::sdk::propagation::TraceContextPropagator;
+    use opentelemetry::sdk::trace::TracerProvider;
+    use opentelemetry::trace::TracerProvider as _;
+    use tracing::info_span;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// The trace id of a W3C `traceparent`, formatted as `version-traceid-spanid-flags`.
+    fn trace_id(context: &TraceContext) -> &str {
+        context
+            .get("traceparent")
+            .unwrap()
+            .split('-')
+            .nth(1)
+            .unwrap()
+    }
+
+    #[test]
+    fn sampled_context_survives_request_metadata() {
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        let tracer = TracerProvider::builder().build().tracer("test");
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let client_span = info_span!("submit_transaction");
+            let _guard = client_span.enter();
+            let sent = TraceContext::current();
+            assert!(sent.get("traceparent").unwrap().ends_with("-01"));
+
+            let mut metadata = MetadataMap::new();
+            inject_current(&mut metadata);
+            let received = extract(&metadata);
+            assert_eq!(received, sent);
+
+            // the span handling the request on the receiving side joins the sender's trace
+            let server_span = info_span!("handle_transaction");
+            received.set_as_parent_of(&server_span);
+            let server = TraceContext::from_span(&server_span);
+            assert_eq!(trace_id(&server), trace_id(&sent));
+            assert_ne!(server, sent);
+        });
+    }
+}
@@ -26,6 +26,7 @@ use std::collections::HashSet;
 use std::fs;
 use std::{convert::TryInto, env};
 
+use sui_config::node::ExecutionCacheConfig;
 use sui_json_rpc_types::{
     SuiArgument, SuiExecutionResult, SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTypeTag,
 };
@@ -2921,10 +2922,15 @@ async fn test_authority_persist() {
 
     let perpetual_tables = Arc::new(AuthorityPerpetualTables::open(&path, None));
     // Create an authority
-    let store =
-        AuthorityStore::open_with_committee_for_testing(perpetual_tables, &committee, &genesis, 0)
-            .await
-            .unwrap();
+    let store = AuthorityStore::open_with_committee_for_testing(
+        perpetual_tables,
+        &committee,
+        &genesis,
+        0,
+        None,
+    )
+    .await
+    .unwrap();
     let authority = init_state(&genesis, authority_key, store).await;
 
     // Create an object
@@ -2947,10 +2953,15 @@ async fn test_authority_persist() {
     let (genesis, authority_key) = init_state_parameters_from_rng(&mut StdRng::from_seed(seed));
     let committee = genesis.committee().unwrap();
     let perpetual_tables = Arc::new(AuthorityPerpetualTables::open(&path, None));
-    let store =
-        AuthorityStore::open_with_committee_for_testing(perpetual_tables, &committee, &genesis, 0)
-            .await
-            .unwrap();
+    let store = AuthorityStore::open_with_committee_for_testing(
+        perpetual_tables,
+        &committee,
+        &genesis,
+        0,
+        None,
+    )
+    .await
+    .unwrap();
     let authority2 = init_state(&genesis, authority_key, store).await;
     let obj2 = authority2.get_object(&object_id).await.unwrap().unwrap();
 
@@ -2959,6 +2970,184 @@ async fn test_authority_persist() {
     assert_eq!(obj2.owner, recipient);
 }
 
+#[tokio::test]
+async fn test_execution_cache_write_back() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object = Object::with_owner_for_testing(sender);
+    let gas_object = Object::with_owner_for_testing(sender);
+    let object_id = object.id();
+    let authority_state = TestAuthorityBuilder::new()
+        .with_execution_cache_config(ExecutionCacheConfig::default())
+        .build()
+        .await;
+    authority_state
+        .insert_genesis_objects(&[object.clone(), gas_object.clone()])
+        .await;
+    let rgp = authority_state.reference_gas_price_for_testing().unwrap();
+    let store = authority_state.database.clone();
+    let epoch_store = authority_state.epoch_store_for_testing();
+
+    let transfer = |object_ref: ObjectRef, gas_object_ref: ObjectRef| {
+        let data = TransactionData::new_transfer(
+            recipient,
+            object_ref,
+            sender,
+            gas_object_ref,
+            rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            rgp,
+        );
+        to_sender_signed_transaction(data, &sender_key)
+    };
+
+    // The first certificate is pending execution, as if it had been sequenced.
+    let old_object_ref = object.compute_object_reference();
+    let certificate = certify_transaction(
+        &authority_state,
+        transfer(old_object_ref, gas_object.compute_object_reference()),
+    )
+    .await
+    .unwrap();
+    epoch_store
+        .insert_pending_execution(&[VerifiedExecutableTransaction::new_from_certificate(
+            certificate.clone(),
+        )
+        .serializable()])
+        .unwrap();
+    let (effects, _) = authority_state
+        .try_execute_for_test(&certificate)
+        .await
+        .unwrap();
+    let effects = effects.into_data();
+    let tx_digest = *effects.transaction_digest();
+    let new_object_ref = effects
+        .mutated()
+        .into_iter()
+        .find(|(object_ref, _)| object_ref.0 == object_id)
+        .unwrap()
+        .0;
+    let new_gas_object_ref = effects.gas_object().0;
+
+    // The outputs are served from the cache, but not written to the database yet.
+    assert!(store.is_tx_already_executed(&tx_digest).unwrap());
+    assert_eq!(
+        store
+            .get_object(&object_id)
+            .unwrap()
+            .unwrap()
+            .compute_object_reference(),
+        new_object_ref
+    );
+    assert!(authority_state
+        .get_transaction_lock(&new_object_ref, &epoch_store)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(authority_state
+        .get_transaction_lock(&old_object_ref, &epoch_store)
+        .await
+        .is_err());
+    assert!(!store
+        .perpetual_tables
+        .executed_effects
+        .contains_key(&tx_digest)
+        .unwrap());
+    assert!(!store
+        .perpetual_tables
+        .objects
+        .contains_key(&ObjectKey(object_id, new_object_ref.1))
+        .unwrap());
+
+    // The certificate stays pending until its outputs are flushed, so that it is re-executed
+    // after a crash. Its signatures are served from memory in the meantime.
+    let is_pending = |digest: &TransactionDigest| {
+        epoch_store
+            .all_pending_execution()
+            .unwrap()
+            .iter()
+            .any(|cert| cert.digest() == digest)
+    };
+    assert!(is_pending(&tx_digest));
+    assert!(epoch_store
+        .get_effects_signature(&tx_digest)
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        epoch_store.effects_signatures_exists([&tx_digest]).unwrap(),
+        vec![true]
+    );
+
+    // Locking the outputs of a cached transaction writes the lock to the database without
+    // flushing the cache.
+    let transaction = authority_state
+        .verify_transaction(transfer(new_object_ref, new_gas_object_ref))
+        .unwrap();
+    authority_state
+        .handle_transaction(&epoch_store, transaction)
+        .await
+        .unwrap();
+    let tables = &store.perpetual_tables;
+    assert!(!tables.executed_effects.contains_key(&tx_digest).unwrap());
+    assert!(tables
+        .owned_object_transaction_locks
+        .get(&new_object_ref)
+        .unwrap()
+        .flatten()
+        .is_some());
+    assert!(authority_state
+        .get_transaction_lock(&new_object_ref, &epoch_store)
+        .await
+        .unwrap()
+        .is_some());
+
+    // Flushing does not reset the acquired lock.
+    store.flush_execution_cache().unwrap();
+    assert!(tables.executed_effects.contains_key(&tx_digest).unwrap());
+    assert!(tables
+        .objects
+        .contains_key(&ObjectKey(object_id, new_object_ref.1))
+        .unwrap());
+    assert!(!tables
+        .owned_object_transaction_locks
+        .contains_key(&old_object_ref)
+        .unwrap());
+    assert!(tables
+        .owned_object_transaction_locks
+        .get(&new_object_ref)
+        .unwrap()
+        .flatten()
+        .is_some());
+    assert!(!is_pending(&tx_digest));
+    assert!(epoch_store
+        .get_effects_signature(&tx_digest)
+        .unwrap()
+        .is_some());
+
+    // The locked transaction executes on top of the flushed outputs.
+    let (_, effects) = send_and_confirm_transaction(
+        &authority_state,
+        transfer(new_object_ref, new_gas_object_ref),
+    )
+    .await
+    .unwrap();
+    let second_tx_digest = *effects.into_data().transaction_digest();
+    assert!(!tables
+        .executed_effects
+        .contains_key(&second_tx_digest)
+        .unwrap());
+
+    store.flush_execution_cache().unwrap();
+    assert!(tables
+        .executed_effects
+        .contains_key(&second_tx_digest)
+        .unwrap());
+    assert!(!tables
+        .owned_object_transaction_locks
+        .contains_key(&new_object_ref)
+        .unwrap());
+    assert!(store.is_tx_already_executed(&second_tx_digest).unwrap());
+}
+
 #[tokio::test]
 async fn test_idempotent_reversed_confirmation() {
     // In this test we exercise the case where an authority first receive the certificate,
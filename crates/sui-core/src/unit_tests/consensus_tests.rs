@@ -9,6 +9,7 @@ use move_core_types::{account_address::AccountAddress, ident_str};
 use narwhal_types::Transactions;
 use narwhal_types::TransactionsServer;
 use narwhal_types::{Empty, TransactionProto};
+use std::collections::HashSet;
 use sui_network::tonic;
 use sui_types::crypto::deterministic_random_account_key;
 use sui_types::multiaddr::Multiaddr;
@@ -160,6 +161,125 @@ async fn submit_transaction_to_consensus_adapter() {
     waiter.await.unwrap();
 }
 
+#[tokio::test]
+async fn shared_object_congestion_control_defers_transactions() {
+    // Allow a single transaction per shared object in each commit.
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_max_txns_per_shared_object_per_commit_for_testing(1);
+        config
+    });
+
+    // All the test certificates mutate the same shared object.
+    let mut objects = test_gas_objects();
+    objects.push(Object::shared_for_testing());
+    let state = init_state_with_objects(objects).await;
+    let certificates = test_certificates(&state).await;
+    let num_certificates = certificates.len();
+    let epoch_store = state.epoch_store_for_testing();
+
+    let mut transactions: Vec<_> = certificates
+        .into_iter()
+        .map(|certificate| {
+            SequencedConsensusTransaction::new_test(ConsensusTransaction::new_certificate_message(
+                &state.name,
+                certificate,
+            ))
+        })
+        .collect();
+
+    // Every commit schedules one of the certificates, and defers the others to the next commit.
+    let mut scheduled = HashSet::new();
+    for _ in 0..num_certificates {
+        let certs = epoch_store
+            .process_consensus_transactions_for_tests(
+                std::mem::take(&mut transactions),
+                &Arc::new(CheckpointServiceNoop {}),
+                state.db(),
+                &state.metrics.skipped_consensus_txns,
+            )
+            .await
+            .unwrap();
+        assert_eq!(certs.len(), 1);
+        assert!(scheduled.insert(*certs[0].digest()));
+    }
+
+    // No deferred certificates are left.
+    let certs = epoch_store
+        .process_consensus_transactions_for_tests(
+            vec![],
+            &Arc::new(CheckpointServiceNoop {}),
+            state.db(),
+            &state.metrics.skipped_consensus_txns,
+        )
+        .await
+        .unwrap();
+    assert!(certs.is_empty());
+}
+
+#[tokio::test]
+async fn shared_object_congestion_control_schedules_deferred_transactions_at_end_of_epoch() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_max_txns_per_shared_object_per_commit_for_testing(1);
+        config
+    });
+
+    let mut objects = test_gas_objects();
+    objects.push(Object::shared_for_testing());
+    let state = init_state_with_objects(objects).await;
+    let certificates = test_certificates(&state).await;
+    let num_certificates = certificates.len();
+    assert!(num_certificates > 1);
+    let epoch_store = state.epoch_store_for_testing();
+
+    let transactions: Vec<_> = certificates
+        .into_iter()
+        .map(|certificate| {
+            SequencedConsensusTransaction::new_test(ConsensusTransaction::new_certificate_message(
+                &state.name,
+                certificate,
+            ))
+        })
+        .collect();
+
+    // The first commit schedules one certificate and defers the others.
+    let mut scheduled: HashSet<_> = epoch_store
+        .process_consensus_transactions_for_tests(
+            transactions,
+            &Arc::new(CheckpointServiceNoop {}),
+            state.db(),
+            &state.metrics.skipped_consensus_txns,
+        )
+        .await
+        .unwrap()
+        .iter()
+        .map(|certificate| *certificate.digest())
+        .collect();
+    assert_eq!(scheduled.len(), 1);
+
+    // The second commit closes the epoch, as the only validator sends EndOfPublish. All the
+    // deferred certificates have to be scheduled in it, even though they exceed the budget.
+    let mut end_of_publish = SequencedConsensusTransaction::new_test(
+        ConsensusTransaction::new_end_of_publish(state.name),
+    );
+    end_of_publish.certificate_author = state.name;
+    end_of_publish.consensus_index.last_committed_round = 2;
+    let certs = epoch_store
+        .process_consensus_transactions_for_tests(
+            vec![end_of_publish],
+            &Arc::new(CheckpointServiceNoop {}),
+            state.db(),
+            &state.metrics.skipped_consensus_txns,
+        )
+        .await
+        .unwrap();
+    assert_eq!(certs.len(), num_certificates - 1);
+    scheduled.extend(certs.iter().map(|certificate| *certificate.digest()));
+    assert_eq!(scheduled.len(), num_certificates);
+    assert!(!epoch_store
+        .get_reconfig_state_read_lock_guard()
+        .should_accept_consensus_certs());
+}
+
 pub struct ConsensusMockServer {
     sender: Sender<TransactionProto>,
 }
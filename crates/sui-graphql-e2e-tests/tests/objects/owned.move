@@ -0,0 +1,19 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Query the gas coin of an account once it has been checkpointed
+
+//# init --accounts A --simulator
+
+//# create-checkpoint
+
+//# run-graphql
+{
+  object(address: "@{obj_0_0}") {
+    version
+    kind
+    owner {
+      location
+    }
+  }
+}
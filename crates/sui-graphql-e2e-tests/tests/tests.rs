@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// These tests run an indexer and a GraphQL server against a local Postgres database (see
+// `ConnectionConfig::ci_integration_test_cfg`). They share that database, so they have to run
+// one at a time:
+//
+//   cargo test -p sui-graphql-e2e-tests --features pg_integration -- --test-threads 1
+
+pub const TEST_DIR: &str = "tests";
+
+#[cfg(feature = "pg_integration")]
+datatest_stable::harness!(
+    sui_transactional_test_runner::run_test,
+    TEST_DIR,
+    r".*\.(mvir|move)$"
+);
+
+#[cfg(not(feature = "pg_integration"))]
+fn main() {}
@@ -11,6 +11,7 @@ use simulacrum::Simulacrum;
 use std::env;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use sui_indexer::errors::IndexerError;
 use sui_indexer::indexer_v2::IndexerV2;
 use sui_indexer::metrics::IndexerMetrics;
@@ -18,6 +19,7 @@ use sui_indexer::new_pg_connection_pool_impl;
 use sui_indexer::store::PgIndexerStoreV2;
 use sui_indexer::utils::reset_database;
 use sui_indexer::IndexerConfig;
+use sui_rest_api::node_state_getter::NodeStateGetter;
 use sui_swarm_config::genesis_config::{AccountConfig, DEFAULT_GAS_AMOUNT};
 use test_cluster::TestCluster;
 use test_cluster::TestClusterBuilder;
@@ -82,20 +84,36 @@ pub async fn serve_simulator(
     graphql_connection_config: ConnectionConfig,
     internal_data_source_rpc_port: u16,
     simulator: Arc<Simulacrum<StdRng>>,
+) -> SimulatorCluster {
+    serve_executor(
+        graphql_connection_config,
+        internal_data_source_rpc_port,
+        simulator,
+    )
+    .await
+}
+
+/// Serves the checkpoints of `executor` over the REST API, indexes them and starts a GraphQL server
+/// on top of the indexer database. Unlike [`serve_simulator`], `executor` can keep producing
+/// checkpoints while the cluster runs.
+pub async fn serve_executor(
+    graphql_connection_config: ConnectionConfig,
+    internal_data_source_rpc_port: u16,
+    executor: Arc<dyn NodeStateGetter>,
 ) -> SimulatorCluster {
     let db_url = graphql_connection_config.db_url.clone();
 
-    let sim_server_url: SocketAddr = format!("127.0.0.1:{}", internal_data_source_rpc_port)
+    let executor_server_url: SocketAddr = format!("127.0.0.1:{}", internal_data_source_rpc_port)
         .parse()
         .unwrap();
 
     let simulator_server_handle = tokio::spawn(async move {
-        sui_rest_api::start_service(sim_server_url, simulator, Some("/rest".to_owned())).await;
+        sui_rest_api::start_service(executor_server_url, executor, Some("/rest".to_owned())).await;
     });
 
     // Starts indexer
     let (pg_store, pg_handle) =
-        start_test_indexer(Some(db_url), format!("http://{}", sim_server_url)).await;
+        start_test_indexer(Some(db_url), format!("http://{}", executor_server_url)).await;
 
     // Starts graphql server
     let graphql_server_handle = start_graphql_server(graphql_connection_config.clone()).await;
@@ -118,6 +136,34 @@ pub async fn serve_simulator(
     }
 }
 
+impl SimulatorCluster {
+    /// Waits until the GraphQL server reports `checkpoint` as its latest checkpoint, or a later
+    /// one. Returns an error if that does not happen within `timeout`.
+    pub async fn wait_for_checkpoint_catchup(
+        &self,
+        checkpoint: u64,
+        timeout: Duration,
+    ) -> Result<(), anyhow::Error> {
+        let query = "{ checkpoint { sequenceNumber } }".to_string();
+        tokio::time::timeout(timeout, async {
+            loop {
+                let latest = self
+                    .graphql_client
+                    .execute(query.clone(), vec![])
+                    .await
+                    .ok()
+                    .and_then(|res| res["data"]["checkpoint"]["sequenceNumber"].as_u64());
+                if latest.is_some_and(|latest| latest >= checkpoint) {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out waiting for checkpoint {checkpoint} to be indexed"))
+    }
+}
+
 async fn start_graphql_server(graphql_connection_config: ConnectionConfig) -> JoinHandle<()> {
     let server_config = ServerConfig {
         connection: graphql_connection_config,
@@ -0,0 +1,222 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use sui_rest_api::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::{info, warn};
+
+use super::interface::OutOfOrderHandler;
+use super::source::CheckpointSource;
+use super::watermark::WatermarkStore;
+
+/// Processes a fixed range of checkpoints with an [`OutOfOrderHandler`], split into chunks that
+/// are handed out to a number of workers. Progress is tracked under a watermark of its own, so
+/// a restarted backfill resumes after the last contiguous chunk it completed.
+pub(crate) struct Backfill {
+    handler: Arc<dyn OutOfOrderHandler>,
+    range: RangeInclusive<CheckpointSequenceNumber>,
+    workers: usize,
+}
+
+impl Backfill {
+    const CHUNK_SIZE: u64 = 100;
+    const RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+    pub fn new(
+        handler: Arc<dyn OutOfOrderHandler>,
+        range: RangeInclusive<CheckpointSequenceNumber>,
+        workers: usize,
+    ) -> Self {
+        Self {
+            handler,
+            range,
+            workers: workers.max(1),
+        }
+    }
+
+    pub fn task_name(&self) -> String {
+        format!(
+            "{}:backfill:{}-{}",
+            self.handler.name(),
+            self.range.start(),
+            self.range.end()
+        )
+    }
+
+    pub async fn run(self, source: Arc<dyn CheckpointSource>, store: Arc<dyn WatermarkStore>) {
+        let task = self.task_name();
+        let watermark = store
+            .get(&task)
+            .await
+            .expect("Failed to read backfill watermark");
+        let start = match watermark {
+            Some(watermark) => std::cmp::max(watermark.saturating_add(1), *self.range.start()),
+            None => *self.range.start(),
+        };
+        let end = *self.range.end();
+        if start > end {
+            info!(task = %task, "Backfill already complete");
+            return;
+        }
+        info!(task = %task, start, end, workers = self.workers, "Starting backfill");
+
+        let context = Arc::new(Context {
+            task,
+            end,
+            handler: self.handler,
+            source,
+            store,
+            next_chunk: AtomicU64::new(start),
+            tracker: tokio::sync::Mutex::new(Tracker::new(start)),
+        });
+        let workers = (0..self.workers)
+            .map(|_| mysten_metrics::spawn_monitored_task!(context.clone().work()))
+            .collect::<Vec<_>>();
+        for worker in futures::future::join_all(workers).await {
+            worker.expect("Backfill worker panicked");
+        }
+        info!(task = %context.task, "Backfill complete");
+    }
+}
+
+struct Context {
+    task: String,
+    end: CheckpointSequenceNumber,
+    handler: Arc<dyn OutOfOrderHandler>,
+    source: Arc<dyn CheckpointSource>,
+    store: Arc<dyn WatermarkStore>,
+    next_chunk: AtomicU64,
+    tracker: tokio::sync::Mutex<Tracker>,
+}
+
+impl Context {
+    async fn work(self: Arc<Self>) {
+        loop {
+            let start = self
+                .next_chunk
+                .fetch_add(Backfill::CHUNK_SIZE, Ordering::Relaxed);
+            if start > self.end {
+                return;
+            }
+            let end = std::cmp::min(start.saturating_add(Backfill::CHUNK_SIZE - 1), self.end);
+
+            let mut checkpoints = Vec::with_capacity((end - start + 1) as usize);
+            for sequence_number in start..=end {
+                checkpoints.push(self.fetch(sequence_number).await);
+            }
+            while let Err(e) = self.handler.process_checkpoints(&checkpoints).await {
+                warn!(task = %self.task, start, end, "Failed to process checkpoints: {e}");
+                tokio::time::sleep(Backfill::RETRY_INTERVAL).await;
+            }
+
+            // The tracker stays locked while persisting, so watermarks are written in order.
+            let mut tracker = self.tracker.lock().await;
+            if let Some(watermark) = tracker.complete(start, end) {
+                if let Err(e) = self.store.set(&self.task, watermark).await {
+                    warn!(task = %self.task, watermark, "Failed to persist watermark: {e}");
+                }
+            }
+        }
+    }
+
+    /// Waits for the checkpoint to become available, as backfills may run ahead of the chain.
+    async fn fetch(&self, sequence_number: CheckpointSequenceNumber) -> CheckpointData {
+        loop {
+            match self.source.get_checkpoint(sequence_number).await {
+                Ok(Some(checkpoint)) => return checkpoint,
+                Ok(None) => {}
+                Err(e) => warn!(
+                    task = %self.task,
+                    checkpoint = sequence_number,
+                    "Failed to fetch checkpoint: {e}"
+                ),
+            }
+            tokio::time::sleep(Backfill::RETRY_INTERVAL).await;
+        }
+    }
+}
+
+/// Tracks the chunks completed by the workers, which may finish out of order, to derive the
+/// highest checkpoint below which every checkpoint has been processed.
+struct Tracker {
+    next: CheckpointSequenceNumber,
+    /// Completed chunks past `next`, by their first checkpoint.
+    completed: BTreeMap<CheckpointSequenceNumber, CheckpointSequenceNumber>,
+}
+
+impl Tracker {
+    fn new(start: CheckpointSequenceNumber) -> Self {
+        Self {
+            next: start,
+            completed: BTreeMap::new(),
+        }
+    }
+
+    /// Records the chunk `start..=end` as completed and returns the new watermark, if it
+    /// advanced.
+    fn complete(
+        &mut self,
+        start: CheckpointSequenceNumber,
+        end: CheckpointSequenceNumber,
+    ) -> Option<CheckpointSequenceNumber> {
+        self.completed.insert(start, end);
+        let mut watermark = None;
+        while let Some(end) = self.completed.remove(&self.next) {
+            watermark = Some(end);
+            self.next = end.saturating_add(1);
+        }
+        watermark
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::source::{write_checkpoint, ObjectStoreCheckpointSource};
+    use crate::framework::test_utils::{test_checkpoint, RecordingHandler};
+    use crate::framework::watermark::InMemoryWatermarkStore;
+
+    #[tokio::test]
+    async fn workers_process_every_checkpoint_once() {
+        let dir = tempfile::tempdir().unwrap();
+        for sequence_number in 0..450 {
+            write_checkpoint(dir.path(), &test_checkpoint(sequence_number))
+                .await
+                .unwrap();
+        }
+        let source = Arc::new(ObjectStoreCheckpointSource::local_directory(dir.path()).unwrap());
+        let store = Arc::new(InMemoryWatermarkStore::default());
+        let handler = RecordingHandler::new("recording");
+
+        let backfill = Backfill::new(Arc::new(handler.clone()), 10..=449, 3);
+        let task = backfill.task_name();
+        backfill.run(source.clone(), store.clone()).await;
+
+        let mut processed = handler.processed();
+        processed.sort();
+        assert_eq!(processed, (10..=449).collect::<Vec<_>>());
+        assert_eq!(store.get(&task).await.unwrap(), Some(449));
+
+        // A completed backfill doesn't process anything when run again.
+        Backfill::new(Arc::new(handler.clone()), 10..=449, 3)
+            .run(source, store)
+            .await;
+        assert_eq!(handler.processed().len(), 440);
+    }
+
+    #[test]
+    fn watermark_advances_over_contiguous_chunks() {
+        let mut tracker = Tracker::new(100);
+        assert_eq!(tracker.complete(200, 299), None);
+        assert_eq!(tracker.complete(300, 349), None);
+        assert_eq!(tracker.complete(100, 199), Some(349));
+        assert_eq!(tracker.complete(450, 549), None);
+        assert_eq!(tracker.complete(350, 449), Some(549));
+        assert!(tracker.completed.is_empty());
+    }
+}
@@ -1,14 +1,31 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use sui_storage::object_store::ObjectStoreConfig;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
+use super::backfill::Backfill;
 use super::fetcher::CheckpointFetcher;
+use super::interface::OutOfOrderHandler;
+use super::source::{CheckpointSource, ObjectStoreCheckpointSource, RestCheckpointSource};
+use super::watermark::{InMemoryWatermarkStore, WatermarkStore};
 use super::Handler;
 
+enum Source {
+    LocalDirectory(PathBuf),
+    Archive(ObjectStoreConfig),
+}
+
 pub struct IndexerBuilder {
     rest_url: Option<String>,
+    source: Option<Source>,
     handlers: Vec<Box<dyn Handler>>,
+    backfills: Vec<Backfill>,
+    watermark_store: Arc<dyn WatermarkStore>,
     last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
     checkpoint_buffer_size: usize,
 }
@@ -20,22 +37,63 @@ impl IndexerBuilder {
     pub fn new() -> Self {
         Self {
             rest_url: None,
+            source: None,
             handlers: Vec::new(),
+            backfills: Vec::new(),
+            watermark_store: Arc::new(InMemoryWatermarkStore::default()),
             last_downloaded_checkpoint: None,
             checkpoint_buffer_size: Self::DEFAULT_CHECKPOINT_BUFFER_SIZE,
         }
     }
 
+    /// Reads checkpoints from the REST api at `rest_url`, unless a local directory or an archive
+    /// is set, in which case objects they lack are downloaded from it.
     pub fn rest_url<T: Into<String>>(mut self, rest_url: T) -> Self {
         self.rest_url = Some(rest_url.into());
         self
     }
 
+    /// Reads checkpoints from `<sequence number>.chk` files in a local directory, e.g. one
+    /// populated with [`write_checkpoint`](super::source::write_checkpoint), instead of the REST
+    /// api.
+    pub fn local_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.source = Some(Source::LocalDirectory(directory.into()));
+        self
+    }
+
+    /// Reads checkpoints from `<sequence number>.chk` files in an object store instead of the
+    /// REST api.
+    pub fn archive(mut self, config: ObjectStoreConfig) -> Self {
+        self.source = Some(Source::Archive(config));
+        self
+    }
+
     pub fn handler<T: Handler + 'static>(mut self, handler: T) -> Self {
         self.handlers.push(Box::new(handler));
         self
     }
 
+    /// Processes `range` with `handler` in `workers` parallel workers, next to the live
+    /// handlers.
+    pub fn backfill<T: OutOfOrderHandler + 'static>(
+        mut self,
+        handler: Arc<T>,
+        range: RangeInclusive<CheckpointSequenceNumber>,
+        workers: usize,
+    ) -> Self {
+        self.backfills.push(Backfill::new(handler, range, workers));
+        self
+    }
+
+    /// Where the watermarks of handlers and backfills are kept. Without a persistent store,
+    /// handlers start after `last_downloaded_checkpoint` and backfills from the start of their
+    /// range.
+    pub fn watermark_store<T: WatermarkStore + 'static>(mut self, store: T) -> Self {
+        self.watermark_store = Arc::new(store);
+        self
+    }
+
+    /// The checkpoint after which handlers without a watermark start.
     pub fn last_downloaded_checkpoint(
         mut self,
         last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
@@ -50,6 +108,68 @@ impl IndexerBuilder {
     }
 
     pub async fn run(self) {
+        assert!(!self.handlers.is_empty() || !self.backfills.is_empty());
+
+        // experimental rest api route is found at `/rest` on the same interface as the jsonrpc
+        // service
+        let rest_client = self
+            .rest_url
+            .map(|rest_url| sui_rest_api::Client::new(format!("{rest_url}/rest")));
+        let source: Arc<dyn CheckpointSource> = match self.source {
+            None => Arc::new(RestCheckpointSource::new(
+                rest_client.expect("No checkpoint source set"),
+            )),
+            Some(source) => {
+                let source = match source {
+                    Source::LocalDirectory(directory) => {
+                        ObjectStoreCheckpointSource::local_directory(directory)
+                            .expect("Failed to open checkpoint directory")
+                    }
+                    Source::Archive(config) => ObjectStoreCheckpointSource::new(&config)
+                        .expect("Failed to open checkpoint archive"),
+                };
+                match rest_client {
+                    Some(client) => Arc::new(source.with_rest_fallback(client)),
+                    None => Arc::new(source),
+                }
+            }
+        };
+
+        let backfills = self
+            .backfills
+            .into_iter()
+            .map(|backfill| {
+                mysten_metrics::spawn_monitored_task!(
+                    backfill.run(source.clone(), self.watermark_store.clone())
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if self.handlers.is_empty() {
+            for backfill in futures::future::join_all(backfills).await {
+                backfill.expect("Backfill panicked");
+            }
+            return;
+        }
+
+        let mut handlers = Vec::with_capacity(self.handlers.len());
+        for handler in self.handlers {
+            let watermark = self
+                .watermark_store
+                .get(handler.name())
+                .await
+                .expect("Failed to read handler watermark")
+                .or(self.last_downloaded_checkpoint);
+            handlers.push((handler, watermark));
+        }
+        // Restarting from the lowest watermark means no handler misses a checkpoint, while the
+        // runner keeps handlers that are further ahead from seeing checkpoints twice.
+        let last_downloaded_checkpoint = handlers
+            .iter()
+            .map(|(_, watermark)| *watermark)
+            .min()
+            .flatten();
+
         let (downloaded_checkpoint_data_sender, downloaded_checkpoint_data_receiver) =
             mysten_metrics::metered_channel::channel(
                 self.checkpoint_buffer_size,
@@ -59,23 +179,19 @@ impl IndexerBuilder {
                     .with_label_values(&["checkpoint_tx_downloading"]),
             );
 
-        // experimental rest api route is found at `/rest` on the same interface as the jsonrpc
-        // service
-        let rest_api_url = format!("{}/rest", self.rest_url.unwrap());
         let fetcher = CheckpointFetcher::new(
-            sui_rest_api::Client::new(rest_api_url),
-            self.last_downloaded_checkpoint,
+            source,
+            last_downloaded_checkpoint,
             downloaded_checkpoint_data_sender,
         );
         mysten_metrics::spawn_monitored_task!(fetcher.run());
 
-        assert!(!self.handlers.is_empty());
-
-        super::runner::run(
+        super::runner::run_with_watermarks(
             mysten_metrics::metered_channel::ReceiverStream::new(
                 downloaded_checkpoint_data_receiver,
             ),
-            self.handlers,
+            handlers,
+            self.watermark_store,
         )
         .await;
     }
@@ -1,13 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use anyhow::Result;
-use sui_rest_api::{CheckpointData, Client};
+use sui_rest_api::CheckpointData;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tracing::{info, warn};
 
+use super::source::CheckpointSource;
+
 pub struct CheckpointFetcher {
-    client: Client,
+    source: Arc<dyn CheckpointSource>,
     last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
     highest_known_checkpoint: CheckpointSequenceNumber,
     sender: mysten_metrics::metered_channel::Sender<CheckpointData>,
@@ -18,12 +22,12 @@ impl CheckpointFetcher {
     const CHECKPOINT_DOWNLOAD_CONCURRENCY: usize = 100;
 
     pub fn new(
-        client: Client,
+        source: Arc<dyn CheckpointSource>,
         last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
         sender: mysten_metrics::metered_channel::Sender<CheckpointData>,
     ) -> Self {
         Self {
-            client,
+            source,
             last_downloaded_checkpoint,
             highest_known_checkpoint: 0,
             sender,
@@ -51,10 +55,19 @@ impl CheckpointFetcher {
         }
     }
 
+    /// Sources that don't know their latest checkpoint are probed for the next
+    /// `CHECKPOINT_DOWNLOAD_CONCURRENCY` checkpoints instead, of which only the ones preceding
+    /// the first missing checkpoint are forwarded.
     async fn update_highest_known_checkpoint(&mut self) -> Result<()> {
-        let checkpoint = self.client.get_latest_checkpoint().await?;
-        self.highest_known_checkpoint =
-            std::cmp::max(self.highest_known_checkpoint, *checkpoint.sequence_number());
+        let highest = match self.source.latest_checkpoint().await? {
+            Some(latest) => latest,
+            None => self
+                .last_downloaded_checkpoint
+                .map(|i| i.saturating_add(1))
+                .unwrap_or(0)
+                .saturating_add(Self::CHECKPOINT_DOWNLOAD_CONCURRENCY as u64 - 1),
+        };
+        self.highest_known_checkpoint = std::cmp::max(self.highest_known_checkpoint, highest);
         Ok(())
     }
 
@@ -72,12 +85,15 @@ impl CheckpointFetcher {
         }
 
         let mut checkpoint_stream = checkpoint_range
-            .map(|next| self.client.get_full_checkpoint(next))
+            .map(|next| self.source.get_checkpoint(next))
             .pipe(futures::stream::iter)
             .buffered(Self::CHECKPOINT_DOWNLOAD_CONCURRENCY);
 
         while let Some(maybe_checkpoint) = checkpoint_stream.next().await {
-            let checkpoint = maybe_checkpoint?;
+            let Some(checkpoint) = maybe_checkpoint? else {
+                // Not available yet, the next attempt resumes from here.
+                break;
+            };
             self.last_downloaded_checkpoint =
                 Some(*checkpoint.checkpoint_summary.sequence_number());
 
@@ -1,8 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod backfill;
 mod builder;
 pub mod interface;
+pub mod source;
+pub mod watermark;
+
+#[cfg(test)]
+mod test_utils;
 
 // TODO remove the pub(crater) once indexer_v2.rs is renamed to lib.rs
 pub(crate) mod fetcher;
@@ -10,3 +16,5 @@ pub(crate) mod runner;
 
 pub use builder::IndexerBuilder;
 pub use interface::Handler;
+pub use source::{CheckpointSource, ObjectStoreCheckpointSource, RestCheckpointSource};
+pub use watermark::{FileWatermarkStore, InMemoryWatermarkStore, WatermarkStore};
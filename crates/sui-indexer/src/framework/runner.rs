@@ -1,13 +1,39 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use sui_rest_api::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use super::interface::Handler;
+use super::watermark::{InMemoryWatermarkStore, WatermarkStore};
 
-pub async fn run<S>(stream: S, mut handlers: Vec<Box<dyn Handler>>)
+pub async fn run<S>(stream: S, handlers: Vec<Box<dyn Handler>>)
 where
     S: futures::Stream<Item = CheckpointData> + std::marker::Unpin,
+{
+    run_with_watermarks(
+        stream,
+        handlers
+            .into_iter()
+            .map(|handler| (handler, None))
+            .collect(),
+        Arc::new(InMemoryWatermarkStore::default()),
+    )
+    .await
+}
+
+/// Runs `handlers` on the checkpoints of `stream`, each of them paired with its watermark.
+/// Checkpoints at or below a handler's watermark are skipped for that handler, so a stream
+/// starting at the lowest watermark doesn't process any checkpoint twice. The watermark of a
+/// handler is persisted in `store` after every batch it processed.
+pub async fn run_with_watermarks<S>(
+    stream: S,
+    mut handlers: Vec<(Box<dyn Handler>, Option<CheckpointSequenceNumber>)>,
+    store: Arc<dyn WatermarkStore>,
+) where
+    S: futures::Stream<Item = CheckpointData> + std::marker::Unpin,
 {
     use futures::StreamExt;
 
@@ -19,11 +45,66 @@ where
     let mut chunks: futures::stream::ReadyChunks<S> = stream.ready_chunks(batch_size);
     while let Some(checkpoints) = chunks.next().await {
         //TODO create tracing spans for processing
-        futures::future::join_all(
-            handlers
-                .iter_mut()
-                .map(|handler| async { handler.process_checkpoints(&checkpoints).await.unwrap() }),
+        futures::future::join_all(handlers.iter_mut().map(|(handler, watermark)| {
+            let store = store.clone();
+            let checkpoints = unprocessed(&checkpoints, *watermark);
+            async move {
+                let Some(last) = checkpoints.last() else {
+                    return;
+                };
+                let last = *last.checkpoint_summary.sequence_number();
+                handler.process_checkpoints(checkpoints).await.unwrap();
+                store
+                    .set(handler.name(), last)
+                    .await
+                    .expect("Failed to persist handler watermark");
+                *watermark = Some(last);
+            }
+        }))
+        .await;
+    }
+}
+
+/// The checkpoints of an ordered batch above `watermark`.
+fn unprocessed(
+    checkpoints: &[CheckpointData],
+    watermark: Option<CheckpointSequenceNumber>,
+) -> &[CheckpointData] {
+    let Some(watermark) = watermark else {
+        return checkpoints;
+    };
+    let start = checkpoints.partition_point(|checkpoint| {
+        *checkpoint.checkpoint_summary.sequence_number() <= watermark
+    });
+    &checkpoints[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::test_utils::{test_checkpoint, RecordingHandler};
+
+    #[tokio::test]
+    async fn handlers_resume_from_their_own_watermarks() {
+        let behind = RecordingHandler::new("behind");
+        let ahead = RecordingHandler::new("ahead");
+        let store = Arc::new(InMemoryWatermarkStore::default());
+
+        // After a restart, the stream starts after the lowest watermark.
+        let checkpoints: Vec<_> = (3..20).map(test_checkpoint).collect();
+        run_with_watermarks(
+            futures::stream::iter(checkpoints),
+            vec![
+                (Box::new(behind.clone()) as Box<dyn Handler>, Some(2)),
+                (Box::new(ahead.clone()), Some(10)),
+            ],
+            store.clone(),
         )
         .await;
+
+        assert_eq!(behind.processed(), (3..20).collect::<Vec<_>>());
+        assert_eq!(ahead.processed(), (11..20).collect::<Vec<_>>());
+        assert_eq!(store.get("behind").await.unwrap(), Some(19));
+        assert_eq!(store.get("ahead").await.unwrap(), Some(19));
     }
 }
@@ -0,0 +1,189 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use object_store::path::Path as ObjectPath;
+use object_store::DynObjectStore;
+use sui_rest_api::{CheckpointData, CheckpointTransaction, Client};
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use sui_types::storage::ObjectKey;
+
+/// Where the framework reads checkpoints from.
+#[async_trait::async_trait]
+pub trait CheckpointSource: Send + Sync {
+    /// The highest checkpoint available, if the source is able to tell. Sources that are not
+    /// are probed for new checkpoints instead.
+    async fn latest_checkpoint(&self) -> Result<Option<CheckpointSequenceNumber>>;
+
+    /// Returns `None` if the checkpoint is not available (yet).
+    async fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>>;
+}
+
+/// Downloads checkpoints from the REST api of a fullnode.
+pub struct RestCheckpointSource {
+    client: Client,
+}
+
+impl RestCheckpointSource {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait::async_trait]
+impl CheckpointSource for RestCheckpointSource {
+    async fn latest_checkpoint(&self) -> Result<Option<CheckpointSequenceNumber>> {
+        let checkpoint = self.client.get_latest_checkpoint().await?;
+        Ok(Some(*checkpoint.sequence_number()))
+    }
+
+    async fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>> {
+        Ok(Some(
+            self.client.get_full_checkpoint(sequence_number).await?,
+        ))
+    }
+}
+
+/// Reads checkpoints from an object store, e.g. an archive bucket or a local directory. Every
+/// checkpoint is stored in a file of its own, `<sequence number>.chk`, holding the BCS encoded
+/// [`CheckpointData`] as served by the REST api.
+///
+/// Archives may leave out the input and output objects of transactions. Those are downloaded
+/// from the REST api set with [`with_rest_fallback`](Self::with_rest_fallback), and a checkpoint
+/// lacking objects is an error without it.
+pub struct ObjectStoreCheckpointSource {
+    store: Arc<DynObjectStore>,
+    rest_fallback: Option<Client>,
+}
+
+impl ObjectStoreCheckpointSource {
+    pub fn new(config: &ObjectStoreConfig) -> Result<Self> {
+        Ok(Self {
+            store: config.make()?,
+            rest_fallback: None,
+        })
+    }
+
+    /// Reads checkpoints from a local directory, e.g. one populated with [`write_checkpoint`].
+    pub fn local_directory<P: Into<PathBuf>>(directory: P) -> Result<Self> {
+        Self::new(&ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(directory.into()),
+            ..Default::default()
+        })
+    }
+
+    /// Downloads the objects missing from a checkpoint with `client`.
+    pub fn with_rest_fallback(mut self, client: Client) -> Self {
+        self.rest_fallback = Some(client);
+        self
+    }
+
+    pub fn checkpoint_path(sequence_number: CheckpointSequenceNumber) -> ObjectPath {
+        ObjectPath::from(format!("{sequence_number}.chk"))
+    }
+
+    async fn fill_missing_objects(&self, checkpoint: &mut CheckpointData) -> Result<()> {
+        let sequence_number = *checkpoint.checkpoint_summary.sequence_number();
+        for transaction in &mut checkpoint.transactions {
+            let input_keys = CheckpointTransaction::input_object_keys(&transaction.effects);
+            let output_keys = CheckpointTransaction::output_object_keys(&transaction.effects);
+            let digest = *transaction.effects.transaction_digest();
+            for (objects, keys) in [
+                (&mut transaction.input_objects, input_keys),
+                (&mut transaction.output_objects, output_keys),
+            ] {
+                for ObjectKey(id, version) in missing_object_keys(objects, keys) {
+                    let Some(client) = &self.rest_fallback else {
+                        bail!(
+                            "Object {id} version {version} of transaction {digest} is missing \
+                             from checkpoint {sequence_number}"
+                        );
+                    };
+                    objects.push(client.get_object_with_version(id, version).await?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CheckpointSource for ObjectStoreCheckpointSource {
+    async fn latest_checkpoint(&self) -> Result<Option<CheckpointSequenceNumber>> {
+        Ok(None)
+    }
+
+    async fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>> {
+        let path = Self::checkpoint_path(sequence_number);
+        let bytes = match self.store.get(&path).await {
+            Ok(result) => result.bytes().await?,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut checkpoint = bcs::from_bytes(&bytes)?;
+        self.fill_missing_objects(&mut checkpoint).await?;
+        Ok(Some(checkpoint))
+    }
+}
+
+/// The keys in `keys` of which `objects` holds no object.
+fn missing_object_keys(objects: &[Object], keys: Vec<ObjectKey>) -> Vec<ObjectKey> {
+    let present: HashSet<_> = objects
+        .iter()
+        .map(|object| ObjectKey(object.id(), object.version()))
+        .collect();
+    keys.into_iter()
+        .filter(|key| !present.contains(key))
+        .collect()
+}
+
+/// Adds `checkpoint` to a directory read by [`ObjectStoreCheckpointSource::local_directory`].
+/// The file is renamed into place, so readers never see it partially written.
+pub async fn write_checkpoint(directory: &Path, checkpoint: &CheckpointData) -> Result<()> {
+    let path = checkpoint_path(directory, *checkpoint.checkpoint_summary.sequence_number());
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, bcs::to_bytes(checkpoint)?).await?;
+    tokio::fs::rename(&tmp_path, &path).await?;
+    Ok(())
+}
+
+fn checkpoint_path(directory: &Path, sequence_number: CheckpointSequenceNumber) -> PathBuf {
+    directory.join(format!("{sequence_number}.chk"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::test_utils::test_checkpoint;
+
+    #[tokio::test]
+    async fn local_directory_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = ObjectStoreCheckpointSource::local_directory(dir.path()).unwrap();
+        assert!(source.get_checkpoint(7).await.unwrap().is_none());
+
+        write_checkpoint(dir.path(), &test_checkpoint(7))
+            .await
+            .unwrap();
+        let checkpoint = source.get_checkpoint(7).await.unwrap().unwrap();
+        assert_eq!(*checkpoint.checkpoint_summary.sequence_number(), 7);
+        assert_eq!(source.latest_checkpoint().await.unwrap(), None);
+    }
+}
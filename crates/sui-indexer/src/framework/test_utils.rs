@@ -0,0 +1,78 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use fastcrypto::traits::KeyPair;
+use sui_rest_api::CheckpointData;
+use sui_types::base_types::ExecutionDigests;
+use sui_types::committee::Committee;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
+    SignedCheckpointSummary,
+};
+
+use super::interface::OutOfOrderHandler;
+
+/// An empty checkpoint, certified by a single validator committee.
+pub(crate) fn test_checkpoint(sequence_number: CheckpointSequenceNumber) -> CheckpointData {
+    let (committee, keys) = Committee::new_simple_test_committee_of_size(1);
+    let contents =
+        CheckpointContents::new_with_digests_and_signatures(Vec::<ExecutionDigests>::new(), vec![]);
+    let summary = CheckpointSummary::new(
+        committee.epoch,
+        sequence_number,
+        0,
+        &contents,
+        None,
+        GasCostSummary::default(),
+        None,
+        0,
+    );
+    let signature =
+        SignedCheckpointSummary::sign(committee.epoch, &summary, &keys[0], keys[0].public().into());
+    CheckpointData {
+        checkpoint_summary: CertifiedCheckpointSummary::new(summary, vec![signature], &committee)
+            .unwrap(),
+        checkpoint_contents: contents,
+        transactions: vec![],
+    }
+}
+
+/// Records the sequence numbers of the checkpoints it processes, in processing order.
+#[derive(Clone)]
+pub(crate) struct RecordingHandler {
+    name: String,
+    processed: Arc<Mutex<Vec<CheckpointSequenceNumber>>>,
+}
+
+impl RecordingHandler {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            processed: Default::default(),
+        }
+    }
+
+    pub fn processed(&self) -> Vec<CheckpointSequenceNumber> {
+        self.processed.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl OutOfOrderHandler for RecordingHandler {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn process_checkpoints(&self, checkpoints: &[CheckpointData]) -> Result<()> {
+        self.processed.lock().unwrap().extend(
+            checkpoints
+                .iter()
+                .map(|checkpoint| *checkpoint.checkpoint_summary.sequence_number()),
+        );
+        Ok(())
+    }
+}
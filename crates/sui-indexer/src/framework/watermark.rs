@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+/// Persists the progress of the tasks run by the framework: one watermark per live handler,
+/// keyed by [`Handler::name`](super::Handler::name), and one per backfill.
+///
+/// A watermark is the highest checkpoint up to which a task has processed every checkpoint.
+#[async_trait::async_trait]
+pub trait WatermarkStore: Send + Sync {
+    async fn get(&self, task: &str) -> Result<Option<CheckpointSequenceNumber>>;
+    async fn set(&self, task: &str, watermark: CheckpointSequenceNumber) -> Result<()>;
+}
+
+/// Keeps watermarks for the lifetime of the process only. Used when no other store is
+/// configured, in which case progress tracking is left to the handlers.
+#[derive(Default)]
+pub struct InMemoryWatermarkStore {
+    watermarks: Mutex<HashMap<String, CheckpointSequenceNumber>>,
+}
+
+#[async_trait::async_trait]
+impl WatermarkStore for InMemoryWatermarkStore {
+    async fn get(&self, task: &str) -> Result<Option<CheckpointSequenceNumber>> {
+        Ok(self.watermarks.lock().unwrap().get(task).copied())
+    }
+
+    async fn set(&self, task: &str, watermark: CheckpointSequenceNumber) -> Result<()> {
+        self.watermarks
+            .lock()
+            .unwrap()
+            .insert(task.to_string(), watermark);
+        Ok(())
+    }
+}
+
+/// Keeps all watermarks in a single JSON file, which is replaced atomically on every update.
+pub struct FileWatermarkStore {
+    path: PathBuf,
+    watermarks: tokio::sync::Mutex<BTreeMap<String, CheckpointSequenceNumber>>,
+}
+
+impl FileWatermarkStore {
+    pub async fn open<P: Into<PathBuf>>(path: P) -> Result<Self> {
+        let path = path.into();
+        let watermarks = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            watermarks: tokio::sync::Mutex::new(watermarks),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl WatermarkStore for FileWatermarkStore {
+    async fn get(&self, task: &str) -> Result<Option<CheckpointSequenceNumber>> {
+        Ok(self.watermarks.lock().await.get(task).copied())
+    }
+
+    async fn set(&self, task: &str, watermark: CheckpointSequenceNumber) -> Result<()> {
+        // Holding the lock while writing keeps concurrent updates from overtaking each other.
+        let mut watermarks = self.watermarks.lock().await;
+        watermarks.insert(task.to_string(), watermark);
+        let tmp_path = self.path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, serde_json::to_vec_pretty(&*watermarks)?).await?;
+        tokio::fs::rename(&tmp_path, &self.path).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_store_persists_watermarks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("watermarks.json");
+
+        let store = FileWatermarkStore::open(&path).await.unwrap();
+        assert_eq!(store.get("checkpoint-handler").await.unwrap(), None);
+        store.set("checkpoint-handler", 10).await.unwrap();
+        store.set("object-handler", 5).await.unwrap();
+        store.set("checkpoint-handler", 11).await.unwrap();
+
+        let store = FileWatermarkStore::open(&path).await.unwrap();
+        assert_eq!(store.get("checkpoint-handler").await.unwrap(), Some(11));
+        assert_eq!(store.get("object-handler").await.unwrap(), Some(5));
+    }
+}
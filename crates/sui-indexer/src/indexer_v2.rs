@@ -14,12 +14,14 @@ use mysten_metrics::spawn_monitored_task;
 use prometheus::Registry;
 use std::env;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use sui_json_rpc::ServerType;
 use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle};
 use tokio::runtime::Handle;
 use tracing::info;
 
 use crate::framework::fetcher::CheckpointFetcher;
+use crate::framework::source::RestCheckpointSource;
 use crate::handlers::checkpoint_handler_v2::new_handlers;
 use crate::processors_v2::processor_orchestrator_v2::ProcessorOrchestratorV2;
 use crate::store::{IndexerStoreV2, PgIndexerAnalyticalStore};
@@ -56,7 +58,7 @@ impl IndexerV2 {
         let rest_api_url = format!("{}/rest", config.rpc_client_url);
         let rest_client = sui_rest_api::Client::new(&rest_api_url);
         let fetcher = CheckpointFetcher::new(
-            rest_client.clone(),
+            Arc::new(RestCheckpointSource::new(rest_client)),
             last_seq_from_db,
             downloaded_checkpoint_data_sender,
         );
@@ -0,0 +1,51 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reference implementation of the external signer protocol, serving the keys of a keystore file.
+//! Not meant for production use, as it holds the private keys in a plain file.
+//!
+//! Usage: sui-test-signer <keystore path>
+
+use fastcrypto::encoding::{Base64, Encoding};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use sui_keys::external_signer::{SignerRequest, SignerResponse};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
+use sui_types::crypto::EncodeDecodeBase64;
+
+fn handle(keystore: &FileBasedKeystore, request: SignerRequest) -> SignerResponse {
+    match request {
+        SignerRequest::Keys => SignerResponse::Keys(
+            keystore
+                .keys()
+                .iter()
+                .map(EncodeDecodeBase64::encode_base64)
+                .collect(),
+        ),
+        SignerRequest::SignHashed { address, msg } => match Base64::decode(&msg) {
+            Ok(msg) => match keystore.sign_hashed(&address, &msg) {
+                Ok(signature) => SignerResponse::Signature(signature.encode_base64()),
+                Err(e) => SignerResponse::Error(e.to_string()),
+            },
+            Err(e) => SignerResponse::Error(format!("Invalid base64 message: {e}")),
+        },
+    }
+}
+
+fn main() -> Result<(), anyhow::Error> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow::anyhow!("Usage: sui-test-signer <keystore path>"))?;
+    let keystore = FileBasedKeystore::new(&PathBuf::from(path))?;
+
+    let mut stdout = io::stdout();
+    for line in io::stdin().lock().lines() {
+        let response = match serde_json::from_str(&line?) {
+            Ok(request) => handle(&keystore, request),
+            Err(e) => SignerResponse::Error(format!("Invalid request: {e}")),
+        };
+        writeln!(stdout, "{}", serde_json::to_string(&response)?)?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,204 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A keystore delegating to an external signer process, e.g. a wrapper around an HSM, a hardware
+//! wallet or a vault service.
+//!
+//! For each request, the signer command is spawned and receives a single JSON encoded
+//! [`SignerRequest`] line on stdin. It must answer with a single JSON encoded [`SignerResponse`]
+//! line on stdout and exit. Signers may also serve several requests, one per line, until stdin is
+//! closed. Anything written to stderr is passed through to the user.
+//!
+//! ```text
+//! > {"method":"keys"}
+//! < {"keys":["<Base64 flag || pk>", ...]}
+//! > {"method":"sign_hashed","address":"0x...","msg":"<Base64 message>"}
+//! < {"signature":"<Base64 flag || sig || pk>"}
+//! < {"error":"<reason>"}
+//! ```
+//!
+//! `sign_hashed` signs the message as is: for transactions, the message is the Blake2b256 digest
+//! of the BCS serialized intent message. The `sui-test-signer` binary is a reference signer
+//! backed by a keystore file.
+
+use crate::keystore::AccountKeystore;
+use anyhow::{anyhow, bail};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::HashFunction;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{
+    DefaultHash, EncodeDecodeBase64, PublicKey, Signature, SuiKeyPair, SuiSignature,
+};
+
+/// A request sent to the external signer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// List the public keys of the signer.
+    Keys,
+    /// Sign the Base64 encoded `msg` with the key of `address`.
+    SignHashed { address: SuiAddress, msg: String },
+}
+
+/// The answer of the external signer to a [`SignerRequest`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerResponse {
+    /// Base64 encoded `flag || pk` of each key.
+    Keys(Vec<String>),
+    /// Base64 encoded `flag || sig || pk`.
+    Signature(String),
+    Error(String),
+}
+
+/// How to start the external signer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ExternalSignerConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Keystore whose keys are held by an external signer. The public keys are fetched once, when the
+/// keystore is created.
+pub struct ExternalKeystore {
+    config: ExternalSignerConfig,
+    keys: Vec<PublicKey>,
+}
+
+impl ExternalKeystore {
+    pub fn new(config: ExternalSignerConfig) -> Result<Self, anyhow::Error> {
+        let mut keystore = Self {
+            config,
+            keys: vec![],
+        };
+        keystore.keys = match keystore.call(&SignerRequest::Keys)? {
+            SignerResponse::Keys(keys) => keys
+                .iter()
+                .map(|key| {
+                    PublicKey::decode_base64(key)
+                        .map_err(|e| anyhow!("Invalid public key from external signer: {e}"))
+                })
+                .collect::<Result<_, _>>()?,
+            response => bail!("Unexpected response from external signer: {:?}", response),
+        };
+        Ok(keystore)
+    }
+
+    pub fn config(&self) -> &ExternalSignerConfig {
+        &self.config
+    }
+
+    /// Spawns the signer and sends it `request`.
+    fn call(&self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let mut child = Command::new(&self.config.command)
+            .args(&self.config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| anyhow!("Cannot start external signer {}: {e}", self.config.command))?;
+        {
+            let mut stdin = child
+                .stdin
+                .take()
+                .ok_or_else(|| anyhow!("Cannot write to external signer"))?;
+            writeln!(stdin, "{}", serde_json::to_string(request)?)?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!("External signer exited with {}", output.status);
+        }
+        let stdout = String::from_utf8(output.stdout)?;
+        let line = stdout
+            .lines()
+            .next()
+            .ok_or_else(|| anyhow!("External signer did not answer"))?;
+        match serde_json::from_str(line)? {
+            SignerResponse::Error(error) => bail!("External signer failed: {error}"),
+            response => Ok(response),
+        }
+    }
+
+    fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, anyhow::Error> {
+        if !self.addresses().contains(address) {
+            bail!("Cannot find key for address: [{address}]");
+        }
+        let request = SignerRequest::SignHashed {
+            address: *address,
+            msg: Base64::encode(msg),
+        };
+        let signature = match self.call(&request)? {
+            SignerResponse::Signature(signature) => Signature::decode_base64(&signature)
+                .map_err(|e| anyhow!("Invalid signature from external signer: {e}"))?,
+            response => bail!("Unexpected response from external signer: {:?}", response),
+        };
+        let signer = SuiAddress::from(&signature.to_public_key()?);
+        if signer != *address {
+            bail!("External signer signed for {signer} instead of {address}");
+        }
+        Ok(signature)
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn add_key(&mut self, _keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        bail!("Keys cannot be added to an external signer keystore")
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys.clone()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        bail!("The private key of [{address}] is held by the external signer")
+    }
+
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.sign(address, msg)
+            .map_err(|e| signature::Error::from_source(e.to_string()))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent, msg);
+        let mut hasher = DefaultHash::default();
+        hasher.update(&bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?);
+        let signature = self.sign_hashed(address, &hasher.finalize().digest)?;
+        signature
+            .verify_secure(&intent_msg, *address, signature.scheme())
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        Ok(signature)
+    }
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.config.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        ExternalKeystore::new(ExternalSignerConfig::deserialize(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+}
@@ -5,8 +5,9 @@ use std::str::FromStr;
 use fastcrypto::hash::HashFunction;
 use tempfile::TempDir;
 
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignature, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -69,3 +70,43 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn external_signer_keystore_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut file_keystore = FileBasedKeystore::new(&keystore_path)?;
+    file_keystore.generate_and_add_new_key(SignatureScheme::ED25519, None, None)?;
+    file_keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None, None)?;
+
+    // The external keystore is configured like any other keystore, e.g. in client.yaml
+    let config = serde_json::json!({
+        "External": {
+            "command": env!("CARGO_BIN_EXE_sui-test-signer"),
+            "args": [keystore_path],
+        }
+    });
+    let mut keystore: Keystore = serde_json::from_value(config.clone())?;
+    assert_eq!(serde_json::to_value(&keystore)?, config);
+    assert!(keystore.to_string().contains("External"));
+    assert_eq!(keystore.keys(), file_keystore.keys());
+
+    for address in keystore.addresses() {
+        let msg = Intent::sui_app(IntentScope::PersonalMessage);
+        let signature = keystore.sign_secure(&address, &msg, Intent::sui_transaction())?;
+        signature.verify_secure(
+            &IntentMessage::new(Intent::sui_transaction(), &msg),
+            address,
+            signature.scheme(),
+        )?;
+    }
+
+    assert!(keystore
+        .sign_hashed(&SuiAddress::random_for_testing_only(), &[0; 32])
+        .is_err());
+    assert!(keystore.get_key(&keystore.addresses()[0]).is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None)
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,345 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, ensure, Result};
+use sui_rest_api::CheckpointTransaction;
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
+use sui_types::object::Object;
+use tracing::info;
+
+use crate::proof::{verify_end_of_epoch_checkpoint, verify_object, verify_transaction};
+use crate::source::CheckpointSource;
+
+/// Follows the committee changes of the network from a trusted genesis committee, and uses the
+/// committees to verify transactions and objects.
+pub struct LightClient {
+    /// The committee of each verified epoch, in order, starting with the genesis committee.
+    committees: Vec<Committee>,
+    /// The last checkpoint of each epoch but the latest verified one, in order.
+    end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+}
+
+impl LightClient {
+    pub fn new(genesis_committee: Committee) -> Self {
+        Self {
+            committees: vec![genesis_committee],
+            end_of_epoch_checkpoints: vec![],
+        }
+    }
+
+    /// Creates a light client from previously verified end of epoch checkpoints, verifying them
+    /// again.
+    pub fn from_end_of_epoch_checkpoints(
+        genesis_committee: Committee,
+        checkpoints: impl IntoIterator<Item = CertifiedCheckpointSummary>,
+    ) -> Result<Self> {
+        let mut client = Self::new(genesis_committee);
+        for checkpoint in checkpoints {
+            client.add_end_of_epoch_checkpoint(checkpoint)?;
+        }
+        Ok(client)
+    }
+
+    pub fn end_of_epoch_checkpoints(&self) -> &[CertifiedCheckpointSummary] {
+        &self.end_of_epoch_checkpoints
+    }
+
+    /// The committee of the latest verified epoch.
+    pub fn latest_committee(&self) -> &Committee {
+        self.committees.last().unwrap()
+    }
+
+    pub fn committee(&self, epoch: EpochId) -> Option<&Committee> {
+        let genesis_epoch = self.committees[0].epoch;
+        let index = epoch.checked_sub(genesis_epoch)?;
+        self.committees.get(index as usize)
+    }
+
+    /// Verifies the last checkpoint of the latest verified epoch, and moves on to the next epoch.
+    pub fn add_end_of_epoch_checkpoint(
+        &mut self,
+        checkpoint: CertifiedCheckpointSummary,
+    ) -> Result<()> {
+        let next_committee = verify_end_of_epoch_checkpoint(self.latest_committee(), &checkpoint)?;
+        info!(
+            epoch = next_committee.epoch,
+            checkpoint = checkpoint.sequence_number,
+            "Verified committee"
+        );
+        self.committees.push(next_committee);
+        self.end_of_epoch_checkpoints.push(checkpoint);
+        Ok(())
+    }
+
+    /// Verifies the committees of all epochs up to the epoch of the latest checkpoint of `source`.
+    pub async fn sync(&mut self, source: &impl CheckpointSource) -> Result<()> {
+        let latest = source.get_latest_checkpoint().await?;
+        while self.latest_committee().epoch < latest.epoch {
+            let epoch = self.latest_committee().epoch;
+            let first = self
+                .end_of_epoch_checkpoints
+                .last()
+                .map_or(0, |checkpoint| checkpoint.sequence_number + 1);
+            let last =
+                find_last_checkpoint_of_epoch(source, epoch, first, latest.sequence_number).await?;
+            let checkpoint = source.get_checkpoint(last).await?;
+            self.add_end_of_epoch_checkpoint(checkpoint)?;
+        }
+        Ok(())
+    }
+
+    /// Fetches checkpoint `sequence_number` and verifies that it contains the effects and events
+    /// of transaction `digest`.
+    pub async fn get_verified_transaction(
+        &self,
+        source: &impl CheckpointSource,
+        sequence_number: CheckpointSequenceNumber,
+        digest: &TransactionDigest,
+    ) -> Result<CheckpointTransaction> {
+        let checkpoint = source.get_full_checkpoint(sequence_number).await?;
+        let committee = self.checkpoint_committee(&checkpoint.checkpoint_summary)?;
+        verify_transaction(committee, &checkpoint, digest).cloned()
+    }
+
+    /// Fetches checkpoint `sequence_number` and verifies the state of object `id` at the end of
+    /// the checkpoint.
+    pub async fn get_verified_object(
+        &self,
+        source: &impl CheckpointSource,
+        sequence_number: CheckpointSequenceNumber,
+        id: ObjectID,
+    ) -> Result<Object> {
+        let checkpoint = source.get_full_checkpoint(sequence_number).await?;
+        let committee = self.checkpoint_committee(&checkpoint.checkpoint_summary)?;
+        verify_object(committee, &checkpoint, id)
+    }
+
+    fn checkpoint_committee(&self, summary: &CertifiedCheckpointSummary) -> Result<&Committee> {
+        self.committee(summary.epoch).ok_or_else(|| {
+            anyhow!(
+                "Checkpoint {} is from epoch {}, which has not been synced",
+                summary.sequence_number,
+                summary.epoch
+            )
+        })
+    }
+}
+
+/// Binary searches for the last checkpoint of `epoch`, given the first checkpoint of the epoch and
+/// a later checkpoint from a later epoch. The summaries fetched during the search are not
+/// verified: a source returning bad summaries can only make verifying the result fail.
+async fn find_last_checkpoint_of_epoch(
+    source: &impl CheckpointSource,
+    epoch: EpochId,
+    first: CheckpointSequenceNumber,
+    later: CheckpointSequenceNumber,
+) -> Result<CheckpointSequenceNumber> {
+    ensure!(
+        first < later,
+        "Checkpoint {later} is not after the first checkpoint of epoch {epoch}"
+    );
+    let (mut low, mut high) = (first, later);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if source.get_checkpoint(mid).await?.epoch <= epoch {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::checkpoint_with_transaction;
+    use async_trait::async_trait;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::BTreeMap;
+    use sui_rest_api::CheckpointData;
+    use sui_types::committee::ProtocolVersion;
+    use sui_types::crypto::{get_key_pair_from_rng, AuthorityKeyPair, KeypairTraits};
+    use sui_types::effects::TransactionEffectsAPI;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::{CheckpointContents, CheckpointSummary, EndOfEpochData};
+
+    /// A source serving checkpoints from memory.
+    struct TestSource {
+        checkpoints: BTreeMap<CheckpointSequenceNumber, CheckpointData>,
+    }
+
+    #[async_trait]
+    impl CheckpointSource for TestSource {
+        async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary> {
+            let checkpoint = self.checkpoints.values().last().unwrap();
+            Ok(checkpoint.checkpoint_summary.clone())
+        }
+
+        async fn get_checkpoint(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> Result<CertifiedCheckpointSummary> {
+            let checkpoint = self.get_full_checkpoint(sequence_number).await?;
+            Ok(checkpoint.checkpoint_summary)
+        }
+
+        async fn get_full_checkpoint(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> Result<CheckpointData> {
+            self.checkpoints
+                .get(&sequence_number)
+                .cloned()
+                .ok_or_else(|| anyhow!("Missing checkpoint {sequence_number}"))
+        }
+    }
+
+    /// Creates a committee of four validators with keys generated from `seed`.
+    fn committee(epoch: EpochId, seed: u8) -> (Committee, Vec<AuthorityKeyPair>) {
+        let mut rng = StdRng::from_seed([seed; 32]);
+        let keys: Vec<AuthorityKeyPair> =
+            (0..4).map(|_| get_key_pair_from_rng(&mut rng).1).collect();
+        let voting_rights = keys.iter().map(|key| (key.public().into(), 2500)).collect();
+        (Committee::new(epoch, voting_rights), keys)
+    }
+
+    fn checkpoint(
+        sequence_number: CheckpointSequenceNumber,
+        committee: &Committee,
+        keys: &[AuthorityKeyPair],
+        next_committee: Option<&Committee>,
+    ) -> CheckpointData {
+        let contents = CheckpointContents::new_with_digests_only_for_tests([]);
+        let end_of_epoch_data = next_committee.map(|next_committee| EndOfEpochData {
+            next_epoch_committee: next_committee.voting_rights.clone(),
+            next_epoch_protocol_version: ProtocolVersion::MAX,
+            epoch_commitments: vec![],
+        });
+        let summary = CheckpointSummary::new(
+            committee.epoch,
+            sequence_number,
+            0,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            end_of_epoch_data,
+            0,
+        );
+        CheckpointData {
+            checkpoint_summary: CertifiedCheckpointSummary::new_from_keypairs_for_testing(
+                summary, keys, committee,
+            ),
+            checkpoint_contents: contents,
+            transactions: vec![],
+        }
+    }
+
+    /// Builds a chain where epoch `e` ends at checkpoint `ends[e]`.
+    fn chain(ends: &[CheckpointSequenceNumber]) -> (Committee, TestSource, Vec<Committee>) {
+        let committees: Vec<_> = (0..=ends.len() as u64)
+            .map(|epoch| committee(epoch, epoch as u8))
+            .collect();
+        let mut checkpoints = BTreeMap::new();
+        let mut epoch = 0;
+        for sequence_number in 0..=ends.last().unwrap() + 1 {
+            let (committee, keys) = &committees[epoch];
+            let is_last = ends.get(epoch) == Some(&sequence_number);
+            let next_committee = is_last.then(|| &committees[epoch + 1].0);
+            checkpoints.insert(
+                sequence_number,
+                checkpoint(sequence_number, committee, keys, next_committee),
+            );
+            if is_last {
+                epoch += 1;
+            }
+        }
+        let genesis_committee = committees[0].0.clone();
+        let committees = committees.into_iter().map(|(c, _)| c).collect();
+        (genesis_committee, TestSource { checkpoints }, committees)
+    }
+
+    #[tokio::test]
+    async fn test_sync_committees() {
+        let (genesis_committee, source, committees) = chain(&[4, 5, 13]);
+        let mut client = LightClient::new(genesis_committee.clone());
+        client.sync(&source).await.unwrap();
+
+        assert_eq!(client.latest_committee(), committees.last().unwrap());
+        for committee in &committees {
+            assert_eq!(client.committee(committee.epoch), Some(committee));
+        }
+        let ends: Vec<_> = client
+            .end_of_epoch_checkpoints()
+            .iter()
+            .map(|checkpoint| checkpoint.sequence_number)
+            .collect();
+        assert_eq!(ends, vec![4, 5, 13]);
+
+        // Syncing again is a no-op, and the verified checkpoints can be loaded again.
+        client.sync(&source).await.unwrap();
+        let reloaded = LightClient::from_end_of_epoch_checkpoints(
+            genesis_committee,
+            client.end_of_epoch_checkpoints().to_vec(),
+        )
+        .unwrap();
+        assert_eq!(reloaded.latest_committee(), client.latest_committee());
+    }
+
+    #[tokio::test]
+    async fn test_reject_wrong_committee() {
+        let (genesis_committee, mut source, _) = chain(&[4, 9]);
+        // Replace the last checkpoint of epoch 0 with one certified by another committee.
+        let (impostor, keys) = committee(0, 100);
+        let (next_committee, _) = committee(1, 101);
+        source
+            .checkpoints
+            .insert(4, checkpoint(4, &impostor, &keys, Some(&next_committee)));
+
+        let mut client = LightClient::new(genesis_committee);
+        assert!(client.sync(&source).await.is_err());
+        assert_eq!(client.latest_committee().epoch, 0);
+    }
+
+    #[tokio::test]
+    async fn test_verified_transaction_and_object() {
+        let (genesis_committee, mut source, _) = chain(&[4, 9]);
+        let (committee, keys) = committee(1, 1);
+        let (checkpoint, object) = checkpoint_with_transaction(&committee, &keys, 7);
+        let digest = *checkpoint.transactions[0].transaction.digest();
+        source.checkpoints.insert(7, checkpoint);
+
+        // The checkpoint can't be verified before the committee of its epoch is.
+        let mut client = LightClient::new(genesis_committee);
+        assert!(client
+            .get_verified_transaction(&source, 7, &digest)
+            .await
+            .is_err());
+
+        client.sync(&source).await.unwrap();
+        let transaction = client
+            .get_verified_transaction(&source, 7, &digest)
+            .await
+            .unwrap();
+        assert_eq!(transaction.effects.transaction_digest(), &digest);
+        assert_eq!(
+            client
+                .get_verified_object(&source, 7, object.id())
+                .await
+                .unwrap(),
+            object
+        );
+
+        // Neither is part of another checkpoint.
+        assert!(client
+            .get_verified_transaction(&source, 8, &digest)
+            .await
+            .is_err());
+        assert!(client
+            .get_verified_object(&source, 8, object.id())
+            .await
+            .is_err());
+    }
+}
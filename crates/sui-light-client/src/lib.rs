@@ -0,0 +1,16 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A light client for Sui. Starting from the genesis committee, it follows the committee of each
+//! epoch through the last checkpoint of the previous epoch, and uses the committees to verify that
+//! transactions, their events, and objects are part of certified checkpoints, without trusting
+//! the full node serving the data.
+
+mod client;
+pub mod proof;
+mod source;
+#[cfg(test)]
+mod test_utils;
+
+pub use client::LightClient;
+pub use source::CheckpointSource;
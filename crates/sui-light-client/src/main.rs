@@ -0,0 +1,116 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context;
+use clap::*;
+use std::path::{Path, PathBuf};
+use sui_config::genesis::Genesis;
+use sui_light_client::LightClient;
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
+
+#[derive(Parser)]
+#[command(
+    name = "sui-light-client",
+    about = "Verify transactions and objects against certified checkpoints, starting from genesis"
+)]
+struct Opts {
+    /// URL of the REST API of a full node
+    #[arg(long)]
+    rest_url: String,
+    /// Path to the genesis blob of the network
+    #[arg(long)]
+    genesis: PathBuf,
+    /// File storing the verified end of epoch checkpoints between runs
+    #[arg(long, default_value = "end_of_epoch_checkpoints.bcs")]
+    checkpoints: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Verify the committees of all epochs up to the latest checkpoint
+    Sync,
+    /// Verify that the effects and events of a transaction are part of a checkpoint
+    CheckTransaction {
+        #[arg(long)]
+        checkpoint: CheckpointSequenceNumber,
+        #[arg(long)]
+        digest: TransactionDigest,
+    },
+    /// Verify the state of an object at the end of a checkpoint that wrote it
+    CheckObject {
+        #[arg(long)]
+        checkpoint: CheckpointSequenceNumber,
+        #[arg(long)]
+        object_id: ObjectID,
+    },
+}
+
+fn load_checkpoints(path: &Path) -> anyhow::Result<Vec<CertifiedCheckpointSummary>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Unable to read checkpoints from {}", path.display()))?;
+    bcs::from_bytes(&bytes).context("Unable to deserialize checkpoints")
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Opts::parse();
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+
+    let genesis_committee = Genesis::load(&opts.genesis)?.committee()?;
+    let mut client = LightClient::from_end_of_epoch_checkpoints(
+        genesis_committee,
+        load_checkpoints(&opts.checkpoints)?,
+    )?;
+    let source = sui_rest_api::Client::new(opts.rest_url);
+
+    match opts.command {
+        Command::Sync => {
+            client.sync(&source).await?;
+            std::fs::write(
+                &opts.checkpoints,
+                bcs::to_bytes(client.end_of_epoch_checkpoints())?,
+            )
+            .with_context(|| {
+                format!(
+                    "Unable to write checkpoints to {}",
+                    opts.checkpoints.display()
+                )
+            })?;
+            println!(
+                "Verified committees up to epoch {}",
+                client.latest_committee().epoch
+            );
+        }
+        Command::CheckTransaction { checkpoint, digest } => {
+            let transaction = client
+                .get_verified_transaction(&source, checkpoint, &digest)
+                .await?;
+            println!(
+                "Transaction {digest} is part of checkpoint {checkpoint}\nEffects: {}\nEvents: {}",
+                serde_json::to_string_pretty(&transaction.effects)?,
+                serde_json::to_string_pretty(&transaction.events)?,
+            );
+        }
+        Command::CheckObject {
+            checkpoint,
+            object_id,
+        } => {
+            let object = client
+                .get_verified_object(&source, checkpoint, object_id)
+                .await?;
+            println!(
+                "Object {object_id} at the end of checkpoint {checkpoint}: {}",
+                serde_json::to_string_pretty(&object)?
+            );
+        }
+    }
+    Ok(())
+}
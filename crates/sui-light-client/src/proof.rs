@@ -0,0 +1,213 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that facts about transactions and objects are part of a certified checkpoint, using
+//! only the committee of the checkpoint's epoch.
+
+use anyhow::{anyhow, bail, ensure, Result};
+use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::committee::Committee;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+use sui_types::object::Object;
+
+/// Verifies that `summary` is certified by `committee` and, if they are provided, that
+/// `contents` are the contents of the checkpoint.
+pub fn verify_checkpoint(
+    committee: &Committee,
+    summary: &CertifiedCheckpointSummary,
+    contents: Option<&CheckpointContents>,
+) -> Result<()> {
+    ensure!(
+        summary.epoch == committee.epoch,
+        "Checkpoint {} is from epoch {}, but the committee is the committee of epoch {}",
+        summary.sequence_number,
+        summary.epoch,
+        committee.epoch
+    );
+    summary
+        .verify_with_contents(committee, contents)
+        .map_err(|e| anyhow!("Checkpoint {} is not valid: {e}", summary.sequence_number))
+}
+
+/// Verifies the last checkpoint of an epoch with the committee of that epoch, and returns the
+/// committee of the next epoch.
+pub fn verify_end_of_epoch_checkpoint(
+    committee: &Committee,
+    summary: &CertifiedCheckpointSummary,
+) -> Result<Committee> {
+    verify_checkpoint(committee, summary, None)?;
+    let next_epoch_committee = summary.next_epoch_committee().ok_or_else(|| {
+        anyhow!(
+            "Checkpoint {} is not the last checkpoint of epoch {}",
+            summary.sequence_number,
+            summary.epoch
+        )
+    })?;
+    Ok(Committee::new(
+        summary.epoch + 1,
+        next_epoch_committee.iter().cloned().collect(),
+    ))
+}
+
+/// Verifies that the effects and events of the transaction with digest `digest` are part of the
+/// certified `checkpoint`, and returns them.
+pub fn verify_transaction<'a>(
+    committee: &Committee,
+    checkpoint: &'a CheckpointData,
+    digest: &TransactionDigest,
+) -> Result<&'a CheckpointTransaction> {
+    verify_checkpoint(
+        committee,
+        &checkpoint.checkpoint_summary,
+        Some(&checkpoint.checkpoint_contents),
+    )?;
+    let transaction = checkpoint
+        .transactions
+        .iter()
+        .find(|tx| tx.effects.transaction_digest() == digest)
+        .ok_or_else(|| {
+            anyhow!(
+                "Transaction {digest} is not part of checkpoint {}",
+                checkpoint.checkpoint_summary.sequence_number
+            )
+        })?;
+    verify_checkpoint_transaction(&checkpoint.checkpoint_contents, transaction)?;
+    Ok(transaction)
+}
+
+/// Verifies the state of object `id` at the end of the certified `checkpoint` against the effects
+/// of the last transaction of the checkpoint that wrote it, and returns it.
+pub fn verify_object(
+    committee: &Committee,
+    checkpoint: &CheckpointData,
+    id: ObjectID,
+) -> Result<Object> {
+    verify_checkpoint(
+        committee,
+        &checkpoint.checkpoint_summary,
+        Some(&checkpoint.checkpoint_contents),
+    )?;
+    let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+
+    // Transactions are causally ordered in the checkpoint, so the last one that changed the
+    // object determines its state.
+    for transaction in checkpoint.transactions.iter().rev() {
+        let effects = &transaction.effects;
+        if effects
+            .all_removed_objects()
+            .iter()
+            .any(|(object_ref, _)| object_ref.0 == id)
+        {
+            bail!("Object {id} was deleted or wrapped in checkpoint {sequence_number}");
+        }
+        let Some((object_ref, _, _)) = effects
+            .all_changed_objects()
+            .into_iter()
+            .find(|(object_ref, _, _)| object_ref.0 == id)
+        else {
+            continue;
+        };
+
+        verify_checkpoint_transaction(&checkpoint.checkpoint_contents, transaction)?;
+        let object = transaction
+            .output_objects
+            .iter()
+            .find(|object| object.id() == id)
+            .ok_or_else(|| {
+                anyhow!("Object {id} is missing from the outputs of checkpoint {sequence_number}")
+            })?;
+        ensure!(
+            object.compute_object_reference() == object_ref,
+            "Object {id} does not match the effects of transaction {}",
+            effects.transaction_digest()
+        );
+        return Ok(object.clone());
+    }
+
+    bail!("Object {id} was not written in checkpoint {sequence_number}")
+}
+
+/// Verifies that a transaction of a checkpoint is consistent with its effects, and that the
+/// effects are part of the checkpoint's contents.
+fn verify_checkpoint_transaction(
+    contents: &CheckpointContents,
+    transaction: &CheckpointTransaction,
+) -> Result<()> {
+    let digests = transaction.effects.execution_digests();
+    ensure!(
+        *transaction.transaction.digest() == digests.transaction,
+        "Transaction {} does not match its effects",
+        transaction.transaction.digest()
+    );
+    ensure!(
+        contents.iter().any(|d| *d == digests),
+        "Effects of transaction {} are not part of the checkpoint",
+        digests.transaction
+    );
+    match (transaction.effects.events_digest(), &transaction.events) {
+        (Some(digest), Some(events)) => ensure!(
+            events.digest() == *digest,
+            "Events of transaction {} do not match its effects",
+            digests.transaction
+        ),
+        (None, None) => (),
+        _ => bail!(
+            "Events of transaction {} do not match its effects",
+            digests.transaction
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::checkpoint_with_transaction;
+    use sui_types::effects::TransactionEffects;
+
+    #[test]
+    fn test_verify_transaction_and_object() {
+        let (committee, keys) = Committee::new_simple_test_committee();
+        let (checkpoint, object) = checkpoint_with_transaction(&committee, &keys, 1);
+        let digest = *checkpoint.transactions[0].transaction.digest();
+
+        let transaction = verify_transaction(&committee, &checkpoint, &digest).unwrap();
+        assert_eq!(transaction.effects.transaction_digest(), &digest);
+        assert_eq!(
+            verify_object(&committee, &checkpoint, object.id()).unwrap(),
+            object
+        );
+
+        assert!(verify_transaction(&committee, &checkpoint, &TransactionDigest::random()).is_err());
+        assert!(verify_object(&committee, &checkpoint, ObjectID::random()).is_err());
+    }
+
+    #[test]
+    fn test_reject_tampered_checkpoint() {
+        let (committee, keys) = Committee::new_simple_test_committee();
+        let (checkpoint, object) = checkpoint_with_transaction(&committee, &keys, 1);
+        let digest = *checkpoint.transactions[0].transaction.digest();
+
+        // Certified by the committee of another epoch.
+        let next_committee = Committee::new(1, committee.voting_rights.iter().cloned().collect());
+        assert!(verify_transaction(&next_committee, &checkpoint, &digest).is_err());
+
+        // Events missing.
+        let mut tampered = checkpoint.clone();
+        tampered.transactions[0].events = None;
+        assert!(verify_transaction(&committee, &tampered, &digest).is_err());
+
+        // Object state that does not match the effects.
+        let mut tampered = checkpoint.clone();
+        tampered.transactions[0].output_objects =
+            vec![Object::immutable_with_id_for_testing(object.id())];
+        assert!(verify_object(&committee, &tampered, object.id()).is_err());
+
+        // Effects that are not part of the checkpoint.
+        let mut tampered = checkpoint;
+        tampered.transactions[0].effects = TransactionEffects::default();
+        assert!(verify_transaction(&committee, &tampered, &digest).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use async_trait::async_trait;
+use sui_rest_api::CheckpointData;
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
+
+/// Where a light client gets checkpoints from. Nothing returned by a source is trusted: the light
+/// client verifies everything it uses against the committees it has verified.
+///
+/// Only the REST API of a full node is supported. Checkpoint archives are not: they hold the
+/// transactions and effects of checkpoints, but not the events and objects that
+/// `get_full_checkpoint` has to return.
+#[async_trait]
+pub trait CheckpointSource: Send + Sync {
+    async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary>;
+
+    async fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<CertifiedCheckpointSummary>;
+
+    async fn get_full_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<CheckpointData>;
+}
+
+#[async_trait]
+impl CheckpointSource for sui_rest_api::Client {
+    async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary> {
+        sui_rest_api::Client::get_latest_checkpoint(self).await
+    }
+
+    async fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<CertifiedCheckpointSummary> {
+        sui_rest_api::Client::get_checkpoint(self, sequence_number).await
+    }
+
+    async fn get_full_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<CheckpointData> {
+        sui_rest_api::Client::get_full_checkpoint(self, sequence_number).await
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_types::base_types::{random_object_ref, ObjectID};
+use sui_types::committee::Committee;
+use sui_types::crypto::{get_key_pair, AccountKeyPair, AuthorityKeyPair};
+use sui_types::effects::{TransactionEffects, TransactionEvents};
+use sui_types::execution_status::ExecutionStatus;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
+};
+use sui_types::object::{Object, Owner};
+use sui_types::transaction::TransactionData;
+use sui_types::utils::to_sender_signed_transaction;
+
+/// Creates checkpoint `sequence_number`, certified by `committee`, with a single transaction
+/// that creates an object.
+pub(crate) fn checkpoint_with_transaction(
+    committee: &Committee,
+    keys: &[AuthorityKeyPair],
+    sequence_number: CheckpointSequenceNumber,
+) -> (CheckpointData, Object) {
+    let (sender, key): (_, AccountKeyPair) = get_key_pair();
+    let object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    let transaction = to_sender_signed_transaction(
+        TransactionData::new_transfer_sui(
+            sender,
+            sender,
+            None,
+            random_object_ref(),
+            1_000_000,
+            1_000,
+        ),
+        &key,
+    );
+    let events = TransactionEvents::default();
+    let effects = TransactionEffects::new_from_execution_v1(
+        ExecutionStatus::Success,
+        committee.epoch,
+        GasCostSummary::default(),
+        vec![],
+        vec![],
+        *transaction.digest(),
+        vec![(object.compute_object_reference(), object.owner)],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        (random_object_ref(), Owner::AddressOwner(sender)),
+        Some(events.digest()),
+        vec![],
+    );
+
+    let contents =
+        CheckpointContents::new_with_digests_only_for_tests([effects.execution_digests()]);
+    let summary = CheckpointSummary::new(
+        committee.epoch,
+        sequence_number,
+        1,
+        &contents,
+        None,
+        GasCostSummary::default(),
+        None,
+        0,
+    );
+    let checkpoint = CheckpointData {
+        checkpoint_summary: CertifiedCheckpointSummary::new_from_keypairs_for_testing(
+            summary, keys, committee,
+        ),
+        checkpoint_contents: contents,
+        transactions: vec![CheckpointTransaction {
+            transaction,
+            effects,
+            events: Some(events),
+            input_objects: vec![],
+            output_objects: vec![object.clone()],
+        }],
+    };
+    (checkpoint, object)
+}
@@ -0,0 +1,227 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::query::Sample;
+use crate::QueryType;
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+// Recorded Prometheus HTTP API responses, keyed by the PromQL query they were returned for.
+// Allows running checks offline, e.g. to test the rules of a benchmark gate against a
+// known result. A fixture file is a JSON object of the form
+//
+// {
+//   "max(current_epoch{network=\"testnet\"})": {
+//     "status": "success",
+//     "data": {
+//       "resultType": "vector",
+//       "result": [{ "metric": {}, "value": [1628553600, "42"] }]
+//     }
+//   }
+// }
+//
+// i.e. responses can be copied verbatim from the `/api/v1/query` and `/api/v1/query_range`
+// endpoints.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Fixture {
+    responses: BTreeMap<String, Response>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Response {
+    status: String,
+    data: ResponseData,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "resultType", content = "result", rename_all = "lowercase")]
+enum ResponseData {
+    Vector(Vec<InstantSeries>),
+    Matrix(Vec<RangeSeries>),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct InstantSeries {
+    #[serde(default)]
+    metric: BTreeMap<String, String>,
+    value: (f64, String),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RangeSeries {
+    #[serde(default)]
+    metric: BTreeMap<String, String>,
+    values: Vec<(f64, String)>,
+}
+
+impl Fixture {
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), anyhow::Error> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+        Ok(())
+    }
+
+    // Returns the samples of the first series of the recorded response for `query`, just
+    // like queries against a live server do.
+    pub fn samples(
+        &self,
+        query: &str,
+        query_type: &QueryType,
+    ) -> Result<Vec<Sample>, anyhow::Error> {
+        let response = self
+            .responses
+            .get(query)
+            .ok_or_else(|| anyhow!("No recorded response for {query}"))?;
+        if response.status != "success" {
+            return Err(anyhow!(
+                "Recorded response for {query} has status {}",
+                response.status
+            ));
+        }
+
+        let values = match (&response.data, query_type) {
+            (ResponseData::Vector(result), QueryType::Instant) => {
+                result.first().map(|series| vec![series.value.clone()])
+            }
+            (ResponseData::Matrix(result), QueryType::Range { .. }) => {
+                result.first().map(|series| series.values.clone())
+            }
+            _ => {
+                return Err(anyhow!(
+                    "Recorded response for {query} does not match query type {query_type}"
+                ))
+            }
+        };
+        let values = values
+            .ok_or_else(|| anyhow!("Did not get expected response from server for {query}"))?;
+
+        values
+            .into_iter()
+            .map(|(timestamp, value)| {
+                Ok(Sample {
+                    timestamp,
+                    value: value
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid sample value {value} for {query}"))?,
+                })
+            })
+            .collect()
+    }
+
+    // Records `samples` as the response for `query`. Only a single series is kept, which is
+    // all that checks look at.
+    pub fn record(&mut self, query: &str, query_type: &QueryType, samples: &[Sample]) {
+        let values = samples
+            .iter()
+            .map(|sample| (sample.timestamp, sample.value.to_string()));
+        let data = match query_type {
+            QueryType::Instant => ResponseData::Vector(
+                values
+                    .take(1)
+                    .map(|value| InstantSeries {
+                        metric: BTreeMap::new(),
+                        value,
+                    })
+                    .collect(),
+            ),
+            QueryType::Range { .. } => ResponseData::Matrix(vec![RangeSeries {
+                metric: BTreeMap::new(),
+                values: values.collect(),
+            }]),
+        };
+        self.responses.insert(
+            query.to_string(),
+            Response {
+                status: "success".to_string(),
+                data,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_samples() {
+        let fixture: Fixture = serde_json::from_str(
+            r#"{
+                "up": {
+                    "status": "success",
+                    "data": {
+                        "resultType": "vector",
+                        "result": [{ "metric": { "job": "sui" }, "value": [1628553600, "1"] }]
+                    }
+                },
+                "rate(x[5m])": {
+                    "status": "success",
+                    "data": {
+                        "resultType": "matrix",
+                        "result": [{ "metric": {}, "values": [[1628553600, "1.5"], [1628553660, "NaN"]] }]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let range = QueryType::Range {
+            start: "now-1h".to_string(),
+            end: "now".to_string(),
+            step: 60.0,
+        };
+
+        assert_eq!(
+            fixture.samples("up", &QueryType::Instant).unwrap(),
+            vec![Sample {
+                timestamp: 1628553600.0,
+                value: 1.0
+            }]
+        );
+        let samples = fixture.samples("rate(x[5m])", &range).unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].value, 1.5);
+        assert!(samples[1].value.is_nan());
+
+        assert!(fixture.samples("up", &range).is_err());
+        assert!(fixture.samples("down", &QueryType::Instant).is_err());
+    }
+
+    #[test]
+    fn test_fixture_round_trip() {
+        let samples = vec![
+            Sample {
+                timestamp: 1628553600.0,
+                value: 1.0,
+            },
+            Sample {
+                timestamp: 1628553660.0,
+                value: 2.5,
+            },
+        ];
+        let range = QueryType::Range {
+            start: "now-1h".to_string(),
+            end: "now".to_string(),
+            step: 60.0,
+        };
+        let mut fixture = Fixture::default();
+        fixture.record("up", &QueryType::Instant, &samples);
+        fixture.record("rate(x[5m])", &range, &samples);
+
+        let fixture: Fixture =
+            serde_json::from_str(&serde_json::to_string(&fixture).unwrap()).unwrap();
+        assert_eq!(
+            fixture.samples("up", &QueryType::Instant).unwrap(),
+            samples[..1]
+        );
+        assert_eq!(fixture.samples("rate(x[5m])", &range).unwrap(), samples);
+    }
+}
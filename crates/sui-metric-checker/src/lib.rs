@@ -1,12 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use crate::slo::Slo;
 use anyhow::anyhow;
 use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 use humantime::parse_duration;
 use serde::Deserialize;
 use strum_macros::Display;
 
+pub mod fixture;
 pub mod query;
+pub mod report;
+pub mod slo;
 
 #[derive(Debug, Display, Deserialize, PartialEq)]
 pub enum QueryType {
@@ -63,7 +67,11 @@ pub struct Query {
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
+    #[serde(default)]
     pub queries: Vec<Query>,
+    // Named groups of checks with multiple conditions each, see `slo::Slo`.
+    #[serde(default)]
+    pub slos: Vec<Slo>,
 }
 
 // Used to  mock now() in tests and use consistent now() return values across
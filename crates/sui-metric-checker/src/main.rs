@@ -8,24 +8,40 @@ use once_cell::sync::Lazy;
 use prometheus_http_query::Client;
 use std::fs::File;
 use std::io::Read;
+use std::path::PathBuf;
 use std::time::Duration;
-use sui_metric_checker::query::{instant_query, range_query};
-use sui_metric_checker::{
-    fails_threshold_condition, timestamp_string_to_unix_seconds, Config, NowProvider, QueryType,
-};
+use sui_metric_checker::fixture::Fixture;
+use sui_metric_checker::query::{MetricSource, Sample};
+use sui_metric_checker::report::{CheckReport, Report, SloReport};
+use sui_metric_checker::slo::{Aggregation, Severity};
+use sui_metric_checker::{fails_threshold_condition, Config, NowProvider, QueryType};
+use tracing::{info, warn};
 
 #[derive(Parser)]
 pub struct Opts {
-    #[arg(long, required = true)]
-    api_user: String,
-    #[arg(long, required = true)]
-    api_key: String,
+    #[arg(long, required_unless_present = "fixture")]
+    api_user: Option<String>,
+    #[arg(long, required_unless_present = "fixture")]
+    api_key: Option<String>,
     // Path to the config file
     #[arg(long, required = true)]
     config: String,
     // URL of the Prometheus server
-    #[arg(long, required = true)]
-    url: String,
+    #[arg(long, required_unless_present = "fixture")]
+    url: Option<String>,
+    // Path to a file of recorded Prometheus responses to evaluate the config against
+    // instead of querying a server.
+    #[arg(long, conflicts_with_all = ["url", "record_fixture"])]
+    fixture: Option<PathBuf>,
+    // Path to write the responses of all queries to, for later use with `--fixture`.
+    #[arg(long)]
+    record_fixture: Option<PathBuf>,
+    // Path to write the SLO results to as JSON.
+    #[arg(long)]
+    report_json: Option<PathBuf>,
+    // Path to write the SLO results to as JUnit XML.
+    #[arg(long)]
+    report_junit: Option<PathBuf>,
 }
 
 // This allows us to use the same value for now() for all queries checked during
@@ -39,6 +55,25 @@ impl NowProvider for UtcNowOnceProvider {
     }
 }
 
+async fn fetch_samples(
+    source: &MetricSource,
+    backoff: &ExponentialBackoff,
+    query: &str,
+    query_type: &QueryType,
+) -> Result<Vec<Sample>, anyhow::Error> {
+    retry(backoff.clone(), || async {
+        source
+            .samples::<UtcNowOnceProvider>(query, query_type)
+            .await
+            .map_err(|error| match source {
+                // Recorded responses do not change between attempts.
+                MetricSource::Fixture(_) => backoff::Error::permanent(error),
+                MetricSource::Prometheus { .. } => backoff::Error::transient(error),
+            })
+    })
+    .await
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     let opts: Opts = Opts::parse();
@@ -46,54 +81,46 @@ async fn main() -> Result<(), anyhow::Error> {
         .with_env()
         .init();
 
-    let auth_header = format!("{}:{}", opts.api_user, opts.api_key);
-
     let mut file = File::open(opts.config)?;
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
     let config: Config = serde_yaml::from_str(&contents)?;
 
-    let client = {
-        let c = reqwest::Client::builder()
-            .no_proxy()
-            .timeout(Duration::from_secs(10))
-            .build()
-            .unwrap();
-        Client::from(c, &opts.url).unwrap()
+    let source = if let Some(fixture) = &opts.fixture {
+        MetricSource::Fixture(Fixture::load(fixture)?)
+    } else {
+        let auth_header = format!(
+            "{}:{}",
+            opts.api_user.unwrap_or_default(),
+            opts.api_key.unwrap_or_default()
+        );
+        let client = {
+            let c = reqwest::Client::builder()
+                .no_proxy()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap();
+            Client::from(c, &opts.url.unwrap_or_default()).unwrap()
+        };
+        MetricSource::Prometheus {
+            auth_header,
+            client,
+        }
     };
+    let mut recorded = opts.record_fixture.as_ref().map(|_| Fixture::default());
 
     let backoff = ExponentialBackoff {
         max_elapsed_time: Some(Duration::from_secs(5)),
         ..ExponentialBackoff::default()
     };
-
     let mut failed_queries = Vec::new();
     for query in config.queries {
-        let queried_result = match query.query_type {
-            QueryType::Instant => {
-                retry(backoff.clone(), || async {
-                    instant_query(&auth_header, client.clone(), &query.query)
-                        .await
-                        .map_err(backoff::Error::transient)
-                })
-                .await
-            }
-            QueryType::Range { start, end, step } => {
-                retry(backoff.clone(), || async {
-                    range_query(
-                        &auth_header,
-                        client.clone(),
-                        &query.query,
-                        timestamp_string_to_unix_seconds::<UtcNowOnceProvider>(&start)?,
-                        timestamp_string_to_unix_seconds::<UtcNowOnceProvider>(&end)?,
-                        step,
-                    )
-                    .await
-                    .map_err(backoff::Error::transient)
-                })
-                .await
-            }
-        };
+        let samples = fetch_samples(&source, &backoff, &query.query, &query.query_type).await;
+        if let (Some(recorded), Ok(samples)) = (&mut recorded, &samples) {
+            recorded.record(&query.query, &query.query_type, samples);
+        }
+        // Instant queries have a single sample, range queries are checked by their average.
+        let queried_result = samples.and_then(|samples| Aggregation::Avg.apply(&samples));
 
         if let Some(validate_result) = query.validate_result {
             match queried_result {
@@ -119,6 +146,37 @@ async fn main() -> Result<(), anyhow::Error> {
         }
     }
 
+    let mut report = Report::default();
+    for slo in &config.slos {
+        let mut checks = Vec::new();
+        for check in &slo.checks {
+            let samples = fetch_samples(&source, &backoff, &check.query, &check.query_type).await;
+            if let (Some(recorded), Ok(samples)) = (&mut recorded, &samples) {
+                recorded.record(&check.query, &check.query_type, samples);
+            }
+            checks.push(CheckReport::evaluate(check, samples));
+        }
+        report.slos.push(SloReport::new(slo, checks));
+    }
+
+    if let (Some(path), Some(recorded)) = (&opts.record_fixture, &recorded) {
+        recorded.save(path)?;
+    }
+    if let Some(path) = &opts.report_json {
+        std::fs::write(path, report.to_json()?)?;
+    }
+    if let Some(path) = &opts.report_junit {
+        std::fs::write(path, report.to_junit())?;
+    }
+
+    for failure in report.failures(Severity::Info) {
+        info!("{failure}");
+    }
+    for failure in report.failures(Severity::Warning) {
+        warn!("{failure}");
+    }
+    failed_queries.extend(report.failures(Severity::Critical));
+
     if !failed_queries.is_empty() {
         return Err(anyhow!(
             "Following queries failed to meet threshold conditions: {failed_queries:#?}"
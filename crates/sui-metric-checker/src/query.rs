@@ -1,17 +1,75 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::unix_seconds_to_timestamp_string;
+use crate::fixture::Fixture;
+use crate::{timestamp_string_to_unix_seconds, unix_seconds_to_timestamp_string};
+use crate::{NowProvider, QueryType};
 use anyhow::anyhow;
 use base64::{engine::general_purpose, Engine};
 use prometheus_http_query::Client;
 use reqwest::header::{HeaderValue, AUTHORIZATION};
 use tracing::debug;
 
+// A single data point of a query result, with the timestamp in unix seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sample {
+    pub timestamp: f64,
+    pub value: f64,
+}
+
+// Where query results are taken from.
+pub enum MetricSource {
+    // A live Prometheus server, authenticated with basic auth.
+    Prometheus { auth_header: String, client: Client },
+    // Responses recorded earlier, for running checks offline.
+    Fixture(Fixture),
+}
+
+impl MetricSource {
+    // Returns the samples of the first series of the query result.
+    pub async fn samples<N: NowProvider>(
+        &self,
+        query: &str,
+        query_type: &QueryType,
+    ) -> Result<Vec<Sample>, anyhow::Error> {
+        match self {
+            MetricSource::Prometheus {
+                auth_header,
+                client,
+            } => match query_type {
+                QueryType::Instant => {
+                    instant_query_samples(auth_header, client.clone(), query).await
+                }
+                QueryType::Range { start, end, step } => {
+                    range_query_samples(
+                        auth_header,
+                        client.clone(),
+                        query,
+                        timestamp_string_to_unix_seconds::<N>(start)?,
+                        timestamp_string_to_unix_seconds::<N>(end)?,
+                        *step,
+                    )
+                    .await
+                }
+            },
+            MetricSource::Fixture(fixture) => fixture.samples(query, query_type),
+        }
+    }
+}
+
 pub async fn instant_query(
     auth_header: &str,
     client: Client,
     query: &str,
 ) -> Result<f64, anyhow::Error> {
+    let samples = instant_query_samples(auth_header, client, query).await?;
+    Ok(samples[0].value)
+}
+
+pub async fn instant_query_samples(
+    auth_header: &str,
+    client: Client,
+    query: &str,
+) -> Result<Vec<Sample>, anyhow::Error> {
     debug!("Executing {query}");
     let response = client
         .query(query)
@@ -33,7 +91,10 @@ pub async fn instant_query(
     if !result.is_empty() {
         let first = result.first().unwrap();
         debug!("Got value {}", first.sample().value());
-        Ok(first.sample().value())
+        Ok(vec![Sample {
+            timestamp: first.sample().timestamp(),
+            value: first.sample().value(),
+        }])
     } else {
         Err(anyhow!(
             "Did not get expected response from server for {query}"
@@ -50,6 +111,27 @@ pub async fn range_query(
     end: i64,
     step: f64,
 ) -> Result<f64, anyhow::Error> {
+    let samples = range_query_samples(auth_header, client, query, start, end, step).await?;
+    let sum: f64 = samples.iter().map(|sample| sample.value).sum();
+    let count = samples.len();
+
+    let avg = if count > 0 { sum / count as f64 } else { 0.0 };
+    debug!(
+        "Got average value {avg} over time range {} - {}",
+        unix_seconds_to_timestamp_string(start),
+        unix_seconds_to_timestamp_string(end)
+    );
+    Ok(avg)
+}
+
+pub async fn range_query_samples(
+    auth_header: &str,
+    client: Client,
+    query: &str,
+    start: i64,
+    end: i64,
+    step: f64,
+) -> Result<Vec<Sample>, anyhow::Error> {
     debug!("Executing {query}");
     let response = client
         .query_range(query, start, end, step)
@@ -70,16 +152,19 @@ pub async fn range_query(
 
     if !result.is_empty() {
         let samples = result.first().unwrap().samples();
-        let sum: f64 = samples.iter().map(|sample| sample.value()).sum();
-        let count = samples.len();
-
-        let avg = if count > 0 { sum / count as f64 } else { 0.0 };
         debug!(
-            "Got average value {avg} over time range {} - {}",
+            "Got {} samples over time range {} - {}",
+            samples.len(),
             unix_seconds_to_timestamp_string(start),
             unix_seconds_to_timestamp_string(end)
         );
-        Ok(avg)
+        Ok(samples
+            .iter()
+            .map(|sample| Sample {
+                timestamp: sample.timestamp(),
+                value: sample.value(),
+            })
+            .collect())
     } else {
         Err(anyhow!(
             "Did not get expected response from server for {query}"
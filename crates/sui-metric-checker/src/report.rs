@@ -0,0 +1,319 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::query::Sample;
+use crate::slo::{Severity, Slo, SloCheck};
+use serde::Serialize;
+use std::fmt::Write;
+
+// Outcome of evaluating all SLOs of a config, which can be rendered as JSON or as JUnit XML
+// for consumption by CI systems.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub slos: Vec<SloReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SloReport {
+    pub name: String,
+    pub severity: Severity,
+    pub passed: bool,
+    pub checks: Vec<CheckReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckReport {
+    pub query: String,
+    // Set if the query itself failed, in which case no conditions were evaluated.
+    pub error: Option<String>,
+    pub conditions: Vec<ConditionReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConditionReport {
+    pub condition: String,
+    // Value the condition was checked against, unless it could not be computed.
+    pub observed: Option<f64>,
+    pub passed: bool,
+    pub error: Option<String>,
+}
+
+impl CheckReport {
+    pub fn evaluate(check: &SloCheck, samples: Result<Vec<Sample>, anyhow::Error>) -> Self {
+        let samples = match samples {
+            Ok(samples) => samples,
+            Err(error) => {
+                return Self {
+                    query: check.query.clone(),
+                    error: Some(error.to_string()),
+                    conditions: vec![],
+                }
+            }
+        };
+        let conditions = check
+            .conditions
+            .iter()
+            .map(|condition| match condition.observe(&samples) {
+                Ok(observed) => ConditionReport {
+                    condition: condition.to_string(),
+                    observed: Some(observed),
+                    passed: condition.is_met(observed),
+                    error: None,
+                },
+                Err(error) => ConditionReport {
+                    condition: condition.to_string(),
+                    observed: None,
+                    passed: false,
+                    error: Some(error.to_string()),
+                },
+            })
+            .collect();
+        Self {
+            query: check.query.clone(),
+            error: None,
+            conditions,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.conditions.iter().all(|condition| condition.passed)
+    }
+}
+
+impl SloReport {
+    pub fn new(slo: &Slo, checks: Vec<CheckReport>) -> Self {
+        Self {
+            name: slo.name.clone(),
+            severity: slo.severity,
+            passed: checks.iter().all(CheckReport::passed),
+            checks,
+        }
+    }
+
+    // Human readable descriptions of everything that made this SLO fail.
+    pub fn failures(&self) -> Vec<String> {
+        let mut failures = vec![];
+        for check in &self.checks {
+            if let Some(error) = &check.error {
+                failures.push(format!("SLO {}: {error}", self.name));
+            }
+            for condition in check.conditions.iter().filter(|c| !c.passed) {
+                failures.push(format!(
+                    "SLO {}: Query \"{}\" {}",
+                    self.name,
+                    check.query,
+                    condition.failure_message()
+                ));
+            }
+        }
+        failures
+    }
+}
+
+impl ConditionReport {
+    fn failure_message(&self) -> String {
+        match (&self.error, self.observed) {
+            (Some(error), _) => format!("could not be checked for {}: {error}", self.condition),
+            (None, Some(observed)) => format!(
+                "returned value of {observed} which violates {}",
+                self.condition
+            ),
+            (None, None) => format!("violates {}", self.condition),
+        }
+    }
+}
+
+impl Report {
+    pub fn failures(&self, severity: Severity) -> Vec<String> {
+        self.slos
+            .iter()
+            .filter(|slo| slo.severity == severity)
+            .flat_map(SloReport::failures)
+            .collect()
+    }
+
+    pub fn to_json(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    // One test suite per SLO and one test case per condition. Failures of non-critical SLOs
+    // are reported as skipped test cases, so that they show up without failing the build.
+    pub fn to_junit(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let tests: usize = self.slos.iter().map(test_cases).sum();
+        let failures: usize = self
+            .slos
+            .iter()
+            .filter(|slo| slo.severity == Severity::Critical)
+            .map(failed_test_cases)
+            .sum();
+        writeln!(
+            xml,
+            "<testsuites name=\"sui-metric-checker\" tests=\"{tests}\" failures=\"{failures}\">"
+        )
+        .unwrap();
+
+        for slo in &self.slos {
+            let critical = slo.severity == Severity::Critical;
+            let failures = if critical { failed_test_cases(slo) } else { 0 };
+            let skipped = if critical { 0 } else { failed_test_cases(slo) };
+            writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\">",
+                escape(&slo.name),
+                test_cases(slo)
+            )
+            .unwrap();
+            for check in &slo.checks {
+                if let Some(error) = &check.error {
+                    write_test_case(&mut xml, slo, &check.query, Some(error));
+                }
+                for condition in &check.conditions {
+                    let name = format!("{}: {}", check.query, condition.condition);
+                    let failure = (!condition.passed).then(|| condition.failure_message());
+                    write_test_case(&mut xml, slo, &name, failure.as_ref());
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+fn test_cases(slo: &SloReport) -> usize {
+    slo.checks
+        .iter()
+        .map(|check| check.conditions.len() + usize::from(check.error.is_some()))
+        .sum()
+}
+
+fn failed_test_cases(slo: &SloReport) -> usize {
+    slo.checks
+        .iter()
+        .map(|check| {
+            check.conditions.iter().filter(|c| !c.passed).count()
+                + usize::from(check.error.is_some())
+        })
+        .sum()
+}
+
+fn write_test_case(xml: &mut String, slo: &SloReport, name: &str, failure: Option<&String>) {
+    let attributes = format!(
+        "classname=\"{}\" name=\"{}\"",
+        escape(&slo.name),
+        escape(name)
+    );
+    let Some(failure) = failure else {
+        writeln!(xml, "    <testcase {attributes}/>").unwrap();
+        return;
+    };
+    let element = if slo.severity == Severity::Critical {
+        "failure"
+    } else {
+        "skipped"
+    };
+    writeln!(
+        xml,
+        "    <testcase {attributes}><{element} message=\"{}\"/></testcase>",
+        escape(&format!("[{}] {failure}", slo.severity))
+    )
+    .unwrap();
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slo::{Aggregation, SloCondition};
+    use crate::QueryType;
+    use anyhow::anyhow;
+
+    fn slo(name: &str, severity: Severity) -> Slo {
+        Slo {
+            name: name.to_string(),
+            severity,
+            checks: vec![SloCheck {
+                query: "up{job=\"sui\"}".to_string(),
+                query_type: QueryType::Instant,
+                conditions: vec![SloCondition::Between {
+                    aggregation: Aggregation::Last,
+                    min: Some(1.0),
+                    max: None,
+                }],
+            }],
+        }
+    }
+
+    fn evaluate(slo: &Slo, value: f64) -> SloReport {
+        let samples = vec![Sample {
+            timestamp: 0.0,
+            value,
+        }];
+        let checks = slo
+            .checks
+            .iter()
+            .map(|check| CheckReport::evaluate(check, Ok(samples.clone())))
+            .collect();
+        SloReport::new(slo, checks)
+    }
+
+    #[test]
+    fn test_report() {
+        let critical = slo("availability", Severity::Critical);
+        let warning = slo("availability-warning", Severity::Warning);
+        let unreachable = slo("unreachable", Severity::Critical);
+        let report = Report {
+            slos: vec![
+                evaluate(&critical, 1.0),
+                evaluate(&warning, 0.0),
+                SloReport::new(
+                    &unreachable,
+                    vec![CheckReport::evaluate(
+                        &unreachable.checks[0],
+                        Err(anyhow!("connection refused")),
+                    )],
+                ),
+            ],
+        };
+
+        assert!(report.slos[0].passed);
+        assert!(!report.slos[1].passed);
+        assert_eq!(
+            report.failures(Severity::Warning),
+            vec!["SLO availability-warning: Query \"up{job=\"sui\"}\" returned value of 0 which violates last >= 1"]
+        );
+        assert_eq!(
+            report.failures(Severity::Critical),
+            vec!["SLO unreachable: connection refused"]
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["slos"][1]["severity"], "Warning");
+        assert_eq!(
+            json["slos"][1]["checks"][0]["conditions"][0]["observed"],
+            0.0
+        );
+
+        let junit = report.to_junit();
+        assert!(
+            junit.contains("<testsuites name=\"sui-metric-checker\" tests=\"3\" failures=\"1\">")
+        );
+        assert!(junit.contains("name=\"up{job=&quot;sui&quot;}: last &gt;= 1\"/>"));
+        assert!(junit.contains("<skipped message=\"[Warning] returned value of 0"));
+        assert!(junit.contains("<failure message=\"[Critical] connection refused\"/>"));
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::query::Sample;
+use crate::{fails_threshold_condition, Condition, QueryType};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum_macros::Display;
+
+// A named group of checks, e.g.
+//
+// slos:
+//   - name: narwhal-latency
+//     severity: Critical
+//     checks:
+//       - query: 'histogram_quantile(0.50, ...)'
+//         type: !Range
+//           start: "now-8h"
+//           end: "now"
+//           step: 60.0
+//         conditions:
+//           - !Between
+//             aggregation: !Percentile 99.0
+//             max: 5.0
+//           - !RateOfChange
+//             max: 0.001
+//
+// An SLO is met if every condition of every check is met.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Slo {
+    pub name: String,
+    #[serde(default)]
+    pub severity: Severity,
+    pub checks: Vec<SloCheck>,
+}
+
+// Determines how a violated SLO is reported. Only violations of critical SLOs make the
+// program exit with an error.
+#[derive(Clone, Copy, Debug, Default, Display, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Severity {
+    #[default]
+    Critical,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct SloCheck {
+    // PromQL query to execute
+    pub query: String,
+    // Type of query to execute - Instant or Range.
+    #[serde(rename = "type")]
+    pub query_type: QueryType,
+    // All conditions have to be met by the query result for the check to pass.
+    pub conditions: Vec<SloCondition>,
+}
+
+// Reduces the samples of a query result to a single value. Instant queries return a single
+// sample, so all aggregations yield the same value for them.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+pub enum Aggregation {
+    #[default]
+    Avg,
+    Min,
+    Max,
+    // Value of the most recent sample.
+    Last,
+    // Nearest-rank percentile, within (0, 100].
+    Percentile(f64),
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub enum SloCondition {
+    // Fails if the aggregated value violates the condition, with the same semantics as
+    // `validate_result` of plain queries.
+    Threshold {
+        #[serde(default)]
+        aggregation: Aggregation,
+        threshold: f64,
+        failure_condition: Condition,
+    },
+    // Fails if the aggregated value is outside of [min, max]. Either bound may be omitted.
+    Between {
+        #[serde(default)]
+        aggregation: Aggregation,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    // Fails if the change per second between the first and the last sample of a range
+    // query is outside of [min, max]. Either bound may be omitted.
+    RateOfChange {
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+impl Aggregation {
+    pub fn apply(&self, samples: &[Sample]) -> Result<f64, anyhow::Error> {
+        if samples.is_empty() {
+            return Err(anyhow!("Cannot aggregate empty query result"));
+        }
+        let mut values: Vec<f64> = samples.iter().map(|sample| sample.value).collect();
+        let value = match self {
+            Aggregation::Avg => values.iter().sum::<f64>() / values.len() as f64,
+            Aggregation::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregation::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Aggregation::Last => *values.last().unwrap(),
+            Aggregation::Percentile(percentile) => {
+                if !(*percentile > 0.0 && *percentile <= 100.0) {
+                    return Err(anyhow!("Percentile {percentile} is not within (0, 100]"));
+                }
+                values.sort_by(f64::total_cmp);
+                let rank = (percentile / 100.0 * values.len() as f64).ceil() as usize;
+                values[rank.clamp(1, values.len()) - 1]
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Aggregation::Avg => write!(f, "avg"),
+            Aggregation::Min => write!(f, "min"),
+            Aggregation::Max => write!(f, "max"),
+            Aggregation::Last => write!(f, "last"),
+            Aggregation::Percentile(percentile) => write!(f, "p{percentile}"),
+        }
+    }
+}
+
+impl SloCondition {
+    // Computes the value of the query result this condition is checked against.
+    pub fn observe(&self, samples: &[Sample]) -> Result<f64, anyhow::Error> {
+        match self {
+            SloCondition::Threshold { aggregation, .. }
+            | SloCondition::Between { aggregation, .. } => aggregation.apply(samples),
+            SloCondition::RateOfChange { .. } => {
+                let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+                    return Err(anyhow!(
+                        "Cannot compute rate of change of empty query result"
+                    ));
+                };
+                let elapsed = last.timestamp - first.timestamp;
+                if elapsed <= 0.0 {
+                    return Err(anyhow!(
+                        "Rate of change requires samples spanning a time range"
+                    ));
+                }
+                Ok((last.value - first.value) / elapsed)
+            }
+        }
+    }
+
+    pub fn is_met(&self, observed: f64) -> bool {
+        match self {
+            SloCondition::Threshold {
+                threshold,
+                failure_condition,
+                ..
+            } => !fails_threshold_condition(observed, *threshold, failure_condition),
+            SloCondition::Between { min, max, .. } | SloCondition::RateOfChange { min, max } => {
+                min.map_or(true, |min| observed >= min) && max.map_or(true, |max| observed <= max)
+            }
+        }
+    }
+}
+
+impl fmt::Display for SloCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bounds = |min: &Option<f64>, max: &Option<f64>| match (min, max) {
+            (Some(min), Some(max)) => format!("within [{min}, {max}]"),
+            (Some(min), None) => format!(">= {min}"),
+            (None, Some(max)) => format!("<= {max}"),
+            (None, None) => "unbounded".to_string(),
+        };
+        match self {
+            SloCondition::Threshold {
+                aggregation,
+                threshold,
+                failure_condition,
+            } => write!(f, "{aggregation} not {failure_condition} {threshold}"),
+            SloCondition::Between {
+                aggregation,
+                min,
+                max,
+            } => write!(f, "{aggregation} {}", bounds(min, max)),
+            SloCondition::RateOfChange { min, max } => {
+                write!(f, "rate of change per second {}", bounds(min, max))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(values: &[f64]) -> Vec<Sample> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Sample {
+                timestamp: 1000.0 + 60.0 * i as f64,
+                value: *value,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aggregations() {
+        let samples = samples(&[4.0, 1.0, 3.0, 2.0, 10.0]);
+        assert_eq!(Aggregation::Avg.apply(&samples).unwrap(), 4.0);
+        assert_eq!(Aggregation::Min.apply(&samples).unwrap(), 1.0);
+        assert_eq!(Aggregation::Max.apply(&samples).unwrap(), 10.0);
+        assert_eq!(Aggregation::Last.apply(&samples).unwrap(), 10.0);
+        assert_eq!(Aggregation::Percentile(50.0).apply(&samples).unwrap(), 3.0);
+        assert_eq!(Aggregation::Percentile(80.0).apply(&samples).unwrap(), 4.0);
+        assert_eq!(Aggregation::Percentile(99.0).apply(&samples).unwrap(), 10.0);
+        assert!(Aggregation::Percentile(0.0).apply(&samples).is_err());
+        assert!(Aggregation::Avg.apply(&[]).is_err());
+    }
+
+    #[test]
+    fn test_conditions() {
+        let samples = samples(&[1.0, 2.0, 7.0]);
+
+        let threshold = SloCondition::Threshold {
+            aggregation: Aggregation::Max,
+            threshold: 5.0,
+            failure_condition: Condition::Greater,
+        };
+        assert_eq!(threshold.observe(&samples).unwrap(), 7.0);
+        assert!(!threshold.is_met(7.0));
+
+        let between = SloCondition::Between {
+            aggregation: Aggregation::Avg,
+            min: Some(1.0),
+            max: None,
+        };
+        assert!(between.is_met(between.observe(&samples).unwrap()));
+
+        let rate = SloCondition::RateOfChange {
+            min: None,
+            max: Some(0.01),
+        };
+        assert_eq!(rate.observe(&samples).unwrap(), 0.05);
+        assert!(!rate.is_met(0.05));
+        assert!(rate.observe(&samples[..1]).is_err());
+    }
+
+    #[test]
+    fn test_parse_slos() {
+        let config = r#"
+            slos:
+              - name: latency
+                severity: Warning
+                checks:
+                  - query: 'histogram_quantile(0.50, sum by(le) (rate(round_latency{network="testnet"}[15m])))'
+                    type: !Range
+                      start: "now-1h"
+                      end: "now"
+                      step: 60.0
+                    conditions:
+                      - !Between
+                        aggregation: !Percentile 99.0
+                        max: 5.0
+                      - !RateOfChange
+                        max: 0.001
+                      - !Threshold
+                        threshold: 3.0
+                        failure_condition: Greater
+        "#;
+
+        let config: crate::Config = serde_yaml::from_str(config).unwrap();
+        assert!(config.queries.is_empty());
+
+        let expected_slo = Slo {
+            name: "latency".to_string(),
+            severity: Severity::Warning,
+            checks: vec![SloCheck {
+                query: "histogram_quantile(0.50, sum by(le) (rate(round_latency{network=\"testnet\"}[15m])))".to_string(),
+                query_type: QueryType::Range {
+                    start: "now-1h".to_string(),
+                    end: "now".to_string(),
+                    step: 60.0,
+                },
+                conditions: vec![
+                    SloCondition::Between {
+                        aggregation: Aggregation::Percentile(99.0),
+                        min: None,
+                        max: Some(5.0),
+                    },
+                    SloCondition::RateOfChange {
+                        min: None,
+                        max: Some(0.001),
+                    },
+                    SloCondition::Threshold {
+                        aggregation: Aggregation::Avg,
+                        threshold: 3.0,
+                        failure_condition: Condition::Greater,
+                    },
+                ],
+            }],
+        };
+
+        assert_eq!(config.slos, vec![expected_slo]);
+    }
+}
@@ -19,14 +19,12 @@ use move_binary_format::{
 };
 use move_bytecode_utils::{layout::SerdeLayoutBuilder, module_cache::GetModule};
 use move_compiler::{
-    cfgir::visitor::AbstractInterpreterVisitor,
     compiled_unit::{
         AnnotatedCompiledModule, AnnotatedCompiledScript, CompiledUnitEnum, NamedCompiledModule,
     },
     diagnostics::{report_diagnostics_to_color_buffer, report_warnings},
     expansion::ast::{AttributeName_, Attributes},
     shared::known_attributes::KnownAttribute,
-    typing::visitor::TypingVisitor,
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -55,10 +53,7 @@ use sui_types::{
 use sui_verifier::verifier as sui_bytecode_verifier;
 
 use crate::linters::{
-    coin_field::CoinFieldVisitor, collection_equality::CollectionEqualityVisitor,
-    custom_state_change::CustomStateChangeVerifier, freeze_wrapped::FreezeWrappedVisitor,
-    known_filters, self_transfer::SelfTransferVerifier, share_owned::ShareOwnedVerifier,
-    LINT_WARNING_PREFIX,
+    check_lint_levels, denied_lints, known_filters, linter_visitors, LINT_WARNING_PREFIX,
 };
 
 #[cfg(test)]
@@ -134,18 +129,19 @@ impl BuildConfig {
         lint: bool,
         writer: &mut W,
     ) -> anyhow::Result<(MoveCompiledPackage, FnInfoMap)> {
+        // lint levels are configured in the manifest of the root package
+        let lint_levels = resolution_graph.package_table[&resolution_graph.graph.root_package]
+            .source_package
+            .lints
+            .clone();
+        if lint {
+            check_lint_levels(&lint_levels)?;
+        }
         let build_plan = BuildPlan::create(resolution_graph)?;
         let mut fn_info = None;
         let compiled_pkg = build_plan.compile_with_driver(writer, |compiler| {
             let (files, units_res) = if lint {
-                let lint_visitors = vec![
-                    ShareOwnedVerifier.visitor(),
-                    SelfTransferVerifier.visitor(),
-                    CustomStateChangeVerifier.visitor(),
-                    CoinFieldVisitor.visitor(),
-                    FreezeWrappedVisitor.visitor(),
-                    CollectionEqualityVisitor.visitor(),
-                ];
+                let lint_visitors = linter_visitors(&lint_levels);
                 let (filter_attr_name, filters) = known_filters();
                 compiler
                     .add_visitors(lint_visitors)
@@ -159,6 +155,15 @@ impl BuildConfig {
                     let any_linter_warnings = warning_diags.any_with_prefix(LINT_WARNING_PREFIX);
                     let (filtered_diags_num, filtered_categories) =
                         warning_diags.filtered_source_diags_with_prefix(LINT_WARNING_PREFIX);
+                    let denied = denied_lints(
+                        &lint_levels,
+                        warning_diags
+                            .clone()
+                            .into_vec()
+                            .iter()
+                            .filter(|d| d.info().external_prefix() == Some(LINT_WARNING_PREFIX))
+                            .map(|d| d.info().category()),
+                    );
                     report_warnings(&files, warning_diags);
                     if any_linter_warnings {
                         eprintln!("Please report feedback on the linter warnings at https://forums.sui.io\n");
@@ -166,6 +171,12 @@ impl BuildConfig {
                     if filtered_diags_num > 0 {
                         eprintln!("Total number of linter warnings suppressed: {filtered_diags_num} (filtered categories: {filtered_categories})");
                     }
+                    if !denied.is_empty() {
+                        anyhow::bail!(
+                            "Compilation error: lints denied in the manifest reported warnings: {}",
+                            denied.join(", ")
+                        );
+                    }
                     fn_info = Some(Self::fn_info(&units));
                     Ok((files, units))
                 }
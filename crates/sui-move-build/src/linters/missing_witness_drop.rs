@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags witness-like structs (structs without fields whose name ends with
+//! "witness") that do not have the drop ability. Without drop, a witness cannot be discarded after
+//! it has been used to authorize an operation and has to be unpacked explicitly instead.
+
+use move_compiler::{
+    diag,
+    diagnostics::codes::{custom, DiagnosticInfo, Severity},
+    naming::ast as N,
+    parser::ast::Ability_,
+    shared::{program_info::TypingProgramInfo, CompilationEnv},
+    typing::{ast as T, visitor::TypingVisitor},
+};
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+
+use super::{LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX};
+
+const MISSING_WITNESS_DROP_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::MissingWitnessDrop as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "witness without 'drop'",
+);
+
+const WITNESS_SUFFIX: &str = "witness";
+
+pub struct MissingWitnessDropVisitor;
+
+impl TypingVisitor for MissingWitnessDropVisitor {
+    fn visit(
+        &mut self,
+        env: &mut CompilationEnv,
+        _program_info: &TypingProgramInfo,
+        program: &mut T::Program_,
+    ) {
+        for (_, _, mdef) in program.modules.iter() {
+            env.add_warning_filter_scope(mdef.warning_filter.clone());
+            mdef.structs
+                .iter()
+                .for_each(|(sloc, sname, sdef)| struct_def(env, *sname, sdef, sloc));
+            env.pop_warning_filter_scope();
+        }
+    }
+}
+
+fn struct_def(env: &mut CompilationEnv, sname: Symbol, sdef: &N::StructDefinition, sloc: Loc) {
+    env.add_warning_filter_scope(sdef.warning_filter.clone());
+
+    let N::StructFields::Defined(sfields) = &sdef.fields else {
+        env.pop_warning_filter_scope();
+        return;
+    };
+    let is_witness_like = sfields.is_empty()
+        && sname.as_str().to_lowercase().ends_with(WITNESS_SUFFIX)
+        && !sdef.abilities.has_ability_(Ability_::Key);
+    if is_witness_like && !sdef.abilities.has_ability_(Ability_::Drop) {
+        let msg = format!("Witness '{sname}' does not have the 'drop' ability");
+        let mut d = diag!(MISSING_WITNESS_DROP_DIAG, (sloc, msg));
+        d.add_note(
+            "Values of a struct without 'drop' cannot be discarded and have to be unpacked \
+             explicitly after they have been used. Consider adding 'has drop' to the struct",
+        );
+        env.add_diag(d);
+    }
+
+    env.pop_warning_filter_scope();
+}
@@ -1,17 +1,29 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use move_compiler::{diagnostics::codes::WarningFilter, expansion::ast as E, naming::ast as N};
+use move_compiler::{
+    cfgir::visitor::AbstractInterpreterVisitor, command_line::compiler::Visitor,
+    diagnostics::codes::WarningFilter, expansion::ast as E, naming::ast as N,
+    typing::visitor::TypingVisitor,
+};
 use move_ir_types::location::Loc;
+use move_package::source_package::parsed_manifest::{LintLevel, LintLevels};
+use move_symbol_pool::Symbol;
+use std::collections::BTreeSet;
 
 pub mod coin_field;
 pub mod collection_equality;
 pub mod custom_state_change;
 pub mod freeze_wrapped;
+pub mod missing_witness_drop;
+pub mod public_entry_return;
 pub mod self_transfer;
 pub mod share_owned;
+pub mod unbounded_vector_growth;
+pub mod unused_tx_context;
 
 pub const SUI_PKG_NAME: &str = "sui";
+pub const STD_PKG_NAME: &str = "std";
 
 pub const TRANSFER_MOD_NAME: &str = "transfer";
 pub const TRANSFER_FUN: &str = "transfer";
@@ -21,6 +33,12 @@ pub const PUBLIC_SHARE_FUN: &str = "public_share_object";
 pub const FREEZE_FUN: &str = "freeze_object";
 pub const PUBLIC_FREEZE_FUN: &str = "public_freeze_object";
 
+pub const TX_CONTEXT_MOD_NAME: &str = "tx_context";
+pub const TX_CONTEXT_STRUCT_NAME: &str = "TxContext";
+
+pub const VECTOR_MOD_NAME: &str = "vector";
+pub const PUSH_BACK_FUN: &str = "push_back";
+
 pub const COIN_MOD_NAME: &str = "coin";
 pub const COIN_STRUCT_NAME: &str = "Coin";
 
@@ -57,9 +75,14 @@ pub const CUSTOM_STATE_CHANGE_FILTER_NAME: &str = "custom_state_change";
 pub const COIN_FIELD_FILTER_NAME: &str = "coin_field";
 pub const FREEZE_WRAPPED_FILTER_NAME: &str = "freeze_wrapped";
 pub const COLLECTION_EQUALITY_FILTER_NAME: &str = "collection_equality";
+pub const UNUSED_TX_CONTEXT_FILTER_NAME: &str = "unused_tx_context";
+pub const PUBLIC_ENTRY_RETURN_FILTER_NAME: &str = "public_entry_return";
+pub const MISSING_WITNESS_DROP_FILTER_NAME: &str = "missing_witness_drop";
+pub const UNBOUNDED_VECTOR_GROWTH_FILTER_NAME: &str = "unbounded_vector_growth";
 
 pub const INVALID_LOC: Loc = Loc::invalid();
 
+#[derive(Clone, Copy)]
 pub enum LinterDiagCategory {
     ShareOwned,
     SelfTransfer,
@@ -67,57 +90,159 @@ pub enum LinterDiagCategory {
     CoinField,
     FreezeWrapped,
     CollectionEquality,
+    UnusedTxContext,
+    PublicEntryReturn,
+    MissingWitnessDrop,
+    UnboundedVectorGrowth,
 }
 
+/// The category of each lint, together with the name it is configured by in the manifest and
+/// suppressed by with `#[lint_allow]`.
+pub const LINTS: &[(LinterDiagCategory, &str)] = &[
+    (LinterDiagCategory::ShareOwned, SHARE_OWNED_FILTER_NAME),
+    (LinterDiagCategory::SelfTransfer, SELF_TRANSFER_FILTER_NAME),
+    (
+        LinterDiagCategory::CustomStateChange,
+        CUSTOM_STATE_CHANGE_FILTER_NAME,
+    ),
+    (LinterDiagCategory::CoinField, COIN_FIELD_FILTER_NAME),
+    (
+        LinterDiagCategory::FreezeWrapped,
+        FREEZE_WRAPPED_FILTER_NAME,
+    ),
+    (
+        LinterDiagCategory::CollectionEquality,
+        COLLECTION_EQUALITY_FILTER_NAME,
+    ),
+    (
+        LinterDiagCategory::UnusedTxContext,
+        UNUSED_TX_CONTEXT_FILTER_NAME,
+    ),
+    (
+        LinterDiagCategory::PublicEntryReturn,
+        PUBLIC_ENTRY_RETURN_FILTER_NAME,
+    ),
+    (
+        LinterDiagCategory::MissingWitnessDrop,
+        MISSING_WITNESS_DROP_FILTER_NAME,
+    ),
+    (
+        LinterDiagCategory::UnboundedVectorGrowth,
+        UNBOUNDED_VECTOR_GROWTH_FILTER_NAME,
+    ),
+];
+
 /// A default code for each linter category (as long as only one code per category is used, no other
 /// codes are needed, otherwise they should be defined to be unique per-category).
 pub const LINTER_DEFAULT_DIAG_CODE: u8 = 1;
 
 pub fn known_filters() -> (E::AttributeName_, Vec<WarningFilter>) {
+    let lint_filters = LINTS.iter().map(|(category, name)| {
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            *category as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(name),
+        )
+    });
     (
         E::AttributeName_::Unknown(ALLOW_ATTR_NAME.into()),
-        vec![
-            WarningFilter::All(Some(LINT_WARNING_PREFIX)),
-            WarningFilter::code(
-                Some(LINT_WARNING_PREFIX),
-                LinterDiagCategory::ShareOwned as u8,
-                LINTER_DEFAULT_DIAG_CODE,
-                Some(SHARE_OWNED_FILTER_NAME),
-            ),
-            WarningFilter::code(
-                Some(LINT_WARNING_PREFIX),
-                LinterDiagCategory::SelfTransfer as u8,
-                LINTER_DEFAULT_DIAG_CODE,
-                Some(SELF_TRANSFER_FILTER_NAME),
-            ),
-            WarningFilter::code(
-                Some(LINT_WARNING_PREFIX),
-                LinterDiagCategory::CustomStateChange as u8,
-                LINTER_DEFAULT_DIAG_CODE,
-                Some(CUSTOM_STATE_CHANGE_FILTER_NAME),
-            ),
-            WarningFilter::code(
-                Some(LINT_WARNING_PREFIX),
-                LinterDiagCategory::CoinField as u8,
-                LINTER_DEFAULT_DIAG_CODE,
-                Some(COIN_FIELD_FILTER_NAME),
-            ),
-            WarningFilter::code(
-                Some(LINT_WARNING_PREFIX),
-                LinterDiagCategory::FreezeWrapped as u8,
-                LINTER_DEFAULT_DIAG_CODE,
-                Some(FREEZE_WRAPPED_FILTER_NAME),
-            ),
-            WarningFilter::code(
-                Some(LINT_WARNING_PREFIX),
-                LinterDiagCategory::CollectionEquality as u8,
-                LINTER_DEFAULT_DIAG_CODE,
-                Some(COLLECTION_EQUALITY_FILTER_NAME),
-            ),
-        ],
+        std::iter::once(WarningFilter::All(Some(LINT_WARNING_PREFIX)))
+            .chain(lint_filters)
+            .collect(),
     )
 }
 
+/// Returns the visitors of all lints, leaving out the ones that are allowed in `levels`.
+pub fn linter_visitors(levels: &LintLevels) -> Vec<Visitor> {
+    let visitors = [
+        (
+            SHARE_OWNED_FILTER_NAME,
+            share_owned::ShareOwnedVerifier.visitor(),
+        ),
+        (
+            SELF_TRANSFER_FILTER_NAME,
+            self_transfer::SelfTransferVerifier.visitor(),
+        ),
+        (
+            CUSTOM_STATE_CHANGE_FILTER_NAME,
+            custom_state_change::CustomStateChangeVerifier.visitor(),
+        ),
+        (
+            COIN_FIELD_FILTER_NAME,
+            coin_field::CoinFieldVisitor.visitor(),
+        ),
+        (
+            FREEZE_WRAPPED_FILTER_NAME,
+            freeze_wrapped::FreezeWrappedVisitor.visitor(),
+        ),
+        (
+            COLLECTION_EQUALITY_FILTER_NAME,
+            collection_equality::CollectionEqualityVisitor.visitor(),
+        ),
+        (
+            UNUSED_TX_CONTEXT_FILTER_NAME,
+            unused_tx_context::UnusedTxContextVisitor.visitor(),
+        ),
+        (
+            PUBLIC_ENTRY_RETURN_FILTER_NAME,
+            public_entry_return::PublicEntryReturnVisitor.visitor(),
+        ),
+        (
+            MISSING_WITNESS_DROP_FILTER_NAME,
+            missing_witness_drop::MissingWitnessDropVisitor.visitor(),
+        ),
+        (
+            UNBOUNDED_VECTOR_GROWTH_FILTER_NAME,
+            unbounded_vector_growth::UnboundedVectorGrowthVisitor.visitor(),
+        ),
+    ];
+    visitors
+        .into_iter()
+        .filter(|(name, _)| lint_level(levels, name) != LintLevel::Allow)
+        .map(|(_, visitor)| visitor)
+        .collect()
+}
+
+/// Returns the level of the lint called `name`. Lints that are not configured in the manifest
+/// report warnings.
+pub fn lint_level(levels: &LintLevels, name: &str) -> LintLevel {
+    levels
+        .get(&Symbol::from(name))
+        .copied()
+        .unwrap_or(LintLevel::Warn)
+}
+
+/// Checks that all lints configured in `levels` exist.
+pub fn check_lint_levels(levels: &LintLevels) -> anyhow::Result<()> {
+    for name in levels.keys() {
+        if !LINTS.iter().any(|(_, lint)| name.as_str() == *lint) {
+            let known: Vec<_> = LINTS.iter().map(|(_, lint)| *lint).collect();
+            anyhow::bail!(
+                "Unknown lint '{name}' in the '[lints]' section of the manifest. Known lints: {}",
+                known.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the names of the lints that are denied in `levels` and reported a diagnostic with the
+/// given `categories`.
+pub fn denied_lints(
+    levels: &LintLevels,
+    categories: impl IntoIterator<Item = u8>,
+) -> Vec<&'static str> {
+    let categories: BTreeSet<_> = categories.into_iter().collect();
+    LINTS
+        .iter()
+        .filter(|(category, name)| {
+            categories.contains(&(*category as u8)) && lint_level(levels, name) == LintLevel::Deny
+        })
+        .map(|(_, name)| *name)
+        .collect()
+}
+
 pub fn base_type(t: &N::Type) -> Option<&N::Type> {
     use N::Type_ as T;
     match &t.value {
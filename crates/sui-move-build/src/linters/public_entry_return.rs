@@ -0,0 +1,83 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags public entry functions that return values. Values returned from an entry
+//! function called in a transaction are dropped, while a public function without the entry
+//! modifier can pass the values it returns to subsequent commands of a programmable transaction.
+
+use move_compiler::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::{ModuleIdent, Visibility},
+    naming::ast as N,
+    parser::ast::FunctionName,
+    shared::{program_info::TypingProgramInfo, CompilationEnv},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX};
+
+const PUBLIC_ENTRY_RETURN_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::PublicEntryReturn as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "public entry function returning a value",
+);
+
+pub struct PublicEntryReturnVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+impl TypingVisitorConstructor for PublicEntryReturnVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn visit_function_custom(
+        &mut self,
+        _module: Option<ModuleIdent>,
+        function_name: FunctionName,
+        fdef: &mut T::Function,
+    ) -> bool {
+        let (Visibility::Public(_), Some(_)) = (&fdef.visibility, fdef.entry) else {
+            return true;
+        };
+        let return_type = &fdef.signature.return_type;
+        if matches!(return_type.value, N::Type_::Unit) {
+            return true;
+        }
+
+        let msg = format!("Public entry function '{function_name}' returns a value");
+        let note_msg = "Values returned by an entry function are dropped when it is called \
+                       from a transaction. Removing 'entry' lets callers use the returned \
+                       values in programmable transactions instead";
+        let mut d = diag!(PUBLIC_ENTRY_RETURN_DIAG, (return_type.loc, msg));
+        d.add_note(note_msg);
+        self.env.add_diag(d);
+        true
+    }
+
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+}
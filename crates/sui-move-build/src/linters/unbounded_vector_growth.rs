@@ -0,0 +1,189 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags pushing elements to vector fields of shared objects. Anyone can pass a
+//! shared object to a function that grows one of its vectors, and once the object grows past the
+//! object size limit, every transaction modifying it will fail. The analysis considers an object
+//! shared if its type is passed to sui::transfer::share_object or
+//! sui::transfer::public_share_object in the module defining it.
+
+use std::collections::BTreeSet;
+
+use move_compiler::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::ModuleIdent,
+    naming::ast as N,
+    shared::{program_info::TypingProgramInfo, CompilationEnv, Identifier},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+use move_symbol_pool::Symbol;
+
+use super::{
+    base_type, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX, PUBLIC_SHARE_FUN,
+    PUSH_BACK_FUN, SHARE_FUN, STD_PKG_NAME, SUI_PKG_NAME, TRANSFER_MOD_NAME, VECTOR_MOD_NAME,
+};
+
+const UNBOUNDED_VECTOR_GROWTH_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::UnboundedVectorGrowth as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "unbounded vector growth in a shared object",
+);
+
+const SHARE_FUNCTIONS: &[(&str, &str, &str)] = &[
+    (SUI_PKG_NAME, TRANSFER_MOD_NAME, PUBLIC_SHARE_FUN),
+    (SUI_PKG_NAME, TRANSFER_MOD_NAME, SHARE_FUN),
+];
+
+pub struct UnboundedVectorGrowthVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+    /// The module currently being visited
+    module: Option<ModuleIdent>,
+    /// Structs of the current module that are shared
+    shared: BTreeSet<Symbol>,
+}
+
+/// Collects the structs of a module that are shared within the module.
+struct SharedStructs {
+    module: ModuleIdent,
+    shared: BTreeSet<Symbol>,
+}
+
+impl TypingVisitorConstructor for UnboundedVectorGrowthVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context {
+            env,
+            module: None,
+            shared: BTreeSet::new(),
+        }
+    }
+}
+
+impl Context<'_> {
+    /// Returns the name of the shared struct of the current module that `t` refers to, if any.
+    fn shared_struct(&self, t: &N::Type) -> Option<Symbol> {
+        let sname = module_struct(self.module?, t)?;
+        self.shared.contains(&sname).then_some(sname)
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn visit_module_custom(&mut self, ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        let mut shared_structs = SharedStructs {
+            module: ident,
+            shared: BTreeSet::new(),
+        };
+        for (fname, fdef) in mdef.functions.key_cloned_iter_mut() {
+            shared_structs.visit_function(Some(ident), fname, fdef);
+        }
+        self.module = Some(ident);
+        self.shared = shared_structs.shared;
+        // visit the functions of the module looking for vector growth
+        false
+    }
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        let E::ModuleCall(call) = &exp.exp.value else {
+            return false;
+        };
+        if !is_function(call, STD_PKG_NAME, VECTOR_MOD_NAME, PUSH_BACK_FUN) {
+            return false;
+        }
+        let Some(sp!(_, E::Borrow(true, obj, field))) = first_argument(call).map(|e| &e.exp) else {
+            return false;
+        };
+        let Some(sname) = self.shared_struct(&obj.ty) else {
+            return false;
+        };
+
+        let msg = format!(
+            "Elements are pushed to the vector field '{field}' of shared object '{sname}', which \
+             may grow without bound"
+        );
+        let mut d = diag!(UNBOUNDED_VECTOR_GROWTH_DIAG, (exp.exp.loc, msg));
+        d.add_note(
+            "Objects cannot grow past the maximum object size. Consider bounding the length of \
+             the vector, or storing its elements in a dynamic collection such as \
+             'sui::table_vec::TableVec'",
+        );
+        self.env.add_diag(d);
+        false
+    }
+
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+}
+
+impl TypingVisitorContext for SharedStructs {
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        let E::ModuleCall(call) = &exp.exp.value else {
+            return false;
+        };
+        if !SHARE_FUNCTIONS
+            .iter()
+            .any(|(addr, module, fun)| is_function(call, addr, module, fun))
+        {
+            return false;
+        }
+        if let Some(sname) = call
+            .type_arguments
+            .first()
+            .and_then(|t| module_struct(self.module, t))
+        {
+            self.shared.insert(sname);
+        }
+        false
+    }
+
+    fn add_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+    fn pop_warning_filter_scope(&mut self) {}
+}
+
+/// Returns the name of the struct defined in `module` that `t` (or the type `t` refers to) is an
+/// instance of, if any.
+fn module_struct(module: ModuleIdent, t: &N::Type) -> Option<Symbol> {
+    let sp!(_, N::Type_::Apply(_, sp!(_, tname), _)) = base_type(t)? else {
+        return None;
+    };
+    let N::TypeName_::ModuleType(mident, sname) = tname else {
+        return None;
+    };
+    (mident.value == module.value).then(|| sname.value())
+}
+
+fn is_function(call: &T::ModuleCall, addr: &str, module: &str, fun: &str) -> bool {
+    call.module.value.is(addr, module) && call.name.value().as_str() == fun
+}
+
+fn first_argument(call: &T::ModuleCall) -> Option<&T::Exp> {
+    match &call.arguments.exp.value {
+        T::UnannotatedExp_::ExpList(items) => match items.first()? {
+            T::ExpListItem::Single(e, _) => Some(e),
+            T::ExpListItem::Splat(_, e, _) => Some(e),
+        },
+        _ => Some(&call.arguments),
+    }
+}
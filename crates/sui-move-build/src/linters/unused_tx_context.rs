@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags parameters of type sui::tx_context::TxContext (or references to it) that are
+//! never used in the body of the function. Such parameters only make the function harder to call
+//! and can be removed. Parameters prefixed with an underscore are not flagged.
+
+use std::collections::BTreeSet;
+
+use move_compiler::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::ModuleIdent,
+    naming::ast as N,
+    parser::ast::FunctionName,
+    shared::{program_info::TypingProgramInfo, CompilationEnv, Identifier},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    base_type, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX, SUI_PKG_NAME,
+    TX_CONTEXT_MOD_NAME, TX_CONTEXT_STRUCT_NAME,
+};
+
+const UNUSED_TX_CONTEXT_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::UnusedTxContext as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "unused 'sui::tx_context::TxContext' parameter",
+);
+
+/// Module initializers must take a transaction context, whether they use it or not
+const INIT_FUN: &str = "init";
+
+pub struct UnusedTxContextVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+/// Collects the local variables used in a function body.
+#[derive(Default)]
+struct UsedVars {
+    vars: BTreeSet<N::Var_>,
+}
+
+impl TypingVisitorConstructor for UnusedTxContextVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn visit_function_custom(
+        &mut self,
+        _module: Option<ModuleIdent>,
+        function_name: FunctionName,
+        fdef: &mut T::Function,
+    ) -> bool {
+        if function_name.value().as_str() == INIT_FUN {
+            return true;
+        }
+        let T::FunctionBody_::Defined(seq) = &mut fdef.body.value else {
+            return true;
+        };
+        let tx_context_params: Vec<_> = fdef
+            .signature
+            .parameters
+            .iter()
+            .filter(|(_, var, ty)| !is_intentionally_unused(var) && is_tx_context(ty))
+            .map(|(_, var, _)| *var)
+            .collect();
+        if tx_context_params.is_empty() {
+            return true;
+        }
+
+        let mut used = UsedVars::default();
+        used.visit_seq(seq);
+        for var in tx_context_params {
+            if used.vars.contains(&var.value) {
+                continue;
+            }
+            let msg = format!(
+                "Parameter '{}' of function '{function_name}' is never used",
+                var.value.name
+            );
+            let mut d = diag!(UNUSED_TX_CONTEXT_DIAG, (var.loc, msg));
+            d.add_note("Functions that do not use the transaction context do not need to take it");
+            self.env.add_diag(d);
+        }
+        true
+    }
+
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+}
+
+impl TypingVisitorContext for UsedVars {
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        match &exp.exp.value {
+            E::Move { var, .. } | E::Copy { var, .. } | E::Use(var) | E::BorrowLocal(_, var) => {
+                self.vars.insert(var.value);
+            }
+            _ => (),
+        }
+        false
+    }
+
+    fn add_warning_filter_scope(&mut self, _filter: WarningFilters) {}
+
+    fn pop_warning_filter_scope(&mut self) {}
+}
+
+/// Parameters prefixed with an underscore are declared unused on purpose, e.g. to keep a
+/// signature stable
+fn is_intentionally_unused(var: &N::Var) -> bool {
+    var.value.name.as_str().starts_with('_')
+}
+
+fn is_tx_context(t: &N::Type) -> bool {
+    let Some(sp!(_, N::Type_::Apply(_, sp!(_, tname), _))) = base_type(t) else {
+        return false;
+    };
+    let N::TypeName_::ModuleType(mident, sname) = tname else {
+        return false;
+    };
+    mident.value.is(SUI_PKG_NAME, TX_CONTEXT_MOD_NAME)
+        && sname.value().as_str() == TX_CONTEXT_STRUCT_NAME
+}
@@ -3,7 +3,9 @@
 
 use std::path::Path;
 
-use crate::BuildConfig;
+use sui_types::error::{SuiError, SuiResult};
+
+use crate::{BuildConfig, CompiledPackage};
 
 #[test]
 fn generate_struct_layouts() {
@@ -28,3 +30,35 @@ fn generate_struct_layouts() {
         "0000000000000000000000000000000000000000000000000000000000000002::tx_context::TxContext"
     ));
 }
+
+fn build_with_lints(package: &str) -> SuiResult<CompiledPackage> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("lint_levels")
+        .join(package);
+    let mut config = BuildConfig::new_for_testing();
+    config.lint = true;
+    config.build(path)
+}
+
+#[test]
+fn denied_lint_fails_build() {
+    let Err(SuiError::ModuleBuildFailure { error }) = build_with_lints("deny") else {
+        panic!("A denied lint reporting a warning must fail the build");
+    };
+    assert!(error.contains("unused_tx_context"), "{error}");
+}
+
+#[test]
+fn allowed_lint_is_suppressed() {
+    // The same package fails to build when the lint is denied
+    build_with_lints("allow").unwrap();
+}
+
+#[test]
+fn unknown_lint_fails_build() {
+    let Err(SuiError::ModuleBuildFailure { error }) = build_with_lints("unknown") else {
+        panic!("An unknown lint in the manifest must fail the build");
+    };
+    assert!(error.contains("Unknown lint 'not_a_lint'"), "{error}");
+}
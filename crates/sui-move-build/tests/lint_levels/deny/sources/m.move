@@ -0,0 +1,9 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module lint_levels::m {
+    use sui::tx_context::TxContext;
+
+    #[allow(unused_variable)]
+    public fun unused(ctx: &TxContext) {}
+}
@@ -0,0 +1,17 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module 0x42::test {
+    struct DropWitness has drop {}
+
+    struct MY_WITNESS has drop {}
+
+    struct NotWitnessLike {}
+
+    struct Witness {}
+
+    struct CopyWitness has copy {}
+
+    #[lint_allow(missing_witness_drop)]
+    struct SuppressedWitness {}
+}
@@ -0,0 +1,27 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module 0x42::test {
+    public entry fun no_return_ok(_x: u64) {}
+
+    public fun public_return_ok(x: u64): u64 {
+        x
+    }
+
+    entry fun entry_return_ok(x: u64): u64 {
+        x
+    }
+
+    public entry fun public_entry_return_bad(x: u64): u64 {
+        x
+    }
+
+    public entry fun public_entry_return_tuple_bad(x: u64): (u64, bool) {
+        (x, true)
+    }
+
+    #[lint_allow(public_entry_return)]
+    public entry fun public_entry_return_suppressed(x: u64): u64 {
+        x
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module 0x42::test {
+    use std::vector;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    struct Shared has key {
+        id: UID,
+        items: vector<u64>,
+    }
+
+    struct Owned has key {
+        id: UID,
+        items: vector<u64>,
+    }
+
+    fun init(ctx: &mut TxContext) {
+        transfer::share_object(Shared { id: object::new(ctx), items: vector::empty() });
+        transfer::transfer(Owned { id: object::new(ctx), items: vector::empty() }, @0x42);
+    }
+
+    public fun push_shared_bad(s: &mut Shared, x: u64) {
+        vector::push_back(&mut s.items, x)
+    }
+
+    public fun push_owned_ok(o: &mut Owned, x: u64) {
+        vector::push_back(&mut o.items, x)
+    }
+
+    #[lint_allow(unbounded_vector_growth)]
+    public fun push_shared_suppressed(s: &mut Shared, x: u64) {
+        vector::push_back(&mut s.items, x)
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module 0x42::test {
+    use sui::object::{Self, UID};
+    use sui::tx_context::TxContext;
+
+    struct S has key {
+        id: UID,
+    }
+
+    public fun new_ok(ctx: &mut TxContext): S {
+        S { id: object::new(ctx) }
+    }
+
+    public fun mut_unused_bad(x: u64, ctx: &mut TxContext): u64 {
+        x
+    }
+
+    public fun imm_unused_bad(ctx: &TxContext) {}
+
+    // module initializers must take a transaction context
+    fun init(_ctx: &mut TxContext) {}
+
+    // parameters prefixed with an underscore are intentionally unused
+    public fun unused_underscore_ok(_ctx: &mut TxContext) {}
+
+    #[allow(unused_variable)]
+    #[lint_allow(unused_tx_context)]
+    public fun unused_suppressed(ctx: &mut TxContext) {}
+}
@@ -8,22 +8,15 @@ use move_command_line_common::{
     testing::{add_update_baseline_fix, format_diff, read_env_update_baseline},
 };
 use move_compiler::{
-    cfgir::visitor::AbstractInterpreterVisitor,
     command_line::compiler::move_check_for_errors,
     diagnostics::codes::{self, WarningFilter},
     editions::Flavor,
     expansion::ast as E,
     shared::{NumericalAddress, PackageConfig},
-    typing::visitor::TypingVisitor,
     Compiler, PASS_PARSER,
 };
 
-use sui_move_build::linters::{
-    coin_field::CoinFieldVisitor, collection_equality::CollectionEqualityVisitor,
-    custom_state_change::CustomStateChangeVerifier, freeze_wrapped::FreezeWrappedVisitor,
-    known_filters, self_transfer::SelfTransferVerifier, share_owned::ShareOwnedVerifier,
-    LINT_WARNING_PREFIX,
-};
+use sui_move_build::linters::{known_filters, linter_visitors, LINT_WARNING_PREFIX};
 
 const SUI_FRAMEWORK_PATH: &str = "../sui-framework/packages/sui-framework";
 const MOVE_STDLIB_PATH: &str = "../sui-framework/packages/move-stdlib";
@@ -64,14 +57,7 @@ fn run_tests(path: &Path) -> anyhow::Result<()> {
     let exp_path = path.with_extension(EXP_EXT);
 
     let targets: Vec<String> = vec![path.to_str().unwrap().to_owned()];
-    let lint_visitors = vec![
-        ShareOwnedVerifier.visitor(),
-        SelfTransferVerifier.visitor(),
-        CustomStateChangeVerifier.visitor(),
-        CoinFieldVisitor.visitor(),
-        FreezeWrappedVisitor.visitor(),
-        CollectionEqualityVisitor.visitor(),
-    ];
+    let lint_visitors = linter_visitors(&BTreeMap::new());
     let (filter_attr_name, filters) = known_filters_for_test();
     let (files, comments_and_compiler_res) = Compiler::from_files(
         targets,
@@ -0,0 +1,703 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::build::resolve_lock_file_path;
+use anyhow::{anyhow, bail, Context};
+use clap::Parser;
+use jsonrpsee::{core::client::ClientT, http_client::HttpClientBuilder, rpc_params};
+use move_binary_format::normalized;
+use move_cli::base;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+};
+use sui_json_rpc_types::{
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
+    SuiMoveNormalizedType, SuiMoveVisibility, SuiObjectDataOptions, SuiObjectResponse, SuiRawData,
+};
+use sui_move_build::BuildConfig;
+use sui_types::base_types::ObjectID;
+
+const INDENT: &str = "    ";
+
+/// The modules of each package bindings are generated from, keyed by package address and module
+/// name.
+type Packages = BTreeMap<ObjectID, BTreeMap<String, SuiMoveNormalizedModule>>;
+
+/// A struct, identified by the address of its package, its module and its name.
+type StructId = (ObjectID, String, String);
+
+/// Generate Rust bindings for a Move package: structs that deserialize from the BCS of the
+/// package's Move structs, and functions that add calls to the package's public and entry functions
+/// to a `ProgrammableTransactionBuilder`.
+#[derive(Parser)]
+#[group(id = "sui-move-bindgen")]
+pub struct Bindgen {
+    /// Generate bindings for the package published at this address instead of the local package
+    #[clap(long, requires = "rpc_url")]
+    pub package_id: Option<ObjectID>,
+    /// URL of the fullnode to fetch the published package and its dependencies from
+    #[clap(long)]
+    pub rpc_url: Option<String>,
+    /// File to write the bindings to. The bindings are printed to stdout if no file is given
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+}
+
+impl Bindgen {
+    pub fn execute(
+        &self,
+        path: Option<PathBuf>,
+        build_config: MoveBuildConfig,
+    ) -> anyhow::Result<()> {
+        let (root, root_name, packages) = match (&self.package_id, &self.rpc_url) {
+            (Some(package_id), Some(rpc_url)) => {
+                let (root, packages) = fetch_packages(rpc_url.clone(), *package_id)?;
+                let root_name =
+                    format!("package_{}", root.to_hex_literal().trim_start_matches("0x"));
+                (root, root_name, packages)
+            }
+            _ => build_packages(path, build_config)?,
+        };
+
+        let bindings = Generator::new(root, &root_name, &packages).generate()?;
+        match &self.output {
+            Some(output) => fs::write(output, bindings)?,
+            None => print!("{bindings}"),
+        }
+        Ok(())
+    }
+}
+
+/// Builds the package at `path`, returning the address and name of the package, and the modules of
+/// the package and its dependencies.
+fn build_packages(
+    path: Option<PathBuf>,
+    build_config: MoveBuildConfig,
+) -> anyhow::Result<(ObjectID, String, Packages)> {
+    let rerooted_path = base::reroot_path(path.clone())?;
+    let build_config = resolve_lock_file_path(build_config, path)?;
+    let pkg = BuildConfig {
+        config: build_config,
+        run_bytecode_verifier: true,
+        print_diags_to_stderr: true,
+        lint: false,
+    }
+    .build(rerooted_path)?;
+
+    let root_module = pkg
+        .get_modules()
+        .next()
+        .ok_or_else(|| anyhow!("Package has no modules to generate bindings for"))?;
+    let root = ObjectID::from(*root_module.address());
+    let root_name = pkg.package.compiled_package_info.package_name.to_string();
+
+    let mut packages = Packages::new();
+    for module in pkg.get_modules_and_deps() {
+        let module = SuiMoveNormalizedModule::from(normalized::Module::new(module));
+        packages
+            .entry(parse_address(&module.address)?)
+            .or_default()
+            .insert(module.name.clone(), module);
+    }
+    Ok((root, root_name, packages))
+}
+
+/// Fetches the modules of the package published at `package_id`, and of its dependencies, from the
+/// fullnode at `rpc_url`. Returns the address the package's modules are defined at, which differs
+/// from `package_id` for upgraded packages, together with the modules.
+fn fetch_packages(rpc_url: String, package_id: ObjectID) -> anyhow::Result<(ObjectID, Packages)> {
+    // The JSON-RPC client is async and this command may already be running within an async
+    // runtime, so the packages are fetched on a separate thread with a runtime of its own.
+    std::thread::spawn(move || {
+        tokio::runtime::Runtime::new()?.block_on(async move {
+            let client = HttpClientBuilder::default().build(&rpc_url)?;
+            let response: SuiObjectResponse = client
+                .request(
+                    "sui_getObject",
+                    rpc_params![package_id, SuiObjectDataOptions::new().with_bcs()],
+                )
+                .await
+                .with_context(|| format!("Failed to fetch package {package_id}"))?;
+            let Some(SuiRawData::Package(package)) = response.data.and_then(|data| data.bcs) else {
+                bail!("Object {package_id} is not a package");
+            };
+
+            // Types refer to the packages that define them by their original address, and the
+            // linkage table maps each dependency from its original address to the version the
+            // package was published against, which may be an upgrade at another address.
+            let linked_ids = package
+                .linkage_table
+                .values()
+                .map(|upgrade| upgrade.upgraded_id);
+            let mut packages = Packages::new();
+            let mut root = None;
+            for id in std::iter::once(package_id).chain(linked_ids) {
+                let modules: BTreeMap<String, SuiMoveNormalizedModule> = client
+                    .request("sui_getNormalizedMoveModulesByPackage", rpc_params![id])
+                    .await
+                    .with_context(|| format!("Failed to fetch package {id}"))?;
+                let Some(module) = modules.values().next() else {
+                    bail!("Package {id} has no modules");
+                };
+                let address = parse_address(&module.address)?;
+                root.get_or_insert(address);
+                packages.insert(address, modules);
+            }
+            Ok((root.unwrap(), packages))
+        })
+    })
+    .join()
+    .map_err(|_| anyhow!("Fetching package {package_id} panicked"))?
+}
+
+/// Generates the bindings of the structs and functions of a package, and of the structs of its
+/// dependencies that its structs and functions refer to.
+struct Generator<'a> {
+    root: ObjectID,
+    packages: &'a Packages,
+    package_names: BTreeMap<ObjectID, String>,
+    out: String,
+    indent: usize,
+}
+
+impl<'a> Generator<'a> {
+    fn new(root: ObjectID, root_name: &str, packages: &'a Packages) -> Self {
+        let package_names = packages
+            .keys()
+            .map(|address| {
+                let name = if *address == root {
+                    to_snake_case(root_name)
+                } else {
+                    match address.to_hex_literal().as_str() {
+                        "0x1" => "move_stdlib".to_string(),
+                        "0x2" => "sui".to_string(),
+                        "0x3" => "sui_system".to_string(),
+                        "0xdee9" => "deepbook".to_string(),
+                        literal => format!("package_{}", literal.trim_start_matches("0x")),
+                    }
+                };
+                (*address, name)
+            })
+            .collect();
+        Self {
+            root,
+            packages,
+            package_names,
+            out: String::new(),
+            indent: 0,
+        }
+    }
+
+    fn generate(mut self) -> anyhow::Result<String> {
+        let structs = self.reachable_structs()?;
+        let root_name = self.package_names[&self.root].clone();
+        self.line(&format!(
+            "// Rust bindings for the `{root_name}` Move package, generated by `sui move bindgen`."
+        ));
+        self.line("// Do not edit by hand.");
+
+        for (address, modules) in self.packages {
+            let is_root = *address == self.root;
+            let modules: Vec<_> = modules
+                .values()
+                .filter(|module| {
+                    is_root
+                        || structs
+                            .iter()
+                            .any(|(a, m, _)| a == address && *m == module.name)
+                })
+                .collect();
+            if modules.is_empty() {
+                continue;
+            }
+
+            self.line("");
+            self.line("#[allow(dead_code, non_snake_case, non_camel_case_types, clippy::all)]");
+            self.open(&format!(
+                "pub mod {} {{",
+                rust_ident(&self.package_names[address])
+            ));
+            for (i, module) in modules.into_iter().enumerate() {
+                if i > 0 {
+                    self.line("");
+                }
+                self.open(&format!("pub mod {} {{", rust_ident(&module.name)));
+                let mut first = true;
+                for (name, struct_) in &module.structs {
+                    if !is_root && !structs.contains(&(*address, module.name.clone(), name.clone()))
+                    {
+                        continue;
+                    }
+                    if !first {
+                        self.line("");
+                    }
+                    first = false;
+                    self.generate_struct(module, name, struct_)?;
+                }
+                if is_root {
+                    for (name, function) in &module.exposed_functions {
+                        if !function.is_entry
+                            && !matches!(function.visibility, SuiMoveVisibility::Public)
+                        {
+                            continue;
+                        }
+                        if !first {
+                            self.line("");
+                        }
+                        first = false;
+                        self.generate_function(module, name, function)?;
+                    }
+                }
+                self.close("}");
+            }
+            self.close("}");
+        }
+        Ok(self.out)
+    }
+
+    /// Returns the structs of the root package, and the structs of its dependencies that are
+    /// reachable from the fields of those structs or the signatures of its functions.
+    fn reachable_structs(&self) -> anyhow::Result<BTreeSet<StructId>> {
+        let mut pending = vec![];
+        for module in self.packages[&self.root].values() {
+            for ty in module_types(module) {
+                collect_structs(ty, &mut pending)?;
+            }
+        }
+
+        let mut reachable = BTreeSet::new();
+        while let Some(id) = pending.pop() {
+            if is_builtin_struct(&id) || reachable.contains(&id) {
+                continue;
+            }
+            for field in &self.lookup_struct(&id)?.fields {
+                collect_structs(&field.type_, &mut pending)?;
+            }
+            reachable.insert(id);
+        }
+        Ok(reachable)
+    }
+
+    fn lookup_struct(
+        &self,
+        (address, module, name): &StructId,
+    ) -> anyhow::Result<&SuiMoveNormalizedStruct> {
+        self.packages
+            .get(address)
+            .and_then(|modules| modules.get(module))
+            .and_then(|module| module.structs.get(name))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unable to find struct {}::{module}::{name}",
+                    address.to_hex_literal()
+                )
+            })
+    }
+
+    fn generate_struct(
+        &mut self,
+        module: &SuiMoveNormalizedModule,
+        name: &str,
+        struct_: &SuiMoveNormalizedStruct,
+    ) -> anyhow::Result<()> {
+        // Phantom type parameters do not affect the layout of a struct, so they are left out of
+        // the Rust struct
+        let type_params: Vec<_> = struct_
+            .type_parameters
+            .iter()
+            .enumerate()
+            .filter(|(_, param)| !param.is_phantom)
+            .map(|(i, _)| format!("T{i}"))
+            .collect();
+        let generics = if type_params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", type_params.join(", "))
+        };
+
+        self.line(&format!(
+            "/// Move struct `{}::{}::{name}`.",
+            module.address, module.name
+        ));
+        self.line(
+            "#[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]",
+        );
+        self.open(&format!("pub struct {}{generics} {{", rust_ident(name)));
+        for field in &struct_.fields {
+            let ty = self.rust_type(&field.type_).with_context(|| {
+                format!(
+                    "Unsupported type of field {}::{}::{name}.{}",
+                    module.address, module.name, field.name
+                )
+            })?;
+            self.line(&format!("pub {}: {ty},", rust_ident(&field.name)));
+        }
+        let mut used = BTreeSet::new();
+        for field in &struct_.fields {
+            self.collect_type_params(&field.type_, &mut used)?;
+        }
+        let unused: Vec<_> = type_params
+            .iter()
+            .filter(|param| !used.contains(*param))
+            .cloned()
+            .collect();
+        if !unused.is_empty() {
+            // Rust rejects type parameters that no field refers to
+            self.line("#[serde(skip)]");
+            self.line(&format!(
+                "pub _phantom: ::std::marker::PhantomData<({},)>,",
+                unused.join(", ")
+            ));
+        }
+        self.close("}");
+        Ok(())
+    }
+
+    fn generate_function(
+        &mut self,
+        module: &SuiMoveNormalizedModule,
+        name: &str,
+        function: &SuiMoveNormalizedFunction,
+    ) -> anyhow::Result<()> {
+        let mut params = vec![
+            "builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder"
+                .to_string(),
+            "package: ::sui_types::base_types::ObjectID".to_string(),
+        ];
+        if !function.type_parameters.is_empty() {
+            params.push("type_arguments: ::std::vec::Vec<::sui_types::TypeTag>".to_string());
+        }
+        let mut arguments = vec![];
+        for (i, param) in function.parameters.iter().enumerate() {
+            if is_tx_context(param) {
+                // the transaction context is passed in by the runtime
+                continue;
+            }
+            let arg = format!("arg{i}");
+            if is_pure(param) {
+                params.push(format!("{arg}: {}", self.rust_type(param)?));
+                arguments.push(format!("builder.pure({arg})?"));
+            } else {
+                params.push(format!("{arg}: ::sui_types::transaction::Argument"));
+                arguments.push(arg);
+            }
+        }
+
+        self.line(&format!(
+            "/// Adds a call to `{}::{}::{name}` to `builder`.",
+            module.address, module.name
+        ));
+        self.open(&format!("pub fn {}(", rust_ident(name)));
+        for param in params {
+            self.line(&format!("{param},"));
+        }
+        self.indent -= 1;
+        self.open(") -> ::anyhow::Result<::sui_types::transaction::Argument> {");
+        self.open("let arguments = ::std::vec![");
+        for argument in arguments {
+            self.line(&format!("{argument},"));
+        }
+        self.close("];");
+        self.open("Ok(builder.programmable_move_call(");
+        self.line("package,");
+        self.line(&format!(
+            "::sui_types::Identifier::new({:?})?,",
+            module.name
+        ));
+        self.line(&format!("::sui_types::Identifier::new({name:?})?,"));
+        if function.type_parameters.is_empty() {
+            self.line("::std::vec![],");
+        } else {
+            self.line("type_arguments,");
+        }
+        self.line("arguments,");
+        self.close("))");
+        self.close("}");
+        Ok(())
+    }
+
+    /// The Rust type values of the Move type `ty` are deserialized into.
+    fn rust_type(&self, ty: &SuiMoveNormalizedType) -> anyhow::Result<String> {
+        use SuiMoveNormalizedType as T;
+        Ok(match ty {
+            T::Bool => "bool".to_string(),
+            T::U8 => "u8".to_string(),
+            T::U16 => "u16".to_string(),
+            T::U32 => "u32".to_string(),
+            T::U64 => "u64".to_string(),
+            T::U128 => "u128".to_string(),
+            T::U256 => "::move_core_types::u256::U256".to_string(),
+            T::Address => "::sui_types::base_types::SuiAddress".to_string(),
+            T::Vector(elem) => format!("::std::vec::Vec<{}>", self.rust_type(elem)?),
+            T::TypeParameter(i) => format!("T{i}"),
+            T::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                let id = (parse_address(address)?, module.clone(), name.clone());
+                match builtin_struct_type(&id) {
+                    Some("Option") => {
+                        let [arg] = &type_arguments[..] else {
+                            bail!("Option must have a single type argument");
+                        };
+                        format!("::std::option::Option<{}>", self.rust_type(arg)?)
+                    }
+                    Some(builtin) => builtin.to_string(),
+                    None => {
+                        let struct_ = self.lookup_struct(&id)?;
+                        let args = struct_
+                            .type_parameters
+                            .iter()
+                            .zip(type_arguments)
+                            .filter(|(param, _)| !param.is_phantom)
+                            .map(|(_, arg)| self.rust_type(arg))
+                            .collect::<anyhow::Result<Vec<_>>>()?;
+                        let generics = if args.is_empty() {
+                            String::new()
+                        } else {
+                            format!("<{}>", args.join(", "))
+                        };
+                        format!(
+                            "super::super::{}::{}::{}{generics}",
+                            rust_ident(&self.package_names[&id.0]),
+                            rust_ident(module),
+                            rust_ident(name)
+                        )
+                    }
+                }
+            }
+            T::Signer | T::Reference(_) | T::MutableReference(_) => {
+                bail!("Values of this type cannot be deserialized")
+            }
+        })
+    }
+
+    /// Collects the type parameters that appear in the Rust type of `ty`, which leaves out the
+    /// phantom type arguments of structs.
+    fn collect_type_params(
+        &self,
+        ty: &SuiMoveNormalizedType,
+        acc: &mut BTreeSet<String>,
+    ) -> anyhow::Result<()> {
+        use SuiMoveNormalizedType as T;
+        match ty {
+            T::TypeParameter(i) => {
+                acc.insert(format!("T{i}"));
+            }
+            T::Vector(inner) => self.collect_type_params(inner, acc)?,
+            T::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                let id = (parse_address(address)?, module.clone(), name.clone());
+                if is_builtin_struct(&id) {
+                    for arg in type_arguments {
+                        self.collect_type_params(arg, acc)?;
+                    }
+                } else {
+                    let struct_ = self.lookup_struct(&id)?;
+                    for (param, arg) in struct_.type_parameters.iter().zip(type_arguments) {
+                        if !param.is_phantom {
+                            self.collect_type_params(arg, acc)?;
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn line(&mut self, line: &str) {
+        if !line.is_empty() {
+            for _ in 0..self.indent {
+                self.out.push_str(INDENT);
+            }
+            self.out.push_str(line);
+        }
+        self.out.push('\n');
+    }
+
+    fn open(&mut self, line: &str) {
+        self.line(line);
+        self.indent += 1;
+    }
+
+    fn close(&mut self, line: &str) {
+        self.indent -= 1;
+        self.line(line);
+    }
+}
+
+/// The types of the struct fields and function signatures of `module`, leaving out the transaction
+/// context, which is not passed to the generated functions.
+fn module_types(module: &SuiMoveNormalizedModule) -> impl Iterator<Item = &SuiMoveNormalizedType> {
+    let fields = module
+        .structs
+        .values()
+        .flat_map(|struct_| struct_.fields.iter().map(|field| &field.type_));
+    let signatures = module
+        .exposed_functions
+        .values()
+        .flat_map(|function| function.parameters.iter().chain(&function.return_))
+        .filter(|ty| !is_tx_context(ty));
+    fields.chain(signatures)
+}
+
+fn collect_structs(ty: &SuiMoveNormalizedType, acc: &mut Vec<StructId>) -> anyhow::Result<()> {
+    use SuiMoveNormalizedType as T;
+    match ty {
+        T::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            acc.push((parse_address(address)?, module.clone(), name.clone()));
+            for arg in type_arguments {
+                collect_structs(arg, acc)?;
+            }
+        }
+        T::Vector(inner) | T::Reference(inner) | T::MutableReference(inner) => {
+            collect_structs(inner, acc)?
+        }
+        T::Bool
+        | T::U8
+        | T::U16
+        | T::U32
+        | T::U64
+        | T::U128
+        | T::U256
+        | T::Address
+        | T::Signer
+        | T::TypeParameter(_) => (),
+    }
+    Ok(())
+}
+
+/// The Rust type that Move structs with the same BCS representation as a Rust type map to.
+fn builtin_struct_type((address, module, name): &StructId) -> Option<&'static str> {
+    Some(
+        match (
+            address.to_hex_literal().as_str(),
+            module.as_str(),
+            name.as_str(),
+        ) {
+            ("0x1", "string", "String") | ("0x1", "ascii", "String") => "::std::string::String",
+            ("0x1", "option", "Option") => "Option",
+            ("0x2", "object", "UID") => "::sui_types::id::UID",
+            ("0x2", "object", "ID") => "::sui_types::id::ID",
+            _ => return None,
+        },
+    )
+}
+
+fn is_builtin_struct(id: &StructId) -> bool {
+    builtin_struct_type(id).is_some()
+}
+
+fn is_tx_context(ty: &SuiMoveNormalizedType) -> bool {
+    use SuiMoveNormalizedType as T;
+    match ty {
+        T::Reference(inner) | T::MutableReference(inner) => matches!(
+            &**inner,
+            T::Struct { address, module, name, .. }
+                if parse_address(address).is_ok_and(|a| a.to_hex_literal() == "0x2")
+                    && module == "tx_context"
+                    && name == "TxContext"
+        ),
+        _ => false,
+    }
+}
+
+/// Whether arguments of type `ty` are passed as pure values, rather than as objects or results of
+/// other commands.
+fn is_pure(ty: &SuiMoveNormalizedType) -> bool {
+    use SuiMoveNormalizedType as T;
+    match ty {
+        T::Bool | T::U8 | T::U16 | T::U32 | T::U64 | T::U128 | T::U256 | T::Address => true,
+        T::Vector(inner) => is_pure(inner),
+        T::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => match parse_address(address) {
+            Ok(address) => match builtin_struct_type(&(address, module.clone(), name.clone())) {
+                Some("Option") => type_arguments.iter().all(is_pure),
+                Some("::sui_types::id::UID") | None => false,
+                Some(_) => true,
+            },
+            Err(_) => false,
+        },
+        T::Signer | T::TypeParameter(_) | T::Reference(_) | T::MutableReference(_) => false,
+    }
+}
+
+fn parse_address(address: &str) -> anyhow::Result<ObjectID> {
+    ObjectID::from_hex_literal(address).with_context(|| format!("Invalid address {address}"))
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            snake.push(c);
+        } else if !snake.ends_with('_') {
+            snake.push('_');
+        }
+    }
+    snake
+}
+
+/// Escapes Move identifiers that are Rust keywords.
+fn rust_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "union", "unsafe", "unsized",
+        "use", "virtual", "where", "while", "yield",
+    ];
+    // these keywords cannot be used as raw identifiers
+    const RESERVED: &[&str] = &["crate", "self", "Self", "super"];
+    if RESERVED.contains(&name) {
+        format!("{name}_")
+    } else if KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    #[test]
+    fn test_generate_bindings() {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/unit_tests/data/bindgen/fixture");
+        let (root, root_name, packages) =
+            build_packages(Some(path), BuildConfig::new_for_testing().config).unwrap();
+        assert_eq!(root, ObjectID::from_hex_literal("0xa").unwrap());
+
+        let bindings = Generator::new(root, &root_name, &packages)
+            .generate()
+            .unwrap();
+        assert_snapshot!(bindings);
+    }
+}
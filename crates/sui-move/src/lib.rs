@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
+use move_cli::base::fmt::Fmt;
 #[cfg(feature = "unit_test")]
 use move_cli::base::test::UnitTestResult;
 use move_package::BuildConfig;
@@ -9,6 +10,8 @@ use move_package::BuildConfig;
 use move_unit_test::UnitTestingConfig;
 use std::path::PathBuf;
 
+#[cfg(feature = "bindgen")]
+pub mod bindgen;
 #[cfg(feature = "build")]
 pub mod build;
 #[cfg(feature = "coverage")]
@@ -23,12 +26,15 @@ pub mod unit_test;
 
 #[derive(Parser)]
 pub enum Command {
+    #[cfg(feature = "bindgen")]
+    Bindgen(bindgen::Bindgen),
     #[cfg(feature = "build")]
     Build(build::Build),
     #[cfg(feature = "coverage")]
     Coverage(coverage::Coverage),
     #[cfg(feature = "disassemble")]
     Disassemble(disassemble::Disassemble),
+    Fmt(Fmt),
     New(new::New),
     #[cfg(feature = "prove")]
     Prove(prove::Prover),
@@ -49,12 +55,15 @@ pub fn execute_move_command(
     command: Command,
 ) -> anyhow::Result<()> {
     match command {
+        #[cfg(feature = "bindgen")]
+        Command::Bindgen(c) => c.execute(package_path, build_config),
         #[cfg(feature = "build")]
         Command::Build(c) => c.execute(package_path, build_config),
         #[cfg(feature = "coverage")]
         Command::Coverage(c) => c.execute(package_path, build_config),
         #[cfg(feature = "disassemble")]
         Command::Disassemble(c) => c.execute(package_path, build_config),
+        Command::Fmt(c) => c.execute(package_path, build_config),
         Command::New(c) => c.execute(package_path),
         #[cfg(feature = "prove")]
         Command::Prove(c) => c.execute(package_path, build_config),
@@ -70,6 +79,10 @@ pub fn execute_move_command(
                 check_stackless_vm: c.test.check_stackless_vm,
                 verbose: c.test.verbose_mode,
                 ignore_compile_warnings: c.test.ignore_compile_warnings,
+                report_format: c.test.report_format,
+                report_output: c.test.report_output.clone(),
+                rand_num_iters: c.test.rand_num_iters,
+                seed: c.test.seed,
                 ..UnitTestingConfig::default_with_bound(None)
             };
             let result = c.execute(package_path, build_config, unit_test_config)?;
@@ -0,0 +1,13 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module dep::pair {
+    struct Pair<T1, T2> has copy, drop, store {
+        first: T1,
+        second: T2,
+    }
+
+    public fun new<T1, T2>(first: T1, second: T2): Pair<T1, T2> {
+        Pair { first, second }
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module fixture::vault {
+    use std::option::Option;
+    use std::string::String;
+    use std::vector;
+    use sui::coin::Coin;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+    use dep::pair::{Self, Pair};
+
+    struct Vault<phantom T> has key {
+        id: UID,
+        name: String,
+        coins: vector<Coin<T>>,
+        limits: Pair<u64, Option<u64>>,
+    }
+
+    struct Entry<K: copy + drop + store, V: store> has store {
+        key: K,
+        value: V,
+    }
+
+    entry fun create<T>(name: String, min: u64, max: Option<u64>, ctx: &mut TxContext) {
+        transfer::share_object(Vault<T> {
+            id: object::new(ctx),
+            name,
+            coins: vector[],
+            limits: pair::new(min, max),
+        })
+    }
+
+    public fun deposit<T>(vault: &mut Vault<T>, coin: Coin<T>) {
+        vector::push_back(&mut vault.coins, coin)
+    }
+
+    public fun name<T>(vault: &Vault<T>): String {
+        vault.name
+    }
+}
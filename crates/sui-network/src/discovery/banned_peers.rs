@@ -0,0 +1,58 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anemo::PeerId;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// Peers that have been temporarily banned for misbehaving.
+///
+/// The list is shared between the subsystems of a node: StateSync bans peers that serve it bad
+/// data or keep timing out, and Discovery disconnects from banned peers and does not dial them
+/// again until their ban expires.
+#[derive(Clone, Debug, Default)]
+pub struct BannedPeers {
+    inner: Arc<RwLock<HashMap<PeerId, Instant>>>,
+}
+
+impl BannedPeers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bans `peer_id` for `duration`. Banning a peer that is already banned extends its ban if
+    /// the new ban expires later.
+    pub fn ban(&self, peer_id: PeerId, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut inner = self.inner.write().unwrap();
+        let entry = inner.entry(peer_id).or_insert(until);
+        *entry = std::cmp::max(*entry, until);
+    }
+
+    pub fn unban(&self, peer_id: &PeerId) {
+        self.inner.write().unwrap().remove(peer_id);
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.inner
+            .read()
+            .unwrap()
+            .get(peer_id)
+            .is_some_and(|until| *until > Instant::now())
+    }
+
+    /// Returns the currently banned peers along with the time left on their ban, dropping the
+    /// bans that have expired.
+    pub fn banned_peers(&self) -> Vec<(PeerId, Duration)> {
+        let now = Instant::now();
+        let mut inner = self.inner.write().unwrap();
+        inner.retain(|_peer_id, until| *until > now);
+        inner
+            .iter()
+            .map(|(peer_id, until)| (*peer_id, until.duration_since(now)))
+            .collect()
+    }
+}
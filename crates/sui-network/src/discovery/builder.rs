@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    metrics::Metrics, server::Server, Discovery, DiscoveryEventLoop, DiscoveryServer, State,
+    metrics::Metrics, server::Server, BannedPeers, Discovery, DiscoveryEventLoop, DiscoveryServer,
+    State,
 };
 use crate::discovery::TrustedPeerChangeEvent;
 use anemo::codegen::InboundRequestLayer;
@@ -23,6 +24,7 @@ pub struct Builder {
     config: Option<P2pConfig>,
     metrics: Option<Metrics>,
     trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
+    banned_peers: Option<BannedPeers>,
 }
 
 impl Builder {
@@ -32,6 +34,7 @@ impl Builder {
             config: None,
             metrics: None,
             trusted_peer_change_rx,
+            banned_peers: None,
         }
     }
 
@@ -45,6 +48,13 @@ impl Builder {
         self
     }
 
+    /// Share a list of banned peers with the other subsystems of the node. Discovery will not
+    /// stay connected to, or dial, peers on the list.
+    pub fn banned_peers(mut self, banned_peers: BannedPeers) -> Self {
+        self.banned_peers = Some(banned_peers);
+        self
+    }
+
     pub fn build(self) -> (UnstartedDiscovery, DiscoveryServer<impl Discovery>) {
         let discovery_config = self
             .config
@@ -71,8 +81,10 @@ impl Builder {
             config,
             metrics,
             trusted_peer_change_rx,
+            banned_peers,
         } = self;
         let config = config.unwrap();
+        let banned_peers = banned_peers.unwrap_or_default();
         let metrics = metrics.unwrap_or_else(Metrics::disabled);
         let (sender, receiver) = oneshot::channel();
 
@@ -99,6 +111,7 @@ impl Builder {
                 shutdown_handle: receiver,
                 state,
                 trusted_peer_change_rx,
+                banned_peers,
                 metrics,
             },
             server,
@@ -113,6 +126,7 @@ pub struct UnstartedDiscovery {
     pub(super) shutdown_handle: oneshot::Receiver<()>,
     pub(super) state: Arc<RwLock<State>>,
     pub(super) trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
+    pub(super) banned_peers: BannedPeers,
     pub(super) metrics: Metrics,
 }
 
@@ -124,6 +138,7 @@ impl UnstartedDiscovery {
             shutdown_handle,
             state,
             trusted_peer_change_rx,
+            banned_peers,
             metrics,
         } = self;
 
@@ -152,6 +167,7 @@ impl UnstartedDiscovery {
                 shutdown_handle,
                 state,
                 trusted_peer_change_rx,
+                banned_peers,
                 metrics,
             },
             handle,
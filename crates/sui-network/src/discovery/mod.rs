@@ -27,12 +27,14 @@ const ONE_DAY_MILLISECONDS: u64 = 24 * 60 * 60 * 1_000;
 mod generated {
     include!(concat!(env!("OUT_DIR"), "/sui.Discovery.rs"));
 }
+mod banned_peers;
 mod builder;
 mod metrics;
 mod server;
 #[cfg(test)]
 mod tests;
 
+pub use banned_peers::BannedPeers;
 pub use builder::{Builder, Handle, UnstartedDiscovery};
 pub use generated::{
     discovery_client::DiscoveryClient,
@@ -83,6 +85,7 @@ struct DiscoveryEventLoop {
     shutdown_handle: oneshot::Receiver<()>,
     state: Arc<RwLock<State>>,
     trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
+    banned_peers: BannedPeers,
     metrics: Metrics,
 }
 
@@ -212,6 +215,11 @@ impl DiscoveryEventLoop {
     fn handle_peer_event(&mut self, peer_event: Result<PeerEvent, RecvError>) {
         match peer_event {
             Ok(PeerEvent::NewPeer(peer_id)) => {
+                if self.should_disconnect_banned_peer(&peer_id) {
+                    debug!(?peer_id, "Disconnecting from banned peer");
+                    let _ = self.network.disconnect(peer_id);
+                    return;
+                }
                 if let Some(peer) = self.network.peer(peer_id) {
                     self.state
                         .write()
@@ -242,6 +250,12 @@ impl DiscoveryEventLoop {
         }
     }
 
+    // Allowlisted and seed peers are redialed by anemo as soon as they are disconnected, so banned
+    // ones stay connected and are only avoided by the subsystems that banned them.
+    fn should_disconnect_banned_peer(&self, peer_id: &PeerId) -> bool {
+        self.banned_peers.is_banned(peer_id) && !self.allowlisted_peers.contains_key(peer_id)
+    }
+
     fn handle_tick(&mut self, _now: std::time::Instant, now_unix: u64) {
         self.update_our_info_timestamp(now_unix);
 
@@ -261,6 +275,14 @@ impl DiscoveryEventLoop {
             .known_peers
             .retain(|_k, v| now_unix.saturating_sub(v.timestamp_ms) < ONE_DAY_MILLISECONDS);
 
+        // Disconnect from peers that have been banned since we connected to them
+        for peer_id in self.network.peers() {
+            if self.should_disconnect_banned_peer(&peer_id) {
+                debug!(?peer_id, "Disconnecting from banned peer");
+                let _ = self.network.disconnect(peer_id);
+            }
+        }
+
         // Clean out the pending_dials
         self.pending_dials.retain(|_k, v| !v.is_finished());
         if let Some(abort_handle) = &self.dial_seed_peers_task {
@@ -280,6 +302,7 @@ impl DiscoveryEventLoop {
                 !info.addresses.is_empty() // Peer has addresses we can dial
                 && !state.connected_peers.contains_key(peer_id) // We're not already connected
                 && !self.pending_dials.contains_key(peer_id) // There is no pending dial to this node
+                && !self.banned_peers.is_banned(peer_id) // The peer isn't banned
             })
             .collect::<Vec<_>>();
 
@@ -19,9 +19,11 @@ use tokio::{
 
 use super::{
     metrics::Metrics,
+    peer_scores::PeerScores,
     server::{CheckpointContentsDownloadLimitLayer, Server},
     Handle, PeerHeights, StateSync, StateSyncEventLoop, StateSyncMessage, StateSyncServer,
 };
+use crate::discovery::BannedPeers;
 use sui_types::storage::WriteStore;
 
 pub struct Builder<S> {
@@ -29,6 +31,7 @@ pub struct Builder<S> {
     config: Option<StateSyncConfig>,
     metrics: Option<Metrics>,
     archive_readers: Option<ArchiveReaderBalancer>,
+    banned_peers: Option<BannedPeers>,
 }
 
 impl Builder<()> {
@@ -39,6 +42,7 @@ impl Builder<()> {
             config: None,
             metrics: None,
             archive_readers: None,
+            banned_peers: None,
         }
     }
 }
@@ -50,6 +54,7 @@ impl<S> Builder<S> {
             config: self.config,
             metrics: self.metrics,
             archive_readers: self.archive_readers,
+            banned_peers: self.banned_peers,
         }
     }
 
@@ -67,6 +72,13 @@ impl<S> Builder<S> {
         self.archive_readers = Some(archive_readers);
         self
     }
+
+    /// Share the list of peers banned by StateSync with Discovery, so that it disconnects from
+    /// them.
+    pub fn banned_peers(mut self, banned_peers: BannedPeers) -> Self {
+        self.banned_peers = Some(banned_peers);
+        self
+    }
 }
 
 impl<S> Builder<S>
@@ -128,11 +140,20 @@ where
             config,
             metrics,
             archive_readers,
+            banned_peers,
         } = self;
         let store = store.unwrap();
         let config = config.unwrap_or_default();
         let metrics = metrics.unwrap_or_else(Metrics::disabled);
         let archive_readers = archive_readers.unwrap_or_default();
+        let peer_scores = PeerScores::new(
+            banned_peers.unwrap_or_default(),
+            config.peer_ban_failure_threshold(),
+            config.peer_ban_duration(),
+            metrics.clone(),
+        )
+        .pipe(RwLock::new)
+        .pipe(Arc::new);
 
         let (sender, mailbox) = mpsc::channel(config.mailbox_capacity());
         let (checkpoint_event_sender, _receiver) =
@@ -141,6 +162,7 @@ where
         let handle = Handle {
             sender,
             checkpoint_event_sender: checkpoint_event_sender.clone(),
+            peer_scores: peer_scores.clone(),
         };
         let peer_heights = PeerHeights {
             peers: HashMap::new(),
@@ -165,6 +187,7 @@ where
                 store,
                 download_limit_layer: None,
                 peer_heights,
+                peer_scores,
                 checkpoint_event_sender,
                 metrics,
                 archive_readers,
@@ -181,6 +204,7 @@ pub struct UnstartedStateSync<S> {
     pub(super) download_limit_layer: Option<CheckpointContentsDownloadLimitLayer>,
     pub(super) store: S,
     pub(super) peer_heights: Arc<RwLock<PeerHeights>>,
+    pub(super) peer_scores: Arc<RwLock<PeerScores>>,
     pub(super) checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     pub(super) metrics: Metrics,
     pub(super) archive_readers: ArchiveReaderBalancer,
@@ -199,6 +223,7 @@ where
             download_limit_layer,
             store,
             peer_heights,
+            peer_scores,
             checkpoint_event_sender,
             metrics,
             archive_readers,
@@ -215,6 +240,7 @@ where
                 download_limit_layer,
                 store,
                 peer_heights,
+                peer_scores,
                 checkpoint_event_sender,
                 network,
                 metrics,
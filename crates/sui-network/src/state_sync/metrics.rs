@@ -1,8 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anemo::PeerId;
 use mysten_metrics::histogram::Histogram;
-use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
+use prometheus::{
+    register_gauge_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_with_registry, GaugeVec, IntCounter,
+    IntCounterVec, IntGauge, Registry,
+};
 use std::sync::Arc;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tap::Pipe;
@@ -45,6 +50,35 @@ impl Metrics {
         }
     }
 
+    pub fn set_peer_score(&self, peer_id: &PeerId, score: f64) {
+        if let Some(inner) = &self.0 {
+            inner
+                .peer_score
+                .with_label_values(&[&peer_id.to_string()])
+                .set(score);
+        }
+    }
+
+    pub fn remove_peer_score(&self, peer_id: &PeerId) {
+        if let Some(inner) = &self.0 {
+            let _ = inner
+                .peer_score
+                .remove_label_values(&[&peer_id.to_string()]);
+        }
+    }
+
+    pub fn inc_peer_request_failures(&self, kind: &str) {
+        if let Some(inner) = &self.0 {
+            inner.peer_request_failures.with_label_values(&[kind]).inc();
+        }
+    }
+
+    pub fn inc_peers_banned(&self) {
+        if let Some(inner) = &self.0 {
+            inner.peers_banned.inc();
+        }
+    }
+
     pub fn checkpoint_summary_age_metric(&self) -> Option<&Histogram> {
         if let Some(inner) = &self.0 {
             return Some(&inner.checkpoint_summary_age_ms);
@@ -58,6 +92,9 @@ struct Inner {
     highest_verified_checkpoint: IntGauge,
     highest_synced_checkpoint: IntGauge,
     checkpoint_summary_age_ms: Histogram,
+    peer_score: GaugeVec,
+    peer_request_failures: IntCounterVec,
+    peers_banned: IntCounter,
 }
 
 impl Inner {
@@ -89,6 +126,29 @@ impl Inner {
                 "Age of checkpoints summaries when they arrive and are verified.",
                 registry,
             ),
+
+            peer_score: register_gauge_vec_with_registry!(
+                "state_sync_peer_score",
+                "Score of each peer used to pick which peers to sync checkpoints from",
+                &["peer_id"],
+                registry
+            )
+            .unwrap(),
+
+            peer_request_failures: register_int_counter_vec_with_registry!(
+                "state_sync_peer_request_failures",
+                "Number of failed state sync requests to peers, by kind of failure",
+                &["kind"],
+                registry
+            )
+            .unwrap(),
+
+            peers_banned: register_int_counter_with_registry!(
+                "state_sync_peers_banned",
+                "Number of times a peer was banned for misbehaving",
+                registry
+            )
+            .unwrap(),
         }
         .pipe(Arc::new)
     }
@@ -49,12 +49,12 @@
 
 use anemo::{types::PeerEvent, PeerId, Request, Response, Result};
 use futures::{stream::FuturesOrdered, FutureExt, StreamExt};
-use rand::Rng;
+use rand::distributions::{Distribution, WeightedIndex};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::{
     collections::{HashMap, VecDeque},
     sync::{Arc, RwLock},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use sui_config::p2p::StateSyncConfig;
 use sui_types::{
@@ -80,6 +80,7 @@ mod generated {
 }
 mod builder;
 mod metrics;
+mod peer_scores;
 mod server;
 #[cfg(test)]
 mod tests;
@@ -89,12 +90,15 @@ pub use generated::{
     state_sync_client::StateSyncClient,
     state_sync_server::{StateSync, StateSyncServer},
 };
+pub use peer_scores::PeerScore;
 pub use server::GetCheckpointAvailabilityResponse;
 pub use server::GetCheckpointSummaryRequest;
 use sui_archival::reader::ArchiveReaderBalancer;
 use sui_storage::verify_checkpoint;
 
-use self::{metrics::Metrics, server::CheckpointContentsDownloadLimitLayer};
+use self::{
+    metrics::Metrics, peer_scores::PeerScores, server::CheckpointContentsDownloadLimitLayer,
+};
 
 /// A handle to the StateSync subsystem.
 ///
@@ -104,6 +108,7 @@ use self::{metrics::Metrics, server::CheckpointContentsDownloadLimitLayer};
 pub struct Handle {
     sender: mpsc::Sender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
+    peer_scores: Arc<RwLock<PeerScores>>,
 }
 
 impl Handle {
@@ -126,6 +131,20 @@ impl Handle {
     pub fn subscribe_to_synced_checkpoints(&self) -> broadcast::Receiver<VerifiedCheckpoint> {
         self.checkpoint_event_sender.subscribe()
     }
+
+    /// Scores of the connected peers we've made state sync requests to.
+    pub fn peer_scores(&self) -> HashMap<PeerId, PeerScore> {
+        self.peer_scores.read().unwrap().scores().clone()
+    }
+
+    /// Peers that are currently banned, along with the time left on their ban.
+    pub fn banned_peers(&self) -> Vec<(PeerId, Duration)> {
+        self.peer_scores
+            .read()
+            .unwrap()
+            .banned_peers()
+            .banned_peers()
+    }
 }
 
 struct PeerHeights {
@@ -267,10 +286,12 @@ impl PeerHeights {
     }
 }
 
-// PeerBalancer is an Iterator that selects peers based on RTT with some added randomness.
+// PeerBalancer is an Iterator that selects peers at random, weighted by their score. Banned peers
+// are never selected.
 #[derive(Clone)]
 struct PeerBalancer {
-    peers: VecDeque<(anemo::Peer, PeerStateSyncInfo)>,
+    peers: VecDeque<(anemo::Peer, PeerStateSyncInfo, f64)>,
+    peer_scores: Arc<RwLock<PeerScores>>,
     requested_checkpoint: Option<CheckpointSequenceNumber>,
     request_type: PeerCheckpointRequestType,
 }
@@ -285,20 +306,30 @@ impl PeerBalancer {
     pub fn new(
         network: &anemo::Network,
         peer_heights: Arc<RwLock<PeerHeights>>,
+        peer_scores: Arc<RwLock<PeerScores>>,
         request_type: PeerCheckpointRequestType,
     ) -> Self {
-        let mut peers: Vec<_> = peer_heights
+        let peers: Vec<_> = peer_heights
             .read()
             .unwrap()
             .peers_on_same_chain()
             // Filter out any peers who we aren't connected with.
             .filter_map(|(peer_id, info)| network.peer(*peer_id).map(|peer| (peer, *info)))
             .collect();
-        peers.sort_by(|(peer_a, _), (peer_b, _)| {
-            peer_a.connection_rtt().cmp(&peer_b.connection_rtt())
-        });
+        let peers = {
+            let scores = peer_scores.read().unwrap();
+            peers
+                .into_iter()
+                .filter(|(peer, _info)| !scores.is_banned(&peer.peer_id()))
+                .map(|(peer, info)| {
+                    let weight = scores.selection_weight(&peer.peer_id(), peer.connection_rtt());
+                    (peer, info, weight)
+                })
+                .collect()
+        };
         Self {
-            peers: peers.into(),
+            peers,
+            peer_scores,
             requested_checkpoint: None,
             request_type,
         }
@@ -308,6 +339,10 @@ impl PeerBalancer {
         self.requested_checkpoint = Some(checkpoint);
         self
     }
+
+    pub fn peer_scores(&self) -> &Arc<RwLock<PeerScores>> {
+        &self.peer_scores
+    }
 }
 
 impl Iterator for PeerBalancer {
@@ -315,10 +350,10 @@ impl Iterator for PeerBalancer {
 
     fn next(&mut self) -> Option<Self::Item> {
         while !self.peers.is_empty() {
-            const SELECTION_WINDOW: usize = 2;
-            let idx =
-                rand::thread_rng().gen_range(0..std::cmp::min(SELECTION_WINDOW, self.peers.len()));
-            let (peer, info) = self.peers.remove(idx).unwrap();
+            let idx = WeightedIndex::new(self.peers.iter().map(|(_peer, _info, weight)| *weight))
+                .map(|weights| weights.sample(&mut rand::thread_rng()))
+                .unwrap_or(0);
+            let (peer, info, _weight) = self.peers.remove(idx).unwrap();
             let requested_checkpoint = self.requested_checkpoint.unwrap_or(0);
             match &self.request_type {
                 // Summary will never be pruned
@@ -364,6 +399,7 @@ struct StateSyncEventLoop<S> {
 
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_scores: Arc<RwLock<PeerScores>>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     network: anemo::Network,
     metrics: Metrics,
@@ -412,6 +448,7 @@ where
             self.network.clone(),
             self.store.clone(),
             self.peer_heights.clone(),
+            self.peer_scores.clone(),
             self.weak_sender.clone(),
             self.checkpoint_event_sender.clone(),
             self.config.checkpoint_content_download_concurrency(),
@@ -607,6 +644,7 @@ where
             }
             Ok(PeerEvent::LostPeer(peer_id, _)) => {
                 self.peer_heights.write().unwrap().peers.remove(&peer_id);
+                self.peer_scores.write().unwrap().remove(&peer_id);
             }
 
             Err(RecvError::Closed) => {
@@ -679,6 +717,7 @@ where
                 self.network.clone(),
                 self.store.clone(),
                 self.peer_heights.clone(),
+                self.peer_scores.clone(),
                 self.metrics.clone(),
                 self.config.pinned_checkpoints.clone(),
                 self.config.checkpoint_header_download_concurrency(),
@@ -936,6 +975,7 @@ async fn sync_to_checkpoint<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_scores: Arc<RwLock<PeerScores>>,
     metrics: Metrics,
     pinned_checkpoints: Vec<(CheckpointSequenceNumber, CheckpointDigest)>,
     checkpoint_header_download_concurrency: usize,
@@ -962,6 +1002,7 @@ where
     let peer_balancer = PeerBalancer::new(
         &network,
         peer_heights.clone(),
+        peer_scores.clone(),
         PeerCheckpointRequestType::Summary,
     );
     // range of the next sequence_numbers to fetch
@@ -970,6 +1011,7 @@ where
         .map(|next| {
             let peers = peer_balancer.clone().with_checkpoint(next);
             let peer_heights = peer_heights.clone();
+            let peer_scores = peer_scores.clone();
             let pinned_checkpoints = &pinned_checkpoints;
             async move {
                 if let Some(checkpoint) = peer_heights
@@ -983,12 +1025,20 @@ where
                 // Iterate through peers trying each one in turn until we're able to
                 // successfully get the target checkpoint
                 for mut peer in peers {
+                    let peer_id = peer.inner().peer_id();
                     let request = Request::new(GetCheckpointSummaryRequest::BySequenceNumber(next))
                         .with_timeout(timeout);
+                    let start = Instant::now();
                     if let Some(checkpoint) = peer
                         .get_checkpoint_summary(request)
                         .await
-                        .tap_err(|e| trace!("{e:?}"))
+                        .tap_err(|e| {
+                            trace!("{e:?}");
+                            peer_scores
+                                .write()
+                                .unwrap()
+                                .record_failure(peer_id, start.elapsed() >= timeout);
+                        })
                         .ok()
                         .and_then(Response::into_inner)
                         .tap_none(|| trace!("peer unable to help sync"))
@@ -999,6 +1049,7 @@ where
                                 "peer returned checkpoint with wrong sequence number: expected {next}, got {}",
                                 checkpoint.sequence_number()
                             );
+                            peer_scores.write().unwrap().record_verification_failure(peer_id);
                             continue;
                         }
 
@@ -1014,16 +1065,21 @@ where
                                     pinned_checkpoints[pinned_digest_index].1,
                                     checkpoint_digest
                                 );
+                                peer_scores.write().unwrap().record_verification_failure(peer_id);
                                 continue;
                             }
                         }
 
+                        peer_scores
+                            .write()
+                            .unwrap()
+                            .record_success(peer_id, start.elapsed(), None);
                         // Insert in our store in the event that things fail and we need to retry
                         peer_heights
                             .write()
                             .unwrap()
                             .insert_checkpoint(checkpoint.clone());
-                        return (Some(checkpoint), next, Some(peer.inner().peer_id()));
+                        return (Some(checkpoint), next, Some(peer_id));
                     }
                 }
                 (None, next, None)
@@ -1050,6 +1106,13 @@ where
             match verify_checkpoint(&current, &store, checkpoint) {
                 Ok(verified_checkpoint) => verified_checkpoint,
                 Err(checkpoint) => {
+                    if let Some(peer_id) = maybe_peer_id {
+                        peer_scores
+                            .write()
+                            .unwrap()
+                            .record_verification_failure(peer_id);
+                    }
+
                     let mut peer_heights = peer_heights.write().unwrap();
                     // Remove the checkpoint from our temporary store so that we can try querying
                     // another peer for a different one
@@ -1155,6 +1218,7 @@ async fn sync_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_scores: Arc<RwLock<PeerScores>>,
     sender: mpsc::WeakSender<StateSyncMessage>,
     checkpoint_event_sender: broadcast::Sender<VerifiedCheckpoint>,
     checkpoint_content_download_concurrency: usize,
@@ -1225,6 +1289,7 @@ async fn sync_checkpoint_contents<S>(
                             network.clone(),
                             &store,
                             peer_heights.clone(),
+                            peer_scores.clone(),
                             timeout,
                             checkpoint,
                         ));
@@ -1258,6 +1323,7 @@ async fn sync_checkpoint_contents<S>(
                 network.clone(),
                 &store,
                 peer_heights.clone(),
+                peer_scores.clone(),
                 timeout,
                 next_checkpoint,
             ));
@@ -1279,6 +1345,7 @@ async fn sync_one_checkpoint_contents<S>(
     network: anemo::Network,
     store: S,
     peer_heights: Arc<RwLock<PeerHeights>>,
+    peer_scores: Arc<RwLock<PeerScores>>,
     timeout: Duration,
     checkpoint: VerifiedCheckpoint,
 ) -> Result<(VerifiedCheckpoint, u64), VerifiedCheckpoint>
@@ -1289,6 +1356,7 @@ where
     let peers = PeerBalancer::new(
         &network,
         peer_heights.clone(),
+        peer_scores,
         PeerCheckpointRequestType::Content,
     )
     .with_checkpoint(*checkpoint.sequence_number());
@@ -1333,23 +1401,41 @@ where
 
     // Iterate through our selected peers trying each one in turn until we're able to
     // successfully get the target checkpoint
+    let peer_scores = peers.peer_scores().clone();
     for mut peer in peers {
+        let peer_id = peer.inner().peer_id();
         let request = Request::new(digest).with_timeout(timeout);
+        let start = Instant::now();
         if let Some(contents) = peer
             .get_checkpoint_contents(request)
             .await
-            .tap_err(|e| trace!("{e:?}"))
+            .tap_err(|e| {
+                trace!("{e:?}");
+                peer_scores
+                    .write()
+                    .unwrap()
+                    .record_failure(peer_id, start.elapsed() >= timeout);
+            })
             .ok()
             .and_then(Response::into_inner)
             .tap_none(|| trace!("peer unable to help sync"))
         {
             if contents.verify_digests(digest).is_ok() {
+                peer_scores.write().unwrap().record_success(
+                    peer_id,
+                    start.elapsed(),
+                    Some(contents.size() as u64),
+                );
                 let verified_contents = VerifiedCheckpointContents::new_unchecked(contents.clone());
                 store
                     .insert_checkpoint_contents(checkpoint, verified_contents)
                     .expect("store operation should not fail");
                 return Some(contents);
             }
+            peer_scores
+                .write()
+                .unwrap()
+                .record_verification_failure(peer_id);
         }
     }
     None
@@ -0,0 +1,200 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anemo::PeerId;
+use std::{collections::HashMap, time::Duration};
+use tracing::info;
+
+use super::metrics::Metrics;
+use crate::discovery::BannedPeers;
+
+/// Weight given to the newest sample in the moving averages kept for each peer.
+const EWMA_WEIGHT: f64 = 0.2;
+
+/// Throughput, in transactions per second, that neither raises nor lowers a peer's score. Peers we
+/// haven't downloaded checkpoint contents from are assumed to have this throughput.
+const REFERENCE_THROUGHPUT: f64 = 100.0;
+
+/// Lowest possible score, so that peers that aren't banned always have some chance of being
+/// picked and can recover from a bad streak.
+const MIN_SCORE: f64 = 0.001;
+
+/// Statistics on the requests made to a peer while syncing checkpoints.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PeerScore {
+    /// Moving average of the latency of successful requests.
+    pub latency: Option<Duration>,
+    /// Moving average of the throughput of checkpoint contents downloads, in transactions per
+    /// second.
+    pub throughput: Option<f64>,
+    /// Moving average of the fraction of requests that succeeded.
+    pub reliability: f64,
+    pub successes: u64,
+    pub timeouts: u64,
+    pub errors: u64,
+    pub verification_failures: u64,
+    /// Number of requests that failed since the last one that succeeded.
+    pub consecutive_failures: u32,
+}
+
+impl Default for PeerScore {
+    fn default() -> Self {
+        Self {
+            latency: None,
+            throughput: None,
+            reliability: 1.0,
+            successes: 0,
+            timeouts: 0,
+            errors: 0,
+            verification_failures: 0,
+            consecutive_failures: 0,
+        }
+    }
+}
+
+impl PeerScore {
+    /// Combines the latency, throughput and reliability of the peer into a score between
+    /// `MIN_SCORE` and 1, higher is better.
+    pub fn score(&self) -> f64 {
+        self.score_with_fallback_latency(Duration::ZERO)
+    }
+
+    fn score_with_fallback_latency(&self, fallback_latency: Duration) -> f64 {
+        let latency = self.latency.unwrap_or(fallback_latency).as_secs_f64();
+        let throughput = self.throughput.unwrap_or(REFERENCE_THROUGHPUT);
+        // Between 0 and 2, and 1 at the reference throughput.
+        let throughput_factor = 2.0 * throughput / (throughput + REFERENCE_THROUGHPUT);
+        (self.reliability * throughput_factor / (1.0 + latency)).clamp(MIN_SCORE, 1.0)
+    }
+}
+
+fn ewma(average: Option<f64>, sample: f64) -> f64 {
+    match average {
+        Some(average) => average + EWMA_WEIGHT * (sample - average),
+        None => sample,
+    }
+}
+
+/// Scores the peers we sync checkpoints from, and temporarily bans the ones that keep failing or
+/// serve data that fails verification.
+#[derive(Debug)]
+pub(super) struct PeerScores {
+    scores: HashMap<PeerId, PeerScore>,
+    banned_peers: BannedPeers,
+    ban_failure_threshold: u32,
+    ban_duration: Duration,
+    metrics: Metrics,
+}
+
+impl PeerScores {
+    pub fn new(
+        banned_peers: BannedPeers,
+        ban_failure_threshold: u32,
+        ban_duration: Duration,
+        metrics: Metrics,
+    ) -> Self {
+        Self {
+            scores: HashMap::new(),
+            banned_peers,
+            ban_failure_threshold,
+            ban_duration,
+            metrics,
+        }
+    }
+
+    pub fn get(&self, peer_id: &PeerId) -> PeerScore {
+        self.scores.get(peer_id).copied().unwrap_or_default()
+    }
+
+    pub fn scores(&self) -> &HashMap<PeerId, PeerScore> {
+        &self.scores
+    }
+
+    pub fn banned_peers(&self) -> &BannedPeers {
+        &self.banned_peers
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.banned_peers.is_banned(peer_id)
+    }
+
+    /// Weight used to pick `peer_id` among the peers able to serve a request. The connection RTT
+    /// stands in for the latency of peers we haven't made a successful request to yet.
+    pub fn selection_weight(&self, peer_id: &PeerId, connection_rtt: Duration) -> f64 {
+        self.get(peer_id)
+            .score_with_fallback_latency(connection_rtt)
+    }
+
+    /// Records a successful request to `peer_id`, along with the number of transactions that were
+    /// downloaded if the request was for checkpoint contents.
+    pub fn record_success(&mut self, peer_id: PeerId, latency: Duration, num_txns: Option<u64>) {
+        let score = self.scores.entry(peer_id).or_default();
+        score.successes += 1;
+        score.consecutive_failures = 0;
+        score.reliability = ewma(Some(score.reliability), 1.0);
+        score.latency = Some(Duration::from_secs_f64(ewma(
+            score.latency.map(|latency| latency.as_secs_f64()),
+            latency.as_secs_f64(),
+        )));
+        if let Some(num_txns) = num_txns {
+            let throughput = num_txns as f64 / latency.as_secs_f64().max(f64::EPSILON);
+            score.throughput = Some(ewma(score.throughput, throughput));
+        }
+        self.update_metrics(peer_id);
+    }
+
+    /// Records a request to `peer_id` that timed out or failed, banning the peer once it has
+    /// failed too many requests in a row.
+    pub fn record_failure(&mut self, peer_id: PeerId, timed_out: bool) {
+        let score = self.scores.entry(peer_id).or_default();
+        if timed_out {
+            score.timeouts += 1;
+            self.metrics.inc_peer_request_failures("timeout");
+        } else {
+            score.errors += 1;
+            self.metrics.inc_peer_request_failures("error");
+        }
+        score.consecutive_failures += 1;
+        score.reliability = ewma(Some(score.reliability), 0.0);
+
+        if score.consecutive_failures >= self.ban_failure_threshold {
+            let consecutive_failures = score.consecutive_failures;
+            score.consecutive_failures = 0;
+            self.ban(
+                peer_id,
+                &format!("{consecutive_failures} consecutive failed requests"),
+            );
+        }
+        self.update_metrics(peer_id);
+    }
+
+    /// Records that `peer_id` served data that failed verification, and bans it right away.
+    pub fn record_verification_failure(&mut self, peer_id: PeerId) {
+        let score = self.scores.entry(peer_id).or_default();
+        score.verification_failures += 1;
+        score.consecutive_failures = 0;
+        score.reliability = ewma(Some(score.reliability), 0.0);
+        self.metrics.inc_peer_request_failures("verification");
+        self.ban(peer_id, "serving data that failed verification");
+        self.update_metrics(peer_id);
+    }
+
+    pub fn remove(&mut self, peer_id: &PeerId) {
+        self.scores.remove(peer_id);
+        self.metrics.remove_peer_score(peer_id);
+    }
+
+    fn ban(&mut self, peer_id: PeerId, reason: &str) {
+        info!(
+            ?peer_id,
+            "Banning peer from state sync for {:?}: {reason}", self.ban_duration
+        );
+        self.banned_peers.ban(peer_id, self.ban_duration);
+        self.metrics.inc_peers_banned();
+    }
+
+    fn update_metrics(&self, peer_id: PeerId) {
+        self.metrics
+            .set_peer_score(&peer_id, self.get(&peer_id).score());
+    }
+}
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    discovery::BannedPeers,
     state_sync::{
-        Builder, GetCheckpointSummaryRequest, PeerStateSyncInfo, StateSync, StateSyncMessage,
-        UnstartedStateSync,
+        metrics::Metrics, peer_scores::PeerScores, Builder, GetCheckpointSummaryRequest, PeerScore,
+        PeerStateSyncInfo, StateSync, StateSyncMessage, UnstartedStateSync,
     },
     utils::build_network,
 };
@@ -920,3 +921,134 @@ async fn sync_with_checkpoints_watermark() {
         &last_checkpoint_seq
     );
 }
+
+#[tokio::test]
+async fn banned_peers_are_not_used_for_sync() {
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    // build mock data
+    let (ordered_checkpoints, _, _, _) = committee.make_empty_checkpoints(10, None);
+
+    // Build and connect two nodes
+    let banned_peers = BannedPeers::new();
+    let (builder, server) = Builder::new()
+        .store(SharedInMemoryStore::default())
+        .banned_peers(banned_peers.clone())
+        .build();
+    let network_1 = build_network(|router| router.add_rpc_service(server));
+    let (mut event_loop_1, handle_1) = builder.build(network_1.clone());
+    let (builder, server) = Builder::new().store(SharedInMemoryStore::default()).build();
+    let network_2 = build_network(|router| router.add_rpc_service(server));
+    let (event_loop_2, _handle_2) = builder.build(network_2.clone());
+    network_1.connect(network_2.local_addr()).await.unwrap();
+
+    // Init the root committee in both nodes
+    for store in [&event_loop_1.store, &event_loop_2.store] {
+        store.inner_mut().insert_genesis_state(
+            ordered_checkpoints.first().cloned().unwrap(),
+            empty_contents(),
+            committee.committee().to_owned(),
+        );
+    }
+
+    // Node 2 will have all the data, and Node 1 will know about it
+    {
+        let mut store = event_loop_2.store.inner_mut();
+        for checkpoint in ordered_checkpoints.clone() {
+            store.insert_checkpoint(&checkpoint);
+        }
+    }
+    event_loop_1.peer_heights.write().unwrap().peers.insert(
+        network_2.peer_id(),
+        PeerStateSyncInfo {
+            genesis_checkpoint_digest: *ordered_checkpoints[0].digest(),
+            on_same_chain_as_us: true,
+            height: *ordered_checkpoints.last().unwrap().sequence_number(),
+            lowest: 0,
+        },
+    );
+    event_loop_1
+        .peer_heights
+        .write()
+        .unwrap()
+        .insert_checkpoint(ordered_checkpoints.last().cloned().unwrap().into_inner());
+
+    // Node 2 is banned, so Node 1 can't sync from it
+    banned_peers.ban(network_2.peer_id(), Duration::from_secs(60));
+    assert_eq!(handle_1.banned_peers()[0].0, network_2.peer_id());
+    event_loop_1.maybe_start_checkpoint_summary_sync_task();
+    event_loop_1.tasks.join_next().await.unwrap().unwrap();
+    assert_eq!(
+        event_loop_1
+            .store
+            .get_highest_verified_checkpoint()
+            .unwrap()
+            .sequence_number(),
+        ordered_checkpoints.first().unwrap().sequence_number()
+    );
+
+    // Once the ban is lifted Node 1 syncs from Node 2, and scores it.
+    banned_peers.unban(&network_2.peer_id());
+    event_loop_1.sync_checkpoint_summaries_task = None;
+    event_loop_1.maybe_start_checkpoint_summary_sync_task();
+    event_loop_1.tasks.join_next().await.unwrap().unwrap();
+    assert_eq!(
+        event_loop_1
+            .store
+            .get_highest_verified_checkpoint()
+            .unwrap()
+            .sequence_number(),
+        ordered_checkpoints.last().unwrap().sequence_number()
+    );
+    // Genesis is already known and the target checkpoint was cached, so the rest was downloaded.
+    let score = handle_1.peer_scores()[&network_2.peer_id()];
+    assert_eq!(score.successes, ordered_checkpoints.len() as u64 - 2);
+    assert!(score.latency.is_some());
+    assert!(handle_1.banned_peers().is_empty());
+}
+
+#[test]
+fn peer_scores_ban_misbehaving_peers() {
+    let banned_peers = BannedPeers::new();
+    let mut scores = PeerScores::new(
+        banned_peers.clone(),
+        3,
+        Duration::from_secs(60),
+        Metrics::disabled(),
+    );
+    let fast_peer = PeerId([1; 32]);
+    let slow_peer = PeerId([2; 32]);
+    let flaky_peer = PeerId([3; 32]);
+    let malicious_peer = PeerId([4; 32]);
+
+    // Faster peers score better, and peers we know nothing about get the benefit of the doubt.
+    scores.record_success(fast_peer, Duration::from_millis(10), Some(1_000));
+    scores.record_success(slow_peer, Duration::from_secs(2), Some(10));
+    assert!(scores.get(&fast_peer).score() > scores.get(&slow_peer).score());
+    assert_eq!(scores.get(&flaky_peer).score(), 1.0);
+    assert!(
+        scores.selection_weight(&flaky_peer, Duration::from_millis(10))
+            > scores.selection_weight(&flaky_peer, Duration::from_secs(1))
+    );
+
+    // Peers are banned once they fail too many requests in a row.
+    scores.record_failure(flaky_peer, true);
+    scores.record_failure(flaky_peer, false);
+    scores.record_success(flaky_peer, Duration::from_millis(10), None);
+    scores.record_failure(flaky_peer, true);
+    scores.record_failure(flaky_peer, true);
+    assert!(!scores.is_banned(&flaky_peer));
+    assert!(scores.get(&flaky_peer).score() < scores.get(&fast_peer).score());
+    scores.record_failure(flaky_peer, true);
+    assert!(scores.is_banned(&flaky_peer));
+    let score = scores.get(&flaky_peer);
+    assert_eq!((score.timeouts, score.errors, score.successes), (4, 1, 1));
+
+    // Serving data that fails verification gets a peer banned right away.
+    scores.record_verification_failure(malicious_peer);
+    assert!(banned_peers.is_banned(&malicious_peer));
+    assert!(!banned_peers.is_banned(&fast_peer));
+    assert_eq!(banned_peers.banned_peers().len(), 2);
+
+    scores.remove(&fast_peer);
+    assert_eq!(scores.get(&fast_peer), PeerScore::default());
+}
@@ -39,6 +39,10 @@ use tracing::info;
 //
 //   $ curl 'http://127.0.0.1:1337/node-config'
 //
+// View the scores of the peers used by state sync, and the peers that are currently banned:
+//
+//   $ curl 'http://127.0.0.1:1337/peers'
+//
 // Set a time-limited tracing config. After the duration expires, tracing will be disabled
 // automatically.
 //
@@ -56,6 +60,7 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const PEERS: &str = "/peers";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -74,6 +79,7 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(PEERS, get(peers))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -216,6 +222,25 @@ async fn node_config(State(state): State<Arc<AppState>>) -> (StatusCode, String)
     (StatusCode::OK, format!("{:#?}\n", node_config))
 }
 
+async fn peers(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let state_sync = &state.node.state_sync;
+    let mut scores: Vec<_> = state_sync.peer_scores().into_iter().collect();
+    scores.sort_by(|(_, a), (_, b)| b.score().total_cmp(&a.score()));
+
+    let mut output = String::new();
+    for (peer_id, score) in &scores {
+        output.push_str(&format!(
+            "{peer_id}: score {:.3}, {score:?}\n",
+            score.score()
+        ));
+    }
+    for (peer_id, remaining) in state_sync.banned_peers() {
+        output.push_str(&format!("{peer_id}: banned for {remaining:?}\n"));
+    }
+
+    (StatusCode::OK, output)
+}
+
 #[derive(Deserialize)]
 struct Epoch {
     epoch: u64,
@@ -43,6 +43,7 @@ use checkpoint_executor::CheckpointExecutor;
 use fastcrypto_zkp::bn254::zk_login::JWK;
 pub use handle::SuiNodeHandle;
 use mysten_metrics::{spawn_monitored_task, RegistryService};
+use mysten_network::fault_injection::{self, FaultInjectionLayer};
 use mysten_network::server::ServerBuilder;
 use narwhal_network::metrics::MetricsMakeCallbackHandler;
 use narwhal_network::metrics::{NetworkConnectionMetrics, NetworkMetrics};
@@ -438,6 +439,7 @@ impl SuiNode {
             config
                 .expensive_safety_check_config
                 .enable_epoch_sui_conservation_check(),
+            config.execution_cache_config,
             &prometheus_registry,
         )
         .await?;
@@ -502,12 +504,13 @@ impl SuiNode {
         );
 
         let index_store = if is_full_node && config.enable_index_processing {
-            Some(Arc::new(IndexStore::new(
+            Some(Arc::new(IndexStore::new_with_event_retention(
                 config.db_path().join("indexes"),
                 &prometheus_registry,
                 epoch_store
                     .protocol_config()
                     .max_move_identifier_len_as_option(),
+                config.event_index_retention_secs.map(Duration::from_secs),
             )))
         } else {
             None
@@ -863,15 +866,18 @@ impl SuiNode {
         archive_readers: ArchiveReaderBalancer,
         prometheus_registry: &Registry,
     ) -> Result<(Network, discovery::Handle, state_sync::Handle)> {
+        let banned_peers = discovery::BannedPeers::new();
         let (state_sync, state_sync_server) = state_sync::Builder::new()
             .config(config.p2p_config.state_sync.clone().unwrap_or_default())
             .store(state_sync_store)
             .archive_readers(archive_readers)
+            .banned_peers(banned_peers.clone())
             .with_metrics(prometheus_registry)
             .build();
 
         let (discovery, discovery_server) = discovery::Builder::new(trusted_peer_change_rx)
             .config(config.p2p_config.clone())
+            .banned_peers(banned_peers)
             .build();
 
         let p2p_network = {
@@ -894,6 +900,7 @@ impl SuiNode {
                     Arc::new(inbound_network_metrics),
                     config.p2p_config.excessive_message_size(),
                 )))
+                .layer(FaultInjectionLayer::anemo())
                 .service(routes);
 
             let outbound_layer = ServiceBuilder::new()
@@ -953,6 +960,7 @@ impl SuiNode {
                 .config(anemo_config)
                 .outbound_request_layer(outbound_layer)
                 .start(service)?;
+            fault_injection::register_peer(network.peer_id());
             info!(
                 server_name = server_name,
                 "P2p network started on {}",
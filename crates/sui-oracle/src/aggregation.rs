@@ -0,0 +1,199 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{AggregationConfig, AggregationMethod};
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Rejection {
+    Stale,
+    Outlier,
+}
+
+impl Rejection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rejection::Stale => "stale",
+            Rejection::Outlier => "outlier",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Aggregate<'a> {
+    pub value: f64,
+    pub num_sources: usize,
+    pub rejected: Vec<(&'a str, Rejection)>,
+}
+
+/// Aggregates the latest value of each data source, given as `(source, value, age)`. Values
+/// older than `max_staleness` and outliers are rejected first.
+pub(crate) fn aggregate<'a>(
+    values: impl IntoIterator<Item = (&'a str, f64, Duration)>,
+    config: &AggregationConfig,
+    max_staleness: Duration,
+) -> anyhow::Result<Aggregate<'a>> {
+    let mut rejected = vec![];
+    let mut fresh = vec![];
+    for (source, value, age) in values {
+        if age > max_staleness {
+            rejected.push((source, Rejection::Stale));
+        } else {
+            fresh.push((source, value));
+        }
+    }
+
+    if let Some(max_deviation) = config.max_deviation_from_median {
+        let mut sorted: Vec<_> = fresh.iter().map(|(_, value)| *value).collect();
+        sorted.sort_by(f64::total_cmp);
+        if let Some(median) = median(&sorted) {
+            fresh.retain(|(source, value)| {
+                let is_outlier = deviation(median, *value) > max_deviation;
+                if is_outlier {
+                    rejected.push((*source, Rejection::Outlier));
+                }
+                !is_outlier
+            });
+        }
+    }
+
+    if fresh.len() < config.min_sources.max(1) {
+        anyhow::bail!(
+            "Only {} usable values, at least {} are needed. Rejected: {:?}",
+            fresh.len(),
+            config.min_sources.max(1),
+            rejected
+        );
+    }
+
+    let mut sorted: Vec<_> = fresh.iter().map(|(_, value)| *value).collect();
+    sorted.sort_by(f64::total_cmp);
+    let value = match config.method {
+        AggregationMethod::Median => median(&sorted),
+        AggregationMethod::TrimmedMean => trimmed_mean(&sorted, config.trim_fraction),
+    }
+    .expect("There is at least one value");
+    Ok(Aggregate {
+        value,
+        num_sources: sorted.len(),
+        rejected,
+    })
+}
+
+/// Whether `value` should be submitted, given the last submitted value and the time elapsed since.
+pub(crate) fn should_submit(
+    last_submitted: Option<(f64, Duration)>,
+    value: f64,
+    deviation_threshold: Option<f64>,
+    heartbeat_interval: Option<Duration>,
+) -> bool {
+    let Some((last_value, elapsed)) = last_submitted else {
+        return true;
+    };
+    if deviation_threshold.is_none() && heartbeat_interval.is_none() {
+        return true;
+    }
+    deviation_threshold.is_some_and(|threshold| deviation(last_value, value) > threshold)
+        || heartbeat_interval.is_some_and(|heartbeat| elapsed >= heartbeat)
+}
+
+fn deviation(reference: f64, value: f64) -> f64 {
+    if reference == 0.0 {
+        if value == 0.0 {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        ((value - reference) / reference).abs()
+    }
+}
+
+fn median(sorted: &[f64]) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+fn trimmed_mean(sorted: &[f64], trim_fraction: f64) -> Option<f64> {
+    let trimmed = (sorted.len() as f64 * trim_fraction.clamp(0.0, 0.5)).floor() as usize;
+    let kept = &sorted[trimmed..sorted.len() - trimmed];
+    if kept.is_empty() {
+        return median(sorted);
+    }
+    Some(kept.iter().sum::<f64>() / kept.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRESH: Duration = Duration::from_secs(1);
+    const STALE: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_aggregate() {
+        let mut config = AggregationConfig::default();
+        let values = [
+            ("a", 10.0, FRESH),
+            ("b", 11.0, FRESH),
+            ("c", 12.0, FRESH),
+            ("d", 100.0, FRESH),
+            ("e", 1.0, STALE),
+        ];
+
+        let aggregate = super::aggregate(values, &config, Duration::from_secs(10)).unwrap();
+        assert_eq!(aggregate.value, 11.5);
+        assert_eq!(aggregate.num_sources, 4);
+        assert_eq!(aggregate.rejected, vec![("e", Rejection::Stale)]);
+
+        config.max_deviation_from_median = Some(0.2);
+        let aggregate = super::aggregate(values, &config, Duration::from_secs(10)).unwrap();
+        assert_eq!(aggregate.value, 11.0);
+        assert_eq!(
+            aggregate.rejected,
+            vec![("e", Rejection::Stale), ("d", Rejection::Outlier)]
+        );
+
+        config.max_deviation_from_median = None;
+        config.method = AggregationMethod::TrimmedMean;
+        config.trim_fraction = 0.25;
+        let aggregate = super::aggregate(values, &config, Duration::from_secs(10)).unwrap();
+        assert_eq!(aggregate.value, 11.5);
+
+        config.min_sources = 5;
+        assert!(super::aggregate(values, &config, Duration::from_secs(10)).is_err());
+        assert!(super::aggregate(vec![], &AggregationConfig::default(), FRESH).is_err());
+    }
+
+    #[test]
+    fn test_should_submit() {
+        let elapsed = Duration::from_secs(5);
+        assert!(should_submit(None, 10.0, Some(0.01), None));
+        assert!(should_submit(Some((10.0, elapsed)), 10.0, None, None));
+
+        assert!(!should_submit(
+            Some((10.0, elapsed)),
+            10.05,
+            Some(0.01),
+            None
+        ));
+        assert!(should_submit(Some((10.0, elapsed)), 10.2, Some(0.01), None));
+        assert!(should_submit(Some((10.0, elapsed)), 9.8, Some(0.01), None));
+
+        let heartbeat = Some(Duration::from_secs(10));
+        assert!(!should_submit(Some((10.0, elapsed)), 10.0, None, heartbeat));
+        assert!(should_submit(
+            Some((10.0, Duration::from_secs(10))),
+            10.0,
+            Some(0.01),
+            heartbeat
+        ));
+    }
+}
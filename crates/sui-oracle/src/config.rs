@@ -11,18 +11,107 @@ use sui_types::base_types::ObjectID;
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct DataSourceConfig {
+    /// Name of the source in logs and metrics, defaults to its url.
+    #[serde(default)]
+    pub name: Option<String>,
     pub url: String,
     pub json_path: String,
 }
 
+impl DataSourceConfig {
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.url)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct UploadFeedConfig {
+    /// How often the data sources are polled.
     pub submission_interval: Duration,
-    pub data_source_config: DataSourceConfig,
+    /// Single data source of the feed, kept for compatibility with `data-source-configs`.
+    #[serde(default)]
+    pub data_source_config: Option<DataSourceConfig>,
+    /// Data sources whose values are aggregated into the value of the feed.
+    #[serde(default)]
+    pub data_source_configs: Vec<DataSourceConfig>,
+    #[serde(default)]
+    pub aggregation: AggregationConfig,
+    /// Submit a new value when it deviates from the last submitted one by more than this
+    /// fraction, e.g. 0.005 for 0.5%.
+    #[serde(default)]
+    pub deviation_threshold: Option<f64>,
+    /// Submit the value at least this often, even if it didn't deviate. When neither this nor
+    /// `deviation-threshold` is set, the value is submitted every `submission-interval`.
+    #[serde(default)]
+    pub heartbeat_interval: Option<Duration>,
     pub upload_parameters: UploadParameters,
 }
 
+impl UploadFeedConfig {
+    pub fn data_sources(&self) -> impl Iterator<Item = &DataSourceConfig> {
+        self.data_source_config
+            .iter()
+            .chain(self.data_source_configs.iter())
+    }
+
+    /// Values retrieved longer ago than this are not aggregated.
+    pub fn max_staleness(&self) -> Duration {
+        self.aggregation
+            .max_staleness
+            .unwrap_or(self.submission_interval)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AggregationMethod {
+    #[default]
+    Median,
+    /// Mean of the values, once `trim-fraction` of the lowest and of the highest values have
+    /// been dropped.
+    TrimmedMean,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AggregationConfig {
+    #[serde(default)]
+    pub method: AggregationMethod,
+    #[serde(default = "default_trim_fraction")]
+    pub trim_fraction: f64,
+    /// Defaults to the submission interval of the feed.
+    #[serde(default)]
+    pub max_staleness: Option<Duration>,
+    /// Values that deviate from the median of all values by more than this fraction are
+    /// rejected as outliers.
+    #[serde(default)]
+    pub max_deviation_from_median: Option<f64>,
+    /// Minimum number of values left after rejecting stale values and outliers.
+    #[serde(default = "default_min_sources")]
+    pub min_sources: usize,
+}
+
+impl Default for AggregationConfig {
+    fn default() -> Self {
+        Self {
+            method: AggregationMethod::default(),
+            trim_fraction: default_trim_fraction(),
+            max_staleness: None,
+            max_deviation_from_median: None,
+            min_sources: default_min_sources(),
+        }
+    }
+}
+
+fn default_trim_fraction() -> f64 {
+    0.2
+}
+
+fn default_min_sources() -> usize {
+    1
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct UploadParameters {
@@ -42,7 +131,13 @@ pub struct DownloadFeedConfigs {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct OracleNodeConfig {
-    pub gas_object_id: ObjectID,
+    /// Single gas coin, kept for compatibility with `gas-object-ids`.
+    #[serde(default)]
+    pub gas_object_id: Option<ObjectID>,
+    /// Pool of gas coins to pay for submissions. Every in-flight submission uses its own coin,
+    /// so a slow transaction only holds up the data points it submits.
+    #[serde(default)]
+    pub gas_object_ids: Vec<ObjectID>,
     pub upload_feeds: HashMap<String, HashMap<String, UploadFeedConfig>>,
     pub download_feeds: DownloadFeedConfigs,
 
@@ -50,6 +145,36 @@ pub struct OracleNodeConfig {
     pub metrics_address: SocketAddr,
 }
 
+impl OracleNodeConfig {
+    pub fn gas_object_ids(&self) -> Vec<ObjectID> {
+        let mut gas_object_ids: Vec<_> = self
+            .gas_object_id
+            .iter()
+            .chain(self.gas_object_ids.iter())
+            .copied()
+            .collect();
+        gas_object_ids.sort();
+        gas_object_ids.dedup();
+        gas_object_ids
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !self.gas_object_ids().is_empty(),
+            "At least one gas object must be configured"
+        );
+        for (feed_name, feeds) in &self.upload_feeds {
+            for (source_name, feed) in feeds {
+                anyhow::ensure!(
+                    feed.data_sources().next().is_some(),
+                    "Feed {feed_name} ({source_name}) has no data source"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
 fn default_metrics_address() -> SocketAddr {
     use std::net::{IpAddr, Ipv4Addr};
     SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 9400)
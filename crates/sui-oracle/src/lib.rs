@@ -1,15 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use aggregation::{aggregate, should_submit};
 use chrono::{DateTime, Utc};
-use config::{DownloadFeedConfigs, UploadFeedConfig, UploadParameters};
+use config::{DataSourceConfig, DownloadFeedConfigs, UploadFeedConfig, UploadParameters};
+use futures::future::join_all;
 use metrics::OracleMetrics;
 use mysten_metrics::monitored_scope;
 use once_cell::sync::OnceCell;
 use prometheus::Registry;
+use std::collections::hash_map::Entry;
+use std::collections::HashSet;
 use std::ops::Add;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use std::{collections::HashMap, time::Instant};
 use sui_json_rpc_types::SuiTransactionBlockResponse;
@@ -37,6 +41,7 @@ use tap::tap::TapFallible;
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::base_types::{random_object_ref, ObjectID, ObjectRef};
 use tracing::{debug, error, info, warn};
+mod aggregation;
 pub mod config;
 mod metrics;
 
@@ -48,7 +53,7 @@ static STALE_OBJ_ERROR: OnceCell<String> = OnceCell::new();
 
 pub struct OracleNode {
     upload_feeds: HashMap<String, HashMap<String, UploadFeedConfig>>,
-    gas_obj_id: ObjectID,
+    gas_obj_ids: Vec<ObjectID>,
     download_feeds: DownloadFeedConfigs,
     wallet_ctx: WalletContext,
     metrics: Arc<OracleMetrics>,
@@ -57,14 +62,14 @@ pub struct OracleNode {
 impl OracleNode {
     pub fn new(
         upload_feeds: HashMap<String, HashMap<String, UploadFeedConfig>>,
-        gas_obj_id: ObjectID,
+        gas_obj_ids: Vec<ObjectID>,
         download_feeds: DownloadFeedConfigs,
         wallet_ctx: WalletContext,
         registry: Registry,
     ) -> Self {
         Self {
             upload_feeds,
-            gas_obj_id,
+            gas_obj_ids,
             download_feeds,
             wallet_ctx,
             metrics: Arc::new(OracleMetrics::new(&registry)),
@@ -79,7 +84,7 @@ impl OracleNode {
         let wallet_ctx = Arc::new(self.wallet_ctx);
         DataProviderRunner::new(
             self.upload_feeds,
-            self.gas_obj_id,
+            self.gas_obj_ids,
             wallet_ctx,
             client.clone(),
             signer_address,
@@ -124,7 +129,7 @@ struct DataProviderRunner {
 impl DataProviderRunner {
     pub async fn new(
         upload_feeds: HashMap<String, HashMap<String, UploadFeedConfig>>,
-        gas_coin_id: ObjectID,
+        gas_coin_ids: Vec<ObjectID>,
         wallet_ctx: Arc<WalletContext>,
         client: Arc<SuiClient>,
         signer_address: SuiAddress,
@@ -162,17 +167,31 @@ impl DataProviderRunner {
         }
         info!("Staleness tolerance: {:?}", staleness_tolerance);
 
-        let gas_obj_ref = get_gas_obj_ref(client.read_api(), gas_coin_id, signer_address).await;
-        info!("Gas object: {:?}", gas_obj_ref);
+        let (gas_coins_sender, gas_coins_receiver) =
+            tokio::sync::mpsc::channel(gas_coin_ids.len().max(1));
+        for gas_coin_id in gas_coin_ids {
+            let gas_obj_ref = get_gas_obj_ref(client.read_api(), gas_coin_id, signer_address).await;
+            info!("Gas object: {:?}", gas_obj_ref);
+            gas_coins_sender
+                .try_send(gas_obj_ref)
+                .expect("The gas coin pool has room for every gas coin");
+            metrics.available_gas_coins.inc();
+        }
 
         let uploader = OnChainDataUploader {
-            wallet_ctx: wallet_ctx.clone(),
-            client: client.clone(),
             receiver,
-            signer_address,
-            gas_obj_ref,
             staleness_tolerance,
-            oracle_object_args,
+            submitter: Arc::new(OnChainDataSubmitter {
+                wallet_ctx: wallet_ctx.clone(),
+                client: client.clone(),
+                signer_address,
+                oracle_object_args,
+                metrics: metrics.clone(),
+            }),
+            gas_coins_sender,
+            gas_coins_receiver,
+            in_flight_feeds: Arc::new(Mutex::new(HashSet::new())),
+            pending: HashMap::new(),
             metrics: metrics.clone(),
         };
         Self {
@@ -230,6 +249,15 @@ struct DataProvider {
     metrics: Arc<OracleMetrics>,
 }
 
+/// Values retrieved and submitted by a `DataProvider`.
+#[derive(Default)]
+struct DataProviderState {
+    /// Latest value retrieved from each data source, and when.
+    latest_values: HashMap<String, (f64, Instant)>,
+    /// Last value sent to the uploader, and when.
+    last_submitted: Option<(f64, Instant)>,
+}
+
 impl DataProvider {
     pub async fn run(&self) {
         info!(
@@ -240,81 +268,110 @@ impl DataProvider {
         let mut interval = tokio::time::interval(self.upload_feed.submission_interval);
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
+        let mut state = DataProviderState::default();
         loop {
             interval.tick().await;
-            self.run_once().await;
+            self.run_once(&mut state).await;
         }
     }
 
-    async fn run_once(&self) {
+    async fn run_once(&self, state: &mut DataProviderState) {
         debug!(
             feed_name = self.feed_name,
             source_name = self.source_name,
             "Running data provider once."
         );
-        let value = self.retrieve_from_data_source().await;
-        if value.is_err() {
-            error!(
-                feed_name = self.feed_name,
-                source_name = self.source_name,
-                "Failed to retrieve data from data source: {:?}",
-                value
-            );
+        let data_sources: Vec<_> = self.upload_feed.data_sources().collect();
+        let values = join_all(data_sources.iter().copied().map(retrieve_from_data_source)).await;
+        for (data_source, value) in data_sources.iter().zip(values) {
+            match value {
+                Ok(value) => {
+                    self.metrics
+                        .data_source_successes
+                        .with_label_values(&[&self.feed_name, data_source.name()])
+                        .inc();
+                    state
+                        .latest_values
+                        .insert(data_source.name().to_string(), (value, Instant::now()));
+                }
+                Err(err) => {
+                    error!(
+                        feed_name = self.feed_name,
+                        source_name = self.source_name,
+                        data_source = data_source.name(),
+                        "Failed to retrieve data from data source: {:?}",
+                        err
+                    );
+                    self.metrics
+                        .data_source_errors
+                        .with_label_values(&[&self.feed_name, data_source.name()])
+                        .inc();
+                }
+            }
+        }
+
+        let aggregate = aggregate(
+            state
+                .latest_values
+                .iter()
+                .map(|(name, (value, retrieved_at))| {
+                    (name.as_str(), *value, retrieved_at.elapsed())
+                }),
+            &self.upload_feed.aggregation,
+            self.upload_feed.max_staleness(),
+        );
+        let value = match aggregate {
+            Ok(aggregate) => {
+                for (data_source, rejection) in &aggregate.rejected {
+                    self.metrics
+                        .rejected_values
+                        .with_label_values(&[&self.feed_name, data_source, rejection.as_str()])
+                        .inc();
+                }
+                debug!(
+                    feed_name = self.feed_name,
+                    source_name = self.source_name,
+                    value = aggregate.value,
+                    num_sources = aggregate.num_sources,
+                    "Aggregated data from data sources."
+                );
+                aggregate.value
+            }
+            Err(err) => {
+                warn!(
+                    feed_name = self.feed_name,
+                    source_name = self.source_name,
+                    "Failed to aggregate data: {err}"
+                );
+                self.metrics
+                    .aggregation_failures
+                    .with_label_values(&[&self.feed_name])
+                    .inc();
+                return;
+            }
+        };
+
+        if !should_submit(
+            state
+                .last_submitted
+                .map(|(value, submitted_at)| (value, submitted_at.elapsed())),
+            value,
+            self.upload_feed.deviation_threshold,
+            self.upload_feed.heartbeat_interval,
+        ) {
             self.metrics
-                .data_source_errors
-                .with_label_values(&[&self.feed_name, &self.source_name])
+                .skipped_submissions
+                .with_label_values(&[&self.feed_name])
                 .inc();
             return;
         }
-
-        self.metrics
-            .data_source_successes
-            .with_label_values(&[&self.feed_name, &self.source_name])
-            .inc();
+        state.last_submitted = Some((value, Instant::now()));
 
         // TODO: allow more flexible multiplers and data types
-        let value = (value.unwrap() * METRICS_MULTIPLIER) as u64;
+        let value = (value * METRICS_MULTIPLIER) as u64;
         self.send_to_uploader(value).await;
     }
 
-    async fn retrieve_from_data_source(&self) -> anyhow::Result<f64> {
-        // TODO: support websocket
-        let url = &self.upload_feed.data_source_config.url;
-        let json_path = &self.upload_feed.data_source_config.json_path;
-        let response = reqwest::Client::new().get(url).send().await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch data: {:?}", response);
-        }
-
-        let json_blob: serde_json::Value = response.json().await.unwrap();
-        let data = jsonpath_lib::select(&json_blob, json_path)?;
-
-        if data.is_empty() {
-            anyhow::bail!(
-                "Failed to find data from json blob: {:?} with json path: {:?}",
-                json_blob,
-                json_path
-            );
-        }
-        // Assume there is one single value per request
-        match data[0].as_str() {
-            Some(value_str) => match value_str.parse::<f64>() {
-                Ok(value) => Ok(value),
-                Err(_) => anyhow::bail!(
-                    "Failed to parse data {:?} as f64 from json blob: {:?}",
-                    data[0],
-                    json_blob
-                ),
-            },
-            None => anyhow::bail!(
-                "Failed to parse data {:?} as string from json blob: {:?}",
-                data[0],
-                json_blob
-            ),
-        }
-    }
-
     async fn send_to_uploader(&self, value: u64) {
         let _ = self
             .sender
@@ -330,6 +387,44 @@ impl DataProvider {
     }
 }
 
+async fn retrieve_from_data_source(data_source: &DataSourceConfig) -> anyhow::Result<f64> {
+    // TODO: support websocket
+    let url = &data_source.url;
+    let json_path = &data_source.json_path;
+    let response = reqwest::Client::new().get(url).send().await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to fetch data: {:?}", response);
+    }
+
+    let json_blob: serde_json::Value = response.json().await?;
+    let data = jsonpath_lib::select(&json_blob, json_path)?;
+
+    if data.is_empty() {
+        anyhow::bail!(
+            "Failed to find data from json blob: {:?} with json path: {:?}",
+            json_blob,
+            json_path
+        );
+    }
+    // Assume there is one single value per request
+    match data[0].as_str() {
+        Some(value_str) => match value_str.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(value),
+            _ => anyhow::bail!(
+                "Failed to parse data {:?} as a finite f64 from json blob: {:?}",
+                data[0],
+                json_blob
+            ),
+        },
+        None => anyhow::bail!(
+            "Failed to parse data {:?} as string from json blob: {:?}",
+            data[0],
+            json_blob
+        ),
+    }
+}
+
 fn make_onchain_feed_name(feed_name: &str, source_name: &str) -> String {
     format!(
         "{}-{}",
@@ -339,13 +434,18 @@ fn make_onchain_feed_name(feed_name: &str, source_name: &str) -> String {
 }
 
 struct OnChainDataUploader {
-    wallet_ctx: Arc<WalletContext>,
-    client: Arc<SuiClient>,
     receiver: tokio::sync::mpsc::Receiver<DataPoint>,
-    signer_address: SuiAddress,
-    gas_obj_ref: ObjectRef,
     staleness_tolerance: HashMap<String, Duration>,
-    oracle_object_args: HashMap<ObjectID, ObjectArg>,
+    submitter: Arc<OnChainDataSubmitter>,
+    /// Pool of gas coins that are not in use. Every submission takes a coin out of the pool and
+    /// puts it back once its transaction is done.
+    gas_coins_sender: tokio::sync::mpsc::Sender<ObjectRef>,
+    gas_coins_receiver: tokio::sync::mpsc::Receiver<ObjectRef>,
+    /// Feeds with a submission in flight. A feed is submitted by one transaction at a time, so
+    /// that its data points land on chain in the order they were retrieved.
+    in_flight_feeds: Arc<Mutex<HashSet<String>>>,
+    /// The newest data point of every feed waiting to be submitted.
+    pending: HashMap<String, DataPoint>,
     metrics: Arc<OracleMetrics>,
 }
 
@@ -357,20 +457,36 @@ impl OnChainDataUploader {
         read_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
         loop {
             read_interval.tick().await;
+            // Wait for a free gas coin before collecting, so that data points don't go stale
+            // while waiting for one.
+            let Some(gas_obj_ref) = self.gas_coins_receiver.recv().await else {
+                error!("Gas coin pool closed, stopping OnChainDataUploader");
+                return;
+            };
+            self.metrics.available_gas_coins.dec();
+
             let data_points = self.collect().await;
-            if !data_points.is_empty() {
-                if let Err(err) = self.upload(data_points).await {
-                    error!("Upload failure: {err}. About to resting for {UPLOAD_FAILURE_RECOVER_SEC} sec.");
-                    tokio::time::sleep(Duration::from_secs(UPLOAD_FAILURE_RECOVER_SEC)).await;
-                    self.gas_obj_ref = get_gas_obj_ref(
-                        self.client.read_api(),
-                        self.gas_obj_ref.0,
-                        self.signer_address,
-                    )
-                    .await;
-                    error!("Updated gas object reference: {:?}", self.gas_obj_ref);
+            let data_points = self.schedule(data_points);
+            let submitter = self.submitter.clone();
+            let gas_coins_sender = self.gas_coins_sender.clone();
+            let in_flight_feeds = self.in_flight_feeds.clone();
+            let metrics = self.metrics.clone();
+            tokio::spawn(async move {
+                let feed_names: Vec<_> = data_points.iter().map(|d| d.feed_name.clone()).collect();
+                let gas_obj_ref = if data_points.is_empty() {
+                    gas_obj_ref
+                } else {
+                    submitter.upload_or_recover(data_points, gas_obj_ref).await
+                };
+                {
+                    let mut in_flight_feeds = in_flight_feeds.lock().unwrap();
+                    for feed_name in &feed_names {
+                        in_flight_feeds.remove(feed_name);
+                    }
                 }
-            }
+                metrics.available_gas_coins.inc();
+                let _ = gas_coins_sender.send(gas_obj_ref).await;
+            });
         }
     }
 
@@ -380,31 +496,13 @@ impl OnChainDataUploader {
         while let Ok(Some(data_point)) =
             tokio::time::timeout(Duration::from_millis(100), self.receiver.recv()).await
         {
-            let feed_name = &data_point.feed_name;
             debug!(
                 feed_name = data_point.feed_name,
                 value = data_point.value,
                 "Received data from data provider."
             );
             // TODO: for each source, at most take one value in each submission
-            let staleness_tolerance =
-                self.staleness_tolerance.get(feed_name).unwrap_or_else(|| {
-                    panic!("Bug, missing staleness tolerance for feed: {}", feed_name)
-                });
-            let duration_since = data_point.retrieval_instant.elapsed();
-            if duration_since > staleness_tolerance.add(Duration::from_secs(1)) {
-                warn!(
-                    feed_name,
-                    value = data_point.value,
-                    ?duration_since,
-                    ?staleness_tolerance,
-                    "Data is too stale, skipping."
-                );
-                self.metrics
-                    .data_staleness
-                    .with_label_values(&[feed_name])
-                    .inc();
-            } else {
+            if !self.is_stale(&data_point) {
                 data_points.push(data_point);
             }
 
@@ -418,9 +516,100 @@ impl OnChainDataUploader {
         data_points
     }
 
+    fn is_stale(&self, data_point: &DataPoint) -> bool {
+        let feed_name = &data_point.feed_name;
+        let staleness_tolerance = self
+            .staleness_tolerance
+            .get(feed_name)
+            .unwrap_or_else(|| panic!("Bug, missing staleness tolerance for feed: {}", feed_name));
+        let duration_since = data_point.retrieval_instant.elapsed();
+        if duration_since <= staleness_tolerance.add(Duration::from_secs(1)) {
+            return false;
+        }
+        warn!(
+            feed_name,
+            value = data_point.value,
+            ?duration_since,
+            ?staleness_tolerance,
+            "Data is too stale, skipping."
+        );
+        self.metrics
+            .data_staleness
+            .with_label_values(&[feed_name])
+            .inc();
+        true
+    }
+
+    /// Adds `data_points` to the pending ones, and returns those of feeds without a submission in
+    /// flight, which are then marked in flight. A data point is dropped once a newer one of its
+    /// feed exists, or when it goes stale while its feed is in flight.
+    fn schedule(&mut self, data_points: Vec<DataPoint>) -> Vec<DataPoint> {
+        for data_point in data_points {
+            match self.pending.entry(data_point.feed_name.clone()) {
+                Entry::Occupied(mut entry) => {
+                    if entry.get().retrieval_timestamp <= data_point.retrieval_timestamp {
+                        entry.insert(data_point);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(data_point);
+                }
+            }
+        }
+
+        let mut in_flight_feeds = self.in_flight_feeds.lock().unwrap();
+        let ready: Vec<_> = self
+            .pending
+            .keys()
+            .filter(|feed_name| !in_flight_feeds.contains(*feed_name))
+            .cloned()
+            .collect();
+        let mut data_points = vec![];
+        for feed_name in ready {
+            let data_point = self.pending.remove(&feed_name).unwrap();
+            if !self.is_stale(&data_point) {
+                in_flight_feeds.insert(feed_name);
+                data_points.push(data_point);
+            }
+        }
+        data_points
+    }
+}
+
+/// Builds and executes the transactions that submit data points on chain.
+struct OnChainDataSubmitter {
+    wallet_ctx: Arc<WalletContext>,
+    client: Arc<SuiClient>,
+    signer_address: SuiAddress,
+    oracle_object_args: HashMap<ObjectID, ObjectArg>,
+    metrics: Arc<OracleMetrics>,
+}
+
+impl OnChainDataSubmitter {
+    /// Uploads `data_points` paying with `gas_obj_ref`, and returns the reference of the gas coin
+    /// to use next. On failure, the reference is read again from chain after a while.
+    async fn upload_or_recover(
+        &self,
+        data_points: Vec<DataPoint>,
+        mut gas_obj_ref: ObjectRef,
+    ) -> ObjectRef {
+        if let Err(err) = self.upload(data_points, &mut gas_obj_ref).await {
+            error!(
+                gas_object_id = ?gas_obj_ref.0,
+                "Upload failure: {err}. About to resting for {UPLOAD_FAILURE_RECOVER_SEC} sec."
+            );
+            tokio::time::sleep(Duration::from_secs(UPLOAD_FAILURE_RECOVER_SEC)).await;
+            gas_obj_ref =
+                get_gas_obj_ref(self.client.read_api(), gas_obj_ref.0, self.signer_address).await;
+            error!("Updated gas object reference: {:?}", gas_obj_ref);
+        }
+        gas_obj_ref
+    }
+
     async fn upload(
-        &mut self,
+        &self,
         data_points: Vec<DataPoint>,
+        gas_obj_ref: &mut ObjectRef,
     ) -> anyhow::Result<SuiTransactionBlockEffects> {
         let _scope = monitored_scope("Oracle::OnChainDataUploader::upload");
         // TODO add more error handling & polling perhaps
@@ -492,7 +681,7 @@ impl OnChainDataUploader {
             .await?;
         let tx = TransactionData::new_programmable(
             self.signer_address,
-            vec![self.gas_obj_ref],
+            vec![*gas_obj_ref],
             pt,
             // 15_000_000 is a heuristic number
             15_000_000 * data_points.len() as u64,
@@ -503,7 +692,7 @@ impl OnChainDataUploader {
         let tx_digest = *signed_tx.digest();
 
         let timer_start = Instant::now();
-        let response = self.execute(signed_tx).await?;
+        let response = self.execute(signed_tx, gas_obj_ref).await?;
         let time_spend_sec = timer_start.elapsed().as_secs_f32();
 
         // We asked for effects.
@@ -511,7 +700,7 @@ impl OnChainDataUploader {
         let effects = response.effects.expect("Expect to see effects in response");
 
         // It's critical to update the gas object reference for next transaction
-        self.gas_obj_ref = effects.gas_object().reference.to_object_ref();
+        *gas_obj_ref = effects.gas_object().reference.to_object_ref();
 
         let success = effects.status().is_ok();
 
@@ -566,7 +755,11 @@ impl OnChainDataUploader {
         }
     }
 
-    async fn execute(&mut self, tx: Transaction) -> anyhow::Result<SuiTransactionBlockResponse> {
+    async fn execute(
+        &self,
+        tx: Transaction,
+        gas_obj_ref: &mut ObjectRef,
+    ) -> anyhow::Result<SuiTransactionBlockResponse> {
         let tx_digest = tx.digest();
         let mut retry_attempts = 3;
         loop {
@@ -591,8 +784,8 @@ impl OnChainDataUploader {
                             );
                         if err.to_string().contains(stale_obj_error) {
                             error!(?tx_digest, "Failed to submit tx, it looks like gas object is stale : {:?}", err);
-                            let new_ref = get_gas_obj_ref(self.client.read_api(), self.gas_obj_ref.0, self.signer_address).await;
-                            self.gas_obj_ref = new_ref;
+                            let new_ref = get_gas_obj_ref(self.client.read_api(), gas_obj_ref.0, self.signer_address).await;
+                            *gas_obj_ref = new_ref;
                             info!("Gas object updated: {:?}", new_ref);
                             anyhow::bail!("Gas object is stale, now updated to {:?}. tx_digest={:?}", new_ref, tx_digest);
                         } else {
@@ -24,6 +24,8 @@ async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
     let config = OracleNodeConfig::load(&args.oracle_config_path)?;
+    config.validate()?;
+    let gas_object_ids = config.gas_object_ids();
 
     let wallet_ctx = WalletContext::new(
         &args.client_config_path,
@@ -45,7 +47,7 @@ async fn main() -> anyhow::Result<()> {
 
     OracleNode::new(
         config.upload_feeds,
-        config.gas_object_id,
+        gas_object_ids,
         config.download_feeds,
         wallet_ctx,
         prometheus_registry,
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_int_counter_vec_with_registry, register_int_counter_with_registry, IntCounter,
-    IntCounterVec, Registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, IntCounter, IntCounterVec, IntGauge, Registry,
 };
 
 use mysten_metrics::histogram::{Histogram, HistogramVec};
@@ -13,6 +13,9 @@ pub struct OracleMetrics {
     pub(crate) data_source_successes: IntCounterVec,
     pub(crate) data_source_errors: IntCounterVec,
     pub(crate) data_staleness: IntCounterVec,
+    pub(crate) rejected_values: IntCounterVec,
+    pub(crate) aggregation_failures: IntCounterVec,
+    pub(crate) skipped_submissions: IntCounterVec,
     pub(crate) upload_successes: IntCounterVec,
     pub(crate) upload_data_errors: IntCounterVec,
     pub(crate) download_successes: IntCounterVec,
@@ -24,6 +27,7 @@ pub struct OracleMetrics {
     pub(crate) total_gas_rebate: IntCounter,
     pub(crate) computation_gas_used: Histogram,
     pub(crate) total_data_points_uploaded: IntCounter,
+    pub(crate) available_gas_coins: IntGauge,
 }
 
 impl OracleMetrics {
@@ -50,6 +54,27 @@ impl OracleMetrics {
                 registry,
             )
             .unwrap(),
+            rejected_values: register_int_counter_vec_with_registry!(
+                "oracle_rejected_values",
+                "Total number of data source values left out of aggregation, by reason",
+                &["feed", "source", "reason"],
+                registry,
+            )
+            .unwrap(),
+            aggregation_failures: register_int_counter_vec_with_registry!(
+                "oracle_aggregation_failures",
+                "Total number of times a feed had too few usable values to aggregate",
+                &["feed"],
+                registry,
+            )
+            .unwrap(),
+            skipped_submissions: register_int_counter_vec_with_registry!(
+                "oracle_skipped_submissions",
+                "Total number of values not submitted because they neither deviated enough nor were due for a heartbeat",
+                &["feed"],
+                registry,
+            )
+            .unwrap(),
             upload_successes: register_int_counter_vec_with_registry!(
                 "oracle_upload_successes",
                 "Total number of successful data upload",
@@ -113,6 +138,12 @@ impl OracleMetrics {
                 registry,
             )
             .unwrap(),
+            available_gas_coins: register_int_gauge_with_registry!(
+                "oracle_available_gas_coins",
+                "Number of gas coins not in use by an in-flight submission",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -12,7 +12,7 @@ use tracing::{info, warn};
 
 /// The minimum and maximum protocol versions supported by this build.
 const MIN_PROTOCOL_VERSION: u64 = 1;
-const MAX_PROTOCOL_VERSION: u64 = 31;
+const MAX_PROTOCOL_VERSION: u64 = 32;
 
 // Record history of protocol version allocations here:
 //
@@ -90,6 +90,7 @@ const MAX_PROTOCOL_VERSION: u64 = 31;
 //             Add support for shared obj deletion and receiving objects off of other objects in devnet only.
 // Version 31: Add support for shared object deletion in devnet only.
 //             Add support for getting object ID referenced by receiving object in sui framework.
+// Version 32: Enable shared object congestion control in devnet only.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -839,6 +840,13 @@ pub struct ProtocolConfig {
     /// Maximum allowed precision loss when reducing voting weights for the random beacon
     /// protocol.
     random_beacon_reduction_allowed_delta: Option<u16>,
+
+    /// === shared object congestion control ===
+
+    /// Maximum number of transactions mutating the same shared object that are scheduled in a
+    /// single consensus commit. Transactions over the budget are deferred to later commits.
+    /// Congestion control is disabled when this is not set.
+    max_txns_per_shared_object_per_commit: Option<u64>,
 }
 
 // feature flags
@@ -1409,6 +1417,8 @@ impl ProtocolConfig {
 
             random_beacon_reduction_allowed_delta: None,
 
+            max_txns_per_shared_object_per_commit: None,
+
             // When adding a new constant, set it to None in the earliest version, like this:
             // new_constant: None,
         };
@@ -1643,6 +1653,12 @@ impl ProtocolConfig {
                         cfg.feature_flags.shared_object_deletion = true;
                     }
                 }
+                32 => {
+                    // Only enable shared object congestion control on devnet
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        cfg.max_txns_per_shared_object_per_commit = Some(100);
+                    }
+                }
                 // Use this template when making changes:
                 //
                 //     // modify an existing constant.
@@ -1710,6 +1726,9 @@ impl ProtocolConfig {
     pub fn set_consensus_bad_nodes_stake_threshold(&mut self, val: u64) {
         self.consensus_bad_nodes_stake_threshold = Some(val);
     }
+    pub fn set_max_txns_per_shared_object_per_commit_for_testing(&mut self, val: u64) {
+        self.max_txns_per_shared_object_per_commit = Some(val);
+    }
     pub fn set_receive_object_for_testing(&mut self, val: bool) {
         self.feature_flags.receive_objects = val
     }
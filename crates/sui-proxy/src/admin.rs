@@ -8,6 +8,7 @@ use crate::middleware::{
 };
 use crate::peers::SuiNodeProvider;
 use crate::var;
+use crate::write_buffer::WriteBuffer;
 use anyhow::Result;
 use axum::{extract::DefaultBodyLimit, middleware, routing::post, Extension, Router};
 use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PublicKey};
@@ -65,10 +66,17 @@ pub async fn shutdown_signal(h: axum_server::Handle) {
 pub struct ReqwestClient {
     pub client: reqwest::Client,
     pub settings: RemoteWriteConfig,
+    /// holds metrics while the remote_write url is unavailable, if configured
+    pub buffer: Option<Arc<WriteBuffer>>,
 }
 
+/// make_reqwest_client also opens the remote write buffer if configured, and starts replaying
+/// it in the background, so it must be called from within a tokio runtime
 pub fn make_reqwest_client(settings: RemoteWriteConfig, user_agent: &str) -> ReqwestClient {
-    ReqwestClient {
+    let buffer = settings.buffer.clone().map(|config| {
+        Arc::new(WriteBuffer::open(config).expect("cannot open remote write buffer"))
+    });
+    let rc = ReqwestClient {
         client: reqwest::Client::builder()
             .user_agent(user_agent)
             .pool_max_idle_per_host(settings.pool_max_idle_per_host)
@@ -76,7 +84,13 @@ pub fn make_reqwest_client(settings: RemoteWriteConfig, user_agent: &str) -> Req
             .build()
             .expect("cannot create reqwest client"),
         settings,
+        buffer,
+    };
+    if let Some(buffer) = rc.buffer.clone() {
+        let rc = rc.clone();
+        tokio::spawn(async move { buffer.replay(rc).await });
     }
+    rc
 }
 
 // Labels are adhoc labels we will inject per our config
@@ -5,6 +5,7 @@ use core::time::Duration;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::{serde_as, DurationSeconds};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use tracing::debug;
 
 #[serde_as]
@@ -36,6 +37,32 @@ pub struct RemoteWriteConfig {
     /// <https://docs.rs/reqwest/latest/reqwest/struct.ClientBuilder.html#method.pool_max_idle_per_host>
     #[serde(default = "pool_max_idle_per_host_default")]
     pub pool_max_idle_per_host: usize,
+
+    /// if set, metrics that cannot be delivered while the remote_write url is unavailable are
+    /// buffered on disk and replayed in order once it recovers
+    #[serde(default)]
+    pub buffer: Option<BufferConfig>,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BufferConfig {
+    /// directory holding the buffered metrics
+    /// please use an absolute path
+    pub directory: PathBuf,
+    /// once the buffer holds this many bytes, new metrics are dropped and pushers are asked to
+    /// back off
+    #[serde(default = "buffer_max_size_bytes_default")]
+    pub max_size_bytes: u64,
+    /// buffered metrics older than this are dropped instead of replayed
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(default = "buffer_max_age_default")]
+    pub max_age: Duration,
+    /// how long to wait before replaying again after the remote_write url failed a replay
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(default = "buffer_retry_interval_default")]
+    pub retry_interval: Duration,
 }
 
 #[serde_as]
@@ -64,6 +91,21 @@ fn pool_max_idle_per_host_default() -> usize {
     8
 }
 
+/// the default size limit of the remote write buffer, 1 GiB
+fn buffer_max_size_bytes_default() -> u64 {
+    1 << 30
+}
+
+/// the default age limit of buffered metrics
+fn buffer_max_age_default() -> Duration {
+    Duration::from_secs(2 * 60 * 60)
+}
+
+/// the default wait between replays of the remote write buffer
+fn buffer_retry_interval_default() -> Duration {
+    Duration::from_secs(5)
+}
+
 /// the default hostname we will use if not provided
 fn hostname_default() -> Option<String> {
     Some("localhost".to_string())
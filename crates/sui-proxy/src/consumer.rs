@@ -4,6 +4,7 @@
 use crate::admin::ReqwestClient;
 use crate::prom_to_mimir::Mimir;
 use crate::remote_write::WriteRequest;
+use crate::write_buffer::{BufferError, WriteBuffer};
 use anyhow::Result;
 use axum::body::Bytes;
 use axum::http::StatusCode;
@@ -281,7 +282,7 @@ pub async fn convert_to_remote_write(
         .with_label_values(&["convert_to_remote_write"])
         .start_timer();
 
-    let remote_write_protos = match convert(node_metric.data).await {
+    let mut remote_write_protos = match convert(node_metric.data).await {
         Ok(v) => v,
         Err(err) => {
             timer.stop_and_discard();
@@ -289,31 +290,46 @@ pub async fn convert_to_remote_write(
         }
     };
 
+    // metrics have to reach the remote_write endpoint in order, so while older metrics wait in
+    // the buffer, newer ones queue up behind them. The guard is held until this call is done
+    // posting or buffering, so no other call can buffer metrics in the meantime.
+    let _ordering = match &rc.buffer {
+        Some(buffer) => {
+            let ordering = buffer.ordering().await;
+            if !buffer.is_empty().await {
+                timer.stop_and_discard();
+                return buffer_remaining(buffer, vec![], remote_write_protos).await;
+            }
+            Some(ordering)
+        }
+        None => None,
+    };
+
     // a counter so we don't iterate the node data 2x
     let mut mf_cnt = 0;
-    for request in remote_write_protos {
+    while let Some(request) = remote_write_protos.next() {
         mf_cnt += 1;
-        let compressed = match encode_compress(&request) {
-            Ok(compressed) => compressed,
+        let compressed: Bytes = match encode_compress(&request) {
+            Ok(compressed) => compressed.into(),
             Err(error) => return error,
         };
 
-        let response = match rc
-            .client
-            .post(rc.settings.url.to_owned())
-            .header(reqwest::header::CONTENT_ENCODING, "snappy")
-            .header(reqwest::header::CONTENT_TYPE, "application/x-protobuf")
-            .header("X-Prometheus-Remote-Write-Version", "0.1.0")
-            .basic_auth(
-                rc.settings.username.to_owned(),
-                Some(rc.settings.password.to_owned()),
-            )
-            .body(compressed)
-            .send()
-            .await
-        {
-            Ok(response) => response,
-            Err(error) => {
+        let response = match (post(&rc, compressed.clone()).await, &rc.buffer) {
+            (Ok(response), Some(buffer)) if is_retryable(response.status()) => {
+                error!(
+                    "({}) remote_write endpoint unavailable, buffering metrics",
+                    response.status()
+                );
+                timer.stop_and_discard();
+                return buffer_remaining(buffer, vec![compressed], remote_write_protos).await;
+            }
+            (Ok(response), _) => response,
+            (Err(error), Some(buffer)) => {
+                error!("remote_write endpoint unavailable, buffering metrics: {error}");
+                timer.stop_and_discard();
+                return buffer_remaining(buffer, vec![compressed], remote_write_protos).await;
+            }
+            (Err(error), None) => {
                 CONSUMER_OPS
                     .with_label_values(&["check_response", "INTERNAL_SERVER_ERROR"])
                     .inc();
@@ -339,6 +355,71 @@ pub async fn convert_to_remote_write(
     (StatusCode::CREATED, "created")
 }
 
+/// post a single encoded and compressed WriteRequest to the remote_write endpoint
+pub(crate) async fn post(
+    rc: &ReqwestClient,
+    compressed: impl Into<reqwest::Body>,
+) -> reqwest::Result<reqwest::Response> {
+    rc.client
+        .post(rc.settings.url.to_owned())
+        .header(reqwest::header::CONTENT_ENCODING, "snappy")
+        .header(reqwest::header::CONTENT_TYPE, "application/x-protobuf")
+        .header("X-Prometheus-Remote-Write-Version", "0.1.0")
+        .basic_auth(
+            rc.settings.username.to_owned(),
+            Some(rc.settings.password.to_owned()),
+        )
+        .body(compressed)
+        .send()
+        .await
+}
+
+/// whether a response signals an outage of the remote_write endpoint rather than a problem with
+/// the request, ie sending the same request later may succeed
+pub(crate) fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// durably buffer the payloads that could not be delivered, followed by the rest of the publish
+/// call. The buffer replays them once the remote_write endpoint recovers.
+///
+/// Pushers are told whether their metrics were buffered (202) or dropped because the buffer is
+/// full (429), so they can back off.
+async fn buffer_remaining(
+    buffer: &WriteBuffer,
+    mut payloads: Vec<Bytes>,
+    remaining: impl Iterator<Item = WriteRequest>,
+) -> (StatusCode, &'static str) {
+    for request in remaining {
+        match encode_compress(&request) {
+            Ok(compressed) => payloads.push(compressed.into()),
+            Err(error) => return error,
+        }
+    }
+    match buffer.push(&payloads).await {
+        Ok(()) => {
+            CONSUMER_OPS.with_label_values(&["buffer", "success"]).inc();
+            (StatusCode::ACCEPTED, "buffered")
+        }
+        Err(BufferError::Full) => {
+            CONSUMER_OPS.with_label_values(&["buffer", "full"]).inc();
+            error!("DROPPING METRICS; remote_write buffer is full");
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                "DROPPING METRICS; remote_write buffer is full",
+            )
+        }
+        Err(BufferError::Io(error)) => {
+            CONSUMER_OPS.with_label_values(&["buffer", "failed"]).inc();
+            error!("DROPPING METRICS; unable to write to remote_write buffer: {error}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DROPPING METRICS; unable to write to remote_write buffer",
+            )
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use prometheus::proto;
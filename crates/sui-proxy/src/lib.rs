@@ -10,6 +10,7 @@ pub mod middleware;
 pub mod peers;
 pub mod prom_to_mimir;
 pub mod remote_write;
+pub mod write_buffer;
 
 /// var extracts environment variables at runtime with a default fallback value
 /// if a default is not provided, the value is simply an empty string if not found
@@ -38,15 +39,24 @@ mod tests {
     use crate::histogram_relay::HistogramRelay;
     use crate::prom_to_mimir::tests::*;
 
+    use crate::admin::ReqwestClient;
+    use crate::config::BufferConfig;
+    use crate::remote_write::{TimeSeries, WriteRequest};
     use crate::{admin::CertKeyPair, config::RemoteWriteConfig, peers::SuiNodeProvider};
+    use axum::body::Bytes;
+    use axum::extract::State;
     use axum::http::{header, StatusCode};
     use axum::routing::post;
     use axum::Router;
     use multiaddr::Multiaddr;
+    use prometheus::proto::MetricFamily;
     use prometheus::Encoder;
     use prometheus::PROTOBUF_FORMAT;
+    use prost::Message;
     use protobuf::RepeatedField;
     use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
     use std::time::Duration;
     use sui_tls::{CertVerifier, TlsAcceptor};
 
@@ -67,6 +77,271 @@ mod tests {
             .unwrap();
     }
 
+    /// UnreliableRemoteWrite answers 503 until it is given a budget of requests to accept, and
+    /// records the timeseries of every request it accepted, as `name/i` for the `i` label
+    #[derive(Default)]
+    struct UnreliableRemoteWrite {
+        budget: AtomicUsize,
+        accepted: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl UnreliableRemoteWrite {
+        fn accept(&self, requests: usize) {
+            self.budget.store(requests, Ordering::SeqCst);
+        }
+
+        fn accepted(&self) -> Vec<Vec<String>> {
+            self.accepted.lock().unwrap().clone()
+        }
+    }
+
+    fn series_id(ts: &TimeSeries) -> String {
+        ts.labels
+            .iter()
+            .filter(|l| l.name == "__name__" || l.name == "i")
+            .map(|l| l.value.as_str())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    async fn run_unreliable_remote_write(
+        listener: TcpListener,
+        remote: Arc<UnreliableRemoteWrite>,
+    ) {
+        async fn handler(
+            State(remote): State<Arc<UnreliableRemoteWrite>>,
+            body: Bytes,
+        ) -> StatusCode {
+            let budget = remote
+                .budget
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |b| b.checked_sub(1));
+            if budget.is_err() {
+                return StatusCode::SERVICE_UNAVAILABLE;
+            }
+            let body = snap::raw::Decoder::new().decompress_vec(&body).unwrap();
+            let request = WriteRequest::decode(body.as_slice()).unwrap();
+            remote
+                .accepted
+                .lock()
+                .unwrap()
+                .push(request.timeseries.iter().map(series_id).collect());
+            StatusCode::OK
+        }
+
+        let app = Router::new()
+            .route("/v1/push", post(handler))
+            .with_state(remote);
+
+        axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service())
+            .await
+            .unwrap();
+    }
+
+    /// start a proxy relaying to rc, returning its publish url and a client it accepts
+    async fn run_proxy(rc: ReqwestClient) -> (String, reqwest::Client) {
+        let CertKeyPair(client_priv_cert, client_pub_key) = admin::generate_self_cert("sui".into());
+        let CertKeyPair(server_priv_cert, _) = admin::generate_self_cert("localhost".into());
+
+        let mut allower = SuiNodeProvider::new("".into(), Duration::from_secs(30));
+        allower.get_mut().write().unwrap().insert(
+            client_pub_key.to_owned(),
+            peers::SuiPeer {
+                name: "some-node".into(),
+                p2p_address: Multiaddr::empty(),
+                public_key: client_pub_key.to_owned(),
+            },
+        );
+        let tls_config = CertVerifier::new(allower.clone())
+            .rustls_server_config(
+                vec![server_priv_cert.rustls_certificate()],
+                server_priv_cert.rustls_private_key(),
+            )
+            .unwrap();
+
+        let app = admin::app(
+            Labels {
+                network: "unittest-network".into(),
+                inventory_hostname: "ansible_inventory_name".into(),
+            },
+            rc,
+            HistogramRelay::new(),
+            Some(allower),
+        );
+
+        let listener = std::net::TcpListener::bind("localhost:0").unwrap();
+        let server_url = format!(
+            "https://localhost:{}/publish/metrics",
+            listener.local_addr().unwrap().port()
+        );
+        let acceptor = TlsAcceptor::new(tls_config);
+        tokio::spawn(async move {
+            admin::server(listener, app, Some(acceptor)).await.unwrap();
+        });
+
+        let client = reqwest::Client::builder()
+            .add_root_certificate(server_priv_cert.reqwest_certificate())
+            .identity(client_priv_cert.reqwest_identity())
+            .https_only(true)
+            .build()
+            .unwrap();
+        (server_url, client)
+    }
+
+    /// a counter metric family with one metric per value of the `i` label
+    fn counters(name: &str, values: impl IntoIterator<Item = String>) -> MetricFamily {
+        create_metric_family(
+            name,
+            "some help this is",
+            None,
+            RepeatedField::from_vec(
+                values
+                    .into_iter()
+                    .map(|i| {
+                        create_metric_counter(
+                            RepeatedField::from_vec(create_labels(vec![("i", &i)])),
+                            create_counter(1.0),
+                        )
+                    })
+                    .collect(),
+            ),
+        )
+    }
+
+    async fn publish(
+        client: &reqwest::Client,
+        url: &str,
+        mf: MetricFamily,
+    ) -> (StatusCode, String) {
+        let mut buf = vec![];
+        let encoder = prometheus::ProtobufEncoder::new();
+        encoder.encode(&[mf], &mut buf).unwrap();
+
+        let res = client
+            .post(url)
+            .header(header::CONTENT_TYPE, PROTOBUF_FORMAT)
+            .body(buf)
+            .send()
+            .await
+            .unwrap();
+        (res.status(), res.text().await.unwrap())
+    }
+
+    /// buffered_remote_write takes the remote_write endpoint down, checks that metrics are buffered
+    /// until the buffer is full, and that once the endpoint comes back they are replayed in order,
+    /// resuming a batch where a failed replay left off rather than from its start.
+    #[tokio::test]
+    async fn buffered_remote_write() {
+        let remote = Arc::new(UnreliableRemoteWrite::default());
+        let remote_write_listener = std::net::TcpListener::bind("localhost:0").unwrap();
+        let remote_write_url = format!(
+            "http://localhost:{}/v1/push",
+            remote_write_listener.local_addr().unwrap().port()
+        );
+        tokio::spawn(run_unreliable_remote_write(
+            remote_write_listener,
+            remote.clone(),
+        ));
+
+        let buffer_dir = tempfile::tempdir().unwrap();
+        let rc = admin::make_reqwest_client(
+            RemoteWriteConfig {
+                url: remote_write_url,
+                username: "bar".into(),
+                password: "foo".into(),
+                buffer: Some(BufferConfig {
+                    directory: buffer_dir.path().to_owned(),
+                    max_size_bytes: 256 * 1024,
+                    max_age: Duration::from_secs(3600),
+                    retry_interval: Duration::from_millis(100),
+                }),
+                ..Default::default()
+            },
+            "dummy user agent",
+        );
+        let buffer = rc.buffer.clone().unwrap();
+        let (server_url, client) = run_proxy(rc).await;
+
+        // 600 timeseries are sent as two remote_write requests of 500 and 100 timeseries, which
+        // are buffered together as one batch
+        let first: Vec<String> = (0..600).map(|i| i.to_string()).collect();
+        assert_eq!(
+            publish(&client, &server_url, counters("first", first.clone())).await,
+            (StatusCode::ACCEPTED, "buffered".into())
+        );
+        assert_eq!(
+            publish(&client, &server_url, counters("second", ["0".to_owned()])).await,
+            (StatusCode::ACCEPTED, "buffered".into())
+        );
+
+        // a label of 1 MiB of pseudo random hex digits, which snappy can't compress below the
+        // buffer size
+        let mut x: u64 = 0x2545f4914f6cdd1d;
+        let noise: String = (0..1 << 20)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                char::from_digit((x % 16) as u32, 16).unwrap()
+            })
+            .collect();
+        assert_eq!(
+            publish(&client, &server_url, counters("too_big", [noise])).await,
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                "DROPPING METRICS; remote_write buffer is full".into()
+            )
+        );
+        assert!(remote.accepted().is_empty());
+
+        // the endpoint recovers for a single request, so the replay fails halfway through the
+        // first batch
+        remote.accept(1);
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while remote.accepted().is_empty() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(remote.accepted().len(), 1);
+        assert!(!buffer.is_empty().await);
+
+        remote.accept(usize::MAX);
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !buffer.is_empty().await {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // nothing was sent twice, and everything arrived in the order it was published
+        let ids = |name: &str, values: &[String]| -> Vec<String> {
+            values.iter().map(|i| format!("{name}/{i}")).collect()
+        };
+        assert_eq!(
+            remote.accepted(),
+            vec![
+                ids("first", &first[..500]),
+                ids("first", &first[500..]),
+                ids("second", &["0".to_owned()]),
+            ]
+        );
+
+        // with the buffer drained, metrics are sent directly again
+        assert_eq!(
+            publish(&client, &server_url, counters("third", ["0".to_owned()])).await,
+            (StatusCode::CREATED, "created".into())
+        );
+        assert_eq!(
+            remote.accepted().last().unwrap(),
+            &ids("third", &["0".to_owned()])
+        );
+    }
+
     /// axum_acceptor is a basic e2e test that creates a mock remote_write post endpoint and has a simple
     /// sui-node client that posts data to the proxy using the protobuf format.  The server processes this
     /// data and sends it to the mock remote_write which accepts everything.  Future work is to make this more
@@ -0,0 +1,424 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::admin::ReqwestClient;
+use crate::config::BufferConfig;
+use crate::consumer::{is_retryable, post};
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use prometheus::{register_counter_vec, register_int_gauge_vec};
+use prometheus::{CounterVec, IntGaugeVec};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, MutexGuard, Notify};
+use tracing::{debug, error, info, warn};
+
+const BATCH_EXTENSION: &str = "batch";
+const TMP_EXTENSION: &str = "tmp";
+
+static BUFFER_DEPTH: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "remote_write_buffer_depth",
+        "The number of batches and bytes waiting in the remote_write buffer.",
+        &["unit"]
+    )
+    .unwrap()
+});
+static BUFFER_OPS: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "remote_write_buffer_operations",
+        "Payloads buffered, replayed and dropped by the remote_write buffer, with the drop reason.",
+        &["operation", "reason"]
+    )
+    .unwrap()
+});
+
+/// WriteBuffer is an on-disk write-ahead buffer for remote_write payloads that could not be
+/// delivered to the remote_write endpoint.
+///
+/// Every batch of payloads from one publish call is stored in its own file, named after a
+/// monotonically increasing sequence number and its creation time, eg
+/// 00000000000000000042-1700000000000.batch. A file holds its payloads, already encoded and
+/// compressed, each prefixed with its length as a little endian u32. Batches are replayed in
+/// sequence order and deleted once every payload was accepted or rejected as invalid by the
+/// endpoint. A crash during replay can therefore resend payloads, which mimir discards as
+/// out-of-order samples.
+pub struct WriteBuffer {
+    config: BufferConfig,
+    state: Mutex<State>,
+    notify: Notify,
+    /// serializes publish calls that may write to the remote_write endpoint directly
+    ordering: Mutex<()>,
+}
+
+#[derive(Default)]
+struct State {
+    next_seq: u64,
+    batches: VecDeque<BatchFile>,
+    bytes: u64,
+}
+
+#[derive(Clone, Debug)]
+struct BatchFile {
+    seq: u64,
+    created_ms: u64,
+    size: u64,
+    path: PathBuf,
+}
+
+impl WriteBuffer {
+    /// open the buffer in config.directory, picking up batches left over by a previous run
+    pub fn open(config: BufferConfig) -> Result<Self> {
+        std::fs::create_dir_all(&config.directory)
+            .with_context(|| format!("cannot create {:?}", config.directory))?;
+
+        let mut state = State::default();
+        for entry in std::fs::read_dir(&config.directory)? {
+            let path = entry?.path();
+            match path.extension().and_then(|e| e.to_str()) {
+                Some(BATCH_EXTENSION) => {
+                    let Some((seq, created_ms)) = parse_batch_name(&path) else {
+                        warn!("ignoring unexpected file {path:?} in remote_write buffer");
+                        continue;
+                    };
+                    let size = std::fs::metadata(&path)?.len();
+                    state.batches.push_back(BatchFile {
+                        seq,
+                        created_ms,
+                        size,
+                        path,
+                    });
+                }
+                // a write that did not complete before the last shutdown
+                Some(TMP_EXTENSION) => std::fs::remove_file(&path)?,
+                _ => warn!("ignoring unexpected file {path:?} in remote_write buffer"),
+            }
+        }
+        state.batches.make_contiguous().sort_by_key(|b| b.seq);
+        state.next_seq = state.batches.back().map(|b| b.seq + 1).unwrap_or_default();
+        state.bytes = state.batches.iter().map(|b| b.size).sum();
+        if !state.batches.is_empty() {
+            info!(
+                "remote_write buffer resumes with {} batches, {} bytes",
+                state.batches.len(),
+                state.bytes
+            );
+        }
+        state.update_depth();
+
+        Ok(Self {
+            config,
+            state: Mutex::new(state),
+            notify: Notify::new(),
+            ordering: Mutex::new(()),
+        })
+    }
+
+    /// take the ordering guard, which publish calls must hold from checking `is_empty` until
+    /// their payloads were either delivered or buffered. Otherwise a payload could be sent
+    /// directly while an older one, buffered concurrently, still waits for replay.
+    pub async fn ordering(&self) -> MutexGuard<'_, ()> {
+        self.ordering.lock().await
+    }
+
+    /// whether payloads are waiting to be replayed. New payloads must be buffered behind them
+    /// instead of being sent directly, so that metrics reach the endpoint in order.
+    pub async fn is_empty(&self) -> bool {
+        self.state.lock().await.batches.is_empty()
+    }
+
+    /// durably append a batch of payloads to the buffer
+    pub async fn push<P: AsRef<[u8]>>(&self, payloads: &[P]) -> Result<(), BufferError> {
+        let mut data = Vec::with_capacity(payloads.iter().map(|p| p.as_ref().len() + 4).sum());
+        for payload in payloads {
+            let payload = payload.as_ref();
+            data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            data.extend_from_slice(payload);
+        }
+
+        let mut state = self.state.lock().await;
+        self.evict_expired(&mut state).await;
+        if state.bytes + data.len() as u64 > self.config.max_size_bytes {
+            BUFFER_OPS
+                .with_label_values(&["dropped", "full"])
+                .inc_by(payloads.len() as f64);
+            return Err(BufferError::Full);
+        }
+
+        let seq = state.next_seq;
+        let created_ms = now_ms();
+        let path = self
+            .config
+            .directory
+            .join(format!("{seq:020}-{created_ms}.{BATCH_EXTENSION}"));
+        let tmp_path = path.with_extension(TMP_EXTENSION);
+        write_synced(&tmp_path, &data, &path).await.map_err(|e| {
+            BUFFER_OPS
+                .with_label_values(&["dropped", "io_error"])
+                .inc_by(payloads.len() as f64);
+            BufferError::Io(e.into())
+        })?;
+
+        state.next_seq += 1;
+        state.bytes += data.len() as u64;
+        state.batches.push_back(BatchFile {
+            seq,
+            created_ms,
+            size: data.len() as u64,
+            path,
+        });
+        state.update_depth();
+        BUFFER_OPS
+            .with_label_values(&["buffered", ""])
+            .inc_by(payloads.len() as f64);
+        drop(state);
+
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    /// replay buffered batches in order, for as long as the proxy runs
+    pub async fn replay(&self, rc: ReqwestClient) {
+        // progress within the oldest batch, as (sequence number, payloads sent)
+        let mut progress = (u64::MAX, 0);
+        loop {
+            let batch = {
+                let mut state = self.state.lock().await;
+                self.evict_expired(&mut state).await;
+                state.batches.front().cloned()
+            };
+            let Some(batch) = batch else {
+                self.notify.notified().await;
+                continue;
+            };
+
+            let payloads = match read_batch(&batch.path).await {
+                Ok(payloads) => payloads,
+                Err(error) => {
+                    error!(
+                        "dropping unreadable remote_write buffer batch {:?}: {error}",
+                        batch.path
+                    );
+                    BUFFER_OPS.with_label_values(&["dropped", "corrupt"]).inc();
+                    self.remove(&batch).await;
+                    continue;
+                }
+            };
+
+            if progress.0 != batch.seq {
+                progress = (batch.seq, 0);
+            }
+            match self.replay_batch(&rc, payloads, &mut progress.1).await {
+                Ok(()) => self.remove(&batch).await,
+                Err(error) => {
+                    debug!("remote_write endpoint unavailable, retrying replay later: {error}");
+                    tokio::time::sleep(self.config.retry_interval).await;
+                }
+            }
+        }
+    }
+
+    /// send the payloads of a batch, skipping the first `sent` ones which were delivered by an
+    /// earlier attempt, and advancing `sent` as payloads get delivered
+    async fn replay_batch(
+        &self,
+        rc: &ReqwestClient,
+        payloads: Vec<Vec<u8>>,
+        sent: &mut usize,
+    ) -> Result<()> {
+        for payload in payloads.into_iter().skip(*sent) {
+            let response = post(rc, payload).await?;
+            let status = response.status();
+            if is_retryable(status) {
+                bail!("remote_write endpoint responded with {status}");
+            }
+            if status.is_success() {
+                BUFFER_OPS.with_label_values(&["replayed", ""]).inc();
+            } else {
+                let body = response.text().await.unwrap_or_default();
+                error!("remote_write endpoint rejected buffered payload ({status}): {body}");
+                BUFFER_OPS.with_label_values(&["dropped", "rejected"]).inc();
+            }
+            *sent += 1;
+        }
+        Ok(())
+    }
+
+    fn is_expired(&self, batch: &BatchFile) -> bool {
+        let age = Duration::from_millis(now_ms().saturating_sub(batch.created_ms));
+        age > self.config.max_age
+    }
+
+    async fn evict_expired(&self, state: &mut State) {
+        while let Some(batch) = state.batches.front() {
+            if !self.is_expired(batch) {
+                break;
+            }
+            let batch = state.batches.pop_front().unwrap();
+            warn!(
+                "dropping expired remote_write buffer batch {:?}",
+                batch.path
+            );
+            BUFFER_OPS.with_label_values(&["dropped", "expired"]).inc();
+            state.bytes -= batch.size;
+            remove_file(&batch.path).await;
+        }
+        state.update_depth();
+    }
+
+    async fn remove(&self, batch: &BatchFile) {
+        let mut state = self.state.lock().await;
+        // the batch may have been evicted in the meantime
+        if state.batches.front().is_some_and(|b| b.seq == batch.seq) {
+            state.batches.pop_front();
+            state.bytes -= batch.size;
+            remove_file(&batch.path).await;
+        }
+        state.update_depth();
+    }
+}
+
+/// errors returned when pushing to the buffer
+#[derive(Debug)]
+pub enum BufferError {
+    /// the buffer would exceed BufferConfig::max_size_bytes
+    Full,
+    Io(anyhow::Error),
+}
+
+impl State {
+    fn update_depth(&self) {
+        BUFFER_DEPTH
+            .with_label_values(&["batches"])
+            .set(self.batches.len() as i64);
+        BUFFER_DEPTH
+            .with_label_values(&["bytes"])
+            .set(self.bytes as i64);
+    }
+}
+
+fn parse_batch_name(path: &Path) -> Option<(u64, u64)> {
+    let (seq, created_ms) = path.file_stem()?.to_str()?.split_once('-')?;
+    Some((seq.parse().ok()?, created_ms.parse().ok()?))
+}
+
+/// write data to tmp_path and atomically move it to path once it is on disk
+async fn write_synced(tmp_path: &Path, data: &[u8], path: &Path) -> std::io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+    let mut file = tokio::fs::File::create(tmp_path).await?;
+    file.write_all(data).await?;
+    file.sync_all().await?;
+    tokio::fs::rename(tmp_path, path).await
+}
+
+async fn read_batch(path: &Path) -> Result<Vec<Vec<u8>>> {
+    let data = tokio::fs::read(path).await?;
+    let mut payloads = vec![];
+    let mut rest = data.as_slice();
+    while !rest.is_empty() {
+        if rest.len() < 4 {
+            bail!("truncated payload length");
+        }
+        let (len, tail) = rest.split_at(4);
+        let len = u32::from_le_bytes(len.try_into()?) as usize;
+        if tail.len() < len {
+            bail!("truncated payload");
+        }
+        let (payload, tail) = tail.split_at(len);
+        payloads.push(payload.to_vec());
+        rest = tail;
+    }
+    Ok(payloads)
+}
+
+async fn remove_file(path: &Path) {
+    if let Err(error) = tokio::fs::remove_file(path).await {
+        error!("unable to remove remote_write buffer batch {path:?}: {error}");
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(directory: &Path, max_size_bytes: u64) -> BufferConfig {
+        BufferConfig {
+            directory: directory.to_owned(),
+            max_size_bytes,
+            max_age: Duration::from_secs(3600),
+            retry_interval: Duration::from_secs(1),
+        }
+    }
+
+    async fn drain(buffer: &WriteBuffer) -> Vec<Vec<Vec<u8>>> {
+        let mut batches = vec![];
+        loop {
+            let Some(batch) = buffer.state.lock().await.batches.front().cloned() else {
+                return batches;
+            };
+            batches.push(read_batch(&batch.path).await.unwrap());
+            buffer.remove(&batch).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_buffer_survives_restart_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let buffer = WriteBuffer::open(config(dir.path(), 1024)).unwrap();
+        assert!(buffer.is_empty().await);
+
+        buffer
+            .push(&[b"one".to_vec(), b"two".to_vec()])
+            .await
+            .unwrap();
+        buffer.push(&[b"three".to_vec()]).await.unwrap();
+        assert!(!buffer.is_empty().await);
+        drop(buffer);
+
+        let buffer = WriteBuffer::open(config(dir.path(), 1024)).unwrap();
+        buffer.push(&[b"four".to_vec()]).await.unwrap();
+        assert_eq!(
+            drain(&buffer).await,
+            vec![
+                vec![b"one".to_vec(), b"two".to_vec()],
+                vec![b"three".to_vec()],
+                vec![b"four".to_vec()],
+            ]
+        );
+        assert!(buffer.is_empty().await);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let buffer = WriteBuffer::open(config(dir.path(), 16)).unwrap();
+
+        buffer.push(&[vec![0; 8]]).await.unwrap();
+        // 8 bytes of payload plus its length prefix don't fit a second time
+        assert!(matches!(
+            buffer.push(&[vec![0; 8]]).await,
+            Err(BufferError::Full)
+        ));
+        drop(buffer);
+
+        let mut config = config(dir.path(), 16);
+        config.max_age = Duration::ZERO;
+        let buffer = WriteBuffer::open(config).unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        // the expired batch is evicted, making room for a new one
+        buffer.push(&[vec![0; 8]]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let mut state = buffer.state.lock().await;
+        buffer.evict_expired(&mut state).await;
+        assert!(state.batches.is_empty());
+        assert_eq!(state.bytes, 0);
+    }
+}
@@ -84,30 +84,7 @@ pub async fn get_full_checkpoint(
                 .cloned()
                 .expect("event was already checked to be present")
         });
-        // Note unwrapped_then_deleted contains **updated** versions.
-        let unwrapped_then_deleted_obj_ids = fx
-            .unwrapped_then_deleted()
-            .into_iter()
-            .map(|k| k.0)
-            .collect::<HashSet<_>>();
-
-        let input_object_keys = fx
-            .input_shared_objects()
-            .into_iter()
-            .map(|kind| {
-                let (id, version) = kind.id_and_version();
-                ObjectKey(id, version)
-            })
-            .chain(
-                fx.modified_at_versions()
-                    .into_iter()
-                    .map(|(object_id, version)| ObjectKey(object_id, version)),
-            )
-            .collect::<HashSet<_>>()
-            .into_iter()
-            // Unwrapped-then-deleted objects are not stored in state before the tx, so we have nothing to fetch.
-            .filter(|key| !unwrapped_then_deleted_obj_ids.contains(&key.0))
-            .collect::<Vec<_>>();
+        let input_object_keys = CheckpointTransaction::input_object_keys(&fx);
 
         let input_objects = state
             .multi_get_object_by_key(&input_object_keys)?
@@ -124,11 +101,7 @@ pub async fn get_full_checkpoint(
             })
             .collect::<Result<Vec<_>>>()?;
 
-        let output_object_keys = fx
-            .all_changed_objects()
-            .into_iter()
-            .map(|(object_ref, _owner, _kind)| ObjectKey::from(object_ref))
-            .collect::<Vec<_>>();
+        let output_object_keys = CheckpointTransaction::output_object_keys(&fx);
 
         let output_objects = state
             .multi_get_object_by_key(&output_object_keys)?
@@ -208,6 +181,46 @@ pub struct CheckpointTransaction {
     pub output_objects: Vec<Object>,
 }
 
+impl CheckpointTransaction {
+    /// Keys of the objects in `input_objects` of the transaction with `effects`.
+    pub fn input_object_keys(effects: &TransactionEffects) -> Vec<ObjectKey> {
+        // Note unwrapped_then_deleted contains **updated** versions.
+        let unwrapped_then_deleted_obj_ids = effects
+            .unwrapped_then_deleted()
+            .into_iter()
+            .map(|k| k.0)
+            .collect::<HashSet<_>>();
+
+        effects
+            .input_shared_objects()
+            .into_iter()
+            .map(|kind| {
+                let (id, version) = kind.id_and_version();
+                ObjectKey(id, version)
+            })
+            .chain(
+                effects
+                    .modified_at_versions()
+                    .into_iter()
+                    .map(|(object_id, version)| ObjectKey(object_id, version)),
+            )
+            .collect::<HashSet<_>>()
+            .into_iter()
+            // Unwrapped-then-deleted objects are not stored in state before the tx, so we have nothing to fetch.
+            .filter(|key| !unwrapped_then_deleted_obj_ids.contains(&key.0))
+            .collect()
+    }
+
+    /// Keys of the objects in `output_objects` of the transaction with `effects`.
+    pub fn output_object_keys(effects: &TransactionEffects) -> Vec<ObjectKey> {
+        effects
+            .all_changed_objects()
+            .into_iter()
+            .map(|(object_ref, _owner, _kind)| ObjectKey::from(object_ref))
+            .collect()
+    }
+}
+
 pub async fn get_latest_checkpoint(
     State(state): State<Arc<dyn NodeStateGetter>>,
 ) -> Result<Json<CertifiedCheckpointSummary>, AppError> {
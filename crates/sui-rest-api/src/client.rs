@@ -35,6 +35,22 @@ impl Client {
         Ok(checkpoint)
     }
 
+    pub async fn get_checkpoint(
+        &self,
+        checkpoint_sequence_number: CheckpointSequenceNumber,
+    ) -> Result<CertifiedCheckpointSummary> {
+        let url = format!("{}/checkpoints/{checkpoint_sequence_number}", self.base_url);
+        let checkpoint = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_JSON)
+            .send()
+            .await?
+            .json()
+            .await?;
+        Ok(checkpoint)
+    }
+
     pub async fn get_full_checkpoint(
         &self,
         checkpoint_sequence_number: CheckpointSequenceNumber,
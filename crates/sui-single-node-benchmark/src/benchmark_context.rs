@@ -32,6 +32,7 @@ impl BenchmarkContext {
         workload: Workload,
         benchmark_component: Component,
         checkpoint_size: usize,
+        enable_execution_cache: bool,
     ) -> Self {
         // Increase by 2 so that we could generate one extra sample transaction before benchmarking.
         // as well as reserve 1 account for package publishing.
@@ -49,8 +50,13 @@ impl BenchmarkContext {
         let (_, admin_account) = user_accounts.pop_last().unwrap();
 
         info!("Initializing validator");
-        let validator =
-            SingleValidator::new(&genesis_gas_objects, benchmark_component, checkpoint_size).await;
+        let validator = SingleValidator::new(
+            &genesis_gas_objects,
+            benchmark_component,
+            checkpoint_size,
+            enable_execution_cache,
+        )
+        .await;
 
         Self {
             validator,
@@ -39,6 +39,12 @@ pub struct Command {
         help = "Which component to benchmark"
     )]
     pub component: Component,
+    #[arg(
+        long,
+        help = "Cache the outputs of executed transactions in memory and write them to the \
+            database in batches, instead of writing them after every transaction"
+    )]
+    pub enable_execution_cache: bool,
     #[clap(subcommand)]
     pub workload: WorkloadKind,
 }
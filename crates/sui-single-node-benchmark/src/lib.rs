@@ -18,8 +18,16 @@ pub mod workload;
 /// The different kinds of workloads and components can be found in command.rs.
 /// \checkpoint_size represents both the size of a consensus commit, and size of a checkpoint
 /// if we are benchmarking the checkpoint.
-pub async fn run_benchmark(workload: Workload, component: Component, checkpoint_size: usize) {
-    let mut ctx = BenchmarkContext::new(workload, component, checkpoint_size).await;
+/// \enable_execution_cache makes the validator keep the outputs of executed transactions in
+/// memory, and flush them to the database in batches.
+pub async fn run_benchmark(
+    workload: Workload,
+    component: Component,
+    checkpoint_size: usize,
+    enable_execution_cache: bool,
+) {
+    let mut ctx =
+        BenchmarkContext::new(workload, component, checkpoint_size, enable_execution_cache).await;
     let tx_generator = workload.create_tx_generator(&mut ctx).await;
     let transactions = ctx.generate_transactions(tx_generator).await;
     match component {
@@ -7,6 +7,7 @@ use crate::mock_storage::InMemoryObjectStore;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use sui_config::node::ExecutionCacheConfig;
 use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use sui_core::authority::authority_store_tables::LiveObject;
 use sui_core::authority::test_authority_builder::TestAuthorityBuilder;
@@ -46,14 +47,17 @@ impl SingleValidator {
         genesis_objects: &[Object],
         component: Component,
         checkpoint_size: usize,
+        enable_execution_cache: bool,
     ) -> Self {
-        let validator = TestAuthorityBuilder::new()
+        let mut builder = TestAuthorityBuilder::new()
             .disable_indexer()
             .with_starting_objects(genesis_objects)
             // This is needed to properly run checkpoint executor.
-            .insert_genesis_checkpoint()
-            .build()
-            .await;
+            .insert_genesis_checkpoint();
+        if enable_execution_cache {
+            builder = builder.with_execution_cache_config(ExecutionCacheConfig::default());
+        }
+        let validator = builder.build().await;
         let epoch_store = validator.epoch_store_for_testing().clone();
         let consensus_mode = match component {
             Component::ValidatorWithFakeConsensus => {
@@ -11,7 +11,15 @@ use sui_single_node_benchmark::workload::Workload;
 async fn benchmark_simple_transfer_smoke_test() {
     // This test makes sure that the benchmark runs.
     for component in Component::iter() {
-        run_benchmark(Workload::new(10, WorkloadKind::NoMove, 2), component, 1000).await;
+        for enable_execution_cache in [false, true] {
+            run_benchmark(
+                Workload::new(10, WorkloadKind::NoMove, 2),
+                component,
+                1000,
+                enable_execution_cache,
+            )
+            .await;
+        }
     }
 }
 
@@ -19,18 +27,21 @@ async fn benchmark_simple_transfer_smoke_test() {
 async fn benchmark_move_transactions_smoke_test() {
     // This test makes sure that the benchmark runs.
     for component in Component::iter() {
-        run_benchmark(
-            Workload::new(
-                10,
-                WorkloadKind::Move {
-                    num_dynamic_fields: 1,
-                    computation: 1,
-                },
-                2,
-            ),
-            component,
-            1000,
-        )
-        .await;
+        for enable_execution_cache in [false, true] {
+            run_benchmark(
+                Workload::new(
+                    10,
+                    WorkloadKind::Move {
+                        num_dynamic_fields: 1,
+                        computation: 1,
+                    },
+                    2,
+                ),
+                component,
+                1000,
+                enable_execution_cache,
+            )
+            .await;
+        }
     }
 }
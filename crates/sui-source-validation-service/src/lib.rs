@@ -2,19 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use axum::middleware::{self, Next};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::net::TcpListener;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use std::{ffi::OsString, fs, path::Path, process::Command};
 use tokio::sync::oneshot::Sender;
+use tokio::sync::Semaphore;
 
 use anyhow::{anyhow, bail};
-use axum::extract::{Query, State};
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Query, State};
 use axum::response::{IntoResponse, Response};
-use axum::routing::{get, IntoMakeService};
+use axum::routing::{get, post, IntoMakeService};
 use axum::Extension;
 use axum::{Json, Router, Server};
 use hyper::http::{HeaderName, HeaderValue, Method};
@@ -32,10 +35,15 @@ use url::Url;
 
 use move_compiler::compiled_unit::CompiledUnitEnum;
 use move_core_types::account_address::AccountAddress;
+use move_package::source_package::layout::SourcePackageLayout;
+use move_package::source_package::manifest_parser::parse_move_manifest_from_file;
+use move_package::source_package::parsed_manifest::{
+    Dependency, DependencyKind, GitInfo, InternalDependency,
+};
 use move_package::BuildConfig as MoveBuildConfig;
 use move_symbol_pool::Symbol;
 use sui_move::build::resolve_lock_file_path;
-use sui_move_build::{BuildConfig, SuiPackageHooks};
+use sui_move_build::{BuildConfig, CompiledPackage, SuiPackageHooks};
 use sui_sdk::rpc_types::{SuiTransactionBlockEffects, TransactionFilter};
 use sui_sdk::types::base_types::ObjectID;
 use sui_sdk::SuiClientBuilder;
@@ -60,6 +68,21 @@ pub const WS_PING_INTERVAL: Duration = Duration::from_millis(20_000);
 pub const METRICS_ROUTE: &str = "/metrics";
 pub const METRICS_HOST_PORT: &str = "0.0.0.0:9184";
 
+/// Largest package source archive accepted by `/api/verify`.
+pub const MAX_PACKAGE_ARCHIVE_SIZE: usize = 10 * 1024 * 1024;
+/// Largest total size of the files unpacked from a package source archive.
+pub const MAX_UNPACKED_PACKAGE_SIZE: u64 = 20 * 1024 * 1024;
+/// Largest number of entries in a package source archive.
+pub const MAX_PACKAGE_ARCHIVE_ENTRIES: usize = 1000;
+/// Repository of the Sui framework, which packages submitted to `/api/verify` may depend on by
+/// default.
+pub const SUI_REPOSITORY_URL: &str = "https://github.com/MystenLabs/sui.git";
+/// Number of packages submitted to `/api/verify` that are built at the same time.
+pub const MAX_CONCURRENT_PACKAGE_BUILDS: usize = 2;
+/// Time allowed for building and verifying a package submitted to `/api/verify`, including the
+/// time spent waiting for other builds to complete.
+pub const VERIFY_TIMEOUT: Duration = Duration::from_secs(120);
+
 pub fn host_port() -> String {
     match option_env!("HOST_PORT") {
         Some(v) => v.to_string(),
@@ -137,6 +160,20 @@ impl fmt::Display for Network {
     }
 }
 
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" => Ok(Network::Mainnet),
+            "testnet" => Ok(Network::Testnet),
+            "devnet" => Ok(Network::Devnet),
+            "localnet" => Ok(Network::Localnet),
+            _ => bail!("Unknown network {s}"),
+        }
+    }
+}
+
 /// Map module name to verified source info.
 pub type SourceLookup = BTreeMap<Symbol, SourceInfo>;
 /// Map addresses to module names and sources.
@@ -144,23 +181,36 @@ pub type AddressLookup = BTreeMap<AccountAddress, SourceLookup>;
 /// Top-level lookup that maps network to sources for corresponding on-chain networks.
 pub type NetworkLookup = BTreeMap<Network, AddressLookup>;
 
-pub async fn verify_package(
-    network: &Network,
-    package_path: impl AsRef<Path>,
-) -> anyhow::Result<(Network, AddressLookup)> {
+/// Builds the package at `package_path`. Building is CPU bound and blocking, so it must not run
+/// on the async runtime.
+fn build_package(package_path: &Path) -> anyhow::Result<CompiledPackage> {
     move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
-    let config = resolve_lock_file_path(
-        MoveBuildConfig::default(),
-        Some(package_path.as_ref().to_path_buf()),
-    )?;
+    let config =
+        resolve_lock_file_path(MoveBuildConfig::default(), Some(package_path.to_path_buf()))?;
     let build_config = BuildConfig {
         config,
         run_bytecode_verifier: false, /* no need to run verifier if code is on-chain */
         print_diags_to_stderr: false,
         lint: false,
     };
-    let compiled_package = build_config.build(package_path.as_ref().to_path_buf())?;
+    Ok(build_config.build(package_path.to_path_buf())?)
+}
 
+pub async fn verify_package(
+    network: &Network,
+    package_path: impl AsRef<Path>,
+) -> anyhow::Result<(Network, AddressLookup)> {
+    let build_path = package_path.as_ref().to_path_buf();
+    let compiled_package =
+        tokio::task::spawn_blocking(move || build_package(&build_path)).await??;
+    verify_compiled_package(network, &compiled_package, package_path.as_ref()).await
+}
+
+async fn verify_compiled_package(
+    network: &Network,
+    compiled_package: &CompiledPackage,
+    package_path: &Path,
+) -> anyhow::Result<(Network, AddressLookup)> {
     let network_url = match network {
         Network::Mainnet => MAINNET_URL,
         Network::Testnet => TESTNET_URL,
@@ -170,7 +220,7 @@ pub async fn verify_package(
     let client = SuiClientBuilder::default().build(network_url).await?;
     BytecodeSourceVerifier::new(client.read_api())
         .verify_package(
-            &compiled_package,
+            compiled_package,
             /* verify_deps */ false,
             SourceMode::Verify,
         )
@@ -182,7 +232,7 @@ pub async fn verify_package(
         .as_ref()
         .map(|id| **id)
         .map_err(|_| anyhow!("could not resolve published-at field in package manifest"))?;
-    info!("verifying {} at {address}", package_path.as_ref().display());
+    info!("verifying {} at {address}", package_path.display());
     for v in &compiled_package.package.root_compiled_units {
         let path = v.source_path.to_path_buf();
         let source = Some(fs::read_to_string(path.as_path())?);
@@ -201,6 +251,232 @@ pub async fn verify_package(
     Ok((network.clone(), address_map))
 }
 
+/// Unpacks a gzipped tarball of a Move package into `dest`, and returns the root of the package,
+/// i.e. the directory with the `Move.toml`. The manifest can be at the top of the archive or in
+/// its only directory.
+///
+/// The archive is rejected as soon as it has more than `MAX_PACKAGE_ARCHIVE_ENTRIES` entries, or
+/// its files add up to more than `MAX_UNPACKED_PACKAGE_SIZE` bytes.
+pub fn unpack_package_archive(archive: &[u8], dest: &Path) -> anyhow::Result<PathBuf> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    let mut num_entries = 0;
+    let mut unpacked_size = 0;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_path_buf();
+        num_entries += 1;
+        if num_entries > MAX_PACKAGE_ARCHIVE_ENTRIES {
+            bail!("Package archive has more than {MAX_PACKAGE_ARCHIVE_ENTRIES} entries");
+        }
+        unpacked_size += entry.size();
+        if unpacked_size > MAX_UNPACKED_PACKAGE_SIZE {
+            bail!("Package archive unpacks to more than {MAX_UNPACKED_PACKAGE_SIZE} bytes");
+        }
+        let entry_type = entry.header().entry_type();
+        // Such as the header with the commit id that `git archive` adds.
+        if entry_type == tar::EntryType::XGlobalHeader {
+            continue;
+        }
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            bail!(
+                "Unsupported entry {} in package archive, only files and directories are allowed",
+                path.display()
+            );
+        }
+        // Entries that would be unpacked outside of `dest` are rejected by `unpack_in`.
+        if !entry.unpack_in(dest)? {
+            bail!("Invalid path {} in package archive", path.display());
+        }
+    }
+
+    if dest.join("Move.toml").is_file() {
+        return Ok(dest.to_path_buf());
+    }
+    let mut dirs = fs::read_dir(dest)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|path| path.is_dir());
+    match (dirs.next(), dirs.next()) {
+        (Some(dir), None) if dir.join("Move.toml").is_file() => Ok(dir),
+        _ => bail!("Could not find a Move.toml at the root of the package archive"),
+    }
+}
+
+/// Checks the dependencies of the package at `package_path`, and transitively of its local
+/// dependencies: local dependencies must be under `root`, and git dependencies must be on one of
+/// the `allowed_git_dependencies` repositories.
+pub fn check_package_dependencies(
+    package_path: &Path,
+    root: &Path,
+    allowed_git_dependencies: &[String],
+) -> anyhow::Result<()> {
+    let root = root.canonicalize()?;
+    let mut visited = BTreeSet::new();
+    let mut to_visit = vec![package_path.canonicalize()?];
+    while let Some(package_path) = to_visit.pop() {
+        if !visited.insert(package_path.clone()) {
+            continue;
+        }
+        let manifest = parse_move_manifest_from_file(&package_path)?;
+        for (name, dependency) in manifest
+            .dependencies
+            .iter()
+            .chain(&manifest.dev_dependencies)
+        {
+            let Dependency::Internal(InternalDependency { kind, .. }) = dependency else {
+                bail!("Unsupported external dependency {name}");
+            };
+            match kind {
+                DependencyKind::Local(path) => {
+                    let dependency_path = package_path
+                        .join(path)
+                        .canonicalize()
+                        .map_err(|_| anyhow!("Could not find local dependency {name}"))?;
+                    if !dependency_path.starts_with(&root) {
+                        bail!("Local dependency {name} is outside of the package archive");
+                    }
+                    to_visit.push(dependency_path);
+                }
+                DependencyKind::Git(GitInfo { git_url, .. }) => {
+                    if !allowed_git_dependencies
+                        .iter()
+                        .any(|allowed| same_git_repository(allowed, git_url.as_str()))
+                    {
+                        bail!("Git dependency {name} on {git_url} is not allowed");
+                    }
+                }
+                DependencyKind::Custom(_) => bail!("Unsupported custom dependency {name}"),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compares repository URLs, ignoring case, a trailing slash and the `.git` suffix.
+fn same_git_repository(a: &str, b: &str) -> bool {
+    let normalize = |url: &str| {
+        url.trim_end_matches('/')
+            .trim_end_matches(".git")
+            .to_lowercase()
+    };
+    normalize(a) == normalize(b)
+}
+
+/// Builds the package in the gzipped tarball `archive`, and verifies it against the package
+/// published at `address` on `network`. The build waits for one of `build_permits`, which it
+/// holds until it completes, even if this future is dropped.
+pub async fn verify_package_archive(
+    network: &Network,
+    address: AccountAddress,
+    archive: &[u8],
+    allowed_git_dependencies: &[String],
+    build_permits: Arc<Semaphore>,
+) -> anyhow::Result<AddressLookup> {
+    let tmp_dir = tempfile::tempdir()?;
+    let package_path = unpack_package_archive(archive, tmp_dir.path())?;
+    check_package_dependencies(&package_path, tmp_dir.path(), allowed_git_dependencies)?;
+    // A lock file could pin other dependencies than the checked manifests, so they are resolved
+    // again.
+    let lock_file = package_path.join(SourcePackageLayout::Lock.path());
+    if lock_file.exists() {
+        fs::remove_file(lock_file)?;
+    }
+    let permit = build_permits.acquire_owned().await?;
+    let build_path = package_path.clone();
+    let compiled_package = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        build_package(&build_path)
+    })
+    .await??;
+    let (_, lookup) = verify_compiled_package(network, &compiled_package, &package_path).await?;
+    if !lookup.contains_key(&address) {
+        bail!(
+            "The published-at address in the package manifest does not match the requested address {address}"
+        );
+    }
+    Ok(lookup)
+}
+
+/// Writes the verified sources in `lookup` under `dir`, as `<network>/<address>/<module>.move`,
+/// so that they can be served again after a restart. Sources previously written for the same
+/// address are removed. Returns `lookup` with paths pointing to the written files.
+pub fn persist_sources(
+    dir: &Path,
+    network: &Network,
+    lookup: AddressLookup,
+) -> anyhow::Result<AddressLookup> {
+    let mut persisted = AddressLookup::new();
+    for (address, modules) in lookup {
+        let address_dir = dir.join(network.to_string()).join(address.to_hex_literal());
+        if address_dir.exists() {
+            fs::remove_dir_all(&address_dir)?;
+        }
+        fs::create_dir_all(&address_dir)?;
+        let source_lookup = persisted.entry(address).or_insert_with(SourceLookup::new);
+        for (name, SourceInfo { path, source }) in modules {
+            let source = match source {
+                Some(source) => source,
+                None => fs::read_to_string(&path)?,
+            };
+            let path = address_dir.join(format!("{name}.move"));
+            fs::write(&path, &source)?;
+            source_lookup.insert(
+                name,
+                SourceInfo {
+                    path,
+                    source: Some(source),
+                },
+            );
+        }
+    }
+    Ok(persisted)
+}
+
+/// Loads the sources written under `dir` by `persist_sources`.
+pub fn load_persisted_sources(dir: &Path) -> anyhow::Result<NetworkLookup> {
+    let mut lookup = NetworkLookup::new();
+    if !dir.is_dir() {
+        return Ok(lookup);
+    }
+    for network_dir in fs::read_dir(dir)? {
+        let network_dir = network_dir?.path();
+        let Some(network) = network_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| Network::from_str(name).ok())
+        else {
+            continue;
+        };
+        let address_lookup = lookup.entry(network).or_insert_with(AddressLookup::new);
+        for address_dir in fs::read_dir(&network_dir)? {
+            let address_dir = address_dir?.path();
+            let Some(address) = address_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| AccountAddress::from_hex_literal(name).ok())
+            else {
+                continue;
+            };
+            let source_lookup = address_lookup
+                .entry(address)
+                .or_insert_with(SourceLookup::new);
+            for module in fs::read_dir(&address_dir)? {
+                let path = module?.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("move") {
+                    continue;
+                }
+                let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                let source = Some(fs::read_to_string(&path)?);
+                source_lookup.insert(Symbol::from(name), SourceInfo { path, source });
+            }
+        }
+    }
+    Ok(lookup)
+}
+
 pub fn parse_config(config_path: impl AsRef<Path>) -> anyhow::Result<Config> {
     let contents = fs::read_to_string(config_path)?;
     Ok(toml::from_str(&contents)?)
@@ -456,26 +732,42 @@ pub async fn watch_for_upgrades(
 pub struct AppState {
     pub sources: NetworkLookup,
     pub metrics: Option<SourceServiceMetrics>,
+    /// Where to persist the sources of packages verified through `/api/verify`. When `None`, they
+    /// are only served until the server restarts.
+    pub sources_dir: Option<PathBuf>,
+    /// Repositories that packages verified through `/api/verify` may have git dependencies on.
+    pub allowed_git_dependencies: Vec<String>,
 }
 
 pub fn serve(
     app_state: Arc<RwLock<AppState>>,
 ) -> anyhow::Result<Server<AddrIncoming, IntoMakeService<Router>>> {
-    let app = Router::new()
+    let listener = TcpListener::bind(host_port())?;
+    Ok(Server::from_tcp(listener)?.serve(router(app_state).into_make_service()))
+}
+
+pub fn router(app_state: Arc<RwLock<AppState>>) -> Router {
+    Router::new()
         .route("/api", get(api_route))
         .route("/api/list", get(list_route))
+        .route(
+            "/api/verify",
+            post(verify_route)
+                .layer(DefaultBodyLimit::max(MAX_PACKAGE_ARCHIVE_SIZE))
+                .layer(Extension(Arc::new(Semaphore::new(
+                    MAX_CONCURRENT_PACKAGE_BUILDS,
+                )))),
+        )
         .layer(
             ServiceBuilder::new()
                 .layer(
                     tower_http::cors::CorsLayer::new()
-                        .allow_methods([Method::GET])
+                        .allow_methods([Method::GET, Method::POST])
                         .allow_origin(tower_http::cors::Any),
                 )
                 .layer(middleware::from_fn(check_version_header)),
         )
-        .with_state(app_state);
-    let listener = TcpListener::bind(host_port())?;
-    Ok(Server::from_tcp(listener)?.serve(app.into_make_service()))
+        .with_state(app_state)
 }
 
 #[derive(Deserialize)]
@@ -496,6 +788,19 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
+#[derive(Deserialize)]
+pub struct VerifyRequest {
+    #[serde(default)]
+    network: Network,
+    address: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct VerifyResponse {
+    /// Names of the modules whose source was verified.
+    pub modules: Vec<String>,
+}
+
 async fn api_route(
     State(app_state): State<Arc<RwLock<AppState>>>,
     Query(Request {
@@ -548,6 +853,102 @@ async fn api_route(
     }
 }
 
+async fn verify_route(
+    State(app_state): State<Arc<RwLock<AppState>>>,
+    Extension(build_permits): Extension<Arc<Semaphore>>,
+    Query(VerifyRequest { network, address }): Query<VerifyRequest>,
+    archive: Bytes,
+) -> impl IntoResponse {
+    debug!("verify request network={network}&address={address}");
+    let Ok(address) = AccountAddress::from_hex_literal(&address) else {
+        let error = format!("Invalid hex address {address}");
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse { error }).into_response(),
+        );
+    };
+
+    let (sources_dir, allowed_git_dependencies) = {
+        let app_state = app_state.read().unwrap();
+        if let Some(metrics) = &app_state.metrics {
+            metrics.total_requests_received.inc();
+        }
+        (
+            app_state.sources_dir.clone(),
+            app_state.allowed_git_dependencies.clone(),
+        )
+    };
+
+    let verification = tokio::time::timeout(
+        VERIFY_TIMEOUT,
+        verify_package_archive(
+            &network,
+            address,
+            &archive,
+            &allowed_git_dependencies,
+            build_permits,
+        ),
+    )
+    .await;
+    let Ok(result) = verification else {
+        info!("timed out verifying package at {address} on {network}");
+        let error = format!(
+            "Timed out verifying package at {address} on network {network} after {}s",
+            VERIFY_TIMEOUT.as_secs()
+        );
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse { error }).into_response(),
+        );
+    };
+    let result = result.and_then(|lookup| {
+        let modules: Vec<_> = lookup
+            .values()
+            .flat_map(|modules| modules.keys().map(|name| name.to_string()))
+            .collect();
+        // Sources that are already served, such as those verified from the config, take
+        // precedence, as they do over persisted sources on startup.
+        let lookup: AddressLookup = {
+            let app_state = app_state.read().unwrap();
+            let served = app_state.sources.get(&network);
+            lookup
+                .into_iter()
+                .filter(|(address, _)| !served.is_some_and(|served| served.contains_key(address)))
+                .collect()
+        };
+        let lookup = match &sources_dir {
+            Some(dir) => persist_sources(dir, &network, lookup)?,
+            None => lookup,
+        };
+        Ok((modules, lookup))
+    });
+    let (modules, lookup) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            info!("failed to verify package at {address} on {network}: {e}");
+            let error = format!("Failed to verify package at {address} on network {network}: {e}");
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse { error }).into_response(),
+            );
+        }
+    };
+
+    info!("verified package at {address} on {network}");
+    let mut app_state = app_state.write().unwrap();
+    let served = app_state
+        .sources
+        .entry(network)
+        .or_insert_with(AddressLookup::new);
+    for (address, source_lookup) in lookup {
+        served.entry(address).or_insert(source_lookup);
+    }
+    (
+        StatusCode::OK,
+        Json(VerifyResponse { modules }).into_response(),
+    )
+}
+
 async fn check_version_header<B>(
     headers: HeaderMap,
     req: hyper::Request<B>,
@@ -10,14 +10,22 @@ use clap::Parser;
 use telemetry_subscribers::TelemetryConfig;
 
 use sui_source_validation_service::{
-    host_port, initialize, parse_config, serve, start_prometheus_server, watch_for_upgrades,
-    AppState, DirectorySource, Network, PackageSource, RepositorySource, SourceServiceMetrics,
-    METRICS_HOST_PORT,
+    host_port, initialize, load_persisted_sources, parse_config, serve, start_prometheus_server,
+    watch_for_upgrades, AppState, DirectorySource, Network, PackageSource, RepositorySource,
+    SourceServiceMetrics, METRICS_HOST_PORT, SUI_REPOSITORY_URL,
 };
 
 #[derive(Parser, Debug)]
 struct Args {
     config_path: PathBuf,
+    /// Directory where the sources of packages verified through `/api/verify` are persisted, and
+    /// loaded from on startup.
+    #[arg(long)]
+    sources_dir: Option<PathBuf>,
+    /// Repository that packages verified through `/api/verify` may have git dependencies on. Can
+    /// be repeated.
+    #[arg(long = "allow-git-dependency", default_values_t = [SUI_REPOSITORY_URL.to_string()])]
+    allowed_git_dependencies: Vec<String>,
 }
 
 const GIT_REVISION: &str = {
@@ -40,8 +48,17 @@ pub async fn main() -> anyhow::Result<()> {
     let package_config = parse_config(args.config_path)?;
     let tmp_dir = tempfile::tempdir()?;
     let start = tokio::time::Instant::now();
-    let sources = initialize(&package_config, tmp_dir.path()).await?;
+    let mut sources = initialize(&package_config, tmp_dir.path()).await?;
     info!("verification complete in {:?}", start.elapsed());
+    if let Some(sources_dir) = &args.sources_dir {
+        // Sources verified from the config take precedence over persisted ones.
+        for (network, persisted) in load_persisted_sources(sources_dir)? {
+            let address_lookup = sources.entry(network).or_default();
+            for (address, source_lookup) in persisted {
+                address_lookup.entry(address).or_insert(source_lookup);
+            }
+        }
+    }
 
     let metrics_listener = std::net::TcpListener::bind(METRICS_HOST_PORT)?;
     let registry_service = start_prometheus_server(metrics_listener);
@@ -51,6 +68,8 @@ pub async fn main() -> anyhow::Result<()> {
     let app_state = Arc::new(RwLock::new(AppState {
         sources,
         metrics: Some(metrics),
+        sources_dir: args.sources_dir,
+        allowed_git_dependencies: args.allowed_git_dependencies,
     }));
     let mut threads = vec![];
     let networks_to_watch = vec![
@@ -23,10 +23,13 @@ use tokio::sync::oneshot;
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::Symbol;
 use sui_source_validation_service::{
-    host_port, initialize, serve, start_prometheus_server, verify_packages, watch_for_upgrades,
-    AddressLookup, AppState, CloneCommand, Config, DirectorySource, ErrorResponse, Network,
-    NetworkLookup, Package, PackageSource, RepositorySource, SourceInfo, SourceLookup,
-    SourceResponse, SourceServiceMetrics, METRICS_HOST_PORT, SUI_SOURCE_VALIDATION_VERSION_HEADER,
+    check_package_dependencies, host_port, initialize, load_persisted_sources, persist_sources,
+    router, serve, start_prometheus_server, unpack_package_archive, verify_packages,
+    watch_for_upgrades, AddressLookup, AppState, CloneCommand, Config, DirectorySource,
+    ErrorResponse, Network, NetworkLookup, Package, PackageSource, RepositorySource, SourceInfo,
+    SourceLookup, SourceResponse, SourceServiceMetrics, VerifyResponse,
+    MAX_PACKAGE_ARCHIVE_ENTRIES, MAX_UNPACKED_PACKAGE_SIZE, METRICS_HOST_PORT, SUI_REPOSITORY_URL,
+    SUI_SOURCE_VALIDATION_VERSION_HEADER,
 };
 use test_cluster::TestClusterBuilder;
 
@@ -93,6 +96,8 @@ async fn test_end_to_end() -> anyhow::Result<()> {
     let app_state = Arc::new(RwLock::new(AppState {
         sources,
         metrics: None,
+        sources_dir: None,
+        allowed_git_dependencies: vec![],
     }));
     let app_state_ref = app_state.clone();
     let (tx, rx) = oneshot::channel();
@@ -160,9 +165,84 @@ Multiple source verification errors found:
 - Local dependency did not match its on-chain version at 0000000000000000000000000000000000000000000000000000000000000001::MoveStdlib::address"#
     ];
     expected.assert_eq(truncated_error_message);
+
+    ///////////////////////////
+    // Test /api/verify
+    //////////////////////////
+    let app_state = Arc::new(RwLock::new(AppState {
+        sources: NetworkLookup::new(),
+        metrics: None,
+        sources_dir: None,
+        allowed_git_dependencies: vec![],
+    }));
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/api", listener.local_addr()?);
+    tokio::spawn(hyper::Server::from_tcp(listener)?.serve(router(app_state).into_make_service()));
+
+    let client = Client::new();
+    let verify = |archive: Vec<u8>| {
+        client
+            .post(format!(
+                "{url}/verify?address={package_id}&network=localnet"
+            ))
+            .body(archive)
+            .send()
+    };
+    let source =
+        fs::read_to_string(PathBuf::from(TEST_FIXTURES_DIR).join("custom/sources/custom.move"))?;
+
+    // The source of the published package is verified, and then served.
+    let response = verify(custom_package_archive(package_id, &source)?).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let json = response.json::<VerifyResponse>().await?;
+    assert_eq!(json.modules, vec!["foo".to_string()]);
+    let json = client
+        .get(format!(
+            "{url}?address={package_id}&module=foo&network=localnet"
+        ))
+        .send()
+        .await?
+        .json::<SourceResponse>()
+        .await?;
+    assert_eq!(json.source, source);
+
+    // A source that does not match the published bytecode is rejected.
+    let modified = source.replace("        0", "        1");
+    assert_ne!(modified, source);
+    let response = verify(custom_package_archive(package_id, &modified)?).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let json = response.json::<ErrorResponse>().await?;
+    assert!(json.error.starts_with("Failed to verify package at"));
+
+    // So is a body that is not a gzipped tarball.
+    let response = verify(b"not a package archive".to_vec()).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
     Ok(())
 }
 
+/// A gzipped tarball of the `custom` fixture package published at `package_id`, with `source` as
+/// the source of its module.
+fn custom_package_archive(package_id: ObjectID, source: &str) -> anyhow::Result<Vec<u8>> {
+    let manifest = format!(
+        "[package]\nname = \"custom\"\nversion = \"0.0.1\"\npublished-at = \"{package_id}\"\n\n\
+         [addresses]\ncustom = \"{package_id}\"\n"
+    );
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    for (path, contents) in [
+        ("custom/Move.toml", manifest.as_str()),
+        ("custom/sources/custom.move", source),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, path, contents.as_bytes())?;
+    }
+    Ok(builder.into_inner()?.finish()?)
+}
+
 async fn run_publish(
     package_path: PathBuf,
     context: &mut WalletContext,
@@ -296,6 +376,8 @@ async fn test_api_route() -> anyhow::Result<()> {
     let app_state = Arc::new(RwLock::new(AppState {
         sources,
         metrics: None,
+        sources_dir: None,
+        allowed_git_dependencies: vec![],
     }));
     tokio::spawn(serve(app_state).expect("Cannot start service."));
 
@@ -482,3 +564,172 @@ fn test_clone_command() -> anyhow::Result<()> {
     expect.assert_eq(&format!("{:#?}", command));
     Ok(())
 }
+
+#[test]
+fn test_unpack_package_archive() -> anyhow::Result<()> {
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    builder.append_dir_all("custom", PathBuf::from(TEST_FIXTURES_DIR).join("custom"))?;
+    let archive = builder.into_inner()?.finish()?;
+
+    let tmp_dir = tempfile::tempdir()?;
+    let package_path = unpack_package_archive(&archive, tmp_dir.path())?;
+    assert_eq!(package_path, tmp_dir.path().join("custom"));
+    assert_eq!(
+        fs::read_to_string(package_path.join("sources/custom.move"))?,
+        fs::read_to_string(PathBuf::from(TEST_FIXTURES_DIR).join("custom/sources/custom.move"))?
+    );
+
+    // An archive without a manifest is rejected.
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    builder.append_dir_all(
+        "sources",
+        PathBuf::from(TEST_FIXTURES_DIR).join("custom/sources"),
+    )?;
+    let archive = builder.into_inner()?.finish()?;
+    let tmp_dir = tempfile::tempdir()?;
+    let error = unpack_package_archive(&archive, tmp_dir.path()).unwrap_err();
+    let expected = expect!["Could not find a Move.toml at the root of the package archive"];
+    expected.assert_eq(&error.to_string());
+
+    // So is an archive with too many entries.
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    for i in 0..=MAX_PACKAGE_ARCHIVE_ENTRIES {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(0);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, format!("custom/{i}.move"), std::io::empty())?;
+    }
+    let archive = builder.into_inner()?.finish()?;
+    let tmp_dir = tempfile::tempdir()?;
+    let error = unpack_package_archive(&archive, tmp_dir.path()).unwrap_err();
+    let expected = expect!["Package archive has more than 1000 entries"];
+    expected.assert_eq(&error.to_string());
+
+    // And an archive that unpacks to too many bytes, even if it is small once compressed.
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    let size = MAX_UNPACKED_PACKAGE_SIZE + 1;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(size);
+    header.set_mode(0o644);
+    builder.append_data(
+        &mut header,
+        "custom/sources/custom.move",
+        std::io::repeat(0).take(size),
+    )?;
+    let archive = builder.into_inner()?.finish()?;
+    let tmp_dir = tempfile::tempdir()?;
+    let error = unpack_package_archive(&archive, tmp_dir.path()).unwrap_err();
+    let expected = expect!["Package archive unpacks to more than 20971520 bytes"];
+    expected.assert_eq(&error.to_string());
+    assert!(!tmp_dir.path().join("custom/sources/custom.move").exists());
+    Ok(())
+}
+
+#[test]
+fn test_check_package_dependencies() -> anyhow::Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let archive_dir = tmp_dir.path().join("archive");
+    let write_package = |path: PathBuf, dependencies: &str| -> anyhow::Result<PathBuf> {
+        fs::create_dir_all(&path)?;
+        fs::write(
+            path.join("Move.toml"),
+            format!(
+                "[package]\nname = \"p\"\nversion = \"0.0.1\"\n\n[dependencies]\n{dependencies}\n"
+            ),
+        )?;
+        Ok(path)
+    };
+    let allowed = vec![SUI_REPOSITORY_URL.to_string()];
+    let check = |package_path: &PathBuf| {
+        check_package_dependencies(package_path, &archive_dir, &allowed).map_err(|e| e.to_string())
+    };
+
+    // Local dependencies in the archive and git dependencies on allowed repositories are fine.
+    let sui_dependency = r#"Sui = { git = "https://github.com/mystenlabs/sui", subdir = "crates/sui-framework/packages/sui-framework", rev = "main" }"#;
+    write_package(archive_dir.join("dep"), sui_dependency)?;
+    let package = write_package(archive_dir.join("package"), r#"Dep = { local = "../dep" }"#)?;
+    assert_eq!(check(&package), Ok(()));
+
+    // Local dependencies outside of the archive are not.
+    write_package(tmp_dir.path().join("outside"), "")?;
+    let package = write_package(
+        archive_dir.join("package"),
+        r#"Outside = { local = "../../outside" }"#,
+    )?;
+    let expected = expect!["Local dependency Outside is outside of the package archive"];
+    expected.assert_eq(&check(&package).unwrap_err());
+
+    // Neither are git dependencies on other repositories, including those of local dependencies.
+    write_package(
+        archive_dir.join("dep"),
+        r#"Other = { git = "https://example.com/other.git", rev = "main" }"#,
+    )?;
+    let package = write_package(archive_dir.join("package"), r#"Dep = { local = "../dep" }"#)?;
+    let expected = expect!["Git dependency Other on https://example.com/other.git is not allowed"];
+    expected.assert_eq(&check(&package).unwrap_err());
+    Ok(())
+}
+
+#[test]
+fn test_persisted_sources() -> anyhow::Result<()> {
+    let address = AccountAddress::from_hex_literal("0xc0ffee")?;
+    let mut source_lookup = SourceLookup::new();
+    source_lookup.insert(
+        Symbol::from("custom"),
+        SourceInfo {
+            path: PathBuf::from(TEST_FIXTURES_DIR).join("custom/sources/custom.move"),
+            source: Some("module custom::custom {...}".to_owned()),
+        },
+    );
+    let mut address_lookup = AddressLookup::new();
+    address_lookup.insert(address, source_lookup);
+
+    let tmp_dir = tempfile::tempdir()?;
+    let persisted = persist_sources(tmp_dir.path(), &Network::Testnet, address_lookup)?;
+    let path = &persisted[&address][&Symbol::from("custom")].path;
+    assert_eq!(
+        path,
+        &tmp_dir
+            .path()
+            .join("testnet")
+            .join("0xc0ffee")
+            .join("custom.move")
+    );
+
+    let sources = load_persisted_sources(tmp_dir.path())?;
+    assert_eq!(sources.len(), 1);
+    let source_info = &sources[&Network::Testnet][&address][&Symbol::from("custom")];
+    assert_eq!(&source_info.path, path);
+    let expected = expect!["module custom::custom {...}"];
+    expected.assert_eq(source_info.source.as_deref().unwrap());
+
+    // Persisting the address again replaces all of its previous sources.
+    let mut source_lookup = SourceLookup::new();
+    source_lookup.insert(
+        Symbol::from("other"),
+        SourceInfo {
+            path: PathBuf::from(TEST_FIXTURES_DIR).join("custom/sources/custom.move"),
+            source: Some("module custom::other {...}".to_owned()),
+        },
+    );
+    let mut address_lookup = AddressLookup::new();
+    address_lookup.insert(address, source_lookup);
+    persist_sources(tmp_dir.path(), &Network::Testnet, address_lookup)?;
+    assert!(!path.exists());
+    let sources = load_persisted_sources(tmp_dir.path())?;
+    let modules: Vec<_> = sources[&Network::Testnet][&address].keys().collect();
+    assert_eq!(modules, vec![&Symbol::from("other")]);
+    Ok(())
+}
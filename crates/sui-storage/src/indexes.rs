@@ -9,6 +9,7 @@ use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use itertools::Itertools;
 use move_core_types::identifier::Identifier;
@@ -30,11 +31,13 @@ use sui_types::digests::TransactionEventsDigest;
 use sui_types::dynamic_field::{self, DynamicFieldInfo};
 use sui_types::effects::TransactionEvents;
 use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::event::Event;
 use sui_types::inner_temporary_store::TxCoins;
 use sui_types::object::{Object, Owner};
 use sui_types::parse_sui_struct_tag;
 use tokio::task::spawn_blocking;
 use tracing::{debug, trace};
+use typed_store::rocks::retention::RetentionPolicy;
 use typed_store::rocks::{
     default_db_options, read_size_from_env, DBBatch, DBMap, DBOptions, MetricConf,
 };
@@ -144,6 +147,11 @@ pub struct IndexStoreCacheUpdates {
 pub struct IndexStoreTables {
     /// Index from sui address to transactions initiated by that address.
     #[default_options_override_fn = "transactions_from_addr_table_default_config"]
+    #[secondary_index(
+        primary = "transaction_order",
+        entries_fn = "transactions_from_addr_index_entries",
+        source = "TransactionIndexSource"
+    )]
     transactions_from_addr: DBMap<(SuiAddress, TxSequenceNumber), TransactionDigest>,
 
     /// Index from sui address to transactions that were sent to that address.
@@ -160,6 +168,11 @@ pub struct IndexStoreTables {
 
     /// Index from package id, module and function identifier to transactions that used that moce function call as input.
     #[default_options_override_fn = "transactions_by_move_function_table_default_config"]
+    #[secondary_index(
+        primary = "transaction_order",
+        entries_fn = "transactions_by_move_function_index_entries",
+        source = "TransactionIndexSource"
+    )]
     transactions_by_move_function:
         DBMap<(ObjectID, String, String, TxSequenceNumber), TransactionDigest>,
 
@@ -179,6 +192,10 @@ pub struct IndexStoreTables {
 
     /// Index from transaction digest to sequence number.
     #[default_options_override_fn = "transactions_seq_table_default_config"]
+    #[secondary_index(
+        primary = "transaction_order",
+        entries_fn = "transactions_seq_index_entries"
+    )]
     transactions_seq: DBMap<TransactionDigest, TxSequenceNumber>,
 
     /// This is an index of object references to currently existing objects, indexed by the
@@ -204,14 +221,39 @@ pub struct IndexStoreTables {
     #[default_options_override_fn = "index_table_default_config"]
     event_order: DBMap<EventId, EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
+    #[secondary_index(
+        primary = "event_order",
+        entries_fn = "event_by_move_module_index_entries",
+        source = "Event"
+    )]
     event_by_move_module: DBMap<(ModuleId, EventId), EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
+    #[secondary_index(
+        primary = "event_order",
+        entries_fn = "event_by_move_event_index_entries",
+        source = "Event"
+    )]
     event_by_move_event: DBMap<(StructTag, EventId), EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
+    #[secondary_index(
+        primary = "event_order",
+        entries_fn = "event_by_event_module_index_entries",
+        source = "Event"
+    )]
     event_by_event_module: DBMap<(ModuleId, EventId), EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
+    #[secondary_index(
+        primary = "event_order",
+        entries_fn = "event_by_sender_index_entries",
+        source = "Event"
+    )]
     event_by_sender: DBMap<(SuiAddress, EventId), EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
+    #[secondary_index(
+        primary = "event_order",
+        entries_fn = "event_by_time_index_entries",
+        source = "Event"
+    )]
     event_by_time: DBMap<(u64, EventId), EventIndex>,
 }
 
@@ -233,6 +275,117 @@ pub struct IndexStore {
     max_type_length: u64,
 }
 
+/// Entries of the `transactions_seq` index derived from a `transaction_order` entry
+fn transactions_seq_index_entries(
+    sequence: &TxSequenceNumber,
+    digest: &TransactionDigest,
+) -> [(TransactionDigest, TxSequenceNumber); 1] {
+    [(*digest, *sequence)]
+}
+
+/// Data of an indexed transaction which is not stored in `transaction_order`
+pub struct TransactionIndexSource {
+    sender: SuiAddress,
+    move_functions: Vec<(ObjectID, String, String)>,
+}
+
+impl TransactionIndexSource {
+    /// Source of the indexes of a transaction, for verifying or rebuilding them from the
+    /// transaction data with `verify_transaction_order_indexes_with_source`.
+    pub fn new(sender: SuiAddress, move_functions: Vec<(ObjectID, String, String)>) -> Self {
+        Self {
+            sender,
+            move_functions,
+        }
+    }
+}
+
+/// Entries of the `transactions_from_addr` index of a transaction
+fn transactions_from_addr_index_entries(
+    sequence: &TxSequenceNumber,
+    digest: &TransactionDigest,
+    source: &TransactionIndexSource,
+) -> [((SuiAddress, TxSequenceNumber), TransactionDigest); 1] {
+    [((source.sender, *sequence), *digest)]
+}
+
+/// Entries of the `transactions_by_move_function` index of a transaction
+fn transactions_by_move_function_index_entries(
+    sequence: &TxSequenceNumber,
+    digest: &TransactionDigest,
+    source: &TransactionIndexSource,
+) -> Vec<(
+    (ObjectID, String, String, TxSequenceNumber),
+    TransactionDigest,
+)> {
+    source
+        .move_functions
+        .iter()
+        .map(|(package, module, function)| {
+            (
+                (*package, module.clone(), function.clone(), *sequence),
+                *digest,
+            )
+        })
+        .collect()
+}
+
+/// Entries of the `event_by_move_module` index of an event, by the module which emitted it
+fn event_by_move_module_index_entries(
+    id: &EventId,
+    index: &EventIndex,
+    event: &Event,
+) -> [((ModuleId, EventId), EventIndex); 1] {
+    let module = ModuleId::new(event.package_id.into(), event.transaction_module.clone());
+    [((module, *id), *index)]
+}
+
+/// Entries of the `event_by_move_event` index of an event
+fn event_by_move_event_index_entries(
+    id: &EventId,
+    index: &EventIndex,
+    event: &Event,
+) -> [((StructTag, EventId), EventIndex); 1] {
+    [((event.type_.clone(), *id), *index)]
+}
+
+/// Entries of the `event_by_event_module` index of an event, by the module of its type
+fn event_by_event_module_index_entries(
+    id: &EventId,
+    index: &EventIndex,
+    event: &Event,
+) -> [((ModuleId, EventId), EventIndex); 1] {
+    let module = ModuleId::new(event.type_.address, event.type_.module.clone());
+    [((module, *id), *index)]
+}
+
+/// Entries of the `event_by_sender` index of an event
+fn event_by_sender_index_entries(
+    id: &EventId,
+    index: &EventIndex,
+    event: &Event,
+) -> [((SuiAddress, EventId), EventIndex); 1] {
+    [((event.sender, *id), *index)]
+}
+
+/// Entries of the `event_by_time` index of an event. Its timestamp is in the `event_order` entry,
+/// so the event itself is unused; it is declared with the same source as the other event indexes
+/// to be verified and rebuilt together with them.
+fn event_by_time_index_entries(
+    id: &EventId,
+    index: &EventIndex,
+    _event: &Event,
+) -> [((u64, EventId), EventIndex); 1] {
+    [((index.2, *id), *index)]
+}
+
+/// Timestamp of the serialized `EventIndex` value of an event table entry
+fn event_index_timestamp_ms(value: &[u8]) -> Option<u64> {
+    bcs::from_bytes::<EventIndex>(value)
+        .ok()
+        .map(|(_, _, timestamp_ms)| timestamp_ms)
+}
+
 // These functions are used to initialize the DB tables
 fn transactions_order_table_default_config() -> DBOptions {
     default_db_options()
@@ -277,8 +430,40 @@ fn coin_index_table_default_config() -> DBOptions {
 
 impl IndexStore {
     pub fn new(path: PathBuf, registry: &Registry, max_type_length: Option<u64>) -> Self {
-        let tables =
-            IndexStoreTables::open_tables_read_write(path, MetricConf::default(), None, None);
+        Self::new_with_event_retention(path, registry, max_type_length, None)
+    }
+
+    /// Same as `new`, but events older than `event_retention` are dropped from every event table
+    /// by compactions, if set.
+    pub fn new_with_event_retention(
+        path: PathBuf,
+        registry: &Registry,
+        max_type_length: Option<u64>,
+        event_retention: Option<Duration>,
+    ) -> Self {
+        let tables_config = event_retention.map(|retention| {
+            // All event tables hold an `EventIndex` value, so reading its timestamp drops an
+            // event from all of them
+            let policy = RetentionPolicy::value_timestamp_ms(event_index_timestamp_ms, retention);
+            let mut config = IndexStoreTablesConfigurator::init_with_table_defaults();
+            for (name, options) in [
+                ("event_order", &mut config.event_order),
+                ("event_by_move_module", &mut config.event_by_move_module),
+                ("event_by_move_event", &mut config.event_by_move_event),
+                ("event_by_event_module", &mut config.event_by_event_module),
+                ("event_by_sender", &mut config.event_by_sender),
+                ("event_by_time", &mut config.event_by_time),
+            ] {
+                *options = options.clone().set_retention_policy(name, policy.clone());
+            }
+            config.build()
+        });
+        let tables = IndexStoreTables::open_tables_read_write(
+            path,
+            MetricConf::default(),
+            None,
+            tables_config,
+        );
         let metrics = IndexStoreMetrics::new(registry);
         let caches = IndexStoreCaches {
             per_coin_type_balance: ShardedLruCache::new(1_000_000, 1000),
@@ -465,19 +650,20 @@ impl IndexStore {
         let sequence = self.next_sequence_number.fetch_add(1, Ordering::SeqCst);
         let mut batch = self.tables.transactions_from_addr.batch();
 
-        batch.insert_batch(
-            &self.tables.transaction_order,
-            std::iter::once((sequence, *digest)),
-        )?;
-
-        batch.insert_batch(
-            &self.tables.transactions_seq,
-            std::iter::once((*digest, sequence)),
-        )?;
-
-        batch.insert_batch(
-            &self.tables.transactions_from_addr,
-            std::iter::once(((sender, sequence), *digest)),
+        self.tables.insert_new_transaction_order_with_indexes(
+            &mut batch,
+            std::iter::once((
+                sequence,
+                *digest,
+                TransactionIndexSource {
+                    sender,
+                    move_functions: move_functions
+                        .map(|(obj_id, module, function)| {
+                            (obj_id, module.to_string(), function.to_string())
+                        })
+                        .collect(),
+                },
+            )),
         )?;
 
         batch.insert_batch(
@@ -492,16 +678,6 @@ impl IndexStore {
                 .map(|(obj_ref, _)| ((obj_ref.0, sequence), *digest)),
         )?;
 
-        batch.insert_batch(
-            &self.tables.transactions_by_move_function,
-            move_functions.map(|(obj_id, module, function)| {
-                (
-                    (obj_id, module.to_string(), function.to_string(), sequence),
-                    *digest,
-                )
-            }),
-        )?;
-
         batch.insert_batch(
             &self.tables.transactions_to_addr,
             mutated_objects.filter_map(|(_, owner)| {
@@ -539,66 +715,13 @@ impl IndexStore {
 
         // events
         let event_digest = events.digest();
-        batch.insert_batch(
-            &self.tables.event_order,
-            events
-                .data
-                .iter()
-                .enumerate()
-                .map(|(i, _)| ((sequence, i), (event_digest, *digest, timestamp_ms))),
-        )?;
-        batch.insert_batch(
-            &self.tables.event_by_move_module,
-            events
-                .data
-                .iter()
-                .enumerate()
-                .map(|(i, e)| {
-                    (
-                        i,
-                        ModuleId::new(e.package_id.into(), e.transaction_module.clone()),
-                    )
-                })
-                .map(|(i, m)| ((m, (sequence, i)), (event_digest, *digest, timestamp_ms))),
-        )?;
-        batch.insert_batch(
-            &self.tables.event_by_sender,
+        self.tables.insert_new_event_order_with_indexes(
+            &mut batch,
             events.data.iter().enumerate().map(|(i, e)| {
                 (
-                    (e.sender, (sequence, i)),
-                    (event_digest, *digest, timestamp_ms),
-                )
-            }),
-        )?;
-        batch.insert_batch(
-            &self.tables.event_by_move_event,
-            events.data.iter().enumerate().map(|(i, e)| {
-                (
-                    (e.type_.clone(), (sequence, i)),
-                    (event_digest, *digest, timestamp_ms),
-                )
-            }),
-        )?;
-
-        batch.insert_batch(
-            &self.tables.event_by_time,
-            events.data.iter().enumerate().map(|(i, _)| {
-                (
-                    (timestamp_ms, (sequence, i)),
-                    (event_digest, *digest, timestamp_ms),
-                )
-            }),
-        )?;
-
-        batch.insert_batch(
-            &self.tables.event_by_event_module,
-            events.data.iter().enumerate().map(|(i, e)| {
-                (
-                    (
-                        ModuleId::new(e.type_.address, e.type_.module.clone()),
-                        (sequence, i),
-                    ),
+                    (sequence, i),
                     (event_digest, *digest, timestamp_ms),
+                    e.clone(),
                 )
             }),
         )?;
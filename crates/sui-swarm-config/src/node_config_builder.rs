@@ -171,6 +171,8 @@ impl ValidatorConfigBuilder {
                 .unwrap_or(3600),
             zklogin_oauth_providers: default_zklogin_oauth_providers(),
             overload_threshold_config: Default::default(),
+            execution_cache_config: None,
+            event_index_retention_secs: None,
         }
     }
 
@@ -408,6 +410,8 @@ impl FullnodeConfigBuilder {
             jwk_fetch_interval_seconds: 3600,
             zklogin_oauth_providers: default_zklogin_oauth_providers(),
             overload_threshold_config: Default::default(),
+            execution_cache_config: None,
+            event_index_retention_secs: None,
         }
     }
 }
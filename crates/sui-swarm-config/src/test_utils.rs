@@ -152,6 +152,16 @@ impl CommitteeFixture {
         self.make_checkpoints(number_of_checkpoints, previous_checkpoint, empty_contents)
     }
 
+    pub fn make_checkpoint_with_contents(
+        &self,
+        previous_checkpoint: VerifiedCheckpoint,
+        contents: VerifiedCheckpointContents,
+    ) -> VerifiedCheckpoint {
+        let (mut checkpoints, _, _, _) =
+            self.make_checkpoints(1, Some(previous_checkpoint), || contents.clone());
+        checkpoints.pop().unwrap()
+    }
+
     fn make_checkpoints<F: Fn() -> VerifiedCheckpointContents>(
         &self,
         number_of_checkpoints: usize,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use futures::FutureExt;
+use mysten_network::fault_injection;
 use std::sync::{Arc, Weak};
 use std::thread;
 use sui_config::NodeConfig;
@@ -45,6 +46,12 @@ impl Container {
         let (cancel_sender, cancel_receiver) = tokio::sync::oneshot::channel();
 
         let thread = thread::spawn(move || {
+            // Attribute all network traffic originating from this node's threads to it, so that
+            // faults injected between nodes apply.
+            let node_id =
+                AuthorityPublicKeyBytes::from(config.protocol_key_pair().public()).to_string();
+            fault_injection::set_current_node(Some(node_id.clone()));
+
             let span = if get_global_telemetry_config()
                 .map(|c| c.enable_otlp_tracing)
                 .unwrap_or(false)
@@ -72,6 +79,7 @@ impl Container {
                     let span = span.clone();
                     builder
                         .on_thread_start(move || {
+                            fault_injection::set_current_node(Some(node_id.clone()));
                             SPAN.with(|maybe_entered_span| {
                                 if let Some(span) = &span {
                                     *maybe_entered_span.borrow_mut() = Some(span.clone().entered());
@@ -79,6 +87,7 @@ impl Container {
                             });
                         })
                         .on_thread_stop(|| {
+                            fault_injection::set_current_node(None);
                             SPAN.with(|maybe_entered_span| {
                                 maybe_entered_span.borrow_mut().take();
                             });
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_dag::{export_consensus_dag, DagCommittee, DagFormat},
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
     download_db_snapshot, download_formal_snapshot, get_object, get_transaction_block,
     make_clients, restore_from_db_checkpoint, state_sync_from_archive, verify_archive,
@@ -121,6 +122,29 @@ pub enum ToolCommand {
         cmd: Option<DbToolCommand>,
     },
 
+    /// Export the consensus certificate DAG of a range of rounds, with the leaders, their support
+    /// and their commit status, as JSON or Graphviz DOT
+    #[command(name = "export-consensus-dag")]
+    ExportConsensusDag {
+        /// Path of the consensus DB to read
+        #[arg(long = "db-path")]
+        db_path: PathBuf,
+        /// Path of the authority DB to read the committee from. Without it, only the leaders of
+        /// committed rounds are known.
+        #[arg(long = "authority-db-path")]
+        authority_db_path: Option<PathBuf>,
+        #[arg(long = "from-round")]
+        from_round: u64,
+        /// Last round to export, inclusive
+        #[arg(long = "to-round")]
+        to_round: u64,
+        #[arg(long = "format", value_enum, default_value = "json")]
+        format: DagFormat,
+        /// File to write the DAG to, instead of stdout
+        #[arg(long = "output")]
+        output: Option<PathBuf>,
+    },
+
     /// Tool to sync the node from archive store
     #[command(name = "sync-from-archive")]
     SyncFromArchive {
@@ -389,6 +413,19 @@ impl ToolCommand {
                     None => print_db_all_tables(path)?,
                 }
             }
+            ToolCommand::ExportConsensusDag {
+                db_path,
+                authority_db_path,
+                from_round,
+                to_round,
+                format,
+                output,
+            } => {
+                let committee = authority_db_path
+                    .map(|path| DagCommittee::load(&path))
+                    .transpose()?;
+                export_consensus_dag(&db_path, committee, from_round, to_round, format, output)?;
+            }
             ToolCommand::DumpValidators { genesis, concise } => {
                 let genesis = Genesis::load(genesis).unwrap();
                 if !concise {
@@ -0,0 +1,490 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports the certificate DAG stored by a Narwhal validator, along with the leader of every round
+//! and whether it was committed, to help debugging stalled commits.
+
+use anyhow::{anyhow, bail, Result};
+use clap::ValueEnum;
+use fastcrypto::hash::Hash;
+use narwhal_config::{AuthorityIdentifier, Committee, Stake};
+use narwhal_primary::consensus::{LeaderSchedule, LeaderSwapTable};
+use narwhal_storage::NodeStorage;
+use narwhal_types::{
+    Certificate, CertificateAPI, CertificateDigest, ConsensusCommit, HeaderAPI, Round,
+    SequenceNumber,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::epoch_start_configuration::EpochStartConfigTrait;
+use sui_protocol_config::{Chain, ProtocolConfig};
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum DagFormat {
+    Json,
+    Dot,
+}
+
+/// The certificates of a range of rounds, along with the leaders of the even rounds and the
+/// commits that included them.
+#[derive(Debug, Serialize)]
+pub struct ConsensusDag {
+    pub epoch: Option<u64>,
+    pub from_round: Round,
+    pub to_round: Round,
+    pub certificates: Vec<DagCertificate>,
+    pub leaders: Vec<DagLeader>,
+    pub commits: Vec<DagCommit>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DagCertificate {
+    pub digest: String,
+    pub round: Round,
+    pub author: AuthorityIdentifier,
+    pub hostname: Option<String>,
+    pub parents: Vec<String>,
+    /// Index of the commit that included the certificate, if any.
+    pub committed_in: Option<SequenceNumber>,
+    pub is_leader: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DagLeader {
+    pub round: Round,
+    /// The elected authority. Without a committee, it is only known for committed leaders.
+    pub author: Option<AuthorityIdentifier>,
+    pub hostname: Option<String>,
+    /// Digest of the leader's certificate, if it is in the store.
+    pub certificate: Option<String>,
+    /// Index of the commit the leader was committed in, if any.
+    pub committed_in: Option<SequenceNumber>,
+    /// Authorities whose certificate of the next round has the leader's certificate as parent.
+    pub support: Vec<AuthorityIdentifier>,
+    /// Total stake of `support`, when the committee is known.
+    pub support_stake: Option<Stake>,
+    /// Whether `support_stake` reaches the validity threshold needed to commit the leader.
+    pub has_enough_support: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DagCommit {
+    pub sub_dag_index: SequenceNumber,
+    pub leader_round: Round,
+    pub leader: String,
+    pub num_certificates: usize,
+    pub commit_timestamp: u64,
+    pub reputation_scores: BTreeMap<AuthorityIdentifier, u64>,
+    pub final_of_schedule: bool,
+}
+
+/// The committee of the epoch the DAG belongs to, needed to elect the leaders of rounds that
+/// haven't been committed.
+pub struct DagCommittee {
+    pub committee: Committee,
+    pub bad_nodes_stake_threshold: u64,
+}
+
+impl DagCommittee {
+    /// Loads the committee of the current epoch from the perpetual tables of the authority db.
+    pub fn load(authority_db_path: &Path) -> Result<Self> {
+        let perpetual_db = AuthorityPerpetualTables::open(&authority_db_path.join("store"), None);
+        let epoch_start_configuration = perpetual_db
+            .get_epoch_start_configuration()?
+            .ok_or_else(|| anyhow!("No epoch start configuration found in the authority db"))?;
+        let epoch_start_state = epoch_start_configuration.epoch_start_state();
+        let protocol_config =
+            ProtocolConfig::get_for_version(epoch_start_state.protocol_version(), Chain::Unknown);
+        Ok(Self {
+            committee: epoch_start_state.get_narwhal_committee(),
+            bad_nodes_stake_threshold: protocol_config.consensus_bad_nodes_stake_threshold(),
+        })
+    }
+}
+
+/// Reads the certificates of rounds `from_round..=to_round` and all the commits from the
+/// consensus db at `db_path`, and writes the resulting DAG to `output`, or stdout.
+pub fn export_consensus_dag(
+    db_path: &Path,
+    committee: Option<DagCommittee>,
+    from_round: Round,
+    to_round: Round,
+    format: DagFormat,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    if from_round > to_round {
+        bail!("from-round {from_round} is higher than to-round {to_round}");
+    }
+    let consensus_db = NodeStorage::reopen(db_path, None);
+    // The certificates of the round after the range tell whether its last leader got support.
+    let certificates = consensus_db
+        .certificate_store
+        .after_round(from_round)?
+        .into_iter()
+        .take_while(|certificate| certificate.round() <= to_round + 1)
+        .collect();
+    let commits = consensus_db
+        .consensus_store
+        .read_committed_sub_dags_from(&0)?;
+    let dag = ConsensusDag::new(
+        certificates,
+        &commits,
+        committee.as_ref(),
+        from_round,
+        to_round,
+    )?;
+
+    let content = match format {
+        DagFormat::Json => serde_json::to_string_pretty(&dag)?,
+        DagFormat::Dot => dag.to_dot(),
+    };
+    match output {
+        Some(path) => std::fs::write(path, content)?,
+        None => println!("{content}"),
+    }
+    Ok(())
+}
+
+impl ConsensusDag {
+    /// Builds the DAG of rounds `from_round..=to_round` out of `certificates`, which may also
+    /// contain the certificates of `to_round + 1`, and of all the `commits` of the epoch.
+    pub fn new(
+        certificates: Vec<Certificate>,
+        commits: &[ConsensusCommit],
+        committee: Option<&DagCommittee>,
+        from_round: Round,
+        to_round: Round,
+    ) -> Result<Self> {
+        let epoch = certificates.first().map(|certificate| certificate.epoch());
+        if let (Some(epoch), Some(committee)) = (epoch, committee) {
+            if epoch != committee.committee.epoch() {
+                bail!(
+                    "The committee is for epoch {} but the certificates are from epoch {epoch}",
+                    committee.committee.epoch()
+                );
+            }
+        }
+        let hostname = |author: &AuthorityIdentifier| {
+            committee
+                .and_then(|committee| committee.committee.authority(author))
+                .map(|authority| authority.hostname().to_string())
+        };
+
+        let mut committed_in = HashMap::new();
+        let mut committed_leaders = HashMap::new();
+        for commit in commits {
+            for digest in commit.certificates() {
+                committed_in.insert(digest, commit.sub_dag_index());
+            }
+            committed_leaders.insert(commit.leader_round(), commit);
+        }
+
+        let by_digest: HashMap<CertificateDigest, &Certificate> = certificates
+            .iter()
+            .map(|certificate| (certificate.digest(), certificate))
+            .collect();
+        let mut by_round: BTreeMap<Round, HashMap<AuthorityIdentifier, &Certificate>> =
+            BTreeMap::new();
+        for certificate in &certificates {
+            by_round
+                .entry(certificate.round())
+                .or_default()
+                .insert(certificate.origin(), certificate);
+        }
+
+        // Leaders are elected with the leader swap table built from the last final reputation
+        // scores committed before their round.
+        let schedule = committee.map(|committee| {
+            LeaderSchedule::new(committee.committee.clone(), LeaderSwapTable::default())
+        });
+        let mut final_commits = commits
+            .iter()
+            .filter(|commit| commit.reputation_score().final_of_schedule)
+            .peekable();
+
+        let mut leaders = Vec::new();
+        // Only even rounds have leaders, and round 0 is genesis.
+        let first_leader_round = from_round.max(2);
+        let first_leader_round = first_leader_round + first_leader_round % 2;
+        for round in (first_leader_round..=to_round).step_by(2) {
+            while let Some(commit) = final_commits.next_if(|c| c.leader_round() < round) {
+                if let (Some(schedule), Some(committee)) = (&schedule, committee) {
+                    schedule.update_leader_swap_table(LeaderSwapTable::new(
+                        &committee.committee,
+                        commit.leader_round(),
+                        &commit.reputation_score(),
+                        committee.bad_nodes_stake_threshold,
+                    ));
+                }
+            }
+
+            let committed_leader = committed_leaders.get(&round);
+            let author = match &schedule {
+                Some(schedule) => Some(schedule.leader(round).id()),
+                None => committed_leader
+                    .and_then(|commit| by_digest.get(&commit.leader()))
+                    .map(|certificate| certificate.origin()),
+            };
+            let certificate = author
+                .and_then(|author| by_round.get(&round).and_then(|c| c.get(&author)).copied());
+            let mut support: Vec<_> = match certificate {
+                Some(certificate) => {
+                    let digest = certificate.digest();
+                    by_round
+                        .get(&(round + 1))
+                        .into_iter()
+                        .flat_map(|certificates| certificates.values())
+                        .filter(|child| child.header().parents().contains(&digest))
+                        .map(|child| child.origin())
+                        .collect()
+                }
+                None => Vec::new(),
+            };
+            support.sort();
+            let support_stake = committee.map(|committee| {
+                support
+                    .iter()
+                    .map(|author| committee.committee.stake_by_id(*author))
+                    .sum::<Stake>()
+            });
+            leaders.push(DagLeader {
+                round,
+                author,
+                hostname: author.as_ref().and_then(hostname),
+                certificate: certificate.map(|certificate| format!("{:?}", certificate.digest())),
+                committed_in: committed_leader.map(|commit| commit.sub_dag_index()),
+                support,
+                support_stake,
+                has_enough_support: committee
+                    .zip(support_stake)
+                    .map(|(committee, stake)| stake >= committee.committee.validity_threshold()),
+            });
+        }
+
+        let leader_certificates: Vec<_> = leaders
+            .iter()
+            .filter_map(|leader| leader.certificate.clone())
+            .collect();
+        let certificates = certificates
+            .iter()
+            .filter(|certificate| (from_round..=to_round).contains(&certificate.round()))
+            .map(|certificate| {
+                let digest = format!("{:?}", certificate.digest());
+                DagCertificate {
+                    is_leader: leader_certificates.contains(&digest),
+                    digest,
+                    round: certificate.round(),
+                    author: certificate.origin(),
+                    hostname: hostname(&certificate.origin()),
+                    parents: certificate
+                        .header()
+                        .parents()
+                        .iter()
+                        .map(|parent| format!("{parent:?}"))
+                        .collect(),
+                    committed_in: committed_in.get(&certificate.digest()).copied(),
+                }
+            })
+            .collect();
+
+        // Only the commits of leaders in the range, or whose reputation scores affect them.
+        let commits = commits
+            .iter()
+            .filter(|commit| commit.leader_round() <= to_round)
+            .filter(|commit| {
+                commit.leader_round() >= from_round || commit.reputation_score().final_of_schedule
+            })
+            .map(|commit| {
+                let reputation_score = commit.reputation_score();
+                DagCommit {
+                    sub_dag_index: commit.sub_dag_index(),
+                    leader_round: commit.leader_round(),
+                    leader: format!("{:?}", commit.leader()),
+                    num_certificates: commit.certificates().len(),
+                    commit_timestamp: commit.commit_timestamp(),
+                    reputation_scores: reputation_score.scores_per_authority.into_iter().collect(),
+                    final_of_schedule: reputation_score.final_of_schedule,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            epoch,
+            from_round,
+            to_round,
+            certificates,
+            leaders,
+            commits,
+        })
+    }
+
+    /// Renders the DAG in the Graphviz DOT format, with one row per round. Committed certificates
+    /// are filled, leaders are drawn with a thick border, red when they haven't been committed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph consensus_dag {{").unwrap();
+        writeln!(dot, "  rankdir=BT;").unwrap();
+        writeln!(dot, "  node [shape=box, style=rounded];").unwrap();
+
+        let uncommitted_leaders: Vec<_> = self
+            .leaders
+            .iter()
+            .filter(|leader| leader.committed_in.is_none())
+            .filter_map(|leader| leader.certificate.as_ref())
+            .collect();
+        let mut by_round: BTreeMap<Round, Vec<&DagCertificate>> = BTreeMap::new();
+        for certificate in &self.certificates {
+            by_round
+                .entry(certificate.round)
+                .or_default()
+                .push(certificate);
+        }
+
+        for (round, certificates) in &by_round {
+            writeln!(dot, "  subgraph round_{round} {{").unwrap();
+            writeln!(dot, "    rank=same;").unwrap();
+            for certificate in certificates {
+                let mut label = format!("R{} A{}", certificate.round, certificate.author);
+                if let Some(hostname) = &certificate.hostname {
+                    write!(label, "\\n{hostname}").unwrap();
+                }
+                if let Some(index) = certificate.committed_in {
+                    write!(label, "\\ncommit {index}").unwrap();
+                }
+                let mut style = vec!["rounded"];
+                let mut attributes = Vec::new();
+                if certificate.committed_in.is_some() {
+                    style.push("filled");
+                    attributes.push("fillcolor=lightblue".to_string());
+                }
+                if certificate.is_leader {
+                    style.push("bold");
+                    attributes.push("penwidth=3".to_string());
+                    if uncommitted_leaders.contains(&&certificate.digest) {
+                        attributes.push("color=red".to_string());
+                    }
+                }
+                attributes.push(format!("style=\"{}\"", style.join(",")));
+                writeln!(
+                    dot,
+                    "    \"{}\" [label=\"{label}\", {}];",
+                    certificate.digest,
+                    attributes.join(", ")
+                )
+                .unwrap();
+            }
+            writeln!(dot, "  }}").unwrap();
+        }
+
+        // Parents outside of the range are left out to keep the graph readable.
+        for certificate in &self.certificates {
+            for parent in &certificate.parents {
+                if self.certificates.iter().any(|c| &c.digest == parent) {
+                    writeln!(dot, "  \"{}\" -> \"{parent}\";", certificate.digest).unwrap();
+                }
+            }
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use narwhal_test_utils::{
+        latest_protocol_version, make_optimal_certificates, CommitteeFixture,
+    };
+    use narwhal_types::{CommittedSubDag, ReputationScores};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_consensus_dag_leaders_and_support() {
+        let fixture = CommitteeFixture::builder().build();
+        let committee = fixture.committee();
+        let ids: Vec<_> = fixture.authorities().map(|a| a.id()).collect();
+        let protocol_config = latest_protocol_version();
+        let genesis = Certificate::genesis(&protocol_config, &committee)
+            .iter()
+            .map(|certificate| certificate.digest())
+            .collect::<BTreeSet<_>>();
+        let (certificates, _) =
+            make_optimal_certificates(&committee, &protocol_config, 1..=5, &genesis, &ids);
+        let certificates: Vec<_> = certificates.into_iter().collect();
+
+        let dag_committee = DagCommittee {
+            committee: committee.clone(),
+            bad_nodes_stake_threshold: 0,
+        };
+        let schedule = LeaderSchedule::new(committee.clone(), LeaderSwapTable::default());
+
+        // Commit the leader of round 2 only.
+        let leader_2 = schedule.leader(2).id();
+        let leader_certificate = certificates
+            .iter()
+            .find(|c| c.round() == 2 && c.origin() == leader_2)
+            .unwrap()
+            .clone();
+        let committed: Vec<_> = certificates
+            .iter()
+            .filter(|c| c.round() <= 2)
+            .cloned()
+            .collect();
+        let sub_dag = CommittedSubDag::new(
+            committed.clone(),
+            leader_certificate.clone(),
+            1,
+            ReputationScores::new(&committee),
+            None,
+        );
+        let commits = vec![ConsensusCommit::from_sub_dag(&sub_dag)];
+
+        let dag =
+            ConsensusDag::new(certificates.clone(), &commits, Some(&dag_committee), 1, 4).unwrap();
+
+        assert_eq!(dag.epoch, Some(committee.epoch()));
+        assert_eq!(dag.certificates.len(), 4 * ids.len());
+        assert_eq!(
+            dag.certificates
+                .iter()
+                .filter(|c| c.committed_in == Some(1))
+                .count(),
+            committed.len()
+        );
+        assert_eq!(dag.commits.len(), 1);
+
+        // Every certificate of the next round supports the leaders in an optimal DAG.
+        assert_eq!(
+            dag.leaders.iter().map(|l| l.round).collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        for leader in &dag.leaders {
+            assert_eq!(leader.author, Some(schedule.leader(leader.round).id()));
+            assert_eq!(leader.support.len(), ids.len());
+            assert_eq!(leader.support_stake, Some(committee.total_stake()));
+            assert_eq!(leader.has_enough_support, Some(true));
+        }
+        assert_eq!(dag.leaders[0].committed_in, Some(1));
+        assert_eq!(
+            dag.leaders[0].certificate,
+            Some(format!("{:?}", leader_certificate.digest()))
+        );
+        assert_eq!(dag.leaders[1].committed_in, None);
+        assert_eq!(dag.certificates.iter().filter(|c| c.is_leader).count(), 2);
+
+        // Without a committee, only the committed leader is known.
+        let dag = ConsensusDag::new(certificates, &commits, None, 1, 4).unwrap();
+        assert_eq!(dag.leaders[0].author, Some(leader_2));
+        assert_eq!(dag.leaders[0].support.len(), ids.len());
+        assert_eq!(dag.leaders[0].support_stake, None);
+        assert_eq!(dag.leaders[1].author, None);
+
+        let dot = dag.to_dot();
+        assert!(dot.starts_with("digraph consensus_dag {"));
+        assert_eq!(dot.matches("rank=same").count(), 4);
+    }
+}
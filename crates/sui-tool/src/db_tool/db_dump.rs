@@ -25,14 +25,16 @@ use sui_storage::mutex_table::RwLockTable;
 use sui_storage::IndexStoreTables;
 use sui_types::base_types::{EpochId, ObjectID};
 use tracing::info;
+use typed_store::rocks::export::{ExportedEntry, TableExportOptions};
 use typed_store::rocks::{default_db_options, MetricConf};
-use typed_store::traits::{Map, TableSummary};
+use typed_store::traits::{Map, SecondaryIndexReport, TableSummary};
 
 #[derive(EnumString, Clone, Parser, Debug, ValueEnum)]
 pub enum StoreName {
     Validator,
     Index,
     Epoch,
+    Checkpoint,
     // TODO: Add the new checkpoint v2 tables.
 }
 impl std::fmt::Display for StoreName {
@@ -82,10 +84,63 @@ pub fn table_summary(
             CommitteeStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
                 .table_summary(table_name)
         }
+        StoreName::Checkpoint => {
+            CheckpointStore::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .table_summary(table_name)
+        }
     }
     .map_err(|err| anyhow!(err.to_string()))
 }
 
+pub fn verify_secondary_indexes(
+    store_name: StoreName,
+    epoch: Option<EpochId>,
+    db_path: PathBuf,
+) -> anyhow::Result<BTreeMap<String, SecondaryIndexReport>> {
+    let reports = match store_name {
+        StoreName::Validator => {
+            let mut reports =
+                AuthorityPerpetualTables::open_readonly(&db_path).verify_secondary_indexes()?;
+            if let Some(epoch) = epoch {
+                reports.extend(
+                    AuthorityEpochTables::open_readonly(epoch, &db_path)
+                        .verify_secondary_indexes()?,
+                );
+            }
+            reports
+        }
+        StoreName::Index => {
+            IndexStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .verify_secondary_indexes()?
+        }
+        StoreName::Epoch => {
+            CommitteeStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .verify_secondary_indexes()?
+        }
+        StoreName::Checkpoint => {
+            CheckpointStore::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .verify_secondary_indexes()?
+        }
+    };
+    Ok(reports)
+}
+
+pub fn rebuild_secondary_indexes(store_name: StoreName, db_path: PathBuf) -> anyhow::Result<()> {
+    match store_name {
+        StoreName::Index => {
+            IndexStoreTables::open_tables_read_write(db_path, MetricConf::default(), None, None)
+                .rebuild_secondary_indexes()?;
+        }
+        _ => {
+            return Err(anyhow!(
+                "Store {} does not declare any secondary index",
+                store_name
+            ))
+        }
+    }
+    Ok(())
+}
+
 pub fn print_table_metadata(
     store_name: StoreName,
     epoch: Option<EpochId>,
@@ -116,6 +171,11 @@ pub fn print_table_metadata(
                 .committee_map
                 .rocksdb
         }
+        StoreName::Checkpoint => {
+            CheckpointStore::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .checkpoint_content
+                .rocksdb
+        }
     };
 
     let mut table = Table::new();
@@ -295,6 +355,48 @@ pub fn dump_table(
             CommitteeStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
                 .dump(table_name, page_size, page_number)
         }
+        StoreName::Checkpoint => {
+            CheckpointStore::get_read_only_handle(db_path, None, None, MetricConf::default()).dump(
+                table_name,
+                page_size,
+                page_number,
+            )
+        }
+    }
+    .map_err(|err| anyhow!(err.to_string()))
+}
+
+pub fn export_table(
+    store_name: StoreName,
+    epoch: Option<EpochId>,
+    db_path: PathBuf,
+    table_name: &str,
+    options: &TableExportOptions,
+    sink: &mut dyn FnMut(ExportedEntry) -> eyre::Result<()>,
+) -> anyhow::Result<u64> {
+    match store_name {
+        StoreName::Validator => {
+            let epoch_tables = AuthorityEpochTables::describe_tables();
+            if epoch_tables.contains_key(table_name) {
+                let epoch = epoch.ok_or_else(|| anyhow!("--epoch is required"))?;
+                AuthorityEpochTables::open_readonly(epoch, &db_path)
+                    .export(table_name, options, sink)
+            } else {
+                AuthorityPerpetualTables::open_readonly(&db_path).export(table_name, options, sink)
+            }
+        }
+        StoreName::Index => {
+            IndexStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .export(table_name, options, sink)
+        }
+        StoreName::Epoch => {
+            CommitteeStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .export(table_name, options, sink)
+        }
+        StoreName::Checkpoint => {
+            CheckpointStore::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .export(table_name, options, sink)
+        }
     }
     .map_err(|err| anyhow!(err.to_string()))
 }
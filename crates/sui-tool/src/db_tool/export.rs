@@ -0,0 +1,142 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use clap::ValueEnum;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use sui_types::base_types::EpochId;
+use typed_store::rocks::export::{ExportedEntry, TableExportOptions};
+
+use crate::db_tool::db_dump::{export_table, StoreName};
+
+/// Number of rows buffered before a row group is written to a parquet file.
+const PARQUET_ROW_GROUP_SIZE: usize = 100_000;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ExportFormat {
+    /// One JSON object with `key` and `value` fields per line
+    Jsonl,
+    /// Parquet file with `key` and `value` columns holding JSON strings
+    Parquet,
+}
+
+/// Writes exported entries to a file in one of the supported formats.
+trait EntryWriter {
+    fn write(&mut self, entry: ExportedEntry) -> anyhow::Result<()>;
+    fn finish(self: Box<Self>) -> anyhow::Result<()>;
+}
+
+struct JsonlWriter(BufWriter<File>);
+
+impl EntryWriter for JsonlWriter {
+    fn write(&mut self, entry: ExportedEntry) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.0, &entry)?;
+        self.0.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> anyhow::Result<()> {
+        self.0.flush()?;
+        Ok(())
+    }
+}
+
+struct ParquetWriter {
+    writer: ArrowWriter<File>,
+    keys: Vec<String>,
+    values: Vec<String>,
+}
+
+impl ParquetWriter {
+    fn new(file: File) -> anyhow::Result<Self> {
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let schema = Self::record_batch(vec![], vec![])?.schema();
+        Ok(Self {
+            writer: ArrowWriter::try_new(file, schema, Some(properties))?,
+            keys: vec![],
+            values: vec![],
+        })
+    }
+
+    fn record_batch(keys: Vec<String>, values: Vec<String>) -> anyhow::Result<RecordBatch> {
+        Ok(RecordBatch::try_from_iter([
+            ("key", Arc::new(StringArray::from(keys)) as ArrayRef),
+            ("value", Arc::new(StringArray::from(values)) as ArrayRef),
+        ])?)
+    }
+
+    fn flush_rows(&mut self) -> anyhow::Result<()> {
+        if self.keys.is_empty() {
+            return Ok(());
+        }
+        let batch = Self::record_batch(
+            std::mem::take(&mut self.keys),
+            std::mem::take(&mut self.values),
+        )?;
+        self.writer.write(&batch)?;
+        Ok(())
+    }
+}
+
+impl EntryWriter for ParquetWriter {
+    fn write(&mut self, entry: ExportedEntry) -> anyhow::Result<()> {
+        self.keys.push(entry.key.to_string());
+        self.values.push(entry.value.to_string());
+        if self.keys.len() >= PARQUET_ROW_GROUP_SIZE {
+            self.flush_rows()?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> anyhow::Result<()> {
+        self.flush_rows()?;
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+/// Exports the entries of `table_name` selected by `options` to `output`. Returns the number of
+/// exported entries.
+pub fn export_table_to_file(
+    store_name: StoreName,
+    epoch: Option<EpochId>,
+    db_path: PathBuf,
+    table_name: &str,
+    options: &TableExportOptions,
+    format: ExportFormat,
+    output: &Path,
+) -> anyhow::Result<u64> {
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = File::create(output)?;
+    let mut writer: Box<dyn EntryWriter> = match format {
+        ExportFormat::Jsonl => Box::new(JsonlWriter(BufWriter::new(file))),
+        ExportFormat::Parquet => Box::new(ParquetWriter::new(file)?),
+    };
+    let exported = export_table(
+        store_name,
+        epoch,
+        db_path,
+        table_name,
+        options,
+        &mut |entry| {
+            writer
+                .write(entry)
+                .map_err(|e| eyre::eyre!("Failed to write entry: {e}"))
+        },
+    )
+    .map_err(|e| anyhow!("Failed to export table {table_name}: {e}"))?;
+    writer.finish()?;
+    Ok(exported)
+}
@@ -2,8 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use self::db_dump::{dump_table, duplicate_objects_summary, list_tables, table_summary, StoreName};
+use self::export::{export_table_to_file, ExportFormat};
 use self::index_search::{search_index, SearchRange};
-use crate::db_tool::db_dump::{compact, print_table_metadata, prune_checkpoints, prune_objects};
+use crate::db_tool::db_dump::{
+    compact, print_table_metadata, prune_checkpoints, prune_objects, rebuild_secondary_indexes,
+    verify_secondary_indexes,
+};
 use anyhow::{anyhow, bail};
 use clap::Parser;
 use narwhal_storage::NodeStorage;
@@ -17,8 +21,10 @@ use sui_types::effects::TransactionEffectsAPI;
 use sui_types::messages_checkpoint::CheckpointDigest;
 use sui_types::storage::ObjectKey;
 use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
+use typed_store::rocks::export::TableExportOptions;
 use typed_store::rocks::MetricConf;
 pub mod db_dump;
+mod export;
 mod index_search;
 
 #[derive(Parser)]
@@ -26,6 +32,7 @@ mod index_search;
 pub enum DbToolCommand {
     ListTables,
     Dump(Options),
+    Export(ExportOptions),
     IndexSearchKeyRange(IndexSearchKeyRangeOptions),
     IndexSearchCount(IndexSearchCountOptions),
     TableSummary(Options),
@@ -43,6 +50,8 @@ pub enum DbToolCommand {
     Compact,
     PruneObjects,
     PruneCheckpoints,
+    VerifySecondaryIndexes(SecondaryIndexOptions),
+    RebuildSecondaryIndexes(SecondaryIndexOptions),
 }
 
 #[derive(Parser)]
@@ -91,6 +100,49 @@ pub struct Options {
     epoch: Option<EpochId>,
 }
 
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct ExportOptions {
+    /// The type of store to export from
+    #[arg(long = "store", short = 's', value_enum)]
+    store_name: StoreName,
+    /// The name of the table to export
+    #[arg(long = "table-name", short = 't')]
+    table_name: String,
+    /// The epoch to use when loading AuthorityEpochTables.
+    #[arg(long = "epoch", short = 'e')]
+    epoch: Option<EpochId>,
+    /// The format of the output file
+    #[arg(long = "format", short = 'f', value_enum, default_value = "jsonl")]
+    format: ExportFormat,
+    /// The file to write the entries to
+    #[arg(long = "output", short = 'o')]
+    output: PathBuf,
+    /// Inclusive lower bound of the exported keys, as the JSON encoding of a key
+    #[arg(long = "start-key")]
+    start_key: Option<String>,
+    /// Exclusive upper bound of the exported keys, as the JSON encoding of a key
+    #[arg(long = "end-key")]
+    end_key: Option<String>,
+    /// Export one out of every N entries
+    #[arg(long = "sample-every", default_value_t = 1)]
+    sample_every: u64,
+    /// Maximum number of entries to export
+    #[arg(long = "limit")]
+    limit: Option<u64>,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct SecondaryIndexOptions {
+    /// The type of store holding the indexes
+    #[arg(long = "store", short = 's', value_enum)]
+    store_name: StoreName,
+    /// The epoch to use when loading AuthorityEpochTables.
+    #[arg(long = "epoch", short = 'e')]
+    epoch: Option<EpochId>,
+}
+
 #[derive(Parser)]
 #[command(rename_all = "kebab-case")]
 pub struct PrintConsensusCommitOptions {
@@ -171,6 +223,30 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
             d.page_size,
             d.page_number,
         ),
+        DbToolCommand::Export(d) => {
+            let options = TableExportOptions {
+                start_key: d.start_key,
+                end_key: d.end_key,
+                sample_every: d.sample_every,
+                limit: d.limit,
+            };
+            let exported = export_table_to_file(
+                d.store_name,
+                d.epoch,
+                db_path,
+                &d.table_name,
+                &options,
+                d.format,
+                &d.output,
+            )?;
+            println!(
+                "Exported {} entries of {} to {}",
+                exported,
+                d.table_name,
+                d.output.display()
+            );
+            Ok(())
+        }
         DbToolCommand::TableSummary(d) => {
             print_db_table_summary(d.store_name, d.epoch, db_path, &d.table_name)
         }
@@ -192,6 +268,13 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
         DbToolCommand::Compact => compact(db_path),
         DbToolCommand::PruneObjects => prune_objects(db_path).await,
         DbToolCommand::PruneCheckpoints => prune_checkpoints(db_path).await,
+        DbToolCommand::VerifySecondaryIndexes(d) => {
+            print_secondary_index_reports(d.store_name, d.epoch, db_path)
+        }
+        DbToolCommand::RebuildSecondaryIndexes(d) => {
+            rebuild_secondary_indexes(d.store_name.clone(), db_path.clone())?;
+            print_secondary_index_reports(d.store_name, d.epoch, db_path)
+        }
         DbToolCommand::IndexSearchKeyRange(rg) => {
             let res = search_index(
                 db_path,
@@ -224,6 +307,36 @@ pub fn print_db_all_tables(db_path: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub fn print_secondary_index_reports(
+    store_name: StoreName,
+    epoch: Option<EpochId>,
+    db_path: PathBuf,
+) -> anyhow::Result<()> {
+    let reports = verify_secondary_indexes(store_name.clone(), epoch, db_path)?;
+    if reports.is_empty() {
+        println!("Store {} has no secondary index", store_name);
+    }
+    let mut inconsistent = 0;
+    for (index, report) in reports {
+        println!(
+            "{} (primary {}): expected = {}, missing = {}, mismatched = {}, stale = {}",
+            index,
+            report.primary_table,
+            report.expected_entries,
+            report.missing_entries,
+            report.mismatched_entries,
+            report.stale_entries(),
+        );
+        if !report.is_consistent() {
+            inconsistent += 1;
+        }
+    }
+    if inconsistent > 0 {
+        bail!("{} secondary indexes are inconsistent", inconsistent);
+    }
+    Ok(())
+}
+
 pub fn print_db_duplicates_summary(db_path: PathBuf) -> anyhow::Result<()> {
     let (total_count, duplicate_count, total_bytes, duplicated_bytes) =
         duplicate_objects_summary(db_path);
@@ -60,6 +60,7 @@ use tracing::info;
 use typed_store::rocks::MetricConf;
 
 pub mod commands;
+pub mod consensus_dag;
 pub mod db_tool;
 
 // This functions requires at least one of genesis or fullnode_rpc to be `Some`.
@@ -652,6 +653,7 @@ fn start_summary_sync(
             &committee_store,
             usize::MAX,
             false,
+            None,
             &Registry::default(),
         )
         .await?;
@@ -1260,6 +1262,7 @@ pub async fn state_sync_from_archive(
         &committee_store,
         usize::MAX,
         false,
+        None,
         &Registry::default(),
     )
     .await?;
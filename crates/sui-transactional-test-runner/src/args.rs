@@ -50,6 +50,9 @@ pub struct SuiInitArgs {
     pub max_gas: Option<u64>,
     #[clap(long = "shared-object-deletion")]
     pub shared_object_deletion: Option<bool>,
+    /// Execute transactions with a `Simulacrum` instead of a validator. Required by `run-graphql`.
+    #[clap(long = "simulator")]
+    pub simulator: bool,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -134,6 +137,13 @@ pub struct AdvanceClockCommand {
     pub duration_ns: u64,
 }
 
+#[derive(Debug, clap::Parser)]
+pub struct RunGraphqlCommand {
+    /// Seconds to wait for the GraphQL server to index the latest checkpoint.
+    #[clap(long = "timeout-secs", default_value = "60")]
+    pub timeout_secs: u64,
+}
+
 #[derive(Debug, clap::Parser)]
 pub enum SuiSubcommand {
     #[clap(name = "view-object")]
@@ -156,6 +166,8 @@ pub enum SuiSubcommand {
     AdvanceEpoch,
     #[clap(name = "advance-clock")]
     AdvanceClock(AdvanceClockCommand),
+    #[clap(name = "run-graphql")]
+    RunGraphql(RunGraphqlCommand),
 }
 
 #[derive(Clone, Debug)]
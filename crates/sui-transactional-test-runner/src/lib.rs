@@ -8,7 +8,10 @@ pub mod programmable_transaction_test_parser;
 pub mod test_adapter;
 
 use move_transactional_test_runner::framework::run_test_impl;
+use rand::rngs::StdRng;
+use simulacrum::Simulacrum;
 use std::path::Path;
+use std::sync::RwLock;
 use sui_types::storage::ObjectStore;
 use test_adapter::{SuiTestAdapter, PRE_COMPILED};
 
@@ -23,6 +26,7 @@ use sui_types::base_types::ObjectID;
 use sui_types::base_types::SuiAddress;
 use sui_types::base_types::VersionNumber;
 use sui_types::effects::TransactionEffects;
+use sui_types::effects::TransactionEffectsAPI;
 use sui_types::error::ExecutionError;
 use sui_types::error::SuiError;
 use sui_types::error::SuiResult;
@@ -171,3 +175,109 @@ impl ObjectStore for ValidatorWithFullnode {
             .get_object_by_key(object_id, version)
     }
 }
+
+/// A [`Simulacrum`] executing the transactions of a test. It is shared with the REST service that
+/// feeds its checkpoints to the indexer when the test runs GraphQL queries.
+pub struct SimulatorExecutor(pub Arc<RwLock<Simulacrum<StdRng>>>);
+
+#[async_trait::async_trait]
+impl TransactionalAdapter for SimulatorExecutor {
+    async fn execute_txn(
+        &mut self,
+        transaction: Transaction,
+    ) -> anyhow::Result<(TransactionEffects, Option<ExecutionError>)> {
+        self.0.write().unwrap().execute_transaction(transaction)
+    }
+
+    async fn dev_inspect_transaction_block(
+        &self,
+        _sender: SuiAddress,
+        _transaction_kind: TransactionKind,
+        _gas_price: Option<u64>,
+    ) -> SuiResult<DevInspectResults> {
+        Err(SuiError::UnsupportedFeatureError {
+            error: "dev-inspect is not supported by the simulator".to_string(),
+        })
+    }
+
+    async fn query_events(
+        &self,
+        query: EventFilter,
+        _cursor: Option<EventID>,
+        _limit: usize,
+        _descending: bool,
+    ) -> SuiResult<Vec<SuiEvent>> {
+        let EventFilter::Transaction(digest) = query else {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: format!("Event filter {query:?} is not supported by the simulator"),
+            });
+        };
+        let simulacrum = self.0.read().unwrap();
+        let store = simulacrum.store();
+        let Some(events_digest) = store
+            .get_transaction_effects(&digest)
+            .and_then(|effects| effects.events_digest().copied())
+        else {
+            return Ok(vec![]);
+        };
+        let Some(events) = store.get_transaction_events(&events_digest) else {
+            return Ok(vec![]);
+        };
+        events
+            .data
+            .iter()
+            .enumerate()
+            .map(|(seq, event)| SuiEvent::try_from(event.clone(), digest, seq as u64, None, store))
+            .collect()
+    }
+
+    async fn create_checkpoint(&mut self) -> anyhow::Result<VerifiedCheckpoint> {
+        Ok(self.0.write().unwrap().create_checkpoint())
+    }
+
+    async fn advance_clock(
+        &mut self,
+        duration: std::time::Duration,
+    ) -> anyhow::Result<TransactionEffects> {
+        Ok(self.0.write().unwrap().advance_clock(duration))
+    }
+
+    async fn advance_epoch(&mut self) -> anyhow::Result<()> {
+        self.0.write().unwrap().advance_epoch();
+        Ok(())
+    }
+
+    async fn request_gas(
+        &mut self,
+        address: SuiAddress,
+        amount: u64,
+    ) -> anyhow::Result<TransactionEffects> {
+        self.0.write().unwrap().request_gas(address, amount)
+    }
+}
+
+impl ObjectStore for SimulatorExecutor {
+    fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
+        Ok(self
+            .0
+            .read()
+            .unwrap()
+            .store()
+            .get_object(object_id)
+            .cloned())
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: VersionNumber,
+    ) -> Result<Option<Object>, SuiError> {
+        Ok(self
+            .0
+            .read()
+            .unwrap()
+            .store()
+            .get_object_at_version(object_id, version)
+            .cloned())
+    }
+}
@@ -4,7 +4,7 @@
 //! This module contains the transactional test runner instantiation for the Sui adapter
 
 use crate::{args::*, programmable_transaction_test_parser::parser::ParsedCommand};
-use crate::{TransactionalAdapter, ValidatorWithFullnode};
+use crate::{SimulatorExecutor, TransactionalAdapter, ValidatorWithFullnode};
 use anyhow::{anyhow, bail};
 use async_trait::async_trait;
 use bimap::btree::BiBTreeMap;
@@ -32,16 +32,19 @@ use move_transactional_test_runner::{
 use move_vm_runtime::session::SerializedReturnValues;
 use once_cell::sync::Lazy;
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use simulacrum::Simulacrum;
 use std::fmt::{self, Write};
 use std::time::Duration;
 use std::{
     collections::{BTreeMap, BTreeSet},
     path::Path,
-    sync::Arc,
+    sync::{Arc, RwLock},
 };
 use sui_core::authority::test_authority_builder::TestAuthorityBuilder;
 use sui_core::authority::AuthorityState;
 use sui_framework::DEFAULT_FRAMEWORK_PATH;
+use sui_graphql_rpc::cluster::{serve_executor, SimulatorCluster};
+use sui_graphql_rpc::config::ConnectionConfig;
 use sui_json_rpc::api::QUERY_MAX_RESULT_LIMIT;
 use sui_json_rpc_types::{
     DevInspectResults, EventFilter, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
@@ -50,6 +53,7 @@ use sui_protocol_config::{Chain, ProtocolConfig};
 use sui_storage::{
     key_value_store::TransactionKeyValueStore, key_value_store_metrics::KeyValueStoreMetrics,
 };
+use sui_swarm_config::genesis_config::AccountConfig;
 use sui_types::base_types::SequenceNumber;
 use sui_types::crypto::get_authority_key_pair;
 use sui_types::effects::TransactionEffectsAPI;
@@ -102,6 +106,10 @@ const RNG_SEED: [u8; 32] = [
 const DEFAULT_GAS_BUDGET: u64 = 5_000_000_000;
 const GAS_FOR_TESTING: u64 = GAS_VALUE_FOR_TESTING;
 
+/// Port of the REST service feeding the simulator's checkpoints to the indexer behind
+/// `run-graphql`.
+const GRAPHQL_DATA_SOURCE_PORT: u16 = 3000;
+
 pub struct SuiTestAdapter<'a> {
     pub(crate) compiled_state: CompiledState<'a>,
     /// For upgrades: maps an upgraded package name to the original package name.
@@ -114,6 +122,10 @@ pub struct SuiTestAdapter<'a> {
     gas_price: u64,
     pub(crate) staged_modules: BTreeMap<Symbol, StagedPackage>,
     pub(crate) executor: Box<dyn TransactionalAdapter>,
+    /// Set when the test runs with `--simulator`, shared with `executor`.
+    simulator: Option<Arc<RwLock<Simulacrum<StdRng>>>>,
+    /// Started by the first `run-graphql` command.
+    graphql_cluster: Option<SimulatorCluster>,
 }
 
 pub(crate) struct StagedPackage {
@@ -173,43 +185,46 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter<'a> {
             "Must populate 'pre_compiled_deps' with Sui framework"
         );
 
-        let (additional_mapping, account_names, protocol_config) = match task_opt.map(|t| t.command)
-        {
-            Some((
-                InitCommand { named_addresses },
-                SuiInitArgs {
-                    accounts,
-                    protocol_version,
-                    max_gas,
-                    shared_object_deletion,
-                },
-            )) => {
-                let map = verify_and_create_named_address_mapping(named_addresses).unwrap();
-                let accounts = accounts
-                    .map(|v| v.into_iter().collect::<BTreeSet<_>>())
-                    .unwrap_or_default();
-
-                let mut protocol_config = if let Some(protocol_version) = protocol_version {
-                    ProtocolConfig::get_for_version(protocol_version.into(), Chain::Unknown)
-                } else {
-                    ProtocolConfig::get_for_max_version_UNSAFE()
-                };
-                if let Some(enable) = shared_object_deletion {
-                    protocol_config.set_shared_object_deletion(enable);
+        let (additional_mapping, account_names, protocol_config, simulator) =
+            match task_opt.map(|t| t.command) {
+                Some((
+                    InitCommand { named_addresses },
+                    SuiInitArgs {
+                        accounts,
+                        protocol_version,
+                        max_gas,
+                        shared_object_deletion,
+                        simulator,
+                    },
+                )) => {
+                    if simulator && (max_gas.is_some() || shared_object_deletion.is_some()) {
+                        panic!("Protocol config overrides are not supported with --simulator")
+                    }
+                    let map = verify_and_create_named_address_mapping(named_addresses).unwrap();
+                    let accounts = accounts
+                        .map(|v| v.into_iter().collect::<BTreeSet<_>>())
+                        .unwrap_or_default();
+
+                    let mut protocol_config = if let Some(protocol_version) = protocol_version {
+                        ProtocolConfig::get_for_version(protocol_version.into(), Chain::Unknown)
+                    } else {
+                        ProtocolConfig::get_for_max_version_UNSAFE()
+                    };
+                    if let Some(enable) = shared_object_deletion {
+                        protocol_config.set_shared_object_deletion(enable);
+                    }
+                    if let Some(mx_tx_gas_override) = max_gas {
+                        protocol_config.set_max_tx_gas_for_testing(mx_tx_gas_override)
+                    }
+                    (map, accounts, protocol_config, simulator)
                 }
-                if let Some(mx_tx_gas_override) = max_gas {
-                    protocol_config.set_max_tx_gas_for_testing(mx_tx_gas_override)
+                None => {
+                    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+                    (BTreeMap::new(), BTreeSet::new(), protocol_config, false)
                 }
-                (map, accounts, protocol_config)
-            }
-            None => {
-                let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
-                (BTreeMap::new(), BTreeSet::new(), protocol_config)
-            }
-        };
+            };
 
         let mut named_address_mapping = NAMED_ADDRESSES.clone();
-        let mut account_objects = BTreeMap::new();
         let mut accounts = BTreeMap::new();
         let mut objects = vec![];
         let mut mk_account = || {
@@ -229,10 +244,49 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter<'a> {
         };
         for n in account_names {
             let test_account = mk_account();
-            account_objects.insert(n.clone(), test_account.gas);
             accounts.insert(n, test_account);
         }
-        let default_account = mk_account();
+        let mut default_account = mk_account();
+
+        let (executor, simulator, object_ids): (Box<dyn TransactionalAdapter>, _, Vec<_>) =
+            if simulator {
+                let mut simulator = create_simulator(&protocol_config, accounts.len() + 1);
+                // The simulator starts from its own genesis, so accounts are funded by its faucet
+                // rather than with the objects created above.
+                for test_account in accounts
+                    .values_mut()
+                    .chain(std::iter::once(&mut default_account))
+                {
+                    let effects = simulator
+                        .request_gas(test_account.address, GAS_FOR_TESTING)
+                        .unwrap();
+                    test_account.gas = effects.created()[0].0 .0;
+                }
+                let object_ids = accounts
+                    .values()
+                    .chain(std::iter::once(&default_account))
+                    .map(|test_account| test_account.gas)
+                    .collect();
+                let simulator = Arc::new(RwLock::new(simulator));
+                (
+                    Box::new(SimulatorExecutor(simulator.clone())),
+                    Some(simulator),
+                    object_ids,
+                )
+            } else {
+                let object_ids = objects.iter().map(|obj| obj.id()).collect();
+                let executor = create_val_fullnode_executor(&protocol_config, &objects).await;
+                (Box::new(executor), None, object_ids)
+            };
+        let account_objects: BTreeMap<_, _> = accounts
+            .iter()
+            .map(|(n, test_account)| (n.clone(), test_account.gas))
+            .collect();
+        let gas_price = match &simulator {
+            Some(simulator) => simulator.read().unwrap().reference_gas_price(),
+            // TODO: make this configurable
+            None => 1000,
+        };
         let additional_mapping =
             additional_mapping
                 .into_iter()
@@ -248,12 +302,8 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter<'a> {
             named_address_mapping.insert(name, addr);
         }
 
-        let object_ids = objects.iter().map(|obj| obj.id()).collect::<Vec<_>>();
-
-        let executor = create_val_fullnode_executor(&protocol_config, &objects).await;
-
         let mut test_adapter = Self {
-            executor: Box::new(executor),
+            executor,
             compiled_state: CompiledState::new(
                 named_address_mapping,
                 pre_compiled_deps,
@@ -269,9 +319,10 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter<'a> {
             default_syntax,
             object_enumeration: BiBTreeMap::new(),
             next_fake: (0, 0),
-            // TODO: make this configurable
-            gas_price: 1000,
+            gas_price,
             staged_modules: BTreeMap::new(),
+            simulator,
+            graphql_cluster: None,
         };
         for well_known in WELL_KNOWN_OBJECTS.iter().copied() {
             test_adapter
@@ -508,6 +559,41 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter<'a> {
                     .await?;
                 Ok(None)
             }
+            SuiSubcommand::RunGraphql(RunGraphqlCommand { timeout_secs }) => {
+                let Some(simulator) = self.simulator.clone() else {
+                    bail!("run-graphql requires the test to be initialized with --simulator");
+                };
+                let file = data.ok_or_else(|| anyhow!("Missing GraphQL query"))?;
+                let query = self.interpolate_graphql(&std::fs::read_to_string(file.path())?)?;
+                if self.graphql_cluster.is_none() {
+                    let cluster = serve_executor(
+                        ConnectionConfig::ci_integration_test_cfg(),
+                        GRAPHQL_DATA_SOURCE_PORT,
+                        simulator.clone(),
+                    )
+                    .await;
+                    self.graphql_cluster = Some(cluster);
+                }
+                let cluster = self.graphql_cluster.as_ref().unwrap();
+                // Queries only see checkpointed state, so wait for the latest checkpoint to be
+                // indexed before running them.
+                let latest_checkpoint = simulator
+                    .read()
+                    .unwrap()
+                    .store()
+                    .get_highest_checkpint()
+                    .map_or(0, |checkpoint| *checkpoint.sequence_number());
+                cluster
+                    .wait_for_checkpoint_catchup(
+                        latest_checkpoint,
+                        Duration::from_secs(timeout_secs),
+                    )
+                    .await?;
+                let response = cluster.graphql_client.execute(query, vec![]).await?;
+                Ok(Some(
+                    self.stabilize_str(serde_json::to_string_pretty(&response)?),
+                ))
+            }
 
             SuiSubcommand::ViewObject(ViewObjectCommand { id: fake_id }) => {
                 let obj = get_obj!(fake_id);
@@ -1357,6 +1443,42 @@ impl<'a> SuiTestAdapter<'a> {
         result
     }
 
+    /// Substitutes the variables of a GraphQL query: `@{obj_<task>_<i>}` becomes the ID of the
+    /// object enumerated as `object(<task>,<i>)`, and `@{<name>}` the value of a named address
+    /// (including accounts).
+    fn interpolate_graphql(&self, query: &str) -> anyhow::Result<String> {
+        let mut interpolated = String::new();
+        let mut rest = query;
+        while let Some(start) = rest.find("@{") {
+            interpolated.push_str(&rest[..start]);
+            let variable = &rest[start + 2..];
+            let Some(end) = variable.find('}') else {
+                bail!("Unterminated variable in GraphQL query: {}", &rest[start..]);
+            };
+            let name = &variable[..end];
+            let address: AccountAddress = match self.compiled_state.named_address_mapping.get(name)
+            {
+                Some(addr) => addr.into_inner(),
+                None => {
+                    let fake = name
+                        .strip_prefix("obj_")
+                        .and_then(|ids| ids.split_once('_'))
+                        .and_then(|(task, i)| {
+                            Some(FakeID::Enumerated(task.parse().ok()?, i.parse().ok()?))
+                        })
+                        .ok_or_else(|| anyhow!("Unknown variable @{{{name}}} in GraphQL query"))?;
+                    self.fake_to_real_object_id(fake)
+                        .ok_or_else(|| anyhow!("Unbound fake id {fake} in GraphQL query"))?
+                        .into()
+                }
+            };
+            write!(interpolated, "0x{address:x}").unwrap();
+            rest = &variable[end + 1..];
+        }
+        interpolated.push_str(rest);
+        Ok(interpolated)
+    }
+
     fn remap_hex_str(&self, hex_str: String) -> String {
         let hex_str = if hex_str.starts_with("0x") {
             hex_str
@@ -1520,6 +1642,20 @@ async fn create_validator_fullnode(
     (state, fullnode)
 }
 
+fn create_simulator(protocol_config: &ProtocolConfig, num_accounts: usize) -> Simulacrum<StdRng> {
+    // A single faucet account, with one coin per test account to fund.
+    let faucet = AccountConfig {
+        address: None,
+        gas_amounts: vec![2 * GAS_FOR_TESTING; num_accounts],
+    };
+    Simulacrum::new_with_protocol_version_and_accounts(
+        StdRng::from_seed(RNG_SEED),
+        /* chain_start_timestamp_ms */ 1,
+        protocol_config.version,
+        vec![faucet],
+    )
+}
+
 async fn create_val_fullnode_executor(
     protocol_config: &ProtocolConfig,
     objects: &[Object],
@@ -5,6 +5,7 @@ use core::fmt;
 use std::{
     fmt::{Debug, Display, Formatter, Write},
     path::PathBuf,
+    str::FromStr,
     sync::Arc,
 };
 
@@ -31,9 +32,9 @@ use shared_crypto::intent::Intent;
 use sui_execution::verifier::VerifierOverrides;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    DynamicFieldPage, SuiData, SuiObjectData, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiParsedData, SuiRawData, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    DelegatedStake, DynamicFieldPage, StakeStatus, SuiData, SuiObjectData, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiParsedData, SuiRawData, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
 use sui_keys::keystore::AccountKeystore;
@@ -441,6 +442,51 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Stake SUI with a validator. The stake is taken from the given coins, or from the coins of
+    /// the active address if none are given.
+    #[clap(name = "stake")]
+    Stake {
+        /// Validator to stake with, either its address or its name as registered in the system
+        /// state (case insensitive).
+        #[clap(long)]
+        validator: String,
+
+        /// Coins to stake. If not provided, SUI coins of the active address are used.
+        #[clap(long, num_args(1..))]
+        coins: Vec<ObjectID>,
+
+        /// The amount to stake, in MIST. If not specified, the whole value of the coins is staked.
+        #[clap(long)]
+        amount: Option<u64>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this transaction
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
+        /// (TransactionData) using base64 encoding, and print out the string.
+        #[clap(long, required = false)]
+        serialize_unsigned_transaction: bool,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the signed transaction data
+        /// (SenderSignedData) using base64 encoding, and print out the string.
+        #[clap(long, required = false)]
+        serialize_signed_transaction: bool,
+    },
+
+    /// List the StakedSui objects of an address, grouped by validator, with their estimated rewards.
+    #[clap(name = "stakes")]
+    Stakes {
+        /// Address owning the stakes (Defaults to the active address)
+        #[clap(name = "owner_address")]
+        address: Option<SuiAddress>,
+    },
+
     /// Switch active address and network(e.g., devnet, local rpc server)
     #[clap(name = "switch")]
     Switch {
@@ -525,6 +571,33 @@ pub enum SuiClientCommands {
         serialize_signed_transaction: bool,
     },
 
+    /// Withdraw a StakedSui object, returning the principal and the rewards to its owner.
+    #[clap(name = "unstake")]
+    Unstake {
+        /// ID of the StakedSui object to withdraw
+        #[clap(long)]
+        staked_sui_id: ObjectID,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this transaction
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the unsigned transaction data
+        /// (TransactionData) using base64 encoding, and print out the string.
+        #[clap(long, required = false)]
+        serialize_unsigned_transaction: bool,
+
+        /// Instead of executing the transaction, serialize the bcs bytes of the signed transaction data
+        /// (SenderSignedData) using base64 encoding, and print out the string.
+        #[clap(long, required = false)]
+        serialize_signed_transaction: bool,
+    },
+
     /// Upgrade Move modules
     #[clap(name = "upgrade")]
     Upgrade {
@@ -1234,6 +1307,93 @@ impl SuiClientCommands {
                     MergeCoin
                 )
             }
+            SuiClientCommands::Stake {
+                validator,
+                coins,
+                amount,
+                gas,
+                gas_budget,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+            } => {
+                let client = context.get_client().await?;
+                let validator = resolve_validator(&client, &validator).await?;
+                let (signer, coins) = if coins.is_empty() {
+                    let signer = context.active_address()?;
+                    let mut gas_coins = context.gas_objects(signer).await?;
+                    // Keep the largest coin aside to pay for gas, unless a gas coin was given.
+                    gas_coins.sort_by_key(|(balance, _)| *balance);
+                    if gas.is_none() {
+                        gas_coins.pop();
+                    }
+                    let coins = gas_coins
+                        .into_iter()
+                        .map(|(_, object)| object.object_id)
+                        .filter(|id| Some(*id) != gas)
+                        .collect::<Vec<_>>();
+                    ensure!(
+                        !coins.is_empty(),
+                        "No SUI coins left to stake for address [{signer}] after reserving a gas coin"
+                    );
+                    (signer, coins)
+                } else {
+                    (context.get_object_owner(&coins[0]).await?, coins)
+                };
+                let data = client
+                    .transaction_builder()
+                    .request_add_stake(signer, coins, amount, validator, gas, gas_budget)
+                    .await?;
+                serialize_or_execute!(
+                    data,
+                    serialize_unsigned_transaction,
+                    serialize_signed_transaction,
+                    context,
+                    Stake
+                )
+            }
+            SuiClientCommands::Unstake {
+                staked_sui_id,
+                gas,
+                gas_budget,
+                serialize_unsigned_transaction,
+                serialize_signed_transaction,
+            } => {
+                let signer = context.get_object_owner(&staked_sui_id).await?;
+                let client = context.get_client().await?;
+                let data = client
+                    .transaction_builder()
+                    .request_withdraw_stake(signer, staked_sui_id, gas, gas_budget)
+                    .await?;
+                serialize_or_execute!(
+                    data,
+                    serialize_unsigned_transaction,
+                    serialize_signed_transaction,
+                    context,
+                    Unstake
+                )
+            }
+            SuiClientCommands::Stakes { address } => {
+                let address = address.unwrap_or(context.active_address()?);
+                let client = context.get_client().await?;
+                let stakes = client.governance_api().get_stakes(address).await?;
+                let system_state = client
+                    .governance_api()
+                    .get_latest_sui_system_state()
+                    .await?;
+                SuiClientCommandResult::Stakes(
+                    stakes
+                        .into_iter()
+                        .map(|stake| {
+                            let validator_name = system_state
+                                .active_validators
+                                .iter()
+                                .find(|v| v.sui_address == stake.validator_address)
+                                .map(|v| v.name.clone());
+                            DelegatedStakeOutput::new(stake, validator_name)
+                        })
+                        .collect(),
+                )
+            }
             SuiClientCommands::Switch { address, env } => {
                 match (address, &env) {
                     (None, Some(env)) => {
@@ -1355,6 +1515,39 @@ impl SuiClientCommands {
     }
 }
 
+/// Resolves `validator` to the address of an active validator. `validator` is either an address or
+/// a validator name, matched case insensitively against the latest system state.
+async fn resolve_validator(
+    client: &SuiClient,
+    validator: &str,
+) -> Result<SuiAddress, anyhow::Error> {
+    let system_state = client
+        .governance_api()
+        .get_latest_sui_system_state()
+        .await?;
+    if let Ok(address) = SuiAddress::from_str(validator) {
+        ensure!(
+            system_state
+                .active_validators
+                .iter()
+                .any(|v| v.sui_address == address),
+            "[{address}] is not an active validator"
+        );
+        return Ok(address);
+    }
+    let mut matches = system_state
+        .active_validators
+        .iter()
+        .filter(|v| v.name.eq_ignore_ascii_case(validator));
+    match (matches.next(), matches.next()) {
+        (Some(v), None) => Ok(v.sui_address),
+        (Some(_), Some(_)) => Err(anyhow!(
+            "Several active validators are named [{validator}], use the validator address instead"
+        )),
+        (None, _) => Err(anyhow!("No active validator named [{validator}]")),
+    }
+}
+
 fn compile_package_simple(
     build_config: MoveBuildConfig,
     package_path: PathBuf,
@@ -1650,6 +1843,55 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::MergeCoin(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
+            SuiClientCommandResult::Stake(response) | SuiClientCommandResult::Unstake(response) => {
+                write!(writer, "{}", write_transaction_response(response)?)?;
+            }
+            SuiClientCommandResult::Stakes(stakes) => {
+                if stakes.is_empty() {
+                    write!(f, "No stakes are owned by this address")?;
+                    return Ok(());
+                }
+                let mut builder = TableBuilder::default();
+                builder.set_header([
+                    "validator",
+                    "stakedSuiId",
+                    "principal",
+                    "activeEpoch",
+                    "status",
+                    "estimatedReward",
+                ]);
+                for delegated in stakes {
+                    let validator = match &delegated.validator_name {
+                        Some(name) => format!("{name} ({})", delegated.validator_address),
+                        None => delegated.validator_address.to_string(),
+                    };
+                    for stake in &delegated.stakes {
+                        let (status, reward) = match stake.status {
+                            StakeStatus::Pending => ("Pending", "".to_string()),
+                            StakeStatus::Active { estimated_reward } => {
+                                ("Active", estimated_reward.to_string())
+                            }
+                            StakeStatus::Unstaked => ("Unstaked", "".to_string()),
+                        };
+                        builder.push_record(vec![
+                            validator.clone(),
+                            stake.staked_sui_id.to_string(),
+                            stake.principal.to_string(),
+                            stake.stake_active_epoch.to_string(),
+                            status.to_string(),
+                            reward,
+                        ]);
+                    }
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                let total_principal: u64 = stakes.iter().map(|s| s.total_principal).sum();
+                let total_reward: u64 = stakes.iter().map(|s| s.total_estimated_reward).sum();
+                table.with(TablePanel::footer(format!(
+                    "Total principal: {total_principal} MIST, estimated rewards: {total_reward} MIST"
+                )));
+                write!(f, "{}", table)?
+            }
             SuiClientCommandResult::Switch(response) => {
                 write!(writer, "{}", response)?;
             }
@@ -1866,7 +2108,7 @@ impl SuiClientCommandResult {
         match self {
             Upgrade(b) | Publish(b) | TransactionBlock(b) | Call(b) | Transfer(b)
             | TransferSui(b) | Pay(b) | PaySui(b) | PayAllSui(b) | SplitCoin(b) | MergeCoin(b)
-            | ExecuteSignedTx(b) => Some(b),
+            | Stake(b) | Unstake(b) | ExecuteSignedTx(b) => Some(b),
             _ => None,
         }
     }
@@ -1954,6 +2196,39 @@ impl From<&GasCoin> for GasCoinOutput {
     }
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatedStakeOutput {
+    pub validator_address: SuiAddress,
+    pub validator_name: Option<String>,
+    pub staking_pool: ObjectID,
+    pub stakes: Vec<sui_json_rpc_types::Stake>,
+    pub total_principal: u64,
+    pub total_estimated_reward: u64,
+}
+
+impl DelegatedStakeOutput {
+    fn new(delegated: DelegatedStake, validator_name: Option<String>) -> Self {
+        let total_principal = delegated.stakes.iter().map(|s| s.principal).sum();
+        let total_estimated_reward = delegated
+            .stakes
+            .iter()
+            .map(|s| match s.status {
+                StakeStatus::Active { estimated_reward } => estimated_reward,
+                StakeStatus::Pending | StakeStatus::Unstaked => 0,
+            })
+            .sum();
+        Self {
+            validator_address: delegated.validator_address,
+            validator_name,
+            staking_pool: delegated.staking_pool,
+            stakes: delegated.stakes,
+            total_principal,
+            total_estimated_reward,
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectsOutput {
@@ -2021,11 +2296,14 @@ pub enum SuiClientCommandResult {
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
     SplitCoin(SuiTransactionBlockResponse),
+    Stake(SuiTransactionBlockResponse),
+    Stakes(Vec<DelegatedStakeOutput>),
     Switch(SwitchResponse),
     SyncClientState,
     TransactionBlock(SuiTransactionBlockResponse),
     Transfer(SuiTransactionBlockResponse),
     TransferSui(SuiTransactionBlockResponse),
+    Unstake(SuiTransactionBlockResponse),
     Upgrade(SuiTransactionBlockResponse),
     VerifyBytecodeMeter {
         max_module_ticks: u128,
@@ -1,5 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use crate::multisig_proposal::{MultiSigProposalCommand, MultiSigProposalStatus};
 use crate::zklogin_commands_util::{perform_zk_login_test_tx, read_cli_line};
 use anyhow::anyhow;
 use bip32::DerivationPath;
@@ -143,6 +144,13 @@ pub enum KeyToolCommand {
         #[clap(long)]
         threshold: ThresholdUnit,
     },
+    /// Collect the signatures of a multisig transaction through a proposal file shared between
+    /// the signers, which holds the transaction, the multisig committee, the signatures collected
+    /// so far and a summary of the dry run of the transaction.
+    MultiSigProposal {
+        #[clap(subcommand)]
+        cmd: MultiSigProposalCommand,
+    },
 
     /// Read the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
@@ -360,6 +368,7 @@ pub enum CommandOutput {
     MultiSigAddress(MultiSigAddress),
     MultiSigCombinePartialSig(MultiSigCombinePartialSig),
     MultiSigCombinePartialSigLegacy(MultiSigCombinePartialSigLegacyOutput),
+    MultiSigProposal(MultiSigProposalStatus),
     PrivateKeyBase64(PrivateKeyBase64),
     Show(Key),
     Sign(SignData),
@@ -607,6 +616,10 @@ impl KeyToolCommand {
                 )
             }
 
+            KeyToolCommand::MultiSigProposal { cmd } => {
+                CommandOutput::MultiSigProposal(cmd.execute(keystore).await?)
+            }
+
             KeyToolCommand::Show { file } => {
                 let res = read_keypair_from_file(&file);
                 match res {
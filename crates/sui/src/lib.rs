@@ -6,6 +6,7 @@ pub mod client_commands;
 pub mod console;
 pub mod fire_drill;
 pub mod keytool;
+pub mod multisig_proposal;
 pub mod shell;
 pub mod sui_commands;
 pub mod validator_commands;
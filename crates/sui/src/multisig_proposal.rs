@@ -0,0 +1,516 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! File based workflow to collect the signatures of a multisig transaction.
+//!
+//! A proposal bundles the transaction, the multisig committee, the partial signatures collected
+//! so far and a summary of the dry run of the transaction. Signers review the summary and append
+//! their signature to the file, which is executed once the threshold is reached.
+
+use anyhow::{anyhow, bail, ensure};
+use clap::*;
+use fastcrypto::encoding::{decode_bytes_hex, Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use sui_json_rpc_types::{
+    DryRunTransactionBlockResponse, ObjectChange, SuiExecutionStatus,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_sdk::SuiClientBuilder;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{default_hash, EncodeDecodeBase64, PublicKey, Signature, SuiSignature};
+use sui_types::digests::TransactionDigest;
+use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
+use sui_types::multisig_legacy::{MultiSigLegacy, MultiSigPublicKeyLegacy};
+use sui_types::signature::GenericSignature;
+use sui_types::transaction::{Transaction, TransactionData, TransactionDataAPI};
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum MultiSigProposalCommand {
+    /// Create a proposal file for a transaction sent by a multisig address. The transaction is
+    /// dry run against the given full node to summarize its object and balance changes.
+    Propose {
+        /// Base64 encoded BCS serialized TransactionData
+        #[clap(long)]
+        tx_bytes: String,
+        #[clap(long, num_args(1..))]
+        pks: Vec<PublicKey>,
+        #[clap(long, num_args(1..))]
+        weights: Vec<WeightUnit>,
+        #[clap(long)]
+        threshold: ThresholdUnit,
+        /// Format of the combined multisig signature
+        #[clap(long, value_enum, default_value = "current")]
+        format: MultiSigFormat,
+        #[clap(long)]
+        rpc_url: String,
+        /// File the proposal is written to
+        #[clap(long)]
+        output: PathBuf,
+    },
+    /// Show the summary of a proposal and append a signature from the given address of the
+    /// keystore once confirmed.
+    Sign {
+        #[clap(long)]
+        proposal: PathBuf,
+        #[clap(long, value_parser = decode_bytes_hex::<SuiAddress>)]
+        address: SuiAddress,
+        /// Dry run the transaction again against this full node instead of trusting the summary
+        /// of the proposer.
+        #[clap(long)]
+        rpc_url: Option<String>,
+        /// Sign without asking for confirmation
+        #[clap(short = 'y', long = "yes")]
+        yes: bool,
+    },
+    /// Show the signatures collected for a proposal and their weight against the threshold.
+    Status {
+        #[clap(long)]
+        proposal: PathBuf,
+    },
+    /// Combine the signatures of a proposal which reached its threshold and execute the
+    /// transaction.
+    Execute {
+        #[clap(long)]
+        proposal: PathBuf,
+        #[clap(long)]
+        rpc_url: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "camelCase")]
+pub enum MultiSigFormat {
+    Current,
+    Legacy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalSigner {
+    address: SuiAddress,
+    // Base64 encoded `flag || pk`.
+    public_key: String,
+    weight: WeightUnit,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunSummary {
+    status: String,
+    net_gas_usage: i64,
+    object_changes: Vec<String>,
+    balance_changes: Vec<String>,
+}
+
+/// The content of a proposal file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigProposal {
+    // Base64 encoded BCS serialized TransactionData.
+    tx_bytes: String,
+    format: MultiSigFormat,
+    threshold: ThresholdUnit,
+    signers: Vec<ProposalSigner>,
+    // Base64 encoded `flag || sig || pk` of the signers who signed, in no particular order.
+    signatures: Vec<String>,
+    summary: DryRunSummary,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigProposalStatus {
+    proposal: PathBuf,
+    multisig_address: SuiAddress,
+    tx_digest: TransactionDigest,
+    format: MultiSigFormat,
+    signed_by: Vec<SuiAddress>,
+    missing: Vec<SuiAddress>,
+    weight: ThresholdUnit,
+    threshold: ThresholdUnit,
+    summary: DryRunSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    executed_tx_digest: Option<TransactionDigest>,
+}
+
+impl MultiSigProposalCommand {
+    pub async fn execute(
+        self,
+        keystore: &Keystore,
+    ) -> Result<MultiSigProposalStatus, anyhow::Error> {
+        Ok(match self {
+            MultiSigProposalCommand::Propose {
+                tx_bytes,
+                pks,
+                weights,
+                threshold,
+                format,
+                rpc_url,
+                output,
+            } => {
+                let tx_data: TransactionData = bcs::from_bytes(
+                    &Base64::decode(&tx_bytes)
+                        .map_err(|e| anyhow!("Invalid base64 tx bytes: {:?}", e))?,
+                )?;
+                let summary = dry_run(&rpc_url, tx_data.clone()).await?;
+                let proposal =
+                    MultiSigProposal::new(tx_data, pks, weights, threshold, format, summary)?;
+                proposal.save(&output)?;
+                proposal.status(output, None)?
+            }
+
+            MultiSigProposalCommand::Sign {
+                proposal: path,
+                address,
+                rpc_url,
+                yes,
+            } => {
+                let mut proposal = MultiSigProposal::load(&path)?;
+                let summary = match rpc_url {
+                    Some(rpc_url) => {
+                        let summary = dry_run(&rpc_url, proposal.tx_data()?).await?;
+                        if summary != proposal.summary {
+                            println!("The dry run differs from the summary of the proposal.");
+                        }
+                        summary
+                    }
+                    None => proposal.summary.clone(),
+                };
+                println!("{}", summary);
+                if !yes && !confirm(&format!("Sign transaction as {address}?"))? {
+                    bail!("Signing aborted");
+                }
+                let signature = keystore.sign_secure(
+                    &address,
+                    &proposal.tx_data()?,
+                    Intent::sui_transaction(),
+                )?;
+                proposal.add_signature(signature)?;
+                proposal.save(&path)?;
+                proposal.status(path, None)?
+            }
+
+            MultiSigProposalCommand::Status { proposal: path } => {
+                MultiSigProposal::load(&path)?.status(path, None)?
+            }
+
+            MultiSigProposalCommand::Execute {
+                proposal: path,
+                rpc_url,
+            } => {
+                let proposal = MultiSigProposal::load(&path)?;
+                let signature = proposal.combine()?;
+                let client = SuiClientBuilder::default().build(rpc_url).await?;
+                let response = client
+                    .quorum_driver_api()
+                    .execute_transaction_block(
+                        Transaction::from_generic_sig_data(
+                            proposal.tx_data()?,
+                            Intent::sui_transaction(),
+                            vec![signature],
+                        ),
+                        SuiTransactionBlockResponseOptions::new().with_effects(),
+                        None,
+                    )
+                    .await?;
+                proposal.status(path, Some(response.digest))?
+            }
+        })
+    }
+}
+
+impl MultiSigProposal {
+    pub fn new(
+        tx_data: TransactionData,
+        pks: Vec<PublicKey>,
+        weights: Vec<WeightUnit>,
+        threshold: ThresholdUnit,
+        format: MultiSigFormat,
+        summary: DryRunSummary,
+    ) -> Result<Self, anyhow::Error> {
+        ensure!(
+            pks.len() == weights.len(),
+            "The number of public keys and weights must match"
+        );
+        let signers = pks
+            .into_iter()
+            .zip(weights)
+            .map(|(pk, weight)| ProposalSigner {
+                address: (&pk).into(),
+                public_key: pk.encode_base64(),
+                weight,
+            })
+            .collect();
+        let proposal = Self {
+            tx_bytes: Base64::encode(bcs::to_bytes(&tx_data)?),
+            format,
+            threshold,
+            signers,
+            signatures: vec![],
+            summary,
+        };
+        let multisig_address = proposal.multisig_address()?;
+        ensure!(
+            tx_data.sender() == multisig_address,
+            "Transaction sender {} is not the multisig address {}",
+            tx_data.sender(),
+            multisig_address
+        );
+        Ok(proposal)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read proposal file {:?}: {e}", path))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn tx_data(&self) -> Result<TransactionData, anyhow::Error> {
+        Ok(bcs::from_bytes(&Base64::decode(&self.tx_bytes).map_err(
+            |e| anyhow!("Invalid base64 tx bytes in proposal: {:?}", e),
+        )?)?)
+    }
+
+    fn public_keys(&self) -> Result<Vec<PublicKey>, anyhow::Error> {
+        self.signers
+            .iter()
+            .map(|signer| {
+                PublicKey::decode_base64(&signer.public_key)
+                    .map_err(|e| anyhow!("Invalid public key in proposal: {:?}", e))
+            })
+            .collect()
+    }
+
+    fn weights(&self) -> Vec<WeightUnit> {
+        self.signers.iter().map(|signer| signer.weight).collect()
+    }
+
+    /// Address of the multisig, which also validates the committee of the proposal.
+    pub fn multisig_address(&self) -> Result<SuiAddress, anyhow::Error> {
+        let pks = self.public_keys()?;
+        Ok(match self.format {
+            MultiSigFormat::Current => {
+                (&MultiSigPublicKey::new(pks, self.weights(), self.threshold)?).into()
+            }
+            MultiSigFormat::Legacy => {
+                (&MultiSigPublicKeyLegacy::new(pks, self.weights(), self.threshold)?).into()
+            }
+        })
+    }
+
+    fn signatures(&self) -> Result<Vec<Signature>, anyhow::Error> {
+        self.signatures
+            .iter()
+            .map(|sig| {
+                Signature::decode_base64(sig)
+                    .map_err(|e| anyhow!("Invalid signature in proposal: {:?}", e))
+            })
+            .collect()
+    }
+
+    /// Index in the committee of the signer of `signature`.
+    fn signer_index(&self, signature: &Signature) -> Result<usize, anyhow::Error> {
+        let pk = signature.to_public_key()?;
+        self.public_keys()?
+            .iter()
+            .position(|signer| *signer == pk)
+            .ok_or_else(|| anyhow!("{} is not a signer of the multisig", SuiAddress::from(&pk)))
+    }
+
+    /// Verifies `signature` against the transaction and adds it, replacing any previous signature
+    /// of the same signer.
+    pub fn add_signature(&mut self, signature: Signature) -> Result<(), anyhow::Error> {
+        let index = self.signer_index(&signature)?;
+        let signer = self.signers[index].address;
+        signature.verify_secure(
+            &IntentMessage::new(Intent::sui_transaction(), self.tx_data()?),
+            signer,
+            signature.scheme(),
+        )?;
+        let mut signatures = vec![];
+        for (existing, encoded) in self.signatures()?.iter().zip(&self.signatures) {
+            if self.signer_index(existing)? != index {
+                signatures.push(encoded.clone());
+            }
+        }
+        signatures.push(signature.encode_base64());
+        self.signatures = signatures;
+        Ok(())
+    }
+
+    /// Sum of the weights of the signers who signed.
+    pub fn weight(&self) -> Result<ThresholdUnit, anyhow::Error> {
+        let mut weight = 0;
+        for signature in self.signatures()? {
+            weight += self.signers[self.signer_index(&signature)?].weight as ThresholdUnit;
+        }
+        Ok(weight)
+    }
+
+    /// Combines the collected signatures into a multisig signature of the proposal format.
+    pub fn combine(&self) -> Result<GenericSignature, anyhow::Error> {
+        let weight = self.weight()?;
+        ensure!(
+            weight >= self.threshold,
+            "Collected weight {} is below the threshold {}",
+            weight,
+            self.threshold
+        );
+        // Signatures must be in the order of the public keys of the committee.
+        let mut signatures = self
+            .signatures()?
+            .into_iter()
+            .map(|signature| Ok((self.signer_index(&signature)?, signature)))
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        signatures.sort_by_key(|(index, _)| *index);
+        let signatures = signatures.into_iter().map(|(_, sig)| sig).collect();
+
+        let pks = self.public_keys()?;
+        Ok(match self.format {
+            MultiSigFormat::Current => MultiSig::combine(
+                signatures,
+                MultiSigPublicKey::new(pks, self.weights(), self.threshold)?,
+            )?
+            .into(),
+            MultiSigFormat::Legacy => MultiSigLegacy::combine(
+                signatures,
+                MultiSigPublicKeyLegacy::new(pks, self.weights(), self.threshold)?,
+            )?
+            .into(),
+        })
+    }
+
+    pub fn status(
+        &self,
+        path: PathBuf,
+        executed_tx_digest: Option<TransactionDigest>,
+    ) -> Result<MultiSigProposalStatus, anyhow::Error> {
+        let signed: Vec<usize> = self
+            .signatures()?
+            .iter()
+            .map(|signature| self.signer_index(signature))
+            .collect::<Result<_, _>>()?;
+        let (signed_by, missing) = self
+            .signers
+            .iter()
+            .enumerate()
+            .partition::<Vec<_>, _>(|(index, _)| signed.contains(index));
+        Ok(MultiSigProposalStatus {
+            proposal: path,
+            multisig_address: self.multisig_address()?,
+            tx_digest: TransactionDigest::new(default_hash(&self.tx_data()?)),
+            format: self.format,
+            signed_by: signed_by.into_iter().map(|(_, s)| s.address).collect(),
+            missing: missing.into_iter().map(|(_, s)| s.address).collect(),
+            weight: self.weight()?,
+            threshold: self.threshold,
+            summary: self.summary.clone(),
+            executed_tx_digest,
+        })
+    }
+}
+
+impl DryRunSummary {
+    pub fn new(response: &DryRunTransactionBlockResponse) -> Self {
+        let status = match response.effects.status() {
+            SuiExecutionStatus::Success => "success".to_string(),
+            SuiExecutionStatus::Failure { error } => format!("failure: {error}"),
+        };
+        let object_changes = response
+            .object_changes
+            .iter()
+            .map(|change| match change {
+                ObjectChange::Published {
+                    package_id,
+                    modules,
+                    ..
+                } => format!("published {package_id} with modules {}", modules.join(", ")),
+                ObjectChange::Transferred {
+                    recipient,
+                    object_type,
+                    object_id,
+                    ..
+                } => format!("transferred {object_id} ({object_type}) to {recipient}"),
+                ObjectChange::Mutated {
+                    owner,
+                    object_type,
+                    object_id,
+                    ..
+                } => format!("mutated {object_id} ({object_type}) owned by {owner}"),
+                ObjectChange::Deleted {
+                    object_type,
+                    object_id,
+                    ..
+                } => format!("deleted {object_id} ({object_type})"),
+                ObjectChange::Wrapped {
+                    object_type,
+                    object_id,
+                    ..
+                } => format!("wrapped {object_id} ({object_type})"),
+                ObjectChange::Created {
+                    owner,
+                    object_type,
+                    object_id,
+                    ..
+                } => format!("created {object_id} ({object_type}) owned by {owner}"),
+            })
+            .collect();
+        let balance_changes = response
+            .balance_changes
+            .iter()
+            .map(|change| {
+                format!(
+                    "{} {} for {}",
+                    change.amount, change.coin_type, change.owner
+                )
+            })
+            .collect();
+        Self {
+            status,
+            net_gas_usage: response.effects.gas_cost_summary().net_gas_usage(),
+            object_changes,
+            balance_changes,
+        }
+    }
+}
+
+impl Display for DryRunSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Dry run status: {}", self.status)?;
+        writeln!(f, "Net gas usage: {}", self.net_gas_usage)?;
+        writeln!(f, "Object changes:")?;
+        for change in &self.object_changes {
+            writeln!(f, "  {change}")?;
+        }
+        writeln!(f, "Balance changes:")?;
+        for change in &self.balance_changes {
+            writeln!(f, "  {change}")?;
+        }
+        Ok(())
+    }
+}
+
+async fn dry_run(rpc_url: &str, tx_data: TransactionData) -> Result<DryRunSummary, anyhow::Error> {
+    let client = SuiClientBuilder::default().build(rpc_url).await?;
+    let response = client.read_api().dry_run_transaction_block(tx_data).await?;
+    Ok(DryRunSummary::new(&response))
+}
+
+fn confirm(question: &str) -> Result<bool, anyhow::Error> {
+    print!("{question} [y/N] ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
@@ -5,6 +5,7 @@ use std::str::FromStr;
 
 use crate::keytool::read_authority_keypair_from_file;
 use crate::keytool::read_keypair_from_file;
+use crate::multisig_proposal::{DryRunSummary, MultiSigFormat, MultiSigProposal};
 
 use super::write_keypair_to_file;
 use super::KeyToolCommand;
@@ -14,6 +15,7 @@ use fastcrypto::encoding::Encoding;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use shared_crypto::intent::Intent;
+use shared_crypto::intent::IntentMessage;
 use shared_crypto::intent::IntentScope;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_types::base_types::ObjectDigest;
@@ -31,6 +33,8 @@ use sui_types::crypto::Signature;
 use sui_types::crypto::SignatureScheme;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::SuiSignatureInner;
+use sui_types::multisig::MultiSigPublicKey;
+use sui_types::signature::{AuthenticatorTrait, GenericSignature, VerifyParams};
 use sui_types::transaction::TransactionData;
 use sui_types::transaction::TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
 use tempfile::TempDir;
@@ -494,3 +498,110 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_multisig_proposal() -> Result<(), anyhow::Error> {
+    let keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(3));
+    let pks = keystore.keys();
+    let outsider = Keystore::from(InMemKeystore::new_insecure_for_tests(4));
+    let outsider_address = outsider
+        .addresses()
+        .into_iter()
+        .find(|address| !keystore.addresses().contains(address))
+        .unwrap();
+
+    for format in [MultiSigFormat::Current, MultiSigFormat::Legacy] {
+        let multisig_pk = MultiSigPublicKey::new(pks.clone(), vec![1, 1, 1], 2)?;
+        let multisig_address = SuiAddress::from(&multisig_pk);
+        let gas = (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        );
+        let tx_data = TransactionData::new_pay_sui(
+            multisig_address,
+            vec![gas],
+            vec![SuiAddress::random_for_testing_only()],
+            vec![10000],
+            gas,
+            TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            1,
+        )
+        .unwrap();
+
+        // The sender of the transaction must be the multisig address
+        let other_tx_data = TransactionData::new_pay_sui(
+            outsider_address,
+            vec![gas],
+            vec![SuiAddress::random_for_testing_only()],
+            vec![10000],
+            gas,
+            TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            1,
+        )
+        .unwrap();
+        assert!(MultiSigProposal::new(
+            other_tx_data,
+            pks.clone(),
+            vec![1, 1, 1],
+            2,
+            format,
+            DryRunSummary::default(),
+        )
+        .is_err());
+
+        let mut proposal = MultiSigProposal::new(
+            tx_data.clone(),
+            pks.clone(),
+            vec![1, 1, 1],
+            2,
+            format,
+            DryRunSummary::default(),
+        )?;
+        let sign = |address: SuiAddress, keystore: &Keystore| {
+            keystore
+                .sign_secure(&address, &tx_data, Intent::sui_transaction())
+                .unwrap()
+        };
+
+        // Signing twice with the same key only counts once
+        let first = SuiAddress::from(&pks[2]);
+        proposal.add_signature(sign(first, &keystore))?;
+        proposal.add_signature(sign(first, &keystore))?;
+        assert_eq!(proposal.weight()?, 1);
+        assert!(proposal.combine().is_err());
+
+        // Signatures from keys outside of the committee are rejected
+        assert!(proposal
+            .add_signature(sign(outsider_address, &outsider))
+            .is_err());
+
+        // The proposal survives a round trip through its file
+        let dir = TempDir::new()?;
+        let path = dir.path().join("proposal.json");
+        proposal.save(&path)?;
+        let mut proposal = MultiSigProposal::load(&path)?;
+
+        proposal.add_signature(sign(SuiAddress::from(&pks[0]), &keystore))?;
+        assert_eq!(proposal.weight()?, 2);
+        let status = proposal.status(path, None)?;
+        assert_eq!(
+            serde_json::to_value(&status)?["missing"],
+            serde_json::json!([SuiAddress::from(&pks[1])])
+        );
+
+        let signature = proposal.combine()?;
+        match (&signature, format) {
+            (GenericSignature::MultiSig(_), MultiSigFormat::Current)
+            | (GenericSignature::MultiSigLegacy(_), MultiSigFormat::Legacy) => (),
+            _ => panic!("Unexpected multisig format"),
+        }
+        signature.verify_authenticator(
+            &IntentMessage::new(Intent::sui_transaction(), tx_data.clone()),
+            multisig_address,
+            None,
+            &VerifyParams::default(),
+        )?;
+    }
+    Ok(())
+}
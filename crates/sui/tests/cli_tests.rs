@@ -2387,6 +2387,104 @@ async fn test_stake_with_u64_amount() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_stake_unstake_commands() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let client = context.get_client().await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, None, None, None)
+        .await?
+        .data;
+    let validator = client
+        .governance_api()
+        .get_latest_sui_system_state()
+        .await?
+        .active_validators[0]
+        .clone();
+
+    // Pick the validator by name, in a different case than registered.
+    let resp = SuiClientCommands::Stake {
+        validator: validator.name.to_uppercase(),
+        coins: vec![coins[0].coin_object_id],
+        amount: Some(1_000_000_000),
+        gas: Some(coins[1].coin_object_id),
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Stake(response) = resp else {
+        panic!("Unexpected response: {resp:?}");
+    };
+    assert!(response.status_ok().unwrap());
+
+    let resp = SuiClientCommands::Stakes { address: None }
+        .execute(context)
+        .await?;
+    let SuiClientCommandResult::Stakes(stakes) = resp else {
+        panic!("Unexpected response: {resp:?}");
+    };
+    assert_eq!(1, stakes.len());
+    assert_eq!(validator.sui_address, stakes[0].validator_address);
+    assert_eq!(Some(validator.name.clone()), stakes[0].validator_name);
+    assert_eq!(1_000_000_000, stakes[0].total_principal);
+
+    // Unknown validators are rejected.
+    assert!(SuiClientCommands::Stake {
+        validator: "no such validator".to_string(),
+        coins: vec![coins[0].coin_object_id],
+        amount: None,
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    let staked_sui_id = stakes[0].stakes[0].staked_sui_id;
+    let resp = SuiClientCommands::Unstake {
+        staked_sui_id,
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+        serialize_unsigned_transaction: true,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    assert!(matches!(
+        resp,
+        SuiClientCommandResult::SerializedUnsignedTransaction(_)
+    ));
+
+    let resp = SuiClientCommands::Unstake {
+        staked_sui_id,
+        gas: None,
+        gas_budget: rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+        serialize_unsigned_transaction: false,
+        serialize_signed_transaction: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Unstake(response) = resp else {
+        panic!("Unexpected response: {resp:?}");
+    };
+    assert!(response.status_ok().unwrap());
+    assert!(client
+        .governance_api()
+        .get_stakes(address)
+        .await?
+        .is_empty());
+    Ok(())
+}
+
 async fn test_with_sui_binary(args: &[&str]) -> Result<(), anyhow::Error> {
     let mut cmd = assert_cmd::Command::cargo_bin("sui").unwrap();
     let args = args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
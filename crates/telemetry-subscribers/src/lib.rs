@@ -35,6 +35,7 @@ use crate::file_exporter::{CachedOpenFile, FileExporter};
 
 mod file_exporter;
 pub mod span_latency_prom;
+pub mod trace_context;
 
 /// Alias for a type-erased error type.
 pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -0,0 +1,157 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! W3C trace context propagation, used to stitch spans created by different tasks or processes
+//! into a single trace.
+
+use opentelemetry_api::global;
+use opentelemetry_api::propagation::TextMapPropagator;
+use opentelemetry_api::trace::TraceContextExt;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::Mutex;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// The propagated fields (`traceparent` and `tracestate`) of a sampled span. Empty if the span
+/// is not exported, e.g. because OTLP tracing is disabled or the trace was not sampled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TraceContext(HashMap<String, String>);
+
+impl TraceContext {
+    /// Names of the fields a trace context may consist of.
+    pub const FIELDS: [&'static str; 2] = ["traceparent", "tracestate"];
+
+    /// The trace context of the current span.
+    pub fn current() -> Self {
+        Self::from_span(&Span::current())
+    }
+
+    pub fn from_span(span: &Span) -> Self {
+        let context = span.context();
+        let span_context = context.span().span_context().clone();
+        if !span_context.is_valid() || !span_context.is_sampled() {
+            return Self::default();
+        }
+
+        let mut fields = HashMap::new();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut fields)
+        });
+        Self(fields)
+    }
+
+    /// Makes `span` a child of the span this context was taken from. Has to be called before
+    /// `span` is entered for the first time.
+    pub fn set_as_parent_of(&self, span: &Span) {
+        if self.is_empty() {
+            return;
+        }
+        let context = global::get_text_map_propagator(|propagator| propagator.extract(&self.0));
+        span.set_parent(context);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl FromIterator<(String, String)> for TraceContext {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Remembers the trace contexts of a bounded number of recently seen keys, so that work on the
+/// same key that is performed by unrelated tasks ends up in the same trace.
+pub struct TraceContextRegistry<K> {
+    capacity: usize,
+    inner: Mutex<RegistryInner<K>>,
+}
+
+struct RegistryInner<K> {
+    contexts: HashMap<K, TraceContext>,
+    insertion_order: VecDeque<K>,
+}
+
+impl<K: Hash + Eq + Clone> TraceContextRegistry<K> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(RegistryInner {
+                contexts: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Records `context` for `key`. Empty contexts are ignored, and the first context recorded
+    /// for a key is kept, so that all later work hangs off the span where the key was first seen.
+    pub fn insert(&self, key: K, context: TraceContext) {
+        if context.is_empty() {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        if inner.contexts.contains_key(&key) {
+            return;
+        }
+        if inner.insertion_order.len() >= self.capacity {
+            if let Some(oldest) = inner.insertion_order.pop_front() {
+                inner.contexts.remove(&oldest);
+            }
+        }
+        inner.insertion_order.push_back(key.clone());
+        inner.contexts.insert(key, context);
+    }
+
+    pub fn get(&self, key: &K) -> Option<TraceContext> {
+        self.inner.lock().unwrap().contexts.get(key).cloned()
+    }
+
+    /// Makes `span` a child of the span recorded for `key`, if any.
+    pub fn set_as_parent_of(&self, key: &K, span: &Span) {
+        if let Some(context) = self.get(key) {
+            context.set_as_parent_of(span);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(traceparent: &str) -> TraceContext {
+        [("traceparent".to_string(), traceparent.to_string())]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn registry_is_bounded_and_keeps_first_context() {
+        let registry = TraceContextRegistry::new(2);
+        registry.insert(1, context("a"));
+        registry.insert(1, context("b"));
+        registry.insert(2, TraceContext::default());
+        assert_eq!(registry.get(&1), Some(context("a")));
+        assert_eq!(registry.get(&2), None);
+
+        registry.insert(2, context("c"));
+        registry.insert(3, context("d"));
+        assert_eq!(registry.get(&1), None);
+        assert_eq!(registry.get(&2), Some(context("c")));
+        assert_eq!(registry.get(&3), Some(context("d")));
+    }
+
+    #[test]
+    fn unsampled_spans_have_no_context() {
+        assert!(TraceContext::current().is_empty());
+    }
+}
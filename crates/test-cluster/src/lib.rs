@@ -5,8 +5,10 @@ use futures::{future::join_all, StreamExt};
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::ws_client::WsClient;
 use jsonrpsee::ws_client::WsClientBuilder;
+use mysten_network::fault_injection;
+pub use mysten_network::fault_injection::LinkFaults;
 use rand::{distributions::*, rngs::OsRng, seq::SliceRandom};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::net::SocketAddr;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
@@ -210,6 +212,43 @@ impl TestCluster {
         RandomNodeRestarter::new(self.clone())
     }
 
+    /// Cuts the nodes of every group off from the nodes in the other groups. Nodes that are not
+    /// part of any group are unaffected. Requires `TestClusterBuilder::with_network_fault_injection`.
+    pub fn partition_nodes(&self, groups: &[&[AuthorityName]]) {
+        let groups = groups
+            .iter()
+            .map(|group| group.iter().map(fault_injection_node_id).collect())
+            .collect();
+        network_fault_injector().partition(groups);
+    }
+
+    /// Removes all partitions between the nodes of this cluster.
+    pub fn heal_partitions(&self) {
+        network_fault_injector().heal(&self.fault_injection_node_ids());
+    }
+
+    /// Applies `faults` to all requests sent from `from` to `to`. Requires
+    /// `TestClusterBuilder::with_network_fault_injection`.
+    pub fn set_link_faults(&self, from: &AuthorityName, to: &AuthorityName, faults: LinkFaults) {
+        network_fault_injector().set_link_faults(
+            fault_injection_node_id(from),
+            fault_injection_node_id(to),
+            faults,
+        );
+    }
+
+    /// Removes all partitions and link faults between the nodes of this cluster.
+    pub fn clear_network_faults(&self) {
+        network_fault_injector().clear(&self.fault_injection_node_ids());
+    }
+
+    fn fault_injection_node_ids(&self) -> BTreeSet<fault_injection::NodeId> {
+        self.swarm
+            .all_nodes()
+            .map(|node| fault_injection_node_id(&node.name()))
+            .collect()
+    }
+
     pub async fn get_reference_gas_price(&self) -> u64 {
         self.sui_client()
             .governance_api()
@@ -638,6 +677,21 @@ impl Drop for RandomNodeRestarter {
     }
 }
 
+fn network_fault_injector() -> &'static fault_injection::FaultInjector {
+    let injector = fault_injection::injector();
+    assert!(
+        injector.is_enabled(),
+        "network fault injection must be enabled with `with_network_fault_injection`"
+    );
+    injector
+}
+
+/// Nodes are registered with the fault injector under their authority name, see
+/// `sui_swarm::memory::Container`.
+fn fault_injection_node_id(name: &AuthorityName) -> fault_injection::NodeId {
+    name.to_string()
+}
+
 pub struct TestClusterBuilder {
     genesis_config: Option<GenesisConfig>,
     network_config: Option<NetworkConfig>,
@@ -654,6 +708,7 @@ pub struct TestClusterBuilder {
     jwk_fetch_interval: Option<Duration>,
     config_dir: Option<PathBuf>,
     default_jwks: bool,
+    network_fault_injection: bool,
 }
 
 impl TestClusterBuilder {
@@ -673,6 +728,7 @@ impl TestClusterBuilder {
             jwk_fetch_interval: None,
             config_dir: None,
             default_jwks: false,
+            network_fault_injection: false,
         }
     }
 
@@ -754,6 +810,13 @@ impl TestClusterBuilder {
         self
     }
 
+    /// Allows partitions, latency and message loss to be injected between the nodes of the
+    /// cluster. Not supported in simulation tests.
+    pub fn with_network_fault_injection(mut self) -> Self {
+        self.network_fault_injection = true;
+        self
+    }
+
     pub fn with_fullnode_supported_protocol_versions_config(
         mut self,
         c: SupportedProtocolVersions,
@@ -841,6 +904,10 @@ impl TestClusterBuilder {
             }));
         }
 
+        if self.network_fault_injection {
+            fault_injection::injector().enable();
+        }
+
         let swarm = self.start_swarm().await.unwrap();
         let working_dir = swarm.dir();
 
@@ -89,3 +89,128 @@ async fn test_package_override() {
 
     assert_ne!(framework_ref, modified_ref);
 }
+
+#[cfg(not(msim))]
+#[tokio::test]
+async fn test_network_fault_injection() {
+    use std::time::Duration;
+    use test_cluster::LinkFaults;
+    use tokio::time::{sleep, timeout};
+
+    let cluster = TestClusterBuilder::new()
+        .with_network_fault_injection()
+        .build()
+        .await;
+    let validators = cluster.get_validator_pubkeys();
+    let (isolated, rest) = validators.split_first().unwrap();
+    let fullnode = cluster
+        .fullnode_handle
+        .sui_node
+        .with(|node| node.state().name);
+    let sender = cluster.get_address_0();
+    let highest_checkpoint = || {
+        cluster
+            .swarm
+            .validator_node_handles()
+            .iter()
+            .map(|handle| {
+                handle.with(|node| {
+                    node.state()
+                        .get_latest_checkpoint_sequence_number()
+                        .unwrap_or(0)
+                })
+            })
+            .max()
+            .unwrap()
+    };
+
+    // A single partitioned validator and slow links between the others do not prevent the
+    // remaining quorum from making progress.
+    let connected: Vec<_> = rest.iter().copied().chain([fullnode]).collect();
+    cluster.partition_nodes(&[&[*isolated], &connected[..]]);
+    for to in &rest[1..] {
+        cluster.set_link_faults(
+            &rest[0],
+            to,
+            LinkFaults {
+                latency: Duration::from_millis(50),
+                jitter: Duration::from_millis(50),
+                drop_rate: 0.0,
+            },
+        );
+    }
+    let tx = cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(1), sender)
+        .build();
+    let digest = cluster.sign_and_execute_transaction(&tx).await.digest;
+
+    // The isolated validator never saw the transaction.
+    let isolated_state = cluster
+        .swarm
+        .node(isolated)
+        .unwrap()
+        .get_node_handle()
+        .unwrap()
+        .with(|node| node.state());
+    assert!(!isolated_state.is_tx_already_executed(&digest).unwrap());
+    assert!(isolated_state
+        .database
+        .get_transaction_block(&digest)
+        .unwrap()
+        .is_none());
+
+    // Without a quorum on either side of a 2/2 partition, transactions still execute through
+    // the fullnode, which reaches all validators, but no checkpoint is certified until the
+    // partition heals.
+    cluster.clear_network_faults();
+    cluster.partition_nodes(&[&validators[..2], &validators[2..]]);
+    let tx = cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(1), sender)
+        .build();
+    cluster.sign_and_execute_transaction(&tx).await;
+    sleep(Duration::from_secs(2)).await;
+    let partitioned_checkpoint = highest_checkpoint();
+    sleep(Duration::from_secs(5)).await;
+    assert_eq!(highest_checkpoint(), partitioned_checkpoint);
+
+    cluster.heal_partitions();
+    timeout(Duration::from_secs(30), async {
+        while highest_checkpoint() <= partitioned_checkpoint {
+            sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("Checkpoints did not progress after the partition healed");
+
+    // Requests on links that drop everything are never answered, so the transaction times out.
+    for to in &validators {
+        cluster.set_link_faults(
+            &fullnode,
+            to,
+            LinkFaults {
+                drop_rate: 1.0,
+                ..Default::default()
+            },
+        );
+    }
+    let tx = cluster.sign_transaction(
+        &cluster
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(1), sender)
+            .build(),
+    );
+    assert!(timeout(
+        Duration::from_secs(10),
+        cluster.wallet.execute_transaction_may_fail(tx.clone())
+    )
+    .await
+    .is_err());
+
+    cluster.clear_network_faults();
+    cluster.execute_transaction(tx).await;
+}
@@ -5,17 +5,19 @@ use std::collections::{BTreeMap, HashSet};
 
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::Type::{self};
 use syn::{
     parse_macro_input, AngleBracketedGenericArguments, Attribute, Generics, ItemStruct, Lit, Meta,
-    PathArguments,
+    NestedMeta, PathArguments,
 };
 
 // This is used as default when none is specified
 const DEFAULT_DB_OPTIONS_CUSTOM_FN: &str = "typed_store::rocks::default_db_options";
 // Custom function which returns the option and overrides the defaults for this table
 const DB_OPTIONS_CUSTOM_FUNCTION: &str = "default_options_override_fn";
+// Declares a table as a secondary index of another table of the struct
+const SECONDARY_INDEX: &str = "secondary_index";
 
 /// Options can either be simplified form or
 enum GeneralTableOptions {
@@ -139,6 +141,433 @@ fn get_options_override_function(attr: &Attribute) -> syn::Result<String> {
     Ok(fn_name.value())
 }
 
+/// A table maintained as a secondary index of another table of the same struct.
+/// `entries_fn` has signature (&K, &V) -> impl IntoIterator<Item = (IndexK, IndexV)> and returns
+/// the index entries of a primary entry.
+/// Indexes on data which is not stored in the primary table declare the type of that data as
+/// their `source`, and `entries_fn` has signature (&K, &V, &Source) -> impl IntoIterator<...>
+struct SecondaryIndex {
+    index: Ident,
+    primary: Ident,
+    entries_fn: proc_macro2::TokenStream,
+    source: Option<Type>,
+}
+
+/// Extracts the secondary indexes declared with
+/// `#[secondary_index(primary = "table_name", entries_fn = "function_name", source = "Type")]`,
+/// where `source` is optional
+fn extract_secondary_indexes(input: &ItemStruct) -> syn::Result<Vec<SecondaryIndex>> {
+    let field_names: HashSet<_> = input
+        .fields
+        .iter()
+        .filter_map(|f| f.ident.clone())
+        .collect();
+    let usage = format!(
+        "Expected secondary index in format `#[{SECONDARY_INDEX}(primary = {{table_name}}, entries_fn = {{function_name}}, source = {{type}})]`, with an optional source"
+    );
+
+    let mut indexes = vec![];
+    for field in input.fields.iter() {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path.is_ident(SECONDARY_INDEX))
+        {
+            let meta = attr.parse_meta()?;
+            let Meta::List(list) = &meta else {
+                return Err(syn::Error::new_spanned(meta, usage));
+            };
+            let mut primary = None;
+            let mut entries_fn = None;
+            let mut source = None;
+            for nested in list.nested.iter() {
+                let NestedMeta::Meta(Meta::NameValue(val)) = nested else {
+                    return Err(syn::Error::new_spanned(nested, usage));
+                };
+                let Lit::Str(value) = &val.lit else {
+                    return Err(syn::Error::new_spanned(nested, usage));
+                };
+                if val.path.is_ident("primary") {
+                    primary = Some(value.parse::<Ident>()?);
+                } else if val.path.is_ident("entries_fn") {
+                    entries_fn = Some(value.value().parse().unwrap());
+                } else if val.path.is_ident("source") {
+                    source = Some(value.parse::<Type>()?);
+                } else {
+                    return Err(syn::Error::new_spanned(nested, usage));
+                }
+            }
+            let (Some(primary), Some(entries_fn)) = (primary, entries_fn) else {
+                return Err(syn::Error::new_spanned(meta, usage));
+            };
+            let index = field.ident.clone().unwrap();
+            if !field_names.contains(&primary) || primary == index {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    format!("Primary table `{primary}` of secondary index `{index}` must be another table of the struct"),
+                ));
+            }
+            indexes.push(SecondaryIndex {
+                index,
+                primary,
+                entries_fn,
+                source,
+            });
+        }
+    }
+
+    // The source of the entries of a primary table is passed to all of its indexes
+    for index in &indexes {
+        let (Some(source), Some(other)) = (
+            &index.source,
+            indexes
+                .iter()
+                .filter(|other| other.primary == index.primary)
+                .find_map(|other| other.source.as_ref()),
+        ) else {
+            continue;
+        };
+        if quote!(#source).to_string() != quote!(#other).to_string() {
+            return Err(syn::Error::new_spanned(
+                source,
+                format!(
+                    "All secondary indexes of `{}` must have the same source",
+                    index.primary
+                ),
+            ));
+        }
+    }
+
+    if let Some(chained) = indexes
+        .iter()
+        .find(|i| indexes.iter().any(|other| other.index == i.primary))
+    {
+        return Err(syn::Error::new_spanned(
+            &chained.index,
+            "The primary table of a secondary index cannot itself be a secondary index",
+        ));
+    }
+    Ok(indexes)
+}
+
+/// Generates the methods maintaining, verifying and rebuilding the secondary indexes.
+/// Returns the methods for the read-write struct, and the body of `verify_secondary_indexes`,
+/// which is shared with the read only struct.
+fn secondary_index_methods(
+    indexes: &[SecondaryIndex],
+    field_names: &[Ident],
+    inner_types: &[AngleBracketedGenericArguments],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let mut by_primary: BTreeMap<String, Vec<&SecondaryIndex>> = BTreeMap::new();
+    for index in indexes {
+        by_primary
+            .entry(index.primary.to_string())
+            .or_default()
+            .push(index);
+    }
+
+    let write_methods = by_primary.values().map(|indexes| {
+        let primary = &indexes[0].primary;
+        let inner_type = field_names
+            .iter()
+            .zip(inner_types)
+            .find_map(|(name, inner_type)| (name == primary).then_some(inner_type))
+            .unwrap();
+        let key_type = inner_type.args.first().unwrap();
+        let value_type = inner_type.args.last().unwrap();
+        let index_names: Vec<_> = indexes.iter().map(|i| &i.index).collect();
+        let entries_fns: Vec<_> = indexes.iter().map(|i| &i.entries_fn).collect();
+        let index_entries: Vec<_> = indexes
+            .iter()
+            .map(|i| {
+                let entries_fn = &i.entries_fn;
+                match i.source {
+                    Some(_) => quote! { #entries_fn(key, value, source) },
+                    None => quote! { #entries_fn(key, value) },
+                }
+            })
+            .collect();
+        let source = indexes.iter().find_map(|i| i.source.as_ref());
+        let (entry_type, entry_pattern, primary_entries) = match source {
+            Some(source) => (
+                quote! { (#key_type, #value_type, #source) },
+                quote! { (key, value, source) },
+                quote! { entries.into_iter().map(|(key, value, _)| (key, value)) },
+            ),
+            None => (
+                quote! { (#key_type, #value_type) },
+                quote! { (key, value) },
+                quote! { entries },
+            ),
+        };
+        let insert_new_fn_name = format_ident!("insert_new_{}_with_indexes", primary);
+        let insert_fn_name = format_ident!("insert_{}_with_indexes", primary);
+        let delete_fn_name = format_ident!("delete_{}_with_indexes", primary);
+        let doc_insert_new = format!(
+            "Adds entries of `{primary}` to the batch, together with the entries of its secondary indexes. The keys must not be in `{primary}` yet, as the index entries of overwritten values are not deleted."
+        );
+        let doc_insert = format!(
+            "Adds entries of `{primary}` to the batch, together with the entries of its secondary indexes. The index entries of overwritten values are deleted."
+        );
+        let doc_delete = format!(
+            "Adds the deletion of entries of `{primary}` to the batch, together with the deletion of the entries of its secondary indexes."
+        );
+
+        let insert_new = quote! {
+            #[doc = #doc_insert_new]
+            pub fn #insert_new_fn_name(
+                &self,
+                batch: &mut typed_store::rocks::DBBatch,
+                entries: impl IntoIterator<Item = #entry_type>,
+            ) -> Result<(), typed_store::rocks::TypedStoreError> {
+                let entries: Vec<#entry_type> = entries.into_iter().collect();
+                #(
+                    batch.insert_batch(
+                        &self.#index_names,
+                        entries.iter().flat_map(|#entry_pattern| #index_entries),
+                    )?;
+                )*
+                batch.insert_batch(&self.#primary, #primary_entries)?;
+                Ok(())
+            }
+        };
+        // The index entries of a value can only be deleted when all of them are derived from the
+        // primary table alone
+        if let Some(source) = source {
+            let sourced: Vec<_> = indexes.iter().filter(|i| i.source.is_some()).collect();
+            let sourced_names: Vec<_> = sourced.iter().map(|i| &i.index).collect();
+            let sourced_entries_fns: Vec<_> = sourced.iter().map(|i| &i.entries_fn).collect();
+            let num_sourced = sourced.len();
+            let positions: Vec<_> = (0..num_sourced).collect();
+            let verify_fn_name = format_ident!("verify_{}_indexes_with_source", primary);
+            let rebuild_fn_name = format_ident!("rebuild_{}_indexes_with_source", primary);
+            let doc_verify = format!(
+                "Checks the secondary indexes of `{primary}` which have a source against the contents of `{primary}`. `get_source` returns the source of an entry of `{primary}`, or None if it can't be found, in which case the entry is counted in `missing_sources`. Returns a report per index table."
+            );
+            let doc_rebuild = format!(
+                "Rebuilds the secondary indexes of `{primary}` which have a source from the contents of `{primary}`, with the sources returned by `get_source`. Existing index entries are deleted first, and entries of `{primary}` without a source are not indexed."
+            );
+            return quote! {
+                #insert_new
+
+                #[doc = #doc_verify]
+                pub fn #verify_fn_name<E: From<typed_store::rocks::TypedStoreError>>(
+                    &self,
+                    mut get_source: impl FnMut(&#key_type, &#value_type) -> Result<Option<#source>, E>,
+                ) -> Result<std::collections::BTreeMap<String, typed_store::traits::SecondaryIndexReport>, E> {
+                    let mut reports = vec![
+                        typed_store::traits::SecondaryIndexReport {
+                            primary_table: stringify!(#primary).to_owned(),
+                            ..Default::default()
+                        };
+                        #num_sourced
+                    ];
+                    for entry in typed_store::traits::Map::safe_iter(&self.#primary) {
+                        let (key, value) = entry?;
+                        let Some(source) = get_source(&key, &value)? else {
+                            for report in reports.iter_mut() {
+                                report.missing_sources += 1;
+                            }
+                            continue;
+                        };
+                        #(
+                            for (index_key, index_value) in #sourced_entries_fns(&key, &value, &source) {
+                                let report = &mut reports[#positions];
+                                report.expected_entries += 1;
+                                match typed_store::traits::Map::get(&self.#sourced_names, &index_key)? {
+                                    Some(found) if found == index_value => (),
+                                    Some(_) => report.mismatched_entries += 1,
+                                    None => report.missing_entries += 1,
+                                }
+                            }
+                        )*
+                    }
+                    #(
+                        for entry in typed_store::traits::Map::safe_iter(&self.#sourced_names) {
+                            entry?;
+                            reports[#positions].index_entries += 1;
+                        }
+                    )*
+                    Ok([#(stringify!(#sourced_names).to_owned()),*].into_iter().zip(reports).collect())
+                }
+
+                #[doc = #doc_rebuild]
+                pub fn #rebuild_fn_name<E: From<typed_store::rocks::TypedStoreError>>(
+                    &self,
+                    mut get_source: impl FnMut(&#key_type, &#value_type) -> Result<Option<#source>, E>,
+                ) -> Result<(), E> {
+                    let batch_size = 10_000;
+                    let mut batch = self.#primary.batch();
+                    let mut pending = 0;
+                    #(
+                        for entry in typed_store::traits::Map::safe_iter(&self.#sourced_names) {
+                            let (index_key, _) = entry?;
+                            batch.delete_batch(&self.#sourced_names, std::iter::once(index_key))?;
+                            pending += 1;
+                            if pending >= batch_size {
+                                std::mem::replace(&mut batch, self.#primary.batch()).write()?;
+                                pending = 0;
+                            }
+                        }
+                    )*
+                    for entry in typed_store::traits::Map::safe_iter(&self.#primary) {
+                        let (key, value) = entry?;
+                        let Some(source) = get_source(&key, &value)? else {
+                            continue;
+                        };
+                        #(
+                            for index_entry in #sourced_entries_fns(&key, &value, &source) {
+                                batch.insert_batch(&self.#sourced_names, std::iter::once(index_entry))?;
+                                pending += 1;
+                            }
+                        )*
+                        if pending >= batch_size {
+                            std::mem::replace(&mut batch, self.#primary.batch()).write()?;
+                            pending = 0;
+                        }
+                    }
+                    batch.write()?;
+                    Ok(())
+                }
+            };
+        }
+
+        quote! {
+            #insert_new
+
+            #[doc = #doc_insert]
+            pub fn #insert_fn_name(
+                &self,
+                batch: &mut typed_store::rocks::DBBatch,
+                entries: impl IntoIterator<Item = (#key_type, #value_type)>,
+            ) -> Result<(), typed_store::rocks::TypedStoreError> {
+                let entries: Vec<(#key_type, #value_type)> = entries.into_iter().collect();
+                let previous_values = typed_store::traits::Map::multi_get(
+                    &self.#primary,
+                    entries.iter().map(|(key, _)| key),
+                )?;
+                for ((key, _), previous_value) in entries.iter().zip(previous_values) {
+                    if let Some(previous_value) = previous_value {
+                        #(
+                            batch.delete_batch(
+                                &self.#index_names,
+                                #entries_fns(key, &previous_value).into_iter().map(|(index_key, _)| index_key),
+                            )?;
+                        )*
+                    }
+                }
+                self.#insert_new_fn_name(batch, entries)
+            }
+
+            #[doc = #doc_delete]
+            pub fn #delete_fn_name(
+                &self,
+                batch: &mut typed_store::rocks::DBBatch,
+                keys: impl IntoIterator<Item = #key_type>,
+            ) -> Result<(), typed_store::rocks::TypedStoreError> {
+                let keys: Vec<#key_type> = keys.into_iter().collect();
+                let values = typed_store::traits::Map::multi_get(&self.#primary, &keys)?;
+                for (key, value) in keys.iter().zip(values) {
+                    if let Some(value) = value {
+                        #(
+                            batch.delete_batch(
+                                &self.#index_names,
+                                #entries_fns(key, &value).into_iter().map(|(index_key, _)| index_key),
+                            )?;
+                        )*
+                    }
+                }
+                batch.delete_batch(&self.#primary, keys)?;
+                Ok(())
+            }
+        }
+    });
+
+    // Indexes with a source can't be derived from their primary table alone, so they are
+    // verified and rebuilt by the methods taking their source instead
+    let indexes: Vec<_> = indexes.iter().filter(|i| i.source.is_none()).collect();
+    let index_names: Vec<_> = indexes.iter().map(|i| &i.index).collect();
+    let primary_names: Vec<_> = indexes.iter().map(|i| &i.primary).collect();
+    let entries_fns: Vec<_> = indexes.iter().map(|i| &i.entries_fn).collect();
+
+    let verify_body = quote! {
+        #[allow(unused_mut)]
+        let mut reports = std::collections::BTreeMap::new();
+        #(
+            {
+                let mut report = typed_store::traits::SecondaryIndexReport {
+                    primary_table: stringify!(#primary_names).to_owned(),
+                    ..Default::default()
+                };
+                for entry in typed_store::traits::Map::safe_iter(&self.#primary_names) {
+                    let (key, value) = entry?;
+                    for (index_key, index_value) in #entries_fns(&key, &value) {
+                        report.expected_entries += 1;
+                        match typed_store::traits::Map::get(&self.#index_names, &index_key)? {
+                            Some(found) if found == index_value => (),
+                            Some(_) => report.mismatched_entries += 1,
+                            None => report.missing_entries += 1,
+                        }
+                    }
+                }
+                for entry in typed_store::traits::Map::safe_iter(&self.#index_names) {
+                    entry?;
+                    report.index_entries += 1;
+                }
+                reports.insert(stringify!(#index_names).to_owned(), report);
+            }
+        )*
+        Ok(reports)
+    };
+
+    let methods = quote! {
+        #(#write_methods)*
+
+        /// Checks the secondary indexes against the contents of their primary tables.
+        /// Returns a report per index table.
+        pub fn verify_secondary_indexes(
+            &self,
+        ) -> Result<std::collections::BTreeMap<String, typed_store::traits::SecondaryIndexReport>, typed_store::rocks::TypedStoreError> {
+            #verify_body
+        }
+
+        /// Rebuilds the secondary indexes from the contents of their primary tables.
+        /// Existing index entries are deleted first.
+        pub fn rebuild_secondary_indexes(&self) -> Result<(), typed_store::rocks::TypedStoreError> {
+            #(
+                {
+                    let batch_size = 10_000;
+                    let mut batch = self.#index_names.batch();
+                    let mut pending = 0;
+                    for entry in typed_store::traits::Map::safe_iter(&self.#index_names) {
+                        let (index_key, _) = entry?;
+                        batch.delete_batch(&self.#index_names, std::iter::once(index_key))?;
+                        pending += 1;
+                        if pending >= batch_size {
+                            std::mem::replace(&mut batch, self.#index_names.batch()).write()?;
+                            pending = 0;
+                        }
+                    }
+                    for entry in typed_store::traits::Map::safe_iter(&self.#primary_names) {
+                        let (key, value) = entry?;
+                        for index_entry in #entries_fns(&key, &value) {
+                            batch.insert_batch(&self.#index_names, std::iter::once(index_entry))?;
+                            pending += 1;
+                        }
+                        if pending >= batch_size {
+                            std::mem::replace(&mut batch, self.#index_names.batch()).write()?;
+                            pending = 0;
+                        }
+                    }
+                    batch.write()?;
+                }
+            )*
+            Ok(())
+        }
+    };
+    (methods, verify_body)
+}
+
 fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
     generics
         .params
@@ -159,6 +588,7 @@ fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
 /// 3. Auto-generated `read_only_mode` handle
 /// 4. Auto-generated memory stats method
 /// 5. Other convenience features
+/// 6. Secondary indexes
 ///
 /// 1. Flexible configuration:
 /// a. Static options specified at struct definition
@@ -271,6 +701,42 @@ fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
 /// 5. Other convenience features
 /// `Tables::describe_tables` is used to get a list of the table names and key-value types as string in a BTreeMap
 ///
+/// 6. Secondary indexes
+/// A table can be declared as a secondary index of another table of the struct, the primary.
+/// A user defined function of signature (&K, &V) -> impl IntoIterator<Item = (IndexK, IndexV)> gives the index entries of each primary entry
+/// For each primary, `insert_{primary}_with_indexes` and `delete_{primary}_with_indexes` are generated, which write the primary and
+/// index entries in the same batch, and `verify_secondary_indexes` and `rebuild_secondary_indexes` check and repair the indexes
+/// `insert_new_{primary}_with_indexes` skips reading the values it overwrites, for keys which are known to be new
+/// An index on data which is not stored in the primary table declares its type with `source = "Type"`, and its function takes a third
+/// &Source argument. Entries of its primary are then inserted as (K, V, Source) with `insert_new_{primary}_with_indexes` only,
+/// and the index is not covered by `verify_secondary_indexes` and `rebuild_secondary_indexes`. Instead,
+/// `verify_{primary}_indexes_with_source` and `rebuild_{primary}_indexes_with_source` take a function returning the source
+/// of each primary entry
+///```
+/// use typed_store::rocks::DBMap;
+/// use typed_store::rocks::MetricConf;
+/// use typed_store_derive::DBMapUtils;
+/// use typed_store::traits::TypedStoreDebug;
+/// use typed_store::traits::TableSummary;
+///
+/// fn owner_index(id: &u64, owner: &String) -> [((String, u64), ()); 1] {
+///     [((owner.clone(), *id), ())]
+/// }
+/// #[derive(DBMapUtils)]
+/// struct Tables {
+///     objects: DBMap<u64, String>,
+///     #[secondary_index(primary = "objects", entries_fn = "owner_index")]
+///     objects_by_owner: DBMap<(String, u64), ()>,
+/// }
+///
+/// let primary_path = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+/// let tables = Tables::open_tables_read_write(primary_path, MetricConf::default(), None, None);
+/// let mut batch = tables.objects.batch();
+/// tables.insert_objects_with_indexes(&mut batch, [(1, "alice".to_owned())]).unwrap();
+/// batch.write().unwrap();
+/// assert!(tables.verify_secondary_indexes().unwrap()["objects_by_owner"].is_consistent());
+/// ```
+///
 /// // Bad usage example
 /// // Structs fields most only be of type Store<K, V> or DMBap<K, V>
 /// // This will fail to compile with error `All struct members must be of type Store<K, V> or DMBap<K, V>`
@@ -280,7 +746,7 @@ fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
 /// //     bad_field: u32,
 /// // #}
 
-#[proc_macro_derive(DBMapUtils, attributes(default_options_override_fn))]
+#[proc_macro_derive(DBMapUtils, attributes(default_options_override_fn, secondary_index))]
 pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as ItemStruct);
     let name = &input.ident;
@@ -298,6 +764,21 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     let (field_names, inner_types, derived_table_options, simple_field_type_name_str) =
         extract_struct_info(input.clone(), allowed_strs);
 
+    let secondary_indexes = match extract_secondary_indexes(&input) {
+        Ok(indexes) => indexes,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    if !secondary_indexes.is_empty() && simple_field_type_name_str != "DBMap" {
+        panic!("Secondary indexes are only supported on DBMap tables");
+    }
+    let (secondary_index_methods, verify_secondary_indexes_body) =
+        secondary_index_methods(&secondary_indexes, &field_names, &inner_types);
+    let indexed_table_names: Vec<_> = secondary_indexes
+        .iter()
+        .filter(|i| i.source.is_none())
+        .flat_map(|i| [&i.primary, &i.index])
+        .collect();
+
     let (key_names, value_names): (Vec<_>, Vec<_>) = inner_types
         .iter()
         .map(|q| (q.args.first().unwrap(), q.args.last().unwrap()))
@@ -353,6 +834,16 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 }
             }
 
+            /// Initialize to the options tables are opened with when no config is given, i.e.
+            /// those of their `default_options_override_fn`
+            pub fn init_with_table_defaults() -> Self {
+                Self {
+                    #(
+                        #field_names : #default_options_override_fn_names(),
+                    )*
+                }
+            }
+
             /// Build a config
             pub fn build(&self) -> typed_store::rocks::DBMapTableConfigMap {
                 typed_store::rocks::DBMapTableConfigMap::new([
@@ -502,6 +993,8 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 ) -> #secondary_db_map_struct_name #generics {
                 #secondary_db_map_struct_name::open_tables_read_only(primary_path, with_secondary_path, metric_conf, global_db_options_override)
             }
+
+            #secondary_index_methods
         }
 
 
@@ -562,6 +1055,26 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 })
             }
 
+            /// Stream the entries of a table selected by `options` to `sink`, with decoded keys and values
+            /// Tables must be opened in read only mode using `open_tables_read_only`
+            pub fn export(
+                &self,
+                table_name: &str,
+                options: &typed_store::rocks::export::TableExportOptions,
+                sink: &mut dyn FnMut(typed_store::rocks::export::ExportedEntry) -> eyre::Result<()>,
+            ) -> eyre::Result<u64> {
+                match table_name {
+                    #(
+                        stringify!(#field_names) => {
+                            typed_store::traits::Map::try_catch_up_with_primary(&self.#field_names)?;
+                            self.#field_names.export(options, sink)
+                        }
+                    )*
+
+                    _ => eyre::bail!("No such table name: {}", table_name),
+                }
+            }
+
             /// Get key value sizes from the db
             /// Tables must be opened in read only mode using `open_tables_read_only`
             pub fn table_summary(&self, table_name: &str) -> eyre::Result<typed_store::traits::TableSummary> {
@@ -600,6 +1113,17 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                     (stringify!(#field_names).to_owned(), (stringify!(#key_names).to_owned(), stringify!(#value_names).to_owned())),
                 )*].into_iter().collect()
             }
+
+            /// Checks the secondary indexes against the contents of their primary tables
+            /// Tables must be opened in read only mode using `open_tables_read_only`
+            pub fn verify_secondary_indexes(
+                &self,
+            ) -> Result<std::collections::BTreeMap<String, typed_store::traits::SecondaryIndexReport>, typed_store::rocks::TypedStoreError> {
+                #(
+                    typed_store::traits::Map::try_catch_up_with_primary(&self.#indexed_table_names)?;
+                )*
+                #verify_secondary_indexes_body
+            }
         }
 
         impl <
@@ -632,7 +1156,20 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                     self.table_summary(table_name.as_str())
                 }
 
+                fn export_table(
+                    &self,
+                    table_name: String,
+                    options: &typed_store::rocks::export::TableExportOptions,
+                    sink: &mut dyn FnMut(typed_store::rocks::export::ExportedEntry) -> eyre::Result<()>,
+                ) -> eyre::Result<u64> {
+                    self.export(table_name.as_str(), options, sink)
+                }
 
+                fn verify_secondary_indexes(
+                    &self,
+                ) -> eyre::Result<std::collections::BTreeMap<String, typed_store::traits::SecondaryIndexReport>> {
+                    Ok(#secondary_db_map_struct_name::verify_secondary_indexes(self)?)
+                }
         }
 
     })
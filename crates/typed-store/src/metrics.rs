@@ -254,6 +254,7 @@ pub struct OperationMetrics {
     pub rocksdb_deletes: IntCounterVec,
     pub rocksdb_batch_commit_latency_seconds: HistogramVec,
     pub rocksdb_batch_commit_bytes: HistogramVec,
+    pub rocksdb_compaction_filter_removed_keys: IntCounterVec,
 }
 
 impl OperationMetrics {
@@ -356,6 +357,13 @@ impl OperationMetrics {
                 registry,
             )
             .unwrap(),
+            rocksdb_compaction_filter_removed_keys: register_int_counter_vec_with_registry!(
+                "rocksdb_compaction_filter_removed_keys",
+                "Number of keys dropped by a retention compaction filter",
+                &["filter_name"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -0,0 +1,82 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of table entries with decoded keys and values, for analysis outside of the node.
+//!
+//! Keys and values are decoded to JSON using their serde implementation. Key bounds are given as
+//! the JSON encoding of the key, e.g. `["0x5", 10]` for a `(ObjectID, u64)` key.
+
+use std::fmt::Debug;
+
+use eyre::eyre;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::rocks::DBMap;
+use crate::traits::Map;
+
+/// Selects the entries of a table to export.
+#[derive(Debug, Clone, Default)]
+pub struct TableExportOptions {
+    /// Lower bound of the exported keys, inclusive, as the JSON encoding of a key.
+    pub start_key: Option<String>,
+    /// Upper bound of the exported keys, exclusive, as the JSON encoding of a key.
+    pub end_key: Option<String>,
+    /// Exports one out of every `sample_every` entries. 0 and 1 export every entry.
+    pub sample_every: u64,
+    /// Maximum number of entries to export.
+    pub limit: Option<u64>,
+}
+
+/// A table entry with its key and value decoded to JSON.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedEntry {
+    pub key: serde_json::Value,
+    pub value: serde_json::Value,
+}
+
+/// Decodes `value` to JSON, falling back to its debug representation for values JSON cannot
+/// represent, e.g. maps with non string keys.
+pub fn to_json_value<T: Serialize + Debug>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_else(|_| serde_json::Value::String(format!("{value:?}")))
+}
+
+impl<K, V> DBMap<K, V>
+where
+    K: Serialize + DeserializeOwned + Debug,
+    V: Serialize + DeserializeOwned + Debug,
+{
+    /// Streams the entries selected by `options` to `sink`, in key order. Returns the number of
+    /// exported entries.
+    pub fn export(
+        &self,
+        options: &TableExportOptions,
+        sink: &mut dyn FnMut(ExportedEntry) -> eyre::Result<()>,
+    ) -> eyre::Result<u64> {
+        let parse_bound = |bound: &Option<String>| -> eyre::Result<Option<K>> {
+            bound
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .map_err(|e| eyre!("Invalid key bound for table {}: {e}", self.cf))
+        };
+        let lower_bound = parse_bound(&options.start_key)?;
+        let upper_bound = parse_bound(&options.end_key)?;
+
+        let mut exported = 0;
+        for entry in self
+            .safe_iter_with_bounds(lower_bound, upper_bound)?
+            .step_by(options.sample_every.max(1) as usize)
+        {
+            if options.limit.is_some_and(|limit| exported >= limit) {
+                break;
+            }
+            let (key, value) = entry?;
+            sink(ExportedEntry {
+                key: to_json_value(&key),
+                value: to_json_value(&value),
+            })?;
+            exported += 1;
+        }
+        Ok(exported)
+    }
+}
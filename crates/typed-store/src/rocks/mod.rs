@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 pub mod errors;
+pub mod export;
 pub(crate) mod iter;
 pub(crate) mod keys;
+pub mod retention;
 pub(crate) mod safe_iter;
 pub mod util;
 pub(crate) mod values;
@@ -40,6 +42,7 @@ use tokio::sync::oneshot;
 use tracing::{debug, error, info, instrument, warn};
 
 use self::{iter::Iter, keys::Keys, values::Values};
+use crate::rocks::retention::RetentionPolicy;
 use crate::rocks::safe_iter::SafeIter;
 pub use errors::TypedStoreError;
 use std::ops::Bound;
@@ -1068,6 +1071,58 @@ impl<K, V> DBMap<K, V> {
         Ok(self.rocksdb.snapshot())
     }
 
+    /// Same as `iter_with_bounds`, but performs status checks, and returns an error instead of
+    /// panicking when a bound can't be serialized.
+    pub fn safe_iter_with_bounds(
+        &self,
+        lower_bound: Option<K>,
+        upper_bound: Option<K>,
+    ) -> Result<SafeIter<'_, K, V>, TypedStoreError>
+    where
+        K: Serialize + DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let _timer = self
+            .db_metrics
+            .op_metrics
+            .rocksdb_iter_latency_seconds
+            .with_label_values(&[&self.cf])
+            .start_timer();
+        let bytes_scanned = self
+            .db_metrics
+            .op_metrics
+            .rocksdb_iter_bytes
+            .with_label_values(&[&self.cf]);
+        let keys_scanned = self
+            .db_metrics
+            .op_metrics
+            .rocksdb_iter_keys
+            .with_label_values(&[&self.cf]);
+        let _perf_ctx = if self.iter_sample_interval.sample() {
+            Some(RocksDBPerfContext)
+        } else {
+            None
+        };
+        let mut readopts = self.opts.readopts();
+        if let Some(lower_bound) = lower_bound {
+            readopts.set_iterate_lower_bound(be_fix_int_ser(&lower_bound)?);
+        }
+        if let Some(upper_bound) = upper_bound {
+            readopts.set_iterate_upper_bound(be_fix_int_ser(&upper_bound)?);
+        }
+        let mut db_iter = self.rocksdb.raw_iterator_cf(&self.cf(), readopts);
+        db_iter.seek_to_first();
+        Ok(SafeIter::new(
+            self.cf.clone(),
+            db_iter,
+            Some(_timer),
+            _perf_ctx,
+            Some(bytes_scanned),
+            Some(keys_scanned),
+            Some(self.db_metrics.clone()),
+        ))
+    }
+
     pub fn table_summary(&self) -> eyre::Result<TableSummary> {
         let mut num_keys = 0;
         let mut key_bytes_total = 0;
@@ -2287,6 +2342,14 @@ impl DBOptions {
         self.options.set_min_write_buffer_number_to_merge(2);
         self
     }
+
+    // Drop entries out of the retention window of the policy during compactions, instead of
+    // pruning them with explicit deletes. This replaces any compaction filter set previously.
+    pub fn set_retention_policy(mut self, name: &str, policy: RetentionPolicy) -> DBOptions {
+        self.options
+            .set_compaction_filter(name, policy.compaction_filter(name.to_string()));
+        self
+    }
 }
 
 /// Creates a default RocksDB option, to be used when RocksDB option is unspecified.
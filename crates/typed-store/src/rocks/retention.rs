@@ -0,0 +1,99 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Declarative retention of column family entries, enforced by a RocksDB compaction filter.
+//!
+//! A `RetentionPolicy` extracts a timestamp from the raw bytes of the key or of the value of
+//! each entry, and drops the entry during compaction once it falls out of the retention window.
+//! This lets tables age out without a pruner scanning them and issuing deletes. Entries are only
+//! dropped when their SST file is compacted, so reads may still observe expired entries for a
+//! while.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rocksdb::CompactionDecision;
+
+use crate::metrics::DBMetrics;
+
+/// Extracts the timestamp of an entry from its serialized key or value. Returning `None` keeps
+/// the entry.
+pub type RetentionExtractor = fn(bytes: &[u8]) -> Option<u64>;
+
+/// Extracts a big endian `u64` from the first 8 bytes of the key, which is how typed-store
+/// serializes keys whose first component is a timestamp.
+pub fn u64_key_prefix(key: &[u8]) -> Option<u64> {
+    Some(u64::from_be_bytes(key.get(..8)?.try_into().ok()?))
+}
+
+/// The part of an entry the extractor of a `RetentionPolicy` reads.
+#[derive(Clone, Copy)]
+enum RetentionField {
+    Key,
+    Value,
+}
+
+/// Drops entries whose timestamp is older than `retention` relative to the wall clock.
+#[derive(Clone)]
+pub struct RetentionPolicy {
+    extractor: RetentionExtractor,
+    field: RetentionField,
+    retention: u64,
+}
+
+impl RetentionPolicy {
+    /// Keeps entries whose key timestamp, in milliseconds, is within `retention` of the wall
+    /// clock.
+    pub fn timestamp_ms(extractor: RetentionExtractor, retention: Duration) -> Self {
+        Self {
+            extractor,
+            field: RetentionField::Key,
+            retention: retention.as_millis() as u64,
+        }
+    }
+
+    /// Same as `timestamp_ms`, with the timestamp extracted from the serialized value. Tables
+    /// holding the same values under different keys, e.g. secondary indexes, then drop the same
+    /// entries.
+    pub fn value_timestamp_ms(extractor: RetentionExtractor, retention: Duration) -> Self {
+        Self {
+            field: RetentionField::Value,
+            ..Self::timestamp_ms(extractor, retention)
+        }
+    }
+
+    /// Returns true if the entry with the given key and value is out of the retention window.
+    pub fn is_expired(&self, key: &[u8], value: &[u8]) -> bool {
+        let bytes = match self.field {
+            RetentionField::Key => key,
+            RetentionField::Value => value,
+        };
+        let Some(timestamp_ms) = (self.extractor)(bytes) else {
+            return false;
+        };
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        timestamp_ms.saturating_add(self.retention) < now_ms
+    }
+
+    /// Builds the compaction filter enforcing this policy. Dropped keys are counted under
+    /// `name` in the `rocksdb_compaction_filter_removed_keys` metric.
+    pub(crate) fn compaction_filter(
+        self,
+        name: String,
+    ) -> impl FnMut(u32, &[u8], &[u8]) -> CompactionDecision + Send + 'static {
+        let removed_keys = DBMetrics::get()
+            .op_metrics
+            .rocksdb_compaction_filter_removed_keys
+            .with_label_values(&[&name]);
+        move |_level, key, value| {
+            if self.is_expired(key, value) {
+                removed_keys.inc();
+                CompactionDecision::Remove
+            } else {
+                CompactionDecision::Keep
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use super::*;
+use crate::rocks::export::TableExportOptions;
+use crate::rocks::retention::{u64_key_prefix, RetentionPolicy};
 use crate::rocks::util::{is_ref_count_value, reference_count_merge_operator};
 use crate::{reopen, retry_transaction, retry_transaction_forever};
 use rstest::rstest;
@@ -1234,6 +1236,128 @@ async fn refcount_with_compaction_test() {
     assert!(is_ref_count_value(&value));
 }
 
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[tokio::test]
+async fn retention_policy_compaction_test() {
+    let options = default_db_options()
+        .set_retention_policy(
+            "retention_policy_compaction_test",
+            RetentionPolicy::timestamp_ms(u64_key_prefix, Duration::from_secs(3600)),
+        )
+        .options;
+    let db = DBMap::<(u64, u64), String>::open(
+        temp_dir(),
+        MetricConf::default(),
+        Some(options),
+        None,
+        &ReadWriteOptions::default(),
+    )
+    .expect("failed to open rocksdb");
+    let now_ms = now_ms();
+    // Three timestamps out of the retention window, and two within it.
+    let timestamps = [1, 1_000, now_ms - 7_200_000, now_ms - 60_000, now_ms];
+    for timestamp in timestamps {
+        for i in 0..2 {
+            db.insert(&(timestamp, i), &format!("{timestamp}-{i}"))
+                .unwrap();
+        }
+    }
+    assert_eq!(db.unbounded_iter().count(), 10);
+
+    // Only entries within the retention window are kept.
+    db.rocksdb.flush().unwrap();
+    db.compact_range_to_bottom(&(0u64, 0u64), &(u64::MAX, u64::MAX))
+        .unwrap();
+    let kept: Vec<_> = db.keys().map(|key| key.unwrap().0).collect();
+    assert_eq!(kept, vec![now_ms - 60_000, now_ms - 60_000, now_ms, now_ms]);
+    assert_eq!(
+        DBMetrics::get()
+            .op_metrics
+            .rocksdb_compaction_filter_removed_keys
+            .with_label_values(&["retention_policy_compaction_test"])
+            .get(),
+        6
+    );
+}
+
+#[test]
+fn retention_policy_timestamp_test() {
+    let now_ms = now_ms();
+    let policy = RetentionPolicy::timestamp_ms(u64_key_prefix, Duration::from_secs(60));
+    let key = |timestamp_ms: u64| be_fix_int_ser(&(timestamp_ms, 7u64)).unwrap();
+
+    assert!(!policy.is_expired(&key(now_ms), &[]));
+    assert!(!policy.is_expired(&key(now_ms - 10_000), &[]));
+    assert!(policy.is_expired(&key(now_ms - 120_000), &[]));
+    // Keys too short to hold a timestamp are kept.
+    assert!(!policy.is_expired(&[1, 2, 3], &[]));
+}
+
+#[test]
+fn retention_policy_value_timestamp_test() {
+    let now_ms = now_ms();
+    let value_timestamp = |value: &[u8]| bcs::from_bytes::<(String, u64)>(value).ok().map(|v| v.1);
+    let policy = RetentionPolicy::value_timestamp_ms(value_timestamp, Duration::from_secs(60));
+    let key = be_fix_int_ser(&(0u64, 7u64)).unwrap();
+    let value = |timestamp_ms: u64| bcs::to_bytes(&("value".to_string(), timestamp_ms)).unwrap();
+
+    // The timestamp of the value is used, not the one of the key.
+    assert!(!policy.is_expired(&key, &value(now_ms)));
+    assert!(policy.is_expired(&key, &value(now_ms - 120_000)));
+    // Values which can't be read are kept.
+    assert!(!policy.is_expired(&key, &[1, 2, 3]));
+}
+
+#[tokio::test]
+async fn test_export() {
+    let db = open_map(temp_dir(), None, false);
+    for i in 1..100u64 {
+        db.insert(&(i, i.to_string()), &i.to_string()).unwrap();
+    }
+
+    let export = |options: TableExportOptions| {
+        let mut entries = vec![];
+        let count = db
+            .export(&options, &mut |entry| {
+                entries.push(entry);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(count, entries.len() as u64);
+        entries
+    };
+
+    let entries = export(TableExportOptions::default());
+    assert_eq!(entries.len(), 99);
+    assert_eq!(entries[0].key, serde_json::json!([1, "1"]));
+    assert_eq!(entries[0].value, serde_json::json!("1"));
+
+    // Bounds are JSON encoded keys, the upper bound is exclusive
+    let entries = export(TableExportOptions {
+        start_key: Some(r#"[10, ""]"#.to_owned()),
+        end_key: Some(r#"[20, ""]"#.to_owned()),
+        sample_every: 3,
+        limit: Some(3),
+    });
+    let keys: Vec<_> = entries.iter().map(|entry| entry.key[0].clone()).collect();
+    assert_eq!(keys, vec![10, 13, 16]);
+
+    let invalid = db.export(
+        &TableExportOptions {
+            start_key: Some("not a key".to_owned()),
+            ..Default::default()
+        },
+        &mut |_| Ok(()),
+    );
+    assert!(invalid.is_err());
+}
+
 fn open_map<P: AsRef<Path>, K, V>(
     path: P,
     opt_cf: Option<&str>,
@@ -1,5 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use crate::rocks::export::{ExportedEntry, TableExportOptions};
 use crate::TypedStoreError;
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
@@ -187,6 +188,39 @@ pub struct TableSummary {
     pub value_hist: hdrhistogram::Histogram<u64>,
 }
 
+/// Result of checking a secondary index against its primary table
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SecondaryIndexReport {
+    /// Name of the table the index is derived from
+    pub primary_table: String,
+    /// Number of index entries derived from the primary table
+    pub expected_entries: u64,
+    /// Expected entries absent from the index
+    pub missing_entries: u64,
+    /// Expected entries present in the index with a different value
+    pub mismatched_entries: u64,
+    /// Number of entries found in the index
+    pub index_entries: u64,
+    /// Primary entries whose index entries could not be checked, because the source of the
+    /// index was not found
+    pub missing_sources: u64,
+}
+
+impl SecondaryIndexReport {
+    /// Number of index entries which are not derived from any primary entry
+    pub fn stale_entries(&self) -> u64 {
+        self.index_entries
+            .saturating_sub(self.expected_entries - self.missing_entries)
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.missing_entries == 0
+            && self.mismatched_entries == 0
+            && self.stale_entries() == 0
+            && self.missing_sources == 0
+    }
+}
+
 pub trait TypedStoreDebug {
     /// Dump a DB table with pagination
     fn dump_table(
@@ -207,4 +241,20 @@ pub trait TypedStoreDebug {
 
     /// Return table summary of the input table
     fn table_summary(&self, table_name: String) -> eyre::Result<TableSummary>;
+
+    /// Stream the entries of a table selected by `options` to `sink`, with decoded keys and values
+    /// Returns the number of exported entries
+    fn export_table(
+        &self,
+        table_name: String,
+        _options: &TableExportOptions,
+        _sink: &mut dyn FnMut(ExportedEntry) -> eyre::Result<()>,
+    ) -> eyre::Result<u64> {
+        eyre::bail!("Export is not supported for table {}", table_name)
+    }
+
+    /// Check every secondary index of the DB, keyed by index table name
+    fn verify_secondary_indexes(&self) -> eyre::Result<BTreeMap<String, SecondaryIndexReport>> {
+        Ok(BTreeMap::new())
+    }
 }
@@ -6,7 +6,7 @@ use once_cell::sync::Lazy;
 use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Borrow;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::Debug;
 use std::sync::Mutex;
 use std::time::Duration;
@@ -14,6 +14,7 @@ use typed_store::metrics::SamplingInterval;
 use typed_store::rocks::list_tables;
 use typed_store::rocks::DBMap;
 use typed_store::rocks::RocksDBAccessType;
+use typed_store::rocks::TypedStoreError;
 use typed_store::rocks::{be_fix_int_ser, MetricConf};
 use typed_store::sally::SallyColumn;
 use typed_store::sally::SallyDBOptions;
@@ -339,6 +340,217 @@ async fn macro_test_configure() {
     assert_eq!(TABLE2_OPTIONS_SET_FLAG.lock().unwrap().len(), 6);
 }
 
+/// Index entries of an object by its owner
+fn owner_index_entries(id: &u64, owner: &str) -> [((String, u64), ()); 1] {
+    [((owner.to_owned(), *id), ())]
+}
+
+/// This struct shows how a table can be maintained as a secondary index of another
+#[derive(DBMapUtils)]
+struct TablesSecondaryIndex {
+    objects: DBMap<u64, String>,
+    #[secondary_index(primary = "objects", entries_fn = "owner_index_entries")]
+    objects_by_owner: DBMap<(String, u64), ()>,
+}
+
+#[tokio::test]
+async fn macro_test_secondary_index() {
+    let primary_path = temp_dir();
+    let tables = TablesSecondaryIndex::open_tables_read_write(
+        primary_path.clone(),
+        MetricConf::default(),
+        None,
+        None,
+    );
+    let owned_by = |owner: &str| -> Vec<u64> {
+        tables
+            .objects_by_owner
+            .keys()
+            .map(|key| key.unwrap())
+            .filter(|(o, _)| o == owner)
+            .map(|(_, id)| id)
+            .collect()
+    };
+
+    let mut batch = tables.objects.batch();
+    tables
+        .insert_objects_with_indexes(
+            &mut batch,
+            [
+                (1, "alice".to_owned()),
+                (2, "alice".to_owned()),
+                (3, "bob".to_owned()),
+            ],
+        )
+        .unwrap();
+    batch.write().unwrap();
+    assert_eq!(owned_by("alice"), vec![1, 2]);
+    assert_eq!(owned_by("bob"), vec![3]);
+
+    // Overwriting a value moves its index entry
+    let mut batch = tables.objects.batch();
+    tables
+        .insert_objects_with_indexes(&mut batch, [(2, "bob".to_owned())])
+        .unwrap();
+    batch.write().unwrap();
+    assert_eq!(owned_by("alice"), vec![1]);
+    assert_eq!(owned_by("bob"), vec![2, 3]);
+
+    // Deleting a value deletes its index entry
+    let mut batch = tables.objects.batch();
+    tables
+        .delete_objects_with_indexes(&mut batch, [3, 4])
+        .unwrap();
+    batch.write().unwrap();
+    assert_eq!(owned_by("bob"), vec![2]);
+
+    // New keys can be inserted without reading the previous values
+    let mut batch = tables.objects.batch();
+    tables
+        .insert_new_objects_with_indexes(&mut batch, [(4, "erin".to_owned())])
+        .unwrap();
+    batch.write().unwrap();
+    assert_eq!(owned_by("erin"), vec![4]);
+
+    let reports = tables.verify_secondary_indexes().unwrap();
+    let report = &reports["objects_by_owner"];
+    assert_eq!(report.primary_table, "objects");
+    assert_eq!(report.expected_entries, 3);
+    assert!(report.is_consistent());
+
+    // Corrupt the index by writing the primary table directly
+    tables.objects.insert(&5, &"carol".to_owned()).unwrap();
+    tables
+        .objects_by_owner
+        .insert(&("dave".to_owned(), 6), &())
+        .unwrap();
+    let report = tables.verify_secondary_indexes().unwrap()["objects_by_owner"].clone();
+    assert_eq!(report.missing_entries, 1);
+    assert_eq!(report.stale_entries(), 1);
+    assert!(!report.is_consistent());
+
+    // The read only handle reports the same inconsistencies
+    let read_only = TablesSecondaryIndex::get_read_only_handle(
+        primary_path.clone(),
+        None,
+        None,
+        MetricConf::default(),
+    );
+    assert_eq!(
+        TypedStoreDebug::verify_secondary_indexes(&read_only).unwrap()["objects_by_owner"],
+        report
+    );
+
+    tables.rebuild_secondary_indexes().unwrap();
+    assert!(tables.verify_secondary_indexes().unwrap()["objects_by_owner"].is_consistent());
+    assert_eq!(owned_by("carol"), vec![5]);
+    assert!(owned_by("dave").is_empty());
+}
+
+/// Index entries of an object by the tags it was inserted with
+fn tag_index_entries(id: &u64, _owner: &str, tags: &[String]) -> Vec<((String, u64), ())> {
+    tags.iter().map(|tag| ((tag.clone(), *id), ())).collect()
+}
+
+/// This struct shows how an index can be maintained on data which is not in the primary table
+#[derive(DBMapUtils)]
+struct TablesSourcedIndex {
+    objects: DBMap<u64, String>,
+    #[secondary_index(primary = "objects", entries_fn = "owner_index_entries")]
+    objects_by_owner: DBMap<(String, u64), ()>,
+    #[secondary_index(
+        primary = "objects",
+        entries_fn = "tag_index_entries",
+        source = "Vec<String>"
+    )]
+    objects_by_tag: DBMap<(String, u64), ()>,
+}
+
+#[tokio::test]
+async fn macro_test_sourced_secondary_index() {
+    let tables =
+        TablesSourcedIndex::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+
+    let mut batch = tables.objects.batch();
+    tables
+        .insert_new_objects_with_indexes(
+            &mut batch,
+            [
+                (
+                    1,
+                    "alice".to_owned(),
+                    vec!["red".to_owned(), "blue".to_owned()],
+                ),
+                (2, "bob".to_owned(), vec![]),
+            ],
+        )
+        .unwrap();
+    batch.write().unwrap();
+
+    assert_eq!(tables.objects.get(&2).unwrap(), Some("bob".to_owned()));
+    let owners: Vec<_> = tables.objects_by_owner.keys().map(|k| k.unwrap()).collect();
+    assert_eq!(owners, vec![("alice".to_owned(), 1), ("bob".to_owned(), 2)]);
+    let tags: Vec<_> = tables.objects_by_tag.keys().map(|k| k.unwrap()).collect();
+    assert_eq!(tags, vec![("blue".to_owned(), 1), ("red".to_owned(), 1)]);
+
+    // Only the index derived from the primary table alone is verified without its source
+    let reports = tables.verify_secondary_indexes().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports["objects_by_owner"].expected_entries, 2);
+    assert!(reports["objects_by_owner"].is_consistent());
+
+    // The index with a source is verified against the source of each primary entry
+    let sources: BTreeMap<u64, Vec<String>> =
+        [(1, vec!["red".to_owned(), "blue".to_owned()]), (2, vec![])]
+            .into_iter()
+            .collect();
+    let get_source = |id: &u64, _: &String| Ok::<_, TypedStoreError>(sources.get(id).cloned());
+    let reports = tables
+        .verify_objects_indexes_with_source(get_source)
+        .unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports["objects_by_tag"].primary_table, "objects");
+    assert_eq!(reports["objects_by_tag"].expected_entries, 2);
+    assert!(reports["objects_by_tag"].is_consistent());
+
+    // Corrupt the index
+    tables
+        .objects_by_tag
+        .remove(&("red".to_owned(), 1))
+        .unwrap();
+    tables
+        .objects_by_tag
+        .insert(&("green".to_owned(), 2), &())
+        .unwrap();
+    let report = tables
+        .verify_objects_indexes_with_source(get_source)
+        .unwrap()["objects_by_tag"]
+        .clone();
+    assert_eq!(report.missing_entries, 1);
+    assert_eq!(report.stale_entries(), 1);
+    assert!(!report.is_consistent());
+
+    // Entries whose source is missing can't be checked
+    let report = tables
+        .verify_objects_indexes_with_source(|id, _| {
+            Ok::<_, TypedStoreError>((*id == 2).then(Vec::new))
+        })
+        .unwrap()["objects_by_tag"]
+        .clone();
+    assert_eq!(report.missing_sources, 1);
+    assert!(!report.is_consistent());
+
+    tables
+        .rebuild_objects_indexes_with_source(get_source)
+        .unwrap();
+    assert!(tables
+        .verify_objects_indexes_with_source(get_source)
+        .unwrap()["objects_by_tag"]
+        .is_consistent());
+    let tags: Vec<_> = tables.objects_by_tag.keys().map(|k| k.unwrap()).collect();
+    assert_eq!(tags, vec![("blue".to_owned(), 1), ("red".to_owned(), 1)]);
+}
+
 /// We show that custom functions can be applied
 #[derive(DBMapUtils)]
 struct TablesMemUsage {
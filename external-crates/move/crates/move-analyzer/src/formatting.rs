@@ -0,0 +1,71 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{context::Context, symbols::SymbolicatorRunner};
+use lsp_server::Request;
+use lsp_types::{DocumentFormattingParams, Position, Range, TextEdit};
+use move_command_line_common::files::FileHash;
+use move_compiler::{editions::Edition, parser::formatter::format_file_string};
+use move_package::source_package::manifest_parser::parse_move_manifest_from_file;
+use std::path::Path;
+
+/// Handles a `textDocument/formatting` request by replacing the whole document with its
+/// formatted version. No edits are returned if the document does not parse.
+pub fn on_formatting_request(context: &Context, request: &Request) {
+    eprintln!("handling formatting request");
+    let parameters = serde_json::from_value::<DocumentFormattingParams>(request.params.clone())
+        .expect("could not deserialize formatting request");
+
+    let path = parameters.text_document.uri.to_file_path().unwrap();
+    let edits = match context.files.get(&path) {
+        Some(buffer) => {
+            let file_hash = FileHash::new(buffer);
+            match format_file_string(file_hash, buffer, package_edition(&path)) {
+                Ok(formatted) if formatted != buffer => Some(vec![TextEdit {
+                    range: Range {
+                        start: Position::new(0, 0),
+                        end: end_position(buffer),
+                    },
+                    new_text: formatted,
+                }]),
+                Ok(_) => Some(vec![]),
+                Err(_) => None,
+            }
+        }
+        None => {
+            eprintln!(
+                "Could not read '{:?}' when handling formatting request",
+                path
+            );
+            None
+        }
+    };
+
+    let result = serde_json::to_value(edits).expect("could not serialize formatting response");
+    let response = lsp_server::Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send formatting response: {:?}", err);
+    }
+}
+
+/// Returns the edition declared in the manifest of the package containing `path`, or the default
+/// edition if there is none.
+fn package_edition(path: &Path) -> Edition {
+    path.parent()
+        .and_then(SymbolicatorRunner::root_dir)
+        .and_then(|root| parse_move_manifest_from_file(&root).ok())
+        .and_then(|manifest| manifest.package.edition)
+        .unwrap_or_default()
+}
+
+/// Returns the position just past the last character of `buffer`, with the character offset
+/// counted in UTF-16 code units as LSP requires.
+fn end_position(buffer: &str) -> Position {
+    let line = buffer.matches('\n').count();
+    let last_line = buffer.rsplit('\n').next().unwrap_or("");
+    Position::new(line as u32, last_line.encode_utf16().count() as u32)
+}
@@ -0,0 +1,96 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use anyhow::bail;
+use clap::*;
+use move_command_line_common::files::{find_move_filenames, FileHash};
+use move_compiler::{
+    diagnostics::{report_diagnostics_to_color_buffer, FilesSourceText},
+    parser::formatter::format_file_string,
+};
+use move_package::{
+    source_package::{layout::SourcePackageLayout, manifest_parser::parse_move_manifest_from_file},
+    BuildConfig,
+};
+use move_symbol_pool::Symbol;
+use std::{io::Write, path::PathBuf};
+
+/// Format the Move source files of the package at `path`, rewriting them in place. Indentation and
+/// spacing are normalized, while line breaks and comments are preserved, and formatting already
+/// formatted files leaves them unchanged.
+#[derive(Parser)]
+#[clap(name = "fmt")]
+pub struct Fmt {
+    /// Instead of rewriting files, list the files that are not formatted and exit with an error if
+    /// there are any.
+    #[clap(long = "check")]
+    pub check: bool,
+}
+
+impl Fmt {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        let manifest = parse_move_manifest_from_file(&rerooted_path)?;
+        let edition = manifest
+            .package
+            .edition
+            .or(config.default_edition)
+            .unwrap_or_default();
+
+        let source_dirs = [
+            SourcePackageLayout::Sources,
+            SourcePackageLayout::Specifications,
+            SourcePackageLayout::Tests,
+            SourcePackageLayout::Scripts,
+            SourcePackageLayout::Examples,
+        ]
+        .iter()
+        .map(|layout| rerooted_path.join(layout.path()))
+        .filter(|dir| dir.is_dir())
+        .collect::<Vec<_>>();
+        let mut files = find_move_filenames(&source_dirs, false)?;
+        files.sort();
+
+        let mut invalid = vec![];
+        let mut unformatted = vec![];
+        for file in files {
+            let source = std::fs::read_to_string(&file)?;
+            let file_hash = FileHash::new(&source);
+            let formatted = match format_file_string(file_hash, &source, edition) {
+                Ok(formatted) => formatted,
+                Err(diags) => {
+                    let mut files = FilesSourceText::new();
+                    files.insert(file_hash, (Symbol::from(file.as_str()), source));
+                    let buffer = report_diagnostics_to_color_buffer(&files, diags);
+                    std::io::stderr().write_all(&buffer)?;
+                    invalid.push(file);
+                    continue;
+                }
+            };
+            if formatted == source {
+                continue;
+            }
+            if !self.check {
+                std::fs::write(&file, formatted)?;
+            }
+            unformatted.push(file);
+        }
+
+        if self.check {
+            for file in &unformatted {
+                println!("{}", file);
+            }
+        }
+        if !invalid.is_empty() {
+            bail!(
+                "Unable to format {} file(s) containing syntax errors",
+                invalid.len()
+            )
+        }
+        if self.check && !unformatted.is_empty() {
+            bail!("{} file(s) are not formatted", unformatted.len())
+        }
+        Ok(())
+    }
+}
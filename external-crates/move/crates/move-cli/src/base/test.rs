@@ -14,7 +14,7 @@ use move_compiler::{
 };
 use move_coverage::coverage_map::{output_map_to_file, CoverageMap};
 use move_package::{compilation::build_plan::BuildPlan, BuildConfig};
-use move_unit_test::UnitTestingConfig;
+use move_unit_test::{test_reporter::ReportFormat, UnitTestingConfig};
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::{
     collections::HashMap,
@@ -76,6 +76,20 @@ pub struct Test {
     /// Collect coverage information for later use with the various `move coverage` subcommands
     #[clap(long = "coverage")]
     pub compute_coverage: bool,
+    /// Write a machine-readable report of the test results in the given format
+    #[clap(long = "report-format", value_enum)]
+    pub report_format: Option<ReportFormat>,
+    /// File to write the test report to. Defaults to `test_report.xml` for JUnit reports and
+    /// `test_report.json` for JSON reports
+    #[clap(long = "report-output", requires = "report_format")]
+    pub report_output: Option<PathBuf>,
+    /// Number of times to run each #[random_test] with freshly generated arguments
+    #[clap(name = "rand_num_iters", long = "rand-num-iters")]
+    pub rand_num_iters: Option<u64>,
+    /// Seed for generating the arguments of #[random_test]s. A random seed is used if none is
+    /// given; the seed of a failing random test is reported so that the failure can be reproduced
+    #[clap(name = "seed", long = "seed")]
+    pub seed: Option<u64>,
 }
 
 impl Test {
@@ -98,6 +112,10 @@ impl Test {
             check_stackless_vm,
             verbose_mode,
             compute_coverage,
+            report_format,
+            report_output,
+            rand_num_iters,
+            seed,
         } = self;
         let unit_test_config = UnitTestingConfig {
             gas_limit,
@@ -109,6 +127,10 @@ impl Test {
             check_stackless_vm,
             verbose: verbose_mode,
             ignore_compile_warnings,
+            report_format,
+            report_output,
+            rand_num_iters,
+            seed,
 
             ..UnitTestingConfig::default_with_bound(None)
         };
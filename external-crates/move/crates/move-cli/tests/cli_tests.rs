@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_cli::sandbox::commands::test;
-use std::{env, path::PathBuf};
+use std::{env, path::PathBuf, process::Command};
 
 pub const CLI_METATEST_PATH: [&str; 3] = ["tests", "metatests", "args.txt"];
 
@@ -33,3 +33,44 @@ fn run_metatest() {
     // temp workspace + without coverage
     assert!(test::run_all(&path_metatest, &path_cli_binary, true, false).is_ok());
 }
+
+#[test]
+fn fmt_check_exit_codes() {
+    let package = tempfile::tempdir().unwrap();
+    std::fs::write(
+        package.path().join("Move.toml"),
+        "[package]\nname = \"Test\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir(package.path().join("sources")).unwrap();
+    let source = package.path().join("sources").join("m.move");
+    std::fs::write(&source, "module 0x42::m { fun f(x:u64):u64{x} }\n").unwrap();
+
+    let fmt = |check: bool| {
+        let mut command = Command::new(get_cli_binary_path());
+        command.arg("fmt").arg("--path").arg(package.path());
+        if check {
+            command.arg("--check");
+        }
+        command.output().unwrap()
+    };
+
+    // An unformatted file is listed and fails the check, without being rewritten
+    let output = fmt(true);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("m.move"));
+    assert_eq!(
+        std::fs::read_to_string(&source).unwrap(),
+        "module 0x42::m { fun f(x:u64):u64{x} }\n"
+    );
+
+    assert!(fmt(false).status.success());
+    assert_eq!(
+        std::fs::read_to_string(&source).unwrap(),
+        "module 0x42::m { fun f(x: u64): u64 { x } }\n"
+    );
+
+    let output = fmt(true);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,686 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A layout-preserving formatter for Move source files.
+//!
+//! The formatter works on the token stream produced by the `Lexer`, together with the whitespace
+//! and comments ("trivia") that the lexer skips between tokens. Indentation and the spacing
+//! between tokens on a line are recomputed, but the formatter never adds or removes line breaks:
+//! the lines chosen by the author are kept, including inside delimited lists, and only runs of
+//! blank lines are collapsed into one. Sources that differ only in where their lines break, such
+//! as a struct declared on one line and the same struct with a field per line, therefore stay
+//! different after formatting. All comments, including documentation comments, are preserved
+//! verbatim. Files are parsed before being formatted, so only syntactically valid sources are
+//! rewritten.
+//!
+//! Formatting is idempotent: formatting already formatted source returns it unchanged.
+
+use crate::{
+    diag,
+    diagnostics::Diagnostics,
+    editions::Edition,
+    parser::{
+        comments::verify_string,
+        lexer::{Lexer, Tok},
+        syntax::{make_loc, parse_file_string_with_delimiters, ListDelimiters},
+    },
+    shared::{CompilationEnv, Flags, PackageConfig},
+};
+use move_command_line_common::files::FileHash;
+use std::collections::BTreeMap;
+
+/// The string used for one level of indentation.
+pub const INDENT: &str = "    ";
+
+/// Formats the Move source `input`, keeping its line breaks. `input` must parse under the syntax
+/// of the given `edition`, otherwise the parser's diagnostics are returned.
+pub fn format_file_string(
+    file_hash: FileHash,
+    input: &str,
+    edition: Edition,
+) -> Result<String, Diagnostics> {
+    verify_string(file_hash, input)?;
+    let mut env = CompilationEnv::new(
+        Flags::empty(),
+        vec![],
+        BTreeMap::new(),
+        Some(PackageConfig {
+            edition,
+            ..PackageConfig::default()
+        }),
+    );
+    let (_, _, delimiters) = parse_file_string_with_delimiters(&mut env, file_hash, input, None)?;
+    let items = tokenize(file_hash, input, edition, &delimiters)?;
+    Ok(Printer::default().print(&items))
+}
+
+//**************************************************************************************************
+// Tokenization
+//**************************************************************************************************
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ItemKind {
+    Token(Tok),
+    LineComment,
+    BlockComment,
+}
+
+/// A token or a comment, along with the whitespace that preceded it in the original source.
+#[derive(Clone, Debug)]
+struct Item {
+    kind: ItemKind,
+    text: String,
+    /// Number of line breaks between the previous item and this one.
+    newlines_before: usize,
+    /// Whether there was any whitespace between the previous item and this one.
+    space_before: bool,
+    /// Set for the `<`, `>`, `>>` and `|` tokens that delimit a list of type parameters, type
+    /// arguments or lambda parameters, rather than being binary operators.
+    list_delimiter: Option<ListDelimiter>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ListDelimiter {
+    Open,
+    Close,
+}
+
+fn tokenize(
+    file_hash: FileHash,
+    input: &str,
+    edition: Edition,
+    delimiters: &ListDelimiters,
+) -> Result<Vec<Item>, Diagnostics> {
+    let mut lexer = Lexer::new(input, file_hash, edition.syntax());
+    let mut items = vec![];
+    loop {
+        lexer
+            .advance()
+            .map_err(|err| Diagnostics::from(vec![*err]))?;
+        let start = lexer.start_loc();
+        let (newlines_before, space_before) = split_trivia(
+            file_hash,
+            lexer.previous_end_loc(),
+            input,
+            start,
+            &mut items,
+        )?;
+        let tok = lexer.peek();
+        if tok == Tok::EOF {
+            break;
+        }
+        let list_delimiter = if delimiters.open.contains(&start) {
+            Some(ListDelimiter::Open)
+        } else if delimiters.close.contains(&start) {
+            Some(ListDelimiter::Close)
+        } else {
+            None
+        };
+        items.push(Item {
+            kind: ItemKind::Token(tok),
+            // `&mut ` is lexed together with its trailing space
+            text: lexer.content().trim_end().to_string(),
+            newlines_before,
+            space_before,
+            list_delimiter,
+        });
+    }
+    Ok(items)
+}
+
+/// Splits the whitespace and comments in `input[start..end]`, between two tokens, pushing any
+/// comments found onto `items`. Returns the line breaks and whitespace that follow the last
+/// comment, which belong to the next token.
+fn split_trivia(
+    file_hash: FileHash,
+    start: usize,
+    input: &str,
+    end: usize,
+    items: &mut Vec<Item>,
+) -> Result<(usize, bool), Diagnostics> {
+    let mut rest = &input[start..end];
+    let mut newlines = 0;
+    let mut space = false;
+    while let Some(c) = rest.chars().next() {
+        if c == '\n' {
+            newlines += 1;
+            space = true;
+            rest = &rest[1..];
+        } else if c.is_whitespace() {
+            space = true;
+            rest = &rest[c.len_utf8()..];
+        } else if rest.starts_with("//") {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            items.push(Item {
+                kind: ItemKind::LineComment,
+                text: rest[..end].trim_end().to_string(),
+                newlines_before: newlines,
+                space_before: space,
+                list_delimiter: None,
+            });
+            newlines = 0;
+            space = false;
+            rest = &rest[end..];
+        } else if rest.starts_with("/*") {
+            let end = block_comment_len(rest);
+            items.push(Item {
+                kind: ItemKind::BlockComment,
+                text: rest[..end].replace("\r\n", "\n"),
+                newlines_before: newlines,
+                space_before: space,
+                list_delimiter: None,
+            });
+            newlines = 0;
+            space = false;
+            rest = &rest[end..];
+        } else {
+            let offset = end - rest.len();
+            let loc = make_loc(file_hash, offset, offset + c.len_utf8());
+            return Err(Diagnostics::from(vec![diag!(
+                Syntax::InvalidCharacter,
+                (loc, format!("Unexpected character between tokens: '{}'", c))
+            )]));
+        }
+    }
+    Ok((newlines, space))
+}
+
+/// Returns the length of the (possibly nested) block comment at the start of `text`. The lexer has
+/// already checked that the comment is terminated.
+fn block_comment_len(text: &str) -> usize {
+    let mut depth = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        let rest = &text[pos..];
+        if rest.starts_with("/*") {
+            depth += 1;
+            pos += 2;
+        } else if rest.starts_with("*/") {
+            depth -= 1;
+            pos += 2;
+            if depth == 0 {
+                return pos;
+            }
+        } else {
+            pos += rest.chars().next().unwrap().len_utf8();
+        }
+    }
+    text.len()
+}
+
+//**************************************************************************************************
+// Printing
+//**************************************************************************************************
+
+/// An open `(`, `[` or `{`.
+struct OpenDelimiter {
+    /// Indentation level of the line the delimiter was opened on.
+    indent: usize,
+    /// Whether this is the `{` of a use group, e.g. `use a::m::{Self, T}`, which is printed
+    /// without inner padding.
+    is_use_group: bool,
+}
+
+/// The last token that was printed, used to decide the spacing before the next one.
+#[derive(Copy, Clone)]
+struct PrevToken {
+    tok: Tok,
+    /// Whether this is a prefix operator, e.g. the `&` in `&x` or the `!` in `!b`.
+    is_prefix: bool,
+    /// Whether this is the `!` of a macro call, e.g. `assert!`.
+    is_macro_bang: bool,
+    list_delimiter: Option<ListDelimiter>,
+}
+
+#[derive(Default)]
+struct Printer {
+    out: String,
+    delimiters: Vec<OpenDelimiter>,
+    prev_token: Option<PrevToken>,
+    /// Whether the last item printed was an opening delimiter.
+    after_open: bool,
+    /// Whether the last item printed was a comment.
+    after_comment: bool,
+    /// Indentation level of the current line.
+    line_indent: usize,
+    /// Indentation level of the current line, excluding any continuation indent.
+    block_indent: usize,
+    /// If the current line continues the previous one, the token the previous line ended with.
+    continues: Option<Tok>,
+}
+
+impl Printer {
+    fn print(mut self, items: &[Item]) -> String {
+        for (idx, item) in items.iter().enumerate() {
+            if idx == 0 {
+                self.start_line(item);
+            } else if item.newlines_before > 0 {
+                self.out.push('\n');
+                if item.newlines_before > 1 && !self.after_open && !is_close(item.kind) {
+                    self.out.push('\n');
+                }
+                self.start_line(item);
+            } else if self.space_before(item) {
+                self.out.push(' ');
+            }
+            self.out.push_str(&item.text);
+            self.after_open = false;
+            self.after_comment = true;
+            if let ItemKind::Token(tok) = item.kind {
+                self.after_comment = false;
+                self.record_token(tok, item.list_delimiter);
+            }
+        }
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.out
+    }
+
+    fn start_line(&mut self, item: &Item) {
+        self.continues = None;
+        if is_close(item.kind) {
+            self.block_indent = self.delimiters.last().map(|d| d.indent).unwrap_or(0);
+            self.line_indent = self.block_indent;
+        } else {
+            self.block_indent = self.delimiters.last().map(|d| d.indent + 1).unwrap_or(0);
+            self.line_indent = self.block_indent;
+            if let Some(prev) = self.prev_token {
+                if continues_on_next_line(prev.tok) {
+                    self.continues = Some(prev.tok);
+                    self.line_indent += 1;
+                }
+            }
+        }
+        for _ in 0..self.line_indent {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    fn record_token(&mut self, tok: Tok, list_delimiter: Option<ListDelimiter>) {
+        let prev = self.prev_token;
+        let is_macro_bang = tok == Tok::Exclaim
+            && matches!(
+                prev.map(|p| p.tok),
+                Some(Tok::Identifier | Tok::RestrictedIdentifier)
+            );
+        let is_prefix = match tok {
+            Tok::Exclaim => !is_macro_bang,
+            Tok::Amp | Tok::Star | Tok::Minus => {
+                !prev.map(|p| ends_operand(p.tok)).unwrap_or(false)
+            }
+            _ => false,
+        };
+        match tok {
+            Tok::LParen | Tok::LBracket | Tok::LBrace => {
+                // The body of a function whose signature was split over several lines, e.g. with
+                // the return type on its own line, is indented relative to the start of the
+                // signature
+                let indent = match self.continues {
+                    Some(prev)
+                        if tok == Tok::LBrace && (ends_operand(prev) || prev == Tok::Greater) =>
+                    {
+                        self.block_indent
+                    }
+                    _ => self.line_indent,
+                };
+                self.delimiters.push(OpenDelimiter {
+                    indent,
+                    is_use_group: tok == Tok::LBrace
+                        && matches!(prev.map(|p| p.tok), Some(Tok::ColonColon)),
+                });
+                self.after_open = true;
+            }
+            Tok::RParen | Tok::RBracket | Tok::RBrace => {
+                self.delimiters.pop();
+            }
+            _ => (),
+        }
+        self.prev_token = Some(PrevToken {
+            tok,
+            is_prefix,
+            is_macro_bang,
+            list_delimiter,
+        });
+    }
+
+    /// Decides whether a space separates `item` from the previous item on the same line.
+    fn space_before(&self, item: &Item) -> bool {
+        let tok = match item.kind {
+            ItemKind::LineComment => return true,
+            ItemKind::BlockComment => return item.space_before,
+            ItemKind::Token(tok) => tok,
+        };
+        let prev = match self.prev_token {
+            Some(prev) if !self.after_comment => prev,
+            // Keep the author's spacing around inline block comments
+            _ => return item.space_before,
+        };
+
+        // Tokens that never have a space before them
+        if matches!(
+            tok,
+            Tok::Comma
+                | Tok::Semicolon
+                | Tok::Period
+                | Tok::RParen
+                | Tok::RBracket
+                | Tok::Colon
+                | Tok::ColonColon
+        ) {
+            return false;
+        }
+        // Tokens that never have a space after them
+        if matches!(
+            prev.tok,
+            Tok::LParen
+                | Tok::LBracket
+                | Tok::ColonColon
+                | Tok::Period
+                | Tok::AtSign
+                | Tok::NumSign
+        ) || prev.is_prefix
+        {
+            return false;
+        }
+        if prev.tok == Tok::LBrace {
+            return tok != Tok::RBrace && !self.in_use_group();
+        }
+        if tok == Tok::RBrace {
+            return prev.tok != Tok::LBrace && !self.in_use_group();
+        }
+        // Type parameters and arguments, e.g. `vector<T>`, and lambda parameters, e.g. `|x, y|`,
+        // are written without inner padding. Used as binary operators, `<`, `>` and `|` are
+        // spaced like any other.
+        if prev.list_delimiter == Some(ListDelimiter::Open) {
+            return false;
+        }
+        match item.list_delimiter {
+            Some(ListDelimiter::Open) => return tok == Tok::Pipe,
+            Some(ListDelimiter::Close) => return false,
+            None => (),
+        }
+        // Ranges, e.g. `0..n`, are written without spaces
+        if tok == Tok::PeriodPeriod {
+            return prev.tok == Tok::Comma;
+        }
+        if prev.tok == Tok::PeriodPeriod {
+            return false;
+        }
+        let after_type_args = prev.list_delimiter == Some(ListDelimiter::Close)
+            && matches!(prev.tok, Tok::Greater | Tok::GreaterGreater);
+        match tok {
+            Tok::Exclaim
+                if prev.tok == Tok::Identifier || prev.tok == Tok::RestrictedIdentifier =>
+            {
+                false
+            }
+            Tok::LParen => {
+                !matches!(
+                    prev.tok,
+                    Tok::Identifier | Tok::RestrictedIdentifier | Tok::Public
+                ) && !prev.is_macro_bang
+                    && !after_type_args
+            }
+            Tok::LBracket if after_type_args => false,
+            // Indexing and vector literals, e.g. `v[i]` and `vector[]`, are written without a
+            // space, but spec properties such as `aborts_if [abstract]` are not
+            Tok::LBracket if matches!(prev.tok, Tok::Identifier | Tok::RestrictedIdentifier) => {
+                item.space_before
+            }
+            _ => true,
+        }
+    }
+
+    fn in_use_group(&self) -> bool {
+        self.delimiters
+            .last()
+            .map(|d| d.is_use_group)
+            .unwrap_or(false)
+    }
+}
+
+fn is_close(kind: ItemKind) -> bool {
+    matches!(
+        kind,
+        ItemKind::Token(Tok::RParen | Tok::RBracket | Tok::RBrace)
+    )
+}
+
+/// Returns true if `tok` can end an operand, in which case a following `&`, `*`, `!` or `-` is a
+/// binary operator rather than a prefix one.
+fn ends_operand(tok: Tok) -> bool {
+    matches!(
+        tok,
+        Tok::Identifier
+            | Tok::RestrictedIdentifier
+            | Tok::NumValue
+            | Tok::NumTypedValue
+            | Tok::ByteStringValue
+            | Tok::True
+            | Tok::False
+            | Tok::RParen
+            | Tok::RBracket
+            | Tok::RBrace
+    )
+}
+
+/// Returns true if a line ending in `tok` is continued on the next line, which is then indented
+/// one extra level, e.g. a `let` whose right-hand side starts on the following line.
+fn continues_on_next_line(tok: Tok) -> bool {
+    !matches!(
+        tok,
+        Tok::Semicolon
+            | Tok::Comma
+            | Tok::LParen
+            | Tok::LBracket
+            | Tok::LBrace
+            | Tok::RBracket
+            | Tok::RBrace
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(input: &str) -> String {
+        let file_hash = FileHash::new(input);
+        format_file_string(file_hash, input, Edition::LEGACY).unwrap()
+    }
+
+    fn assert_formats_to(input: &str, expected: &str) {
+        let formatted = format(input);
+        assert_eq!(formatted, expected);
+        assert_eq!(
+            format(&formatted),
+            formatted,
+            "formatting is not idempotent"
+        );
+    }
+
+    #[test]
+    fn test_indentation_and_spacing() {
+        assert_formats_to(
+            r#"module 0x42::m{
+use std::vector ;
+use sui::object::{ Self,UID };
+   struct S has key,store{id:UID,
+      v:vector<u8>}
+public(friend)  fun f (x:&mut S,y :u64):u64{
+let z=x . v ;
+if(vector::length( &z )>y){
+assert!( !(y==0) ,0);
+y*2
+}else{
+*&y
+}
+}
+}"#,
+            r#"module 0x42::m {
+    use std::vector;
+    use sui::object::{Self, UID};
+    struct S has key, store { id: UID,
+        v: vector<u8> }
+    public(friend) fun f(x: &mut S, y: u64): u64 {
+        let z = x.v;
+        if (vector::length(&z) > y) {
+            assert!(!(y == 0), 0);
+            y * 2
+        } else {
+            *&y
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_type_arguments_and_lambdas() {
+        assert_formats_to(
+            r#"module 0x42::m {
+    struct S < T > has drop { v: vector < T > }
+    fun f < T: drop > (x: u64, y: u64, g: | u64 |u64): vector<vector<u64>> {
+        let a = x < y && x>y || (x >>1) < (y<<2) | 1;
+        let v = vector<vector<u64>>[];
+        let s = S<u64>{ v: vector[] };
+        call<u64, T>(|a, b| a+b, | c |c);
+        0
+    }
+    spec f {
+        ensures forall i in 0 .. 10: i < 10;
+    }
+}
+"#,
+            r#"module 0x42::m {
+    struct S<T> has drop { v: vector<T> }
+    fun f<T: drop>(x: u64, y: u64, g: |u64| u64): vector<vector<u64>> {
+        let a = x < y && x > y || (x >> 1) < (y << 2) | 1;
+        let v = vector<vector<u64>>[];
+        let s = S<u64> { v: vector[] };
+        call<u64, T>(|a, b| a + b, |c| c);
+        0
+    }
+    spec f {
+        ensures forall i in 0..10: i < 10;
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_comments_preserved() {
+        assert_formats_to(
+            r#"
+// leading comment
+
+/// Documentation for the module
+module 0x42::m {
+
+
+    /* block
+       comment */
+    const C: u64 = 1; // trailing comment
+    /** documentation */
+    fun f(/* no args */) {
+        // body comment
+
+    }
+}
+"#,
+            r#"// leading comment
+
+/// Documentation for the module
+module 0x42::m {
+    /* block
+       comment */
+    const C: u64 = 1; // trailing comment
+    /** documentation */
+    fun f(/* no args */) {
+        // body comment
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_continuation_lines() {
+        assert_formats_to(
+            r#"module 0x42::m {
+    fun f(a: bool, b: bool): bool {
+        let c =
+        a
+        && b;
+        call(
+        a,
+        b,
+        )
+    }
+    fun g(
+        a: u64)
+    : u64 {
+    a
+    }
+}
+"#,
+            r#"module 0x42::m {
+    fun f(a: bool, b: bool): bool {
+        let c =
+            a
+            && b;
+        call(
+            a,
+            b,
+        )
+    }
+    fun g(
+        a: u64)
+        : u64 {
+        a
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_line_breaks_are_preserved() {
+        // the formatter is layout-preserving: equivalent sources that break their lines in
+        // different places are formatted differently
+        let one_line = r#"module 0x42::m {
+    struct S has drop { a: u64, b: u64 }
+}
+"#;
+        let split = r#"module 0x42::m {
+    struct S has drop {
+        a: u64,
+        b: u64
+    }
+}
+"#;
+        assert_formats_to(one_line, one_line);
+        assert_formats_to(split, split);
+        assert_formats_to(
+            "module 0x42::m {\n    fun f() {}\n\n\n    fun g() {}\n}\n",
+            "module 0x42::m {\n    fun f() {}\n\n    fun g() {}\n}\n",
+        );
+    }
+
+    #[test]
+    fn test_unexpected_trivia_is_an_error() {
+        let input = "  // comment\n  $";
+        let mut items = vec![];
+        let err = split_trivia(FileHash::new(input), 0, input, input.len(), &mut items);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_invalid_source_is_rejected() {
+        let input = "module 0x42::m { fun f( }";
+        let file_hash = FileHash::new(input);
+        assert!(format_file_string(file_hash, input, Edition::LEGACY).is_err());
+    }
+}
@@ -5,6 +5,7 @@
 pub mod ast;
 pub mod comments;
 pub(crate) mod filter;
+pub mod formatter;
 pub mod keywords;
 pub mod lexer;
 pub(crate) mod merge_spec_modules;
@@ -9,6 +9,7 @@
 use move_command_line_common::files::FileHash;
 use move_ir_types::location::*;
 use move_symbol_pool::{symbol, Symbol};
+use std::collections::BTreeSet;
 
 use crate::{
     diag,
@@ -23,6 +24,7 @@ struct Context<'env, 'lexer, 'input> {
     package_name: Option<Symbol>,
     env: &'env mut CompilationEnv,
     tokens: &'lexer mut Lexer<'input>,
+    list_delimiters: ListDelimiters,
 }
 
 impl<'env, 'lexer, 'input> Context<'env, 'lexer, 'input> {
@@ -35,6 +37,25 @@ impl<'env, 'lexer, 'input> Context<'env, 'lexer, 'input> {
             package_name,
             env,
             tokens,
+            list_delimiters: ListDelimiters::default(),
+        }
+    }
+}
+
+/// The `<`, `>` and `|` tokens that open or close a list of type parameters, type arguments or
+/// lambda parameters, by their offset in the file. Only the parser can tell these apart from the
+/// same tokens used as binary operators. The second `>` of a `>>` closing two lists is at the
+/// offset following the first one.
+#[derive(Default, Debug)]
+pub struct ListDelimiters {
+    pub open: BTreeSet<usize>,
+    pub close: BTreeSet<usize>,
+}
+
+impl ListDelimiters {
+    fn record(delimiters: &mut BTreeSet<usize>, tok: Tok, loc: usize) {
+        if matches!(tok, Tok::Less | Tok::Greater | Tok::Pipe) {
+            delimiters.insert(loc);
         }
     }
 }
@@ -259,6 +280,7 @@ where
 {
     let start_loc = context.tokens.start_loc();
     consume_token(context.tokens, start_token)?;
+    ListDelimiters::record(&mut context.list_delimiters.open, start_token, start_loc);
     parse_comma_list_after_start(
         context,
         start_loc,
@@ -283,7 +305,7 @@ where
     F: Fn(&mut Context) -> Result<R, Box<Diagnostic>>,
 {
     adjust_token(context.tokens, end_token);
-    if match_token(context.tokens, end_token)? {
+    if match_list_end(context, end_token)? {
         return Ok(vec![]);
     }
     let mut v = vec![];
@@ -298,7 +320,7 @@ where
         }
         v.push(parse_list_item(context)?);
         adjust_token(context.tokens, end_token);
-        if match_token(context.tokens, end_token)? {
+        if match_list_end(context, end_token)? {
             break Ok(v);
         }
         if !match_token(context.tokens, Tok::Comma)? {
@@ -312,12 +334,22 @@ where
             )));
         }
         adjust_token(context.tokens, end_token);
-        if match_token(context.tokens, end_token)? {
+        if match_list_end(context, end_token)? {
             break Ok(v);
         }
     }
 }
 
+// Consume the token ending a list, if it is next.
+fn match_list_end(context: &mut Context, end_token: Tok) -> Result<bool, Box<Diagnostic>> {
+    let loc = context.tokens.start_loc();
+    let matched = match_token(context.tokens, end_token)?;
+    if matched {
+        ListDelimiters::record(&mut context.list_delimiters.close, end_token, loc);
+    }
+    Ok(matched)
+}
+
 // Parse a list of items, without specified start and end tokens, and the separator determined by
 // the passed function `parse_list_continue`.
 fn parse_list<C, F, R>(
@@ -3656,14 +3688,28 @@ pub fn parse_file_string(
     input: &str,
     package: Option<Symbol>,
 ) -> Result<(Vec<Definition>, MatchedFileCommentMap), Diagnostics> {
+    parse_file_string_with_delimiters(env, file_hash, input, package)
+        .map(|(defs, comments, _)| (defs, comments))
+}
+
+/// Like `parse_file_string`, but also returns the `ListDelimiters` of the file.
+pub fn parse_file_string_with_delimiters(
+    env: &mut CompilationEnv,
+    file_hash: FileHash,
+    input: &str,
+    package: Option<Symbol>,
+) -> Result<(Vec<Definition>, MatchedFileCommentMap, ListDelimiters), Diagnostics> {
     let edition = env.syntax_edition(package);
     let mut tokens = Lexer::new(input, file_hash, edition);
     match tokens.advance() {
         Err(err) => Err(Diagnostics::from(vec![*err])),
         Ok(..) => Ok(()),
     }?;
-    match parse_file(&mut Context::new(env, &mut tokens, package)) {
+    let mut context = Context::new(env, &mut tokens, package);
+    let defs = parse_file(&mut context);
+    let delimiters = std::mem::take(&mut context.list_delimiters);
+    match defs {
         Err(err) => Err(Diagnostics::from(vec![*err])),
-        Ok(def) => Ok((def, tokens.check_and_get_doc_comments(env))),
+        Ok(def) => Ok((def, tokens.check_and_get_doc_comments(env), delimiters)),
     }
 }
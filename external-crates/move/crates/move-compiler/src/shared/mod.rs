@@ -812,6 +812,8 @@ pub mod known_attributes {
         TestOnly,
         // Is a test that will be run
         Test,
+        // Is a test that will be run repeatedly with randomly generated arguments
+        RandTest,
         // This test is expected to fail
         ExpectedFailure,
     }
@@ -856,6 +858,7 @@ pub mod known_attributes {
         pub fn resolve(attribute_str: impl AsRef<str>) -> Option<Self> {
             Some(match attribute_str.as_ref() {
                 TestingAttribute::TEST => Self::Testing(TestingAttribute::Test),
+                TestingAttribute::RAND_TEST => Self::Testing(TestingAttribute::RandTest),
                 TestingAttribute::TEST_ONLY => Self::Testing(TestingAttribute::TestOnly),
                 TestingAttribute::EXPECTED_FAILURE => {
                     Self::Testing(TestingAttribute::ExpectedFailure)
@@ -895,6 +898,7 @@ pub mod known_attributes {
 
     impl TestingAttribute {
         pub const TEST: &'static str = "test";
+        pub const RAND_TEST: &'static str = "random_test";
         pub const EXPECTED_FAILURE: &'static str = "expected_failure";
         pub const TEST_ONLY: &'static str = "test_only";
         pub const ABORT_CODE_NAME: &'static str = "abort_code";
@@ -908,6 +912,7 @@ pub mod known_attributes {
         pub const fn name(&self) -> &str {
             match self {
                 Self::Test => Self::TEST,
+                Self::RandTest => Self::RAND_TEST,
                 Self::TestOnly => Self::TEST_ONLY,
                 Self::ExpectedFailure => Self::EXPECTED_FAILURE,
             }
@@ -931,7 +936,7 @@ pub mod known_attributes {
                 Lazy::new(|| BTreeSet::from([AttributePosition::Function]));
             match self {
                 TestingAttribute::TestOnly => &TEST_ONLY_POSITIONS,
-                TestingAttribute::Test => &TEST_POSITIONS,
+                TestingAttribute::Test | TestingAttribute::RandTest => &TEST_POSITIONS,
                 TestingAttribute::ExpectedFailure => &EXPECTED_FAILURE_POSITIONS,
             }
         }
@@ -276,7 +276,7 @@ fn function(context: &mut Context, name: FunctionName, fdef: &mut T::Function) {
         matches!(
             attr_,
             AttributeName_::Known(KnownAttribute::Testing(
-                TestingAttribute::Test | TestingAttribute::TestOnly
+                TestingAttribute::Test | TestingAttribute::RandTest | TestingAttribute::TestOnly
             ))
         )
     }) {
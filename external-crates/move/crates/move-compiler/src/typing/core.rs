@@ -376,10 +376,12 @@ impl<'env> Context<'env> {
     }
 
     /// current_module.is_test_only || current_function.is_test_only || current_function.is_test
+    /// || current_function.is_random_test
     fn is_testing_context(&self) -> bool {
         // TODO should we store this in the context?
         let test_only = AttributeName_::Known(KnownAttribute::Testing(TestingAttribute::TestOnly));
         let test = AttributeName_::Known(KnownAttribute::Testing(TestingAttribute::Test));
+        let rand_test = AttributeName_::Known(KnownAttribute::Testing(TestingAttribute::RandTest));
 
         self.current_module.as_ref().is_some_and(|m| {
             let minfo = self.module_info(m);
@@ -389,6 +391,7 @@ impl<'env> Context<'env> {
                     let finfo = minfo.functions.get(f).unwrap();
                     finfo.attributes.contains_key_(&test_only)
                         || finfo.attributes.contains_key_(&test)
+                        || finfo.attributes.contains_key_(&rand_test)
                 })
         })
     }
@@ -2576,9 +2576,14 @@ fn unused_module_members(context: &mut Context, mident: &ModuleIdent_, mdef: &T:
 
     for (loc, name, fun) in &mdef.functions {
         if fun.attributes.iter().any(|(_, n, _)| {
-            n == &AttributeName_::Known(KnownAttribute::Testing(TestingAttribute::Test))
+            matches!(
+                n,
+                AttributeName_::Known(KnownAttribute::Testing(
+                    TestingAttribute::Test | TestingAttribute::RandTest
+                ))
+            )
         }) {
-            // functions with #[test] attribute are implicitly used
+            // functions with #[test] or #[random_test] attribute are implicitly used
             continue;
         }
         if is_sui_mode && *name == sui_mode::INIT_FUNCTION_NAME {
@@ -8,8 +8,11 @@ use crate::{
     shared::NumericalAddress,
 };
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
-    value::MoveValue, vm_status::StatusCode,
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
 use std::{collections::BTreeMap, fmt};
 
@@ -34,10 +37,18 @@ pub struct ModuleTestPlan {
 #[derive(Debug, Clone)]
 pub struct TestCase {
     pub test_name: TestName,
-    pub arguments: Vec<MoveValue>,
+    pub arguments: Vec<TestArgument>,
     pub expected_failure: Option<ExpectedFailure>,
 }
 
+#[derive(Debug, Clone)]
+pub enum TestArgument {
+    // a value assigned in the test attribute, e.g. #[test(a = @0x1)]
+    Value(MoveValue),
+    // a value of this type generated by the test runner for a #[random_test]
+    Generate { generator: MoveTypeLayout },
+}
+
 #[derive(Debug, Clone)]
 pub enum ExpectedFailure {
     // expected failure, but codes are not checked
@@ -60,6 +71,15 @@ pub struct ExpectedMoveErrorDisplay<'a> {
     is_past_tense: bool,
 }
 
+impl TestCase {
+    /// Whether any of the arguments of this test are generated by the test runner.
+    pub fn is_random_test(&self) -> bool {
+        self.arguments
+            .iter()
+            .any(|arg| matches!(arg, TestArgument::Generate { .. }))
+    }
+}
+
 impl ModuleTestPlan {
     pub fn new(
         addr: &NumericalAddress,
@@ -8,18 +8,22 @@ use crate::{
     expansion::ast::{
         self as E, Address, Attribute, AttributeValue, ModuleAccess_, ModuleIdent, ModuleIdent_,
     },
-    hlir::translate::display_var,
+    hlir::{ast as H, translate::display_var},
+    naming::ast::BuiltinTypeName_,
     parser::ast::ConstantName,
     shared::{
         known_attributes::{KnownAttribute, TestingAttribute},
         unique_map::UniqueMap,
         CompilationEnv, Identifier, NumericalAddress,
     },
-    unit_test::{ExpectedFailure, ExpectedMoveError, ModuleTestPlan, TestCase},
+    unit_test::{ExpectedFailure, ExpectedMoveError, ModuleTestPlan, TestArgument, TestCase},
 };
 use move_core_types::{
-    account_address::AccountAddress as MoveAddress, language_storage::ModuleId, u256::U256,
-    value::MoveValue, vm_status::StatusCode,
+    account_address::AccountAddress as MoveAddress,
+    language_storage::ModuleId,
+    u256::U256,
+    value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
@@ -125,15 +129,31 @@ fn build_test_info<'func>(
     const IN_THIS_TEST_MSG: &str = "Error found in this test";
 
     let test_attribute_opt = get_attrs(TestingAttribute::Test);
+    let random_test_attribute_opt = get_attrs(TestingAttribute::RandTest);
     let abort_attribute_opt = get_attrs(TestingAttribute::ExpectedFailure);
     let test_only_attribute_opt = get_attrs(TestingAttribute::TestOnly);
 
-    let test_attribute = match test_attribute_opt {
+    // A #[test] function cannot also be annotated #[random_test]
+    if let (Some(test_attribute), Some(random_test_attribute)) =
+        (test_attribute_opt, random_test_attribute_opt)
+    {
+        let msg = "Function annotated as both #[test(...)] and #[random_test]. You need to \
+                   declare it as either one or the other";
+        context.env.add_diag(diag!(
+            Attributes::InvalidUsage,
+            (random_test_attribute.loc, msg),
+            (test_attribute.loc, PREVIOUSLY_ANNOTATED_MSG),
+            (fn_loc, IN_THIS_TEST_MSG),
+        ));
+        return None;
+    }
+
+    let test_attribute = match test_attribute_opt.or(random_test_attribute_opt) {
         None => {
             // expected failures cannot be annotated on non-#[test] functions
             if let Some(abort_attribute) = abort_attribute_opt {
-                let fn_msg = "Only functions defined as a test with #[test] can also have an \
-                              #[expected_failure] attribute";
+                let fn_msg = "Only functions defined as a test with #[test] or #[random_test] can \
+                              also have an #[expected_failure] attribute";
                 let abort_msg = "Attributed as #[expected_failure] here";
                 context.env.add_diag(diag!(
                     Attributes::InvalidUsage,
@@ -158,16 +178,35 @@ fn build_test_info<'func>(
         ))
     }
 
-    let test_annotation_params = parse_test_attribute(context, test_attribute, 0);
+    let test_annotation_params = match random_test_attribute_opt {
+        Some(random_test_attribute) => parse_random_test_attribute(context, random_test_attribute)?,
+        None => parse_test_attribute(context, test_attribute, 0),
+    };
     let mut arguments = Vec::new();
-    for (var, _) in &function.signature.parameters {
+    for (var, ty) in &function.signature.parameters {
         let sp!(vloc, var_) = var.0;
+        if random_test_attribute_opt.is_some() {
+            match convert_type_to_generator_layout(ty) {
+                Some(generator) => arguments.push(TestArgument::Generate { generator }),
+                None => {
+                    let msg = "Unsupported type for generated input. Only primitive types, \
+                               addresses and vectors of these types can be generated";
+                    context.env.add_diag(diag!(
+                        Attributes::InvalidTest,
+                        (ty.loc, msg),
+                        (vloc, "Corresponding to this parameter"),
+                        (fn_loc, IN_THIS_TEST_MSG),
+                    ))
+                }
+            }
+            continue;
+        }
         let var_ = match display_var(var_) {
             crate::hlir::translate::DisplayVar::Orig(s) => s.into(),
             crate::hlir::translate::DisplayVar::Tmp => panic!("ICE temp as parameter"),
         };
         match test_annotation_params.get(&var_) {
-            Some(value) => arguments.push(value.clone()),
+            Some(value) => arguments.push(TestArgument::Value(value.clone())),
             None => {
                 let missing_param_msg = "Missing test parameter assignment in test. Expected a \
                                          parameter to be assigned in this attribute";
@@ -257,6 +296,66 @@ fn parse_test_attribute(
     }
 }
 
+fn parse_random_test_attribute(
+    context: &mut Context,
+    sp!(aloc, random_test_attribute): &E::Attribute,
+) -> Option<BTreeMap<Symbol, MoveValue>> {
+    use E::Attribute_ as EA;
+
+    match random_test_attribute {
+        EA::Name(nm) => {
+            assert!(
+                nm.value.as_str() == TestingAttribute::RandTest.name(),
+                "ICE: We should only be parsing a raw random test attribute"
+            );
+            Some(BTreeMap::new())
+        }
+        EA::Assigned(_, _) | EA::Parameterized(_, _) => {
+            let msg = format!(
+                "Unexpected arguments to #[{}]. All parameters of a random test are generated",
+                TestingAttribute::RAND_TEST
+            );
+            context
+                .env
+                .add_diag(diag!(Attributes::InvalidTest, (*aloc, msg)));
+            None
+        }
+    }
+}
+
+// Returns the layout of values generated for a parameter of type `ty` in a random test, if values
+// of that type can be generated.
+fn convert_type_to_generator_layout(sp!(_, ty): &H::SingleType) -> Option<MoveTypeLayout> {
+    match ty {
+        H::SingleType_::Base(bt) => convert_base_type_to_generator_layout(bt),
+        H::SingleType_::Ref(_, _) => None,
+    }
+}
+
+fn convert_base_type_to_generator_layout(sp!(_, bt): &H::BaseType) -> Option<MoveTypeLayout> {
+    use BuiltinTypeName_ as B;
+    let H::BaseType_::Apply(_, sp!(_, H::TypeName_::Builtin(sp!(_, builtin))), ty_args) = bt else {
+        return None;
+    };
+    Some(match builtin {
+        B::Bool => MoveTypeLayout::Bool,
+        B::U8 => MoveTypeLayout::U8,
+        B::U16 => MoveTypeLayout::U16,
+        B::U32 => MoveTypeLayout::U32,
+        B::U64 => MoveTypeLayout::U64,
+        B::U128 => MoveTypeLayout::U128,
+        B::U256 => MoveTypeLayout::U256,
+        B::Address => MoveTypeLayout::Address,
+        B::Vector => {
+            let [elem_ty] = &ty_args[..] else {
+                return None;
+            };
+            MoveTypeLayout::Vector(Box::new(convert_base_type_to_generator_layout(elem_ty)?))
+        }
+        B::Signer => return None,
+    })
+}
+
 const BAD_ABORT_VALUE_WARNING: &str = "WARNING: passes for an abort from any module.";
 const INVALID_VALUE: &str = "Invalid value in attribute assignment";
 
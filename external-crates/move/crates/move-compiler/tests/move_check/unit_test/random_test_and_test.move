@@ -0,0 +1,6 @@
+// functions cannot be annotated as both #[test] and #[random_test]
+module 0x1::M {
+    #[test]
+    #[random_test]
+    fun foo(_x: u64) { }
+}
@@ -0,0 +1,8 @@
+// #[random_test] takes no arguments, all parameters of a random test are generated
+module 0x1::M {
+    #[random_test(_x = 1)]
+    fun foo(_x: u64) { }
+
+    #[random_test = 0]
+    fun bar(_x: u64) { }
+}
@@ -0,0 +1,24 @@
+// only primitive types, addresses and vectors of these can be generated for a random test
+module 0x1::M {
+    struct S has drop {}
+
+    #[random_test]
+    fun signer_param(
+        _s: signer,
+    ) { }
+
+    #[random_test]
+    fun struct_param(
+        _s: S,
+    ) { }
+
+    #[random_test]
+    fun ref_param(
+        _r: &u64,
+    ) { }
+
+    #[random_test]
+    fun vector_of_signer_param(
+        _v: vector<signer>,
+    ) { }
+}
@@ -24,6 +24,7 @@ const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+const LINTS_NAME: &str = "lints";
 
 const KNOWN_NAMES: &[&str] = &[
     PACKAGE_NAME,
@@ -32,6 +33,7 @@ const KNOWN_NAMES: &[&str] = &[
     DEV_ADDRESSES_NAME,
     DEPENDENCY_NAME,
     DEV_DEPENDENCY_NAME,
+    LINTS_NAME,
 ];
 
 const REQUIRED_FIELDS: &[&str] = &[PACKAGE_NAME];
@@ -89,6 +91,12 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 .transpose()
                 .context("Error parsing '[dev-dependencies]' section of manifest")?
                 .unwrap_or_default();
+            let lints = table
+                .remove(LINTS_NAME)
+                .map(parse_lints)
+                .transpose()
+                .context("Error parsing '[lints]' section of manifest")?
+                .unwrap_or_default();
             Ok(PM::SourceManifest {
                 package,
                 addresses,
@@ -96,6 +104,7 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 build,
                 dependencies,
                 dev_dependencies,
+                lints,
             })
         }
         x => {
@@ -217,6 +226,26 @@ pub fn parse_dependencies(tval: TV) -> Result<PM::Dependencies> {
     }
 }
 
+pub fn parse_lints(tval: TV) -> Result<PM::LintLevels> {
+    match tval {
+        TV::Table(table) => {
+            let mut lints = BTreeMap::new();
+            for (name, level) in table.into_iter() {
+                let level = level
+                    .as_str()
+                    .ok_or_else(|| format_err!("Level of lint '{}' must be a string", name))?;
+                lints.insert(Symbol::from(name), PM::LintLevel::from_str(level)?);
+            }
+            Ok(lints)
+        }
+        x => bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
 pub fn parse_build_info(tval: TV) -> Result<PM::BuildInfo> {
     match tval {
         TV::Table(mut table) => {
@@ -10,6 +10,7 @@ use move_symbol_pool::symbol::Symbol;
 use std::{
     collections::BTreeMap,
     path::{Component, Path, PathBuf},
+    str::FromStr,
 };
 
 pub type NamedAddress = Symbol;
@@ -23,6 +24,7 @@ pub type DevAddressDeclarations = BTreeMap<NamedAddress, AccountAddress>;
 pub type Version = (u64, u64, u64);
 pub type Dependencies = BTreeMap<PackageName, Dependency>;
 pub type Substitution = BTreeMap<NamedAddress, SubstOrRename>;
+pub type LintLevels = BTreeMap<Symbol, LintLevel>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SourceManifest {
@@ -32,6 +34,7 @@ pub struct SourceManifest {
     pub build: Option<BuildInfo>,
     pub dependencies: Dependencies,
     pub dev_dependencies: Dependencies,
+    pub lints: LintLevels,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -103,6 +106,18 @@ pub enum SubstOrRename {
     Assign(AccountAddress),
 }
 
+/// How diagnostics reported by a lint are treated, as configured in the `[lints]` section of the
+/// manifest.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum LintLevel {
+    /// The lint is not run
+    Allow,
+    /// The lint reports warnings
+    Warn,
+    /// The lint reports warnings, and fails the build if it reports any
+    Deny,
+}
+
 impl DependencyKind {
     /// Given a dependency `self` assumed to be defined relative to a `parent` dependency which can
     /// itself be defined in terms of some grandparent dependency (not provided), update `self` to
@@ -141,6 +156,19 @@ impl DependencyKind {
     }
 }
 
+impl FromStr for LintLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "allow" => LintLevel::Allow,
+            "warn" => LintLevel::Warn,
+            "deny" => LintLevel::Deny,
+            _ => bail!("Unknown lint level '{s}'. Expected one of 'allow', 'warn' or 'deny'"),
+        })
+    }
+}
+
 /// Default `DependencyKind` is the one that acts as the left and right identity to
 /// `DependencyKind::rerooted` (modulo path normalization).
 impl Default for DependencyKind {
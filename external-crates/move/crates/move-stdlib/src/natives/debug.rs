@@ -72,7 +72,7 @@ fn native_print(
             single_line,
             include_int_types,
         )?;
-        println!("{}", out);
+        testing::print_output(&out);
     }
 
     Ok(NativeResult::ok(gas_params.base_cost, smallvec![]))
@@ -134,7 +134,7 @@ fn native_print_stack_trace(
     {
         let mut s = String::new();
         context.print_stack_trace(&mut s)?;
-        println!("{}", s);
+        testing::print_output(&s);
     }
 
     Ok(NativeResult::ok(gas_params.base_cost, smallvec![]))
@@ -187,6 +187,9 @@ pub fn make_all(
     make_module_natives(natives)
 }
 
+#[cfg(feature = "testing")]
+pub use testing::{start_capturing_output, stop_capturing_output};
+
 #[cfg(feature = "testing")]
 mod testing {
     use move_binary_format::errors::{PartialVMError, PartialVMResult};
@@ -198,7 +201,7 @@ mod testing {
     };
     use move_vm_runtime::native_functions::NativeContext;
     use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
-    use std::{fmt, fmt::Write};
+    use std::{cell::RefCell, fmt, fmt::Write};
 
     const VECTOR_BEGIN: &str = "[";
 
@@ -210,6 +213,32 @@ mod testing {
 
     const STRUCT_END: &str = "}";
 
+    thread_local! {
+        static CAPTURED_OUTPUT: RefCell<Option<String>> = RefCell::new(None);
+    }
+
+    /// Starts capturing the output of `debug::print` and `debug::print_stack_trace` on the
+    /// current thread. Output is still printed to stdout while it is being captured.
+    pub fn start_capturing_output() {
+        CAPTURED_OUTPUT.with(|captured| *captured.borrow_mut() = Some(String::new()));
+    }
+
+    /// Stops capturing output on the current thread, returning the output captured since the last
+    /// call to `start_capturing_output`, if any.
+    pub fn stop_capturing_output() -> Option<String> {
+        CAPTURED_OUTPUT.with(|captured| captured.borrow_mut().take())
+    }
+
+    pub(super) fn print_output(out: &str) {
+        println!("{}", out);
+        CAPTURED_OUTPUT.with(|captured| {
+            if let Some(buf) = captured.borrow_mut().as_mut() {
+                buf.push_str(out);
+                buf.push('\n');
+            }
+        });
+    }
+
     fn fmt_error_to_partial_vm_error(e: fmt::Error) -> PartialVMError {
         PartialVMError::new(StatusCode::UNKNOWN_STATUS)
             .with_message("write! macro failed with: ".to_string() + e.to_string().as_str())
@@ -7,7 +7,7 @@ pub mod extensions;
 pub mod test_reporter;
 pub mod test_runner;
 
-use crate::test_runner::TestRunner;
+use crate::{test_reporter::ReportFormat, test_runner::TestRunner};
 use clap::*;
 use move_command_line_common::files::verify_and_create_named_address_mapping;
 use move_compiler::{
@@ -24,12 +24,16 @@ use std::{
     collections::BTreeMap,
     io::{Result, Write},
     marker::Send,
+    path::PathBuf,
     sync::Mutex,
 };
 
 /// The default value bounding the amount of gas consumed in a test.
 const DEFAULT_EXECUTION_BOUND: u64 = 1_000_000;
 
+/// The default number of times each random test is run.
+const DEFAULT_RAND_NUM_ITERS: u64 = 10;
+
 #[derive(Debug, Parser, Clone)]
 #[clap(author, version, about)]
 pub struct UnitTestingConfig {
@@ -112,6 +116,24 @@ pub struct UnitTestingConfig {
     /// Whether the test output need to be printed out.
     #[clap(short = 'v', long = "verbose")]
     pub report_writeset: bool,
+
+    /// Write a machine-readable report of the test results in the given format
+    #[clap(long = "report-format", value_enum)]
+    pub report_format: Option<ReportFormat>,
+
+    /// File to write the test report to. Defaults to `test_report.xml` for JUnit reports and
+    /// `test_report.json` for JSON reports
+    #[clap(long = "report-output", requires = "report_format")]
+    pub report_output: Option<PathBuf>,
+
+    /// Number of times to run each #[random_test] with freshly generated arguments
+    #[clap(name = "rand_num_iters", long = "rand-num-iters")]
+    pub rand_num_iters: Option<u64>,
+
+    /// Seed for generating the arguments of #[random_test]s. A random seed is used if none is
+    /// given; the seed of a failing random test is reported so that the failure can be reproduced
+    #[clap(name = "seed", long = "seed")]
+    pub seed: Option<u64>,
 }
 
 fn format_module_id(module_id: &ModuleId) -> String {
@@ -140,6 +162,10 @@ impl UnitTestingConfig {
             list: false,
             named_address_values: vec![],
             report_writeset: false,
+            report_format: None,
+            report_output: None,
+            rand_num_iters: None,
+            seed: None,
         }
     }
 
@@ -241,6 +267,8 @@ impl UnitTestingConfig {
             cost_table,
             verify_and_create_named_address_mapping(self.named_address_values.clone()).unwrap(),
             self.report_writeset,
+            self.rand_num_iters.unwrap_or(DEFAULT_RAND_NUM_ITERS),
+            self.seed,
         )
         .unwrap();
 
@@ -257,6 +285,14 @@ impl UnitTestingConfig {
             test_results.report_goldens(&shared_writer)?;
         }
 
+        if let Some(report_format) = self.report_format {
+            let report_output = self
+                .report_output
+                .clone()
+                .unwrap_or_else(|| report_format.default_output());
+            std::fs::write(report_output, test_results.report(report_format))?;
+        }
+
         let ok = test_results.summarize(&shared_writer)?;
 
         let writer = shared_writer.into_inner().unwrap();
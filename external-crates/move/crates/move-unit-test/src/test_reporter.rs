@@ -14,12 +14,19 @@ use move_compiler::{
     diagnostics::{self, Diagnostic, Diagnostics},
     unit_test::{ModuleTestPlan, TestName, TestPlan},
 };
-use move_core_types::{effects::ChangeSet, language_storage::ModuleId, vm_status::StatusType};
+use move_core_types::{
+    effects::ChangeSet,
+    language_storage::ModuleId,
+    vm_status::{StatusCode, StatusType},
+};
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
+use serde::Serialize;
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Write as _,
     io::{Result, Write},
+    path::PathBuf,
     sync::Mutex,
     time::Duration,
 };
@@ -55,6 +62,16 @@ pub struct TestFailure {
     pub vm_error: Option<VMError>,
     pub failure_reason: FailureReason,
     pub storage_state: Option<String>,
+    pub random_test_inputs: Option<RandomTestInputs>,
+}
+
+/// The inputs a failing random test was run with.
+#[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq)]
+pub struct RandomTestInputs {
+    /// The seed the arguments of the test were generated from
+    pub seed: u64,
+    /// The (shrunk) arguments the test failed with, rendered as Move values
+    pub arguments: Vec<String>,
 }
 
 #[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq)]
@@ -62,6 +79,51 @@ pub struct TestRunInfo {
     pub function_ident: String,
     pub elapsed_time: Duration,
     pub instructions_executed: u64,
+    /// Output of `debug::print` and `debug::print_stack_trace` calls made by the test
+    pub captured_output: String,
+}
+
+/// Machine-readable formats the results of a test run can be reported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// JUnit XML, as consumed by most CI systems
+    Junit,
+    /// A JSON object with one entry per test
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum TestStatus {
+    Pass,
+    Fail,
+    Timeout,
+}
+
+/// A single test in a JSON test report.
+#[derive(Debug, Serialize)]
+struct TestReportEntry {
+    name: String,
+    module: String,
+    function: String,
+    status: TestStatus,
+    duration_secs: f64,
+    instructions_executed: u64,
+    abort_code: Option<u64>,
+    location: Option<String>,
+    seed: Option<u64>,
+    failure: Option<String>,
+    output: String,
+}
+
+/// A JSON test report.
+#[derive(Debug, Serialize)]
+struct TestReport {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    duration_secs: f64,
+    tests: Vec<TestReportEntry>,
 }
 
 #[derive(Debug, Clone)]
@@ -83,6 +145,17 @@ impl TestRunInfo {
             function_ident,
             elapsed_time,
             instructions_executed,
+            captured_output: String::new(),
+        }
+    }
+}
+
+impl ReportFormat {
+    /// The file the report is written to if no output path is given.
+    pub fn default_output(&self) -> PathBuf {
+        match self {
+            ReportFormat::Junit => PathBuf::from("test_report.xml"),
+            ReportFormat::Json => PathBuf::from("test_report.json"),
         }
     }
 }
@@ -147,10 +220,62 @@ impl TestFailure {
             vm_error,
             failure_reason,
             storage_state,
+            random_test_inputs: None,
         }
     }
 
     pub fn render_error(&self, test_plan: &TestPlan) -> String {
+        self.render_error_impl(test_plan, control::SHOULD_COLORIZE.should_colorize())
+    }
+
+    /// The status of the failed test in a test report.
+    fn status(&self) -> TestStatus {
+        match self.failure_reason {
+            FailureReason::Timeout(_) => TestStatus::Timeout,
+            _ => TestStatus::Fail,
+        }
+    }
+
+    /// The abort code of the test, if it failed by aborting.
+    fn abort_code(&self) -> Option<u64> {
+        let vm_error = self.vm_error.as_ref()?;
+        if vm_error.major_status() == StatusCode::ABORTED {
+            vm_error.sub_status()
+        } else {
+            None
+        }
+    }
+
+    /// The function and source line the test failed at, as `<module>::<function>(<file>:<line>)`.
+    fn failure_location(&self, test_plan: &TestPlan) -> Option<String> {
+        let vm_error = self.vm_error.as_ref()?;
+        let Location::Module(module_id) = vm_error.location() else {
+            return None;
+        };
+        let (fdef_idx, offset) = vm_error.offsets().first()?;
+        let named_module = test_plan.module_info.get(module_id)?;
+        let fn_handle_idx = named_module.module.function_def_at(*fdef_idx).function;
+        let fn_id_idx = named_module.module.function_handle_at(fn_handle_idx).name;
+        let fn_name = named_module.module.identifier_at(fn_id_idx);
+        let loc = named_module
+            .source_map
+            .get_function_source_map(*fdef_idx)
+            .ok()?
+            .get_code_location(*offset)?;
+        let (file_name, source) = test_plan.files.get(&loc.file_hash())?;
+        let mut files = SimpleFiles::new();
+        let id = files.add(*file_name, source.as_str());
+        let file_mapping = HashMap::from([(loc.file_hash(), id)]);
+        Some(format!(
+            "{}::{}({}:{})",
+            format_module_id(module_id),
+            fn_name,
+            file_name,
+            Self::get_line_number(&loc, &files, &file_mapping)
+        ))
+    }
+
+    fn render_error_impl(&self, test_plan: &TestPlan, colorize: bool) -> String {
         let error_string = match &self.failure_reason {
             FailureReason::NoError(message) => message.to_string(),
             FailureReason::Timeout(message) => message.to_string(),
@@ -160,7 +285,7 @@ impl TestFailure {
                     expected.verbiage(/* is_past_tense */ false),
                     actual.verbiage(/* is_past_tense */ true),
                 );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
             }
             FailureReason::WrongAbortDEPRECATED(message, expected_code, actual) => {
                 let base_message = format!(
@@ -170,7 +295,7 @@ impl TestFailure {
                     expected_code,
                     actual.verbiage(/* is_past_tense */ true),
                 );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
             }
             FailureReason::UnexpectedError(message, error) => {
                 let prefix = match error.0.status_type() {
@@ -194,7 +319,7 @@ impl TestFailure {
                     message,
                     error.verbiage(/* is_past_tense */ true)
                 );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
             }
             FailureReason::Mismatch {
                 move_vm_return_values,
@@ -218,6 +343,18 @@ impl TestFailure {
             FailureReason::Property(message) => message.clone(),
         };
 
+        let error_string = match &self.random_test_inputs {
+            None => error_string,
+            Some(RandomTestInputs { seed, arguments }) => format!(
+                "{}\n────── Random test inputs ──────\n\
+                Failed with generated arguments ({})\n\
+                Rerun with `--seed {}` to reproduce this failure",
+                error_string,
+                arguments.join(", "),
+                seed,
+            ),
+        };
+
         match &self.storage_state {
             None => error_string,
             Some(storage_state) => {
@@ -316,8 +453,9 @@ impl TestFailure {
         test_plan: &TestPlan,
         base_message: String,
         vm_error: &Option<VMError>,
+        colorize: bool,
     ) -> String {
-        let report_diagnostics = if control::SHOULD_COLORIZE.should_colorize() {
+        let report_diagnostics = if colorize {
             diagnostics::report_diagnostics_to_color_buffer
         } else {
             diagnostics::report_diagnostics_to_buffer
@@ -565,6 +703,171 @@ impl TestResults {
         writeln!(writer.lock().unwrap())
     }
 
+    /// Renders the results of the test run as a machine-readable report in the given format.
+    pub fn report(&self, format: ReportFormat) -> String {
+        let entries = self.report_entries();
+        match format {
+            ReportFormat::Junit => Self::junit_report(&entries),
+            ReportFormat::Json => {
+                let passed = entries
+                    .iter()
+                    .filter(|entry| matches!(entry.status, TestStatus::Pass))
+                    .count();
+                let report = TestReport {
+                    total: entries.len(),
+                    passed,
+                    failed: entries.len() - passed,
+                    duration_secs: entries.iter().map(|entry| entry.duration_secs).sum(),
+                    tests: entries,
+                };
+                serde_json::to_string_pretty(&report).unwrap()
+            }
+        }
+    }
+
+    fn report_entries(&self) -> Vec<TestReportEntry> {
+        let mut entries = vec![];
+        for (module_id, test_results) in &self.final_statistics.passed {
+            for info in test_results {
+                entries.push(TestReportEntry {
+                    name: format!("{}::{}", format_module_id(module_id), info.function_ident),
+                    module: format_module_id(module_id),
+                    function: info.function_ident.clone(),
+                    status: TestStatus::Pass,
+                    duration_secs: info.elapsed_time.as_secs_f64(),
+                    instructions_executed: info.instructions_executed,
+                    abort_code: None,
+                    location: None,
+                    seed: None,
+                    failure: None,
+                    output: info.captured_output.clone(),
+                })
+            }
+        }
+        for (module_id, test_failures) in &self.final_statistics.failed {
+            for failure in test_failures {
+                let info = &failure.test_run_info;
+                entries.push(TestReportEntry {
+                    name: format!("{}::{}", format_module_id(module_id), info.function_ident),
+                    module: format_module_id(module_id),
+                    function: info.function_ident.clone(),
+                    status: failure.status(),
+                    duration_secs: info.elapsed_time.as_secs_f64(),
+                    instructions_executed: info.instructions_executed,
+                    abort_code: failure.abort_code(),
+                    location: failure.failure_location(&self.test_plan),
+                    seed: failure
+                        .random_test_inputs
+                        .as_ref()
+                        .map(|inputs| inputs.seed),
+                    failure: Some(failure.render_error_impl(&self.test_plan, false)),
+                    output: info.captured_output.clone(),
+                })
+            }
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+
+    fn junit_report(entries: &[TestReportEntry]) -> String {
+        let mut modules: BTreeMap<&str, Vec<&TestReportEntry>> = BTreeMap::new();
+        for entry in entries {
+            modules.entry(&entry.module).or_default().push(entry);
+        }
+        let failures = |tests: &[&TestReportEntry]| {
+            tests
+                .iter()
+                .filter(|entry| !matches!(entry.status, TestStatus::Pass))
+                .count()
+        };
+        let all: Vec<_> = entries.iter().collect();
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        writeln!(
+            out,
+            "<testsuites name=\"move-unit-tests\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+            entries.len(),
+            failures(&all),
+            entries.iter().map(|entry| entry.duration_secs).sum::<f64>(),
+        )
+        .unwrap();
+        for (module, tests) in modules {
+            writeln!(
+                out,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+                xml_escape(module),
+                tests.len(),
+                failures(&tests),
+                tests.iter().map(|entry| entry.duration_secs).sum::<f64>(),
+            )
+            .unwrap();
+            for entry in tests {
+                writeln!(
+                    out,
+                    "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">",
+                    xml_escape(&entry.function),
+                    xml_escape(module),
+                    entry.duration_secs,
+                )
+                .unwrap();
+                writeln!(out, "      <properties>").unwrap();
+                writeln!(
+                    out,
+                    "        <property name=\"instructions_executed\" value=\"{}\"/>",
+                    entry.instructions_executed
+                )
+                .unwrap();
+                if let Some(abort_code) = entry.abort_code {
+                    writeln!(
+                        out,
+                        "        <property name=\"abort_code\" value=\"{}\"/>",
+                        abort_code
+                    )
+                    .unwrap();
+                }
+                if let Some(location) = &entry.location {
+                    writeln!(
+                        out,
+                        "        <property name=\"location\" value=\"{}\"/>",
+                        xml_escape(location)
+                    )
+                    .unwrap();
+                }
+                if let Some(seed) = entry.seed {
+                    writeln!(out, "        <property name=\"seed\" value=\"{}\"/>", seed).unwrap();
+                }
+                writeln!(out, "      </properties>").unwrap();
+                if let Some(failure) = &entry.failure {
+                    let message = failure.lines().next().unwrap_or_default();
+                    let kind = match entry.status {
+                        TestStatus::Timeout => "timeout",
+                        _ => "failure",
+                    };
+                    writeln!(
+                        out,
+                        "      <failure message=\"{}\" type=\"{}\">{}</failure>",
+                        xml_escape(message),
+                        kind,
+                        xml_escape(failure),
+                    )
+                    .unwrap();
+                }
+                if !entry.output.is_empty() {
+                    writeln!(
+                        out,
+                        "      <system-out>{}</system-out>",
+                        xml_escape(&entry.output)
+                    )
+                    .unwrap();
+                }
+                writeln!(out, "    </testcase>").unwrap();
+            }
+            writeln!(out, "  </testsuite>").unwrap();
+        }
+        writeln!(out, "</testsuites>").unwrap();
+        out
+    }
+
     /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
     pub fn summarize<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
         let num_failed_tests = self
@@ -618,3 +921,21 @@ impl TestResults {
         Ok(num_failed_tests == 0)
     }
 }
+
+/// Escapes `s` for use in XML attribute values and text.
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // control characters other than whitespace are not allowed in XML 1.0
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -5,7 +5,8 @@
 use crate::{
     extensions, format_module_id,
     test_reporter::{
-        FailureReason, MoveError, TestFailure, TestResults, TestRunInfo, TestStatistics,
+        FailureReason, MoveError, RandomTestInputs, TestFailure, TestResults, TestRunInfo,
+        TestStatistics,
     },
 };
 use anyhow::Result;
@@ -20,13 +21,14 @@ use move_bytecode_utils::Modules;
 use move_compiler::{
     diagnostics::WarningFilters,
     shared::{Flags, NumericalAddress, PackagePaths},
-    unit_test::{ExpectedFailure, ModuleTestPlan, TestCase, TestPlan},
+    unit_test::{ExpectedFailure, ModuleTestPlan, TestArgument, TestCase, TestPlan},
 };
 use move_core_types::{
     account_address::AccountAddress,
     effects::{ChangeSet, Op},
     identifier::IdentStr,
-    value::serialize_values,
+    u256::U256,
+    value::{serialize_values, MoveTypeLayout, MoveValue},
     vm_status::StatusCode,
 };
 use move_model::{
@@ -39,6 +41,7 @@ use move_stackless_bytecode_interpreter::{
     shared::bridge::{adapt_move_vm_change_set, adapt_move_vm_result},
     StacklessBytecodeInterpreter,
 };
+use move_stdlib::natives::debug;
 #[cfg(debug_assertions)]
 use move_vm_profiler::GasProfiler;
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
@@ -48,8 +51,15 @@ use move_vm_test_utils::{
 };
 #[cfg(debug_assertions)]
 use move_vm_types::gas::GasMeter;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    marker::Send,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 
@@ -66,8 +76,16 @@ pub struct SharedTestingConfig {
     check_stackless_vm: bool,
     verbose: bool,
     record_writeset: bool,
+    rand_num_iters: u64,
+    seed: u64,
 }
 
+/// The maximum length of vectors generated as arguments of random tests.
+const MAX_GENERATED_VECTOR_LEN: usize = 32;
+
+/// The maximum number of times a failing random test is re-run while shrinking its arguments.
+const MAX_SHRINK_RUNS: usize = 1000;
+
 pub struct TestRunner {
     num_threads: usize,
     testing_config: SharedTestingConfig,
@@ -136,6 +154,8 @@ impl TestRunner {
         cost_table: Option<CostTable>,
         named_address_values: BTreeMap<String, NumericalAddress>,
         record_writeset: bool,
+        rand_num_iters: u64,
+        seed: Option<u64>,
     ) -> Result<Self> {
         let source_files = tests
             .files
@@ -168,6 +188,8 @@ impl TestRunner {
                 verbose,
                 named_address_values,
                 record_writeset,
+                rand_num_iters,
+                seed: seed.unwrap_or_else(rand::random),
             },
             num_threads,
             tests,
@@ -256,7 +278,7 @@ impl SharedTestingConfig {
         &self,
         test_plan: &ModuleTestPlan,
         function_name: &str,
-        test_info: &TestCase,
+        arguments: &[MoveValue],
     ) -> (
         VMResult<ChangeSet>,
         VMResult<NativeContextExtensions>,
@@ -276,12 +298,13 @@ impl SharedTestingConfig {
 
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
 
+        debug::start_capturing_output();
         let now = Instant::now();
         let serialized_return_values_result = session.execute_function_bypass_visibility(
             &test_plan.module_id,
             IdentStr::new(function_name).unwrap(),
             vec![], // no ty args, at least for now
-            serialize_values(test_info.arguments.iter()),
+            serialize_values(arguments),
             &mut gas_meter,
         );
         let mut return_result = serialized_return_values_result.map(|res| {
@@ -295,7 +318,7 @@ impl SharedTestingConfig {
                 err.remove_exec_state();
             }
         }
-        let test_run_info = TestRunInfo::new(
+        let mut test_run_info = TestRunInfo::new(
             function_name.to_string(),
             now.elapsed(),
             // TODO(Gas): This doesn't look quite right...
@@ -305,6 +328,7 @@ impl SharedTestingConfig {
                 .unwrap()
                 .into(),
         );
+        test_run_info.captured_output = debug::stop_capturing_output().unwrap_or_default();
         match session.finish_with_extensions().0 {
             Ok((cs, _, extensions)) => (Ok(cs), Ok(extensions), return_result, test_run_info),
             Err(err) => (Err(err.clone()), Err(err), return_result, test_run_info),
@@ -316,7 +340,7 @@ impl SharedTestingConfig {
         env: &GlobalEnv,
         test_plan: &ModuleTestPlan,
         function_name: &str,
-        test_info: &TestCase,
+        arguments: &[MoveValue],
     ) -> (
         VMResult<ChangeSet>,
         VMResult<Vec<Vec<u8>>>,
@@ -340,7 +364,7 @@ impl SharedTestingConfig {
             &test_plan.module_id,
             IdentStr::new(function_name).unwrap(),
             &[], // no ty args, at least for now
-            &test_info.arguments,
+            arguments,
             &global_state,
         );
         let prop_check_result = interpreter.report_property_checking_results();
@@ -403,181 +427,283 @@ impl SharedTestingConfig {
         let mut stats = TestStatistics::new();
 
         for (function_name, test_info) in &test_plan.tests {
-            let (cs_result, ext_result, exec_result, test_run_info) =
-                self.execute_via_move_vm(test_plan, function_name, test_info);
+            let result = if test_info.is_random_test() {
+                self.exec_random_test(
+                    stackless_model.as_ref(),
+                    test_plan,
+                    function_name,
+                    test_info,
+                    &mut stats,
+                )
+            } else {
+                let arguments = test_info
+                    .arguments
+                    .iter()
+                    .map(|arg| match arg {
+                        TestArgument::Value(value) => value.clone(),
+                        TestArgument::Generate { .. } => {
+                            unreachable!("ICE: generated argument in a non-random test")
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                self.exec_test(
+                    stackless_model.as_ref(),
+                    test_plan,
+                    function_name,
+                    test_info,
+                    &arguments,
+                    &mut stats,
+                )
+            };
+            match result {
+                Ok(test_run_info) => {
+                    output.pass(function_name);
+                    stats.test_success(test_run_info, test_plan);
+                }
+                Err(test_failure) => {
+                    if let FailureReason::Timeout(_) = test_failure.failure_reason {
+                        output.timeout(function_name);
+                    } else {
+                        output.fail(function_name);
+                    }
+                    stats.test_failure(test_failure, test_plan);
+                }
+            }
+        }
+
+        stats
+    }
 
-            if self.record_writeset {
-                stats.test_output(
-                    function_name.to_string(),
+    /// Runs the test once with the given arguments, returning the run info if the test passed and
+    /// the reason it failed otherwise.
+    fn exec_test(
+        &self,
+        stackless_model: Option<&GlobalEnv>,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        arguments: &[MoveValue],
+        stats: &mut TestStatistics,
+    ) -> Result<TestRunInfo, TestFailure> {
+        let (cs_result, ext_result, exec_result, test_run_info) =
+            self.execute_via_move_vm(test_plan, function_name, arguments);
+
+        if self.record_writeset {
+            stats.test_output(
+                function_name.to_string(),
+                test_plan,
+                format!("{:?}", cs_result),
+            );
+        }
+
+        if self.check_stackless_vm {
+            let (stackless_vm_change_set, stackless_vm_result, _, prop_check_result) = self
+                .execute_via_stackless_vm(
+                    stackless_model.unwrap(),
                     test_plan,
-                    format!("{:?}", cs_result),
+                    function_name,
+                    arguments,
                 );
+            let move_vm_result = adapt_move_vm_result(exec_result.clone());
+            let move_vm_change_set =
+                adapt_move_vm_change_set(cs_result.clone(), &self.starting_storage_state);
+            if stackless_vm_result != move_vm_result
+                || stackless_vm_change_set != move_vm_change_set
+            {
+                return Err(TestFailure::new(
+                    FailureReason::mismatch(
+                        move_vm_result,
+                        move_vm_change_set,
+                        stackless_vm_result,
+                        stackless_vm_change_set,
+                    ),
+                    test_run_info,
+                    None,
+                    None,
+                ));
             }
+            if let Some(prop_failure) = prop_check_result {
+                return Err(TestFailure::new(
+                    FailureReason::property(prop_failure),
+                    test_run_info,
+                    None,
+                    None,
+                ));
+            }
+        }
 
-            if self.check_stackless_vm {
-                let (stackless_vm_change_set, stackless_vm_result, _, prop_check_result) = self
-                    .execute_via_stackless_vm(
-                        stackless_model.as_ref().unwrap(),
-                        test_plan,
-                        function_name,
-                        test_info,
-                    );
-                let move_vm_result = adapt_move_vm_result(exec_result.clone());
-                let move_vm_change_set =
-                    adapt_move_vm_change_set(cs_result.clone(), &self.starting_storage_state);
-                if stackless_vm_result != move_vm_result
-                    || stackless_vm_change_set != move_vm_change_set
-                {
-                    output.fail(function_name);
-                    stats.test_failure(
-                        TestFailure::new(
-                            FailureReason::mismatch(
-                                move_vm_result,
-                                move_vm_change_set,
-                                stackless_vm_result,
-                                stackless_vm_change_set,
-                            ),
+        let save_session_state = || {
+            if self.save_storage_state_on_failure {
+                cs_result.ok().and_then(|changeset| {
+                    ext_result.ok().and_then(|extensions| {
+                        print_resources_and_extensions(
+                            &changeset,
+                            extensions,
+                            &self.starting_storage_state,
+                        )
+                        .ok()
+                    })
+                })
+            } else {
+                None
+            }
+        };
+        match exec_result {
+            Err(err) => {
+                let actual_err =
+                    MoveError(err.major_status(), err.sub_status(), err.location().clone());
+                assert!(err.major_status() != StatusCode::EXECUTED);
+                match test_info.expected_failure.as_ref() {
+                    Some(ExpectedFailure::Expected) => Ok(test_run_info),
+                    Some(ExpectedFailure::ExpectedWithError(expected_err))
+                        if expected_err == &actual_err =>
+                    {
+                        Ok(test_run_info)
+                    }
+                    Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code))
+                        if actual_err.0 == StatusCode::ABORTED
+                            && actual_err.1.is_some()
+                            && actual_err.1.unwrap() == *code =>
+                    {
+                        Ok(test_run_info)
+                    }
+                    // incorrect cases
+                    Some(ExpectedFailure::ExpectedWithError(expected_err)) => {
+                        Err(TestFailure::new(
+                            FailureReason::wrong_error(expected_err.clone(), actual_err),
                             test_run_info,
-                            None,
-                            None,
-                        ),
-                        test_plan,
-                    );
-                    continue;
-                }
-                if let Some(prop_failure) = prop_check_result {
-                    output.fail(function_name);
-                    stats.test_failure(
-                        TestFailure::new(
-                            FailureReason::property(prop_failure),
+                            Some(err),
+                            save_session_state(),
+                        ))
+                    }
+                    Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(expected_code)) => {
+                        Err(TestFailure::new(
+                            FailureReason::wrong_abort_deprecated(*expected_code, actual_err),
                             test_run_info,
-                            None,
-                            None,
-                        ),
-                        test_plan,
-                    );
-                    continue;
+                            Some(err),
+                            save_session_state(),
+                        ))
+                    }
+                    None if err.major_status() == StatusCode::OUT_OF_GAS => {
+                        // Ran out of ticks, report a test timeout and log a test failure
+                        Err(TestFailure::new(
+                            FailureReason::timeout(),
+                            test_run_info,
+                            Some(err),
+                            save_session_state(),
+                        ))
+                    }
+                    None => Err(TestFailure::new(
+                        FailureReason::unexpected_error(actual_err),
+                        test_run_info,
+                        Some(err),
+                        save_session_state(),
+                    )),
                 }
             }
-
-            let save_session_state = || {
-                if self.save_storage_state_on_failure {
-                    cs_result.ok().and_then(|changeset| {
-                        ext_result.ok().and_then(|extensions| {
-                            print_resources_and_extensions(
-                                &changeset,
-                                extensions,
-                                &self.starting_storage_state,
-                            )
-                            .ok()
-                        })
-                    })
+            Ok(_) => {
+                // Expected the test to fail, but it executed
+                if test_info.expected_failure.is_some() {
+                    Err(TestFailure::new(
+                        FailureReason::no_error(),
+                        test_run_info,
+                        None,
+                        save_session_state(),
+                    ))
                 } else {
-                    None
+                    // Expected the test to execute fully and it did
+                    Ok(test_run_info)
                 }
-            };
-            match exec_result {
-                Err(err) => {
-                    let actual_err =
-                        MoveError(err.major_status(), err.sub_status(), err.location().clone());
-                    assert!(err.major_status() != StatusCode::EXECUTED);
-                    match test_info.expected_failure.as_ref() {
-                        Some(ExpectedFailure::Expected) => {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        Some(ExpectedFailure::ExpectedWithError(expected_err))
-                            if expected_err == &actual_err =>
-                        {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code))
-                            if actual_err.0 == StatusCode::ABORTED
-                                && actual_err.1.is_some()
-                                && actual_err.1.unwrap() == *code =>
-                        {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        // incorrect cases
-                        Some(ExpectedFailure::ExpectedWithError(expected_err)) => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::wrong_error(expected_err.clone(), actual_err),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(expected_code)) => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::wrong_abort_deprecated(
-                                        *expected_code,
-                                        actual_err,
-                                    ),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        None if err.major_status() == StatusCode::OUT_OF_GAS => {
-                            // Ran out of ticks, report a test timeout and log a test failure
-                            output.timeout(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::timeout(),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        None => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::unexpected_error(actual_err),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        }
-                    }
+            }
+        }
+    }
+
+    /// Runs a random test `rand_num_iters` times with freshly generated arguments, stopping at the
+    /// first failure. The arguments of a failing run are shrunk to simpler arguments that the test
+    /// still fails with before the failure is reported, together with the seed to reproduce it.
+    fn exec_random_test(
+        &self,
+        stackless_model: Option<&GlobalEnv>,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        stats: &mut TestStatistics,
+    ) -> Result<TestRunInfo, TestFailure> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut total_run_info = TestRunInfo::new(function_name.to_string(), Duration::ZERO, 0);
+        for _ in 0..self.rand_num_iters {
+            let arguments = generate_arguments(&test_info.arguments, &mut rng);
+            match self.exec_test(
+                stackless_model,
+                test_plan,
+                function_name,
+                test_info,
+                &arguments,
+                stats,
+            ) {
+                Ok(test_run_info) => {
+                    total_run_info.elapsed_time += test_run_info.elapsed_time;
+                    total_run_info.instructions_executed += test_run_info.instructions_executed;
+                    total_run_info
+                        .captured_output
+                        .push_str(&test_run_info.captured_output);
                 }
-                Ok(_) => {
-                    // Expected the test to fail, but it executed
-                    if test_info.expected_failure.is_some() {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::no_error(),
-                                test_run_info,
-                                None,
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    } else {
-                        // Expected the test to execute fully and it did
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
-                    }
+                Err(test_failure) => {
+                    let (arguments, mut test_failure) = self.shrink_failure(
+                        stackless_model,
+                        test_plan,
+                        function_name,
+                        test_info,
+                        arguments,
+                        test_failure,
+                    );
+                    test_failure.random_test_inputs = Some(RandomTestInputs {
+                        seed: self.seed,
+                        arguments: arguments.iter().map(|arg| arg.to_string()).collect(),
+                    });
+                    return Err(test_failure);
                 }
             }
         }
+        Ok(total_run_info)
+    }
 
-        stats
+    /// Greedily replaces the arguments of a failing random test with simpler arguments for as long
+    /// as the test keeps failing, returning the simplest failing arguments found and their failure.
+    fn shrink_failure(
+        &self,
+        stackless_model: Option<&GlobalEnv>,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        mut arguments: Vec<MoveValue>,
+        mut test_failure: TestFailure,
+    ) -> (Vec<MoveValue>, TestFailure) {
+        // writesets of the shrinking runs are not recorded
+        let mut scratch_stats = TestStatistics::new();
+        let mut runs = 0;
+        'shrink: loop {
+            for candidate in shrink_arguments(&arguments) {
+                if runs == MAX_SHRINK_RUNS {
+                    break 'shrink;
+                }
+                runs += 1;
+                if let Err(failure) = self.exec_test(
+                    stackless_model,
+                    test_plan,
+                    function_name,
+                    test_info,
+                    &candidate,
+                    &mut scratch_stats,
+                ) {
+                    arguments = candidate;
+                    test_failure = failure;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        (arguments, test_failure)
     }
 
     // TODO: comparison of results via different backends
@@ -592,3 +718,143 @@ impl SharedTestingConfig {
         self.exec_module_tests_move_vm_and_stackless_vm(test_plan, &output)
     }
 }
+
+/// Returns the arguments for one run of a random test, generating a value for each generated
+/// argument.
+fn generate_arguments(arguments: &[TestArgument], rng: &mut StdRng) -> Vec<MoveValue> {
+    arguments
+        .iter()
+        .map(|arg| match arg {
+            TestArgument::Value(value) => value.clone(),
+            TestArgument::Generate { generator } => generate_value(generator, rng),
+        })
+        .collect()
+}
+
+fn generate_value(layout: &MoveTypeLayout, rng: &mut StdRng) -> MoveValue {
+    // Boundary values find many more bugs than uniformly distributed ones, so they are generated
+    // with a higher probability
+    macro_rules! generate_int {
+        ($ty:ty, $variant:ident) => {
+            MoveValue::$variant(match rng.gen_range(0..8) {
+                0 => 0,
+                1 => <$ty>::MAX,
+                2 => rng.gen_range(0..=<$ty>::from(u8::MAX)),
+                _ => rng.gen(),
+            })
+        };
+    }
+    match layout {
+        MoveTypeLayout::Bool => MoveValue::Bool(rng.gen()),
+        MoveTypeLayout::U8 => MoveValue::U8(rng.gen()),
+        MoveTypeLayout::U16 => generate_int!(u16, U16),
+        MoveTypeLayout::U32 => generate_int!(u32, U32),
+        MoveTypeLayout::U64 => generate_int!(u64, U64),
+        MoveTypeLayout::U128 => generate_int!(u128, U128),
+        MoveTypeLayout::U256 => MoveValue::U256(match rng.gen_range(0..8) {
+            0 => U256::zero(),
+            1 => U256::max_value(),
+            2 => U256::from(rng.gen::<u8>()),
+            _ => rng.gen(),
+        }),
+        MoveTypeLayout::Address => MoveValue::Address(AccountAddress::new(rng.gen())),
+        MoveTypeLayout::Vector(elem) => {
+            let len = rng.gen_range(0..=MAX_GENERATED_VECTOR_LEN);
+            MoveValue::Vector((0..len).map(|_| generate_value(elem, rng)).collect())
+        }
+        MoveTypeLayout::Struct(_) | MoveTypeLayout::Signer => {
+            unreachable!("ICE: values of type {} cannot be generated", layout)
+        }
+    }
+}
+
+/// Returns the argument lists obtained by replacing a single argument in `arguments` with a
+/// simpler value, simplest first.
+fn shrink_arguments(arguments: &[MoveValue]) -> Vec<Vec<MoveValue>> {
+    let mut candidates = vec![];
+    for (i, arg) in arguments.iter().enumerate() {
+        for shrunk in shrink_value(arg) {
+            let mut candidate = arguments.to_vec();
+            candidate[i] = shrunk;
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// Returns values that are simpler than `value`, simplest first. Every value returned is strictly
+/// simpler, so repeatedly shrinking a value terminates.
+fn shrink_value(value: &MoveValue) -> Vec<MoveValue> {
+    macro_rules! shrink_int {
+        ($u:expr, $variant:ident) => {{
+            let u = *$u;
+            let mut candidates = vec![];
+            for shrunk in [0, u / 2, u.saturating_sub(1)] {
+                if shrunk < u && !candidates.contains(&shrunk) {
+                    candidates.push(shrunk);
+                }
+            }
+            candidates.into_iter().map(MoveValue::$variant).collect()
+        }};
+    }
+    match value {
+        MoveValue::Bool(b) => {
+            if *b {
+                vec![MoveValue::Bool(false)]
+            } else {
+                vec![]
+            }
+        }
+        MoveValue::U8(u) => shrink_int!(u, U8),
+        MoveValue::U16(u) => shrink_int!(u, U16),
+        MoveValue::U32(u) => shrink_int!(u, U32),
+        MoveValue::U64(u) => shrink_int!(u, U64),
+        MoveValue::U128(u) => shrink_int!(u, U128),
+        MoveValue::U256(u) => {
+            let u = *u;
+            let mut candidates = vec![];
+            for shrunk in [
+                U256::zero(),
+                u >> 1u8,
+                u.checked_sub(U256::from(1u8)).unwrap_or_else(U256::zero),
+            ] {
+                if shrunk < u && !candidates.contains(&shrunk) {
+                    candidates.push(shrunk);
+                }
+            }
+            candidates.into_iter().map(MoveValue::U256).collect()
+        }
+        MoveValue::Address(a) => {
+            if *a == AccountAddress::ZERO {
+                vec![]
+            } else {
+                vec![MoveValue::Address(AccountAddress::ZERO)]
+            }
+        }
+        MoveValue::Vector(elems) => {
+            if elems.is_empty() {
+                return vec![];
+            }
+            let mut candidates = vec![MoveValue::Vector(vec![])];
+            if elems.len() > 1 {
+                let half = elems.len() / 2;
+                candidates.push(MoveValue::Vector(elems[..half].to_vec()));
+                candidates.push(MoveValue::Vector(elems[half..].to_vec()));
+            }
+            for i in 0..elems.len() {
+                let mut shorter = elems.clone();
+                shorter.remove(i);
+                candidates.push(MoveValue::Vector(shorter));
+            }
+            for (i, elem) in elems.iter().enumerate() {
+                for shrunk in shrink_value(elem) {
+                    let mut simpler = elems.clone();
+                    simpler[i] = shrunk;
+                    candidates.push(MoveValue::Vector(simpler));
+                }
+            }
+            candidates
+        }
+        MoveValue::Signer(_) | MoveValue::Struct(_) => vec![],
+    }
+}
@@ -5,21 +5,23 @@
 use move_command_line_common::testing::{
     add_update_baseline_fix, format_diff, read_env_update_baseline, EXP_EXT,
 };
-use move_unit_test::{self, UnitTestingConfig};
-use regex::RegexBuilder;
+use move_unit_test::{self, test_reporter::ReportFormat, UnitTestingConfig};
+use regex::{Regex, RegexBuilder};
 use std::{
     fs,
     path::{Path, PathBuf},
 };
 
 // We don't support statistics tests as that includes times which are variable and will make these
-// tests flaky.
-const TEST_MODIFIER_STRS: &[&str] = &["storage"];
+// tests flaky. Test reports include times too, so they are masked before comparing reports.
+const TEST_MODIFIER_STRS: &[&str] = &["storage", "json", "junit"];
 
 pub fn modify(mut base_config: UnitTestingConfig, modifier_str: &str) -> Option<UnitTestingConfig> {
     // Add future test modifiers here
     match modifier_str {
         "storage" => base_config.report_storage_on_error = true,
+        "json" => base_config.report_format = Some(ReportFormat::Json),
+        "junit" => base_config.report_format = Some(ReportFormat::Junit),
         _ => return None,
     };
     Some(base_config)
@@ -30,10 +32,11 @@ fn run_test_with_modifiers(
     path: &Path,
 ) -> anyhow::Result<Vec<((Vec<u8>, bool), PathBuf)>> {
     let mut results = Vec::new();
+    let report_dir = tempfile::tempdir()?;
 
     for modifier in TEST_MODIFIER_STRS.iter() {
         let modified_exp_path = path.with_extension(format!("{}.{}", modifier, EXP_EXT));
-        if let (Some(test_config), true) = (
+        if let (Some(mut test_config), true) = (
             modify(unit_test_config.clone(), modifier),
             modified_exp_path.exists(),
         ) {
@@ -47,10 +50,20 @@ fn run_test_with_modifiers(
                 );
             }
 
-            results.push((
-                test_config.run_and_report_unit_tests(test_plan.unwrap(), None, None, buffer)?,
-                modified_exp_path,
-            ))
+            // compare the report written by the run instead of its output
+            let report_output = report_dir.path().join(modifier);
+            if test_config.report_format.is_some() {
+                test_config.report_output = Some(report_output.clone());
+            }
+            let (output, ok) =
+                test_config.run_and_report_unit_tests(test_plan.unwrap(), None, None, buffer)?;
+            let output = if test_config.report_format.is_some() {
+                fs::read(&report_output)?
+            } else {
+                output
+            };
+
+            results.push(((output, ok), modified_exp_path))
         }
     }
 
@@ -84,6 +97,8 @@ fn run_test_impl(path: &Path) -> anyhow::Result<()> {
             .collect(),
         report_writeset: true,
         report_stacktrace_on_abort: true,
+        // random tests fail with the same arguments, and report the same seed, on every run
+        seed: Some(42),
 
         ..UnitTestingConfig::default_with_bound(None)
     };
@@ -92,10 +107,30 @@ fn run_test_impl(path: &Path) -> anyhow::Result<()> {
         .multi_line(true)
         .build()
         .unwrap();
+    // test reports include times, which vary between runs, and instruction counts, which are
+    // masked so that cost table changes only affect the goldens that are about gas
+    let report_masks = [
+        (
+            Regex::new(r#"("duration_secs": )[0-9.e-]+"#).unwrap(),
+            "${1}0.0",
+        ),
+        (Regex::new(r#"( time=")[0-9.]+""#).unwrap(), r#"${1}0.000""#),
+        (
+            Regex::new(r#"("instructions_executed": )[0-9]+"#).unwrap(),
+            "${1}0",
+        ),
+        (
+            Regex::new(r#"(name="instructions_executed" value=")[0-9]+""#).unwrap(),
+            r#"${1}0""#,
+        ),
+    ];
 
     for ((buffer, _), exp_path) in run_test_with_modifiers(unit_test_config, path)? {
         let base_output = String::from_utf8(buffer)?;
-        let cleaned_output = regex.replacen(&base_output, 0, r"$1$2");
+        let mut cleaned_output = regex.replacen(&base_output, 0, r"$1$2").into_owned();
+        for (mask, replacement) in &report_masks {
+            cleaned_output = mask.replace_all(&cleaned_output, *replacement).into_owned();
+        }
         if update_baseline {
             fs::write(&exp_path, &*cleaned_output)?
         }
@@ -0,0 +1,17 @@
+address 0x1 {
+module M {
+    use std::debug;
+
+    #[test]
+    fun print_output() {
+        debug::print(&42);
+        debug::print(&true);
+    }
+
+    #[test]
+    fun print_output_fail() { debug::print(&7); abort 0 }
+
+    #[test]
+    fun no_print_output() { }
+}
+}
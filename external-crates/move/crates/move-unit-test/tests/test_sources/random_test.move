@@ -0,0 +1,29 @@
+address 0x1 {
+module M {
+    const ErrorCode: u64 = 100;
+
+    #[random_test]
+    fun add_commutes(a: u32, b: u32) {
+        assert!((a as u64) + (b as u64) == (b as u64) + (a as u64), 0);
+    }
+
+    #[random_test]
+    #[expected_failure(abort_code = ErrorCode)]
+    fun always_aborts(x: u64) {
+        assert!(x < 0, ErrorCode);
+    }
+
+    #[random_test]
+    fun primitive_args(b: bool, a: address, x: u256) {
+        assert!(b || !b, 0);
+        assert!(a == a, 1);
+        assert!(x >= 0, 2);
+    }
+
+    #[random_test]
+    fun vector_args(v: vector<u8>, w: vector<vector<u64>>) {
+        assert!(v == v, 0);
+        assert!(w == w, 1);
+    }
+}
+}
@@ -0,0 +1,15 @@
+address 0x1 {
+module M {
+    use std::vector;
+
+    #[random_test]
+    fun short_vectors(v: vector<u64>) {
+        if (vector::length(&v) >= 3) abort 1
+    }
+
+    #[random_test]
+    fun small_values(x: u64) {
+        if (x >= 10) abort 0
+    }
+}
+}
@@ -38,7 +38,11 @@ use fastcrypto::{
 };
 use mysten_metrics::metered_channel::{channel_with_total, Receiver, Sender};
 use mysten_metrics::monitored_scope;
-use mysten_network::{multiaddr::Protocol, Multiaddr};
+use mysten_network::{
+    fault_injection::{self, FaultInjectionLayer},
+    multiaddr::Protocol,
+    Multiaddr,
+};
 use network::{
     client::NetworkClient,
     epoch_filter::{AllowedEpoch, EPOCH_HEADER_KEY},
@@ -299,6 +303,7 @@ impl Primary {
                 EPOCH_HEADER_KEY.parse().unwrap(),
                 epoch_string.clone(),
             ))
+            .layer(FaultInjectionLayer::anemo())
             .service(routes);
 
         let outbound_layer = ServiceBuilder::new()
@@ -379,6 +384,7 @@ impl Primary {
                 }
             }
         }
+        fault_injection::register_peer(network.peer_id());
         client.set_primary_network(network.clone());
 
         info!("Primary {} listening on {}", authority.id(), address);
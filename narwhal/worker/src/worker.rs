@@ -22,7 +22,11 @@ use config::{Authority, AuthorityIdentifier, Committee, Parameters, WorkerCache,
 use crypto::{traits::KeyPair as _, NetworkKeyPair, NetworkPublicKey};
 use mysten_metrics::metered_channel::channel_with_total;
 use mysten_metrics::spawn_logged_monitored_task;
-use mysten_network::{multiaddr::Protocol, Multiaddr};
+use mysten_network::{
+    fault_injection::{self, FaultInjectionLayer},
+    multiaddr::Protocol,
+    Multiaddr,
+};
 use network::client::NetworkClient;
 use network::epoch_filter::{AllowedEpoch, EPOCH_HEADER_KEY};
 use network::failpoints::FailpointsMakeCallbackHandler;
@@ -204,6 +208,7 @@ impl Worker {
                 EPOCH_HEADER_KEY.parse().unwrap(),
                 epoch_string.clone(),
             ))
+            .layer(FaultInjectionLayer::anemo())
             .service(routes);
 
         let outbound_layer = ServiceBuilder::new()
@@ -283,6 +288,7 @@ impl Worker {
                 }
             }
         }
+        fault_injection::register_peer(network.peer_id());
         client.set_worker_network(id, network.clone());
 
         info!("Worker {} listening to worker messages on {}", id, address);